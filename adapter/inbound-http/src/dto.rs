@@ -1,8 +1,18 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use base64::Engine;
+use catapulte_domain::entity::attachment::{InvalidContentId, validate_content_id};
 use catapulte_domain::entity::body::{BodySource, InvalidPlainBody, MjmlSource, Plain};
+use catapulte_domain::entity::calendar::{
+    Calendar, CalendarEvent, CalendarParticipant, InvalidCalendar,
+};
 use catapulte_domain::entity::email::{EmailId, RecipientKind};
-use catapulte_domain::use_case::submit_email::{AttachmentInput, SubmitEmailInput};
+use catapulte_domain::entity::message_headers::{InvalidHeader, MessageHeaders};
+use catapulte_domain::entity::template::{InvalidLocale, validate_locale};
+use catapulte_domain::entity::tracking::Tracking;
+use catapulte_domain::entity::unsubscribe::{InvalidListName, MailingList};
+use catapulte_domain::use_case::submit_email::{AttachmentInput, CalendarInput, SubmitEmailInput};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
 
@@ -38,6 +48,10 @@ pub struct AttachmentDto {
     pub inline_base64: Option<String>,
     #[serde(default)]
     pub url: Option<String>,
+    /// Sends the attachment inline, for the HTML body to show as
+    /// `<img src="cid:{content_id}">`.
+    #[serde(default)]
+    pub content_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -49,18 +63,165 @@ pub struct SubmitEmailRequest {
     #[serde(default)]
     pub subject: Option<String>,
     pub sender: String,
+    #[serde(default)]
+    pub sender_display_name: Option<String>,
     pub recipients: Vec<RecipientDto>,
+    #[serde(default)]
+    pub reply_to: Option<ReplyToDto>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
     pub body: BodyDto,
     #[serde(default)]
     pub variables: serde_json::Map<String, serde_json::Value>,
     #[serde(default)]
     pub attachments: Vec<AttachmentDto>,
+    #[serde(default)]
+    pub send_at_ms: Option<i64>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub calendar: Option<CalendarDto>,
+    #[serde(default)]
+    pub unsubscribe: Option<UnsubscribeDto>,
+    #[serde(default)]
+    pub tracking: Option<TrackingDto>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct RecipientDto {
     pub kind: RecipientKindDto,
     pub address: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarParticipantDto {
+    pub address: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarEventDto {
+    pub uid: String,
+    #[serde(default)]
+    pub sequence: u32,
+    pub summary: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    /// Defaults to the sender.
+    #[serde(default)]
+    pub organizer: Option<CalendarParticipantDto>,
+    /// Defaults to the `to` and `cc` recipients.
+    #[serde(default)]
+    pub attendees: Option<Vec<CalendarParticipantDto>>,
+}
+
+/// Sends the email for a mailing list, with one-click `List-Unsubscribe`
+/// headers for its recipient.
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeDto {
+    pub list: String,
+}
+
+/// Opt-in engagement tracking, reported as `opened` / `clicked` events.
+#[derive(Debug, Deserialize)]
+pub struct TrackingDto {
+    #[serde(default)]
+    pub opens: bool,
+    #[serde(default)]
+    pub clicks: bool,
+}
+
+impl From<TrackingDto> for Tracking {
+    fn from(dto: TrackingDto) -> Self {
+        Self {
+            opens: dto.opens,
+            clicks: dto.clicks,
+        }
+    }
+}
+
+/// Invitation sent as the `text/calendar` alternative of the body: a raw
+/// iCalendar object, or an event Catapulte serializes.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CalendarDto {
+    Raw { content: String },
+    Event(CalendarEventDto),
+}
+
+impl CalendarDto {
+    /// # Errors
+    ///
+    /// Returns `EnvelopeConversionError` when the raw calendar or a
+    /// participant address is invalid. Events are checked when submitted.
+    fn into_calendar(
+        self,
+        sender: &str,
+        recipients: &[(RecipientKind, String)],
+        headers: &MessageHeaders,
+    ) -> Result<CalendarInput, EnvelopeConversionError> {
+        let event = match self {
+            Self::Raw { content } => return Ok(CalendarInput::Raw(Calendar::parse(&content)?)),
+            Self::Event(event) => event,
+        };
+        let participant = |address: &str| CalendarParticipant {
+            address: address.to_owned(),
+            name: headers.display_name(address).map(str::to_owned),
+        };
+        let organizer = match event.organizer {
+            Some(dto) => validated_participant(dto)?,
+            None => participant(sender),
+        };
+        let attendees = match event.attendees {
+            Some(dtos) => dtos
+                .into_iter()
+                .map(validated_participant)
+                .collect::<Result<_, _>>()?,
+            None => recipients
+                .iter()
+                .filter(|(kind, _)| !matches!(kind, RecipientKind::Bcc))
+                .map(|(_, address)| participant(address))
+                .collect(),
+        };
+        Ok(CalendarInput::Event(CalendarEvent {
+            uid: event.uid,
+            sequence: event.sequence,
+            summary: event.summary,
+            description: event.description,
+            location: event.location,
+            start_ms: event.start_ms,
+            end_ms: event.end_ms,
+            organizer,
+            attendees,
+        }))
+    }
+}
+
+fn validated_participant(
+    dto: CalendarParticipantDto,
+) -> Result<CalendarParticipant, EnvelopeConversionError> {
+    use std::str::FromStr;
+    email_address::EmailAddress::from_str(&dto.address)
+        .context("parsing calendar participant")
+        .map_err(EnvelopeConversionError::InvalidCalendarParticipant)?;
+    Ok(CalendarParticipant {
+        address: dto.address,
+        name: dto.display_name,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplyToDto {
+    pub address: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -101,6 +262,31 @@ impl Serialize for SubmitEmailResponse {
     }
 }
 
+/// Response of `POST /emails/preview`.
+#[derive(Debug, Serialize)]
+pub struct EmailPreviewResponse {
+    pub subject: Option<String>,
+    pub html: Option<String>,
+    pub text: Option<String>,
+    pub warnings: Vec<String>,
+    /// Locale of the template variant used, if a localized one was found.
+    pub locale: Option<String>,
+}
+
+impl From<catapulte_domain::use_case::process_queued_email::EmailPreview> for EmailPreviewResponse {
+    fn from(preview: catapulte_domain::use_case::process_queued_email::EmailPreview) -> Self {
+        let warnings = preview.body.warnings().to_vec();
+        let (text, html) = preview.body.into_plain().into_parts();
+        Self {
+            subject: preview.subject,
+            html,
+            text,
+            warnings,
+            locale: preview.locale,
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum BodyConversionError {
     #[error("invalid plain body")]
@@ -137,6 +323,22 @@ pub enum EnvelopeConversionError {
     TooManyAttachments,
     #[error("attachment too large")]
     AttachmentTooLarge { filename: String },
+    #[error("invalid reply-to address")]
+    InvalidReplyTo(#[source] anyhow::Error),
+    #[error(transparent)]
+    InvalidHeader(#[from] InvalidHeader),
+    #[error(transparent)]
+    InvalidLocale(#[from] InvalidLocale),
+    #[error(transparent)]
+    InvalidContentId(#[from] InvalidContentId),
+    #[error("content id {0:?} is used by more than one attachment")]
+    DuplicateContentId(String),
+    #[error(transparent)]
+    InvalidCalendar(#[from] InvalidCalendar),
+    #[error("invalid calendar participant address")]
+    InvalidCalendarParticipant(#[source] anyhow::Error),
+    #[error(transparent)]
+    InvalidListName(#[from] InvalidListName),
 }
 
 fn validate_sender(sender: &str) -> Result<(), EnvelopeConversionError> {
@@ -149,6 +351,7 @@ fn validate_sender(sender: &str) -> Result<(), EnvelopeConversionError> {
 
 fn validate_recipients(
     recipients: Vec<RecipientDto>,
+    display_names: &mut BTreeMap<String, String>,
 ) -> Result<Vec<(catapulte_domain::entity::email::RecipientKind, String)>, EnvelopeConversionError>
 {
     use std::str::FromStr;
@@ -161,11 +364,78 @@ fn validate_recipients(
             email_address::EmailAddress::from_str(&r.address)
                 .context("parsing recipient")
                 .map_err(EnvelopeConversionError::InvalidRecipient)?;
+            if let Some(name) = r.display_name {
+                display_names.insert(r.address.clone(), name);
+            }
             Ok((r.kind.into(), r.address))
         })
         .collect::<Result<Vec<_>, EnvelopeConversionError>>()
 }
 
+/// Validates sender and recipients and collects the optional header fields
+/// of a submission.
+fn validate_addressing(
+    sender: &str,
+    sender_display_name: Option<String>,
+    recipients: Vec<RecipientDto>,
+    reply_to: Option<ReplyToDto>,
+    custom: BTreeMap<String, String>,
+) -> Result<
+    (
+        Vec<(catapulte_domain::entity::email::RecipientKind, String)>,
+        MessageHeaders,
+    ),
+    EnvelopeConversionError,
+> {
+    use std::str::FromStr;
+    validate_sender(sender)?;
+    let mut display_names = BTreeMap::new();
+    let recipients = validate_recipients(recipients, &mut display_names)?;
+    if let Some(name) = sender_display_name {
+        display_names.insert(sender.to_owned(), name);
+    }
+    let reply_to = match reply_to {
+        Some(ReplyToDto {
+            address,
+            display_name,
+        }) => {
+            email_address::EmailAddress::from_str(&address)
+                .context("parsing reply-to")
+                .map_err(EnvelopeConversionError::InvalidReplyTo)?;
+            if let Some(name) = display_name {
+                display_names.insert(address.clone(), name);
+            }
+            Some(address)
+        }
+        None => None,
+    };
+    let headers = MessageHeaders {
+        reply_to,
+        display_names,
+        custom,
+    };
+    headers.validate()?;
+    Ok((recipients, headers))
+}
+
+fn validated_locale(locale: String) -> Result<String, EnvelopeConversionError> {
+    validate_locale(&locale)?;
+    Ok(locale)
+}
+
+fn validate_content_ids(attachments: &[AttachmentInput]) -> Result<(), EnvelopeConversionError> {
+    let mut seen = std::collections::HashSet::new();
+    for content_id in attachments.iter().filter_map(AttachmentInput::content_id) {
+        validate_content_id(content_id)?;
+        if !seen.insert(content_id) {
+            return Err(EnvelopeConversionError::DuplicateContentId(
+                content_id.to_owned(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn attachment_dto_to_input(a: AttachmentDto) -> Result<AttachmentInput, EnvelopeConversionError> {
     match (a.inline_base64, a.url) {
         (Some(b64), None) => {
@@ -185,6 +455,7 @@ fn attachment_dto_to_input(a: AttachmentDto) -> Result<AttachmentInput, Envelope
                 filename: a.filename,
                 content_type: a.content_type,
                 bytes: bytes::Bytes::from(decoded),
+                content_id: a.content_id,
             })
         }
         (None, Some(raw_url)) => {
@@ -198,6 +469,7 @@ fn attachment_dto_to_input(a: AttachmentDto) -> Result<AttachmentInput, Envelope
                 filename: a.filename,
                 content_type: a.content_type,
                 url: parsed,
+                content_id: a.content_id,
             })
         }
         _ => Err(EnvelopeConversionError::InvalidAttachmentShape {
@@ -212,9 +484,23 @@ impl SubmitEmailRequest {
     /// Returns an error when the request contains invalid fields (bad sender,
     /// recipients, body, or attachments).
     pub fn into_submit_input(self) -> Result<SubmitEmailInput, EnvelopeConversionError> {
-        validate_sender(&self.sender)?;
-        let recipients = validate_recipients(self.recipients)?;
+        let (recipients, headers) = validate_addressing(
+            &self.sender,
+            self.sender_display_name,
+            self.recipients,
+            self.reply_to,
+            self.headers,
+        )?;
         let body = self.body.try_into()?;
+        let locale = self.locale.map(validated_locale).transpose()?;
+        let calendar = self
+            .calendar
+            .map(|c| c.into_calendar(&self.sender, &recipients, &headers))
+            .transpose()?;
+        let list = self
+            .unsubscribe
+            .map(|u| MailingList::parse(&u.list))
+            .transpose()?;
 
         if self.attachments.len() > MAX_ATTACHMENTS_PER_EMAIL {
             return Err(EnvelopeConversionError::TooManyAttachments);
@@ -223,6 +509,7 @@ impl SubmitEmailRequest {
         for a in self.attachments {
             atts.push(attachment_dto_to_input(a)?);
         }
+        validate_content_ids(&atts)?;
 
         Ok(SubmitEmailInput {
             idempotency_key: self.idempotency_key,
@@ -233,6 +520,12 @@ impl SubmitEmailRequest {
             body,
             variables: self.variables,
             attachments: atts,
+            send_at_ms: self.send_at_ms,
+            headers,
+            locale,
+            calendar,
+            list,
+            tracking: self.tracking.map(Tracking::from).unwrap_or_default(),
         })
     }
 }
@@ -247,10 +540,26 @@ pub struct EnvelopeCoreDto {
     #[serde(default)]
     pub subject: Option<String>,
     pub sender: String,
+    #[serde(default)]
+    pub sender_display_name: Option<String>,
     pub recipients: Vec<RecipientDto>,
+    #[serde(default)]
+    pub reply_to: Option<ReplyToDto>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
     pub body: BodyDto,
     #[serde(default)]
     pub variables: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    pub send_at_ms: Option<i64>,
+    #[serde(default)]
+    pub locale: Option<String>,
+    #[serde(default)]
+    pub calendar: Option<CalendarDto>,
+    #[serde(default)]
+    pub unsubscribe: Option<UnsubscribeDto>,
+    #[serde(default)]
+    pub tracking: Option<TrackingDto>,
 }
 
 impl EnvelopeCoreDto {
@@ -263,9 +572,24 @@ impl EnvelopeCoreDto {
         self,
         attachments: Vec<AttachmentInput>,
     ) -> Result<SubmitEmailInput, EnvelopeConversionError> {
-        validate_sender(&self.sender)?;
-        let recipients = validate_recipients(self.recipients)?;
+        validate_content_ids(&attachments)?;
+        let (recipients, headers) = validate_addressing(
+            &self.sender,
+            self.sender_display_name,
+            self.recipients,
+            self.reply_to,
+            self.headers,
+        )?;
         let body = self.body.try_into()?;
+        let locale = self.locale.map(validated_locale).transpose()?;
+        let calendar = self
+            .calendar
+            .map(|c| c.into_calendar(&self.sender, &recipients, &headers))
+            .transpose()?;
+        let list = self
+            .unsubscribe
+            .map(|u| MailingList::parse(&u.list))
+            .transpose()?;
         Ok(SubmitEmailInput {
             idempotency_key: self.idempotency_key,
             correlation_id: self.correlation_id,
@@ -275,6 +599,12 @@ impl EnvelopeCoreDto {
             body,
             variables: self.variables,
             attachments,
+            send_at_ms: self.send_at_ms,
+            headers,
+            locale,
+            calendar,
+            list,
+            tracking: self.tracking.map(Tracking::from).unwrap_or_default(),
         })
     }
 }
@@ -320,9 +650,12 @@ impl TryFrom<BodyDto> for BodySource {
 
 #[cfg(test)]
 mod tests {
+    use catapulte_domain::entity::unsubscribe::MailingList;
+
     use super::{
-        AttachmentDto, BodyConversionError, BodyDto, BodySource, EnvelopeConversionError,
-        MjmlSource, RecipientDto, RecipientKindDto, SubmitEmailRequest,
+        AttachmentDto, BodyConversionError, BodyDto, BodySource, CalendarInput,
+        CalendarParticipant, EnvelopeConversionError, InvalidHeader, MjmlSource, RecipientDto,
+        RecipientKindDto, ReplyToDto, SubmitEmailRequest,
     };
 
     fn base_request() -> SubmitEmailRequest {
@@ -331,9 +664,11 @@ mod tests {
             correlation_id: None,
             subject: None,
             sender: "a@b.c".into(),
+            sender_display_name: None,
             recipients: vec![RecipientDto {
                 kind: RecipientKindDto::To,
                 address: "t@x.y".into(),
+                display_name: None,
             }],
             body: BodyDto::Plain {
                 text: Some("hi".into()),
@@ -341,9 +676,131 @@ mod tests {
             },
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            reply_to: None,
+            headers: std::collections::BTreeMap::new(),
+            locale: None,
+            calendar: None,
+            unsubscribe: None,
+            tracking: None,
         }
     }
 
+    #[test]
+    fn calendar_events_default_to_the_sender_and_visible_recipients() {
+        let req: SubmitEmailRequest = serde_json::from_value(serde_json::json!({
+            "sender": "alice@acme.com",
+            "sender_display_name": "Alice",
+            "recipients": [
+                { "kind": "to", "address": "bob@example.com" },
+                { "kind": "bcc", "address": "audit@acme.com" }
+            ],
+            "body": { "kind": "plain", "text": "See you there" },
+            "calendar": {
+                "kind": "event",
+                "uid": "kickoff-42@acme.com",
+                "summary": "Kickoff",
+                "start_ms": 1_772_375_400_000_i64,
+                "end_ms": 1_772_379_000_000_i64
+            }
+        }))
+        .unwrap();
+        let Some(CalendarInput::Event(event)) = req.into_submit_input().unwrap().calendar else {
+            panic!("expected an event");
+        };
+        assert_eq!(
+            event.organizer,
+            CalendarParticipant {
+                address: "alice@acme.com".into(),
+                name: Some("Alice".into()),
+            }
+        );
+        assert_eq!(
+            event.attendees,
+            vec![CalendarParticipant {
+                address: "bob@example.com".into(),
+                name: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_calendars_are_rejected() {
+        let raw = SubmitEmailRequest {
+            calendar: Some(super::CalendarDto::Raw {
+                content: "BEGIN:VCALENDAR\r\nEND:VCALENDAR".into(),
+            }),
+            ..base_request()
+        };
+        assert!(matches!(
+            raw.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidCalendar(_))
+        ));
+
+        let req: SubmitEmailRequest = serde_json::from_value(serde_json::json!({
+            "sender": "a@b.c",
+            "recipients": [{ "kind": "to", "address": "t@x.y" }],
+            "body": { "kind": "plain", "text": "hi" },
+            "calendar": {
+                "kind": "event",
+                "uid": "1",
+                "summary": "Sync",
+                "start_ms": 0,
+                "end_ms": 1,
+                "attendees": [{ "address": "not an address" }]
+            }
+        }))
+        .unwrap();
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidCalendarParticipant(_))
+        ));
+    }
+
+    #[test]
+    fn unsubscribe_lists_are_validated() {
+        let req: SubmitEmailRequest = serde_json::from_value(serde_json::json!({
+            "sender": "news@acme.com",
+            "recipients": [{ "kind": "to", "address": "bob@example.com" }],
+            "body": { "kind": "plain", "text": "hi" },
+            "unsubscribe": { "list": "newsletter" },
+        }))
+        .unwrap();
+        let input = req.into_submit_input().unwrap();
+        assert_eq!(
+            input.list.as_ref().map(MailingList::as_str),
+            Some("newsletter")
+        );
+
+        let req = SubmitEmailRequest {
+            unsubscribe: Some(super::UnsubscribeDto {
+                list: "news letter".into(),
+            }),
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidListName(_))
+        ));
+    }
+
+    #[test]
+    fn tracking_is_off_unless_asked_for() {
+        let input = base_request().into_submit_input().unwrap();
+        assert!(!input.tracking.is_enabled());
+
+        let req: SubmitEmailRequest = serde_json::from_value(serde_json::json!({
+            "sender": "news@acme.com",
+            "recipients": [{ "kind": "to", "address": "bob@example.com" }],
+            "body": { "kind": "plain", "html": "<p>hi</p>" },
+            "tracking": { "clicks": true },
+        }))
+        .unwrap();
+        let tracking = req.into_submit_input().unwrap().tracking;
+        assert!(!tracking.opens);
+        assert!(tracking.clicks);
+    }
+
     #[test]
     fn plain_with_text_converts_to_plain_body() {
         let dto = BodyDto::Plain {
@@ -412,6 +869,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn send_at_ms_is_carried_into_submit_input() {
+        let req = SubmitEmailRequest {
+            send_at_ms: Some(1_700_000_000_000),
+            ..base_request()
+        };
+        let input = req.into_submit_input().unwrap();
+        assert_eq!(input.send_at_ms, Some(1_700_000_000_000));
+    }
+
     #[test]
     fn invalid_sender_returns_error() {
         let req = SubmitEmailRequest {
@@ -442,6 +909,7 @@ mod tests {
             recipients: vec![RecipientDto {
                 kind: RecipientKindDto::To,
                 address: "bad".into(),
+                display_name: None,
             }],
             ..base_request()
         };
@@ -451,6 +919,82 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn display_names_reply_to_and_headers_are_carried_into_submit_input() {
+        let req = SubmitEmailRequest {
+            sender: "billing@acme.com".into(),
+            sender_display_name: Some("Acme Billing".into()),
+            recipients: vec![RecipientDto {
+                kind: RecipientKindDto::To,
+                address: "jane@example.com".into(),
+                display_name: Some("Jane Doe".into()),
+            }],
+            reply_to: Some(ReplyToDto {
+                address: "support@acme.com".into(),
+                display_name: None,
+            }),
+            headers: [("X-Campaign-Id".to_owned(), "spring-sale".to_owned())].into(),
+            ..base_request()
+        };
+        let headers = req.into_submit_input().unwrap().headers;
+        assert_eq!(
+            headers.display_name("billing@acme.com"),
+            Some("Acme Billing")
+        );
+        assert_eq!(headers.display_name("jane@example.com"), Some("Jane Doe"));
+        assert_eq!(headers.reply_to.as_deref(), Some("support@acme.com"));
+        assert_eq!(headers.custom["X-Campaign-Id"], "spring-sale");
+    }
+
+    #[test]
+    fn reserved_header_returns_error() {
+        let req = SubmitEmailRequest {
+            headers: [("Bcc".to_owned(), "spy@example.com".to_owned())].into(),
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidHeader(
+                InvalidHeader::Reserved(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn locale_is_validated_and_carried_into_submit_input() {
+        let req = SubmitEmailRequest {
+            locale: Some("fr-CA".into()),
+            ..base_request()
+        };
+        assert_eq!(
+            req.into_submit_input().unwrap().locale.as_deref(),
+            Some("fr-CA")
+        );
+        let req = SubmitEmailRequest {
+            locale: Some("fr_CA".into()),
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidLocale(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_reply_to_returns_error() {
+        let req = SubmitEmailRequest {
+            reply_to: Some(ReplyToDto {
+                address: "nope".into(),
+                display_name: None,
+            }),
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidReplyTo(_))
+        ));
+    }
+
     #[test]
     fn valid_base64_attachment_decodes_correctly() {
         use base64::Engine;
@@ -463,6 +1007,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: Some(encoded),
                 url: None,
+                content_id: None,
             }],
             ..base_request()
         };
@@ -474,6 +1019,42 @@ mod tests {
         assert_eq!(bytes.as_ref(), content);
     }
 
+    #[test]
+    fn content_ids_are_validated_and_unique() {
+        let logo = |content_id: &str| AttachmentDto {
+            filename: "logo.png".into(),
+            content_type: "image/png".into(),
+            inline_base64: None,
+            url: Some("https://cdn.example.com/logo.png".into()),
+            content_id: Some(content_id.into()),
+        };
+        let input = SubmitEmailRequest {
+            attachments: vec![logo("logo")],
+            ..base_request()
+        }
+        .into_submit_input()
+        .unwrap();
+        assert_eq!(input.attachments[0].content_id(), Some("logo"));
+
+        let req = SubmitEmailRequest {
+            attachments: vec![logo("<logo>")],
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::InvalidContentId(_))
+        ));
+
+        let req = SubmitEmailRequest {
+            attachments: vec![logo("logo"), logo("logo")],
+            ..base_request()
+        };
+        assert!(matches!(
+            req.into_submit_input(),
+            Err(EnvelopeConversionError::DuplicateContentId(id)) if id == "logo"
+        ));
+    }
+
     #[test]
     fn invalid_base64_attachment_returns_error() {
         let req = SubmitEmailRequest {
@@ -482,6 +1063,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: Some("not valid base64!!!".into()),
                 url: None,
+                content_id: None,
             }],
             ..base_request()
         };
@@ -502,6 +1084,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: Some(encoded.clone()),
                 url: None,
+                content_id: None,
             })
             .collect();
         let req = SubmitEmailRequest {
@@ -526,6 +1109,7 @@ mod tests {
                 content_type: "application/octet-stream".into(),
                 inline_base64: Some(encoded),
                 url: None,
+                content_id: None,
             }],
             ..base_request()
         };
@@ -544,6 +1128,7 @@ mod tests {
                 content_type: "application/pdf".into(),
                 inline_base64: None,
                 url: Some("https://example.com/file.pdf".into()),
+                content_id: None,
             }],
             ..base_request()
         };
@@ -565,6 +1150,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: Some(encoded),
                 url: Some("https://example.com/file.txt".into()),
+                content_id: None,
             }],
             ..base_request()
         };
@@ -582,6 +1168,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: None,
                 url: None,
+                content_id: None,
             }],
             ..base_request()
         };
@@ -599,6 +1186,7 @@ mod tests {
                 content_type: "text/plain".into(),
                 inline_base64: None,
                 url: Some("not a url at all".into()),
+                content_id: None,
             }],
             ..base_request()
         };
@@ -629,6 +1217,19 @@ pub struct ListEventsQuery {
     pub offset: Option<u32>,
 }
 
+/// The filters of [`ListEventsQuery`]; a stream has no pages.
+#[derive(Debug, Deserialize)]
+pub struct StreamEventsQuery {
+    #[serde(default)]
+    pub email_id: Option<String>,
+    #[serde(default)]
+    pub event_type: Option<String>,
+    #[serde(default)]
+    pub sender_name: Option<String>,
+    #[serde(default)]
+    pub error_class: Option<String>,
+}
+
 pub const DEFAULT_EVENTS_LIMIT: u32 = 20;
 pub const MAX_EVENTS_LIMIT: u32 = 100;
 
@@ -667,17 +1268,23 @@ pub struct ListEventsResponse {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EmailStatusDto {
+    Scheduled,
     Queued,
     Sent,
     Failed,
+    Cancelled,
+    Suppressed,
 }
 
 impl From<EmailStatusDto> for catapulte_domain::port::email_repository::EmailStatus {
     fn from(s: EmailStatusDto) -> Self {
         match s {
+            EmailStatusDto::Scheduled => Self::Scheduled,
             EmailStatusDto::Queued => Self::Queued,
             EmailStatusDto::Sent => Self::Sent,
             EmailStatusDto::Failed => Self::Failed,
+            EmailStatusDto::Cancelled => Self::Cancelled,
+            EmailStatusDto::Suppressed => Self::Suppressed,
         }
     }
 }
@@ -719,7 +1326,15 @@ pub struct EmailRecordDto {
     pub sender: String,
     pub recipients: Vec<RecipientResponseDto>,
     pub created_at_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_at_ms: Option<i64>,
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template_version: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 impl From<catapulte_domain::port::email_repository::EmailRecord> for EmailRecordDto {
@@ -727,9 +1342,12 @@ impl From<catapulte_domain::port::email_repository::EmailRecord> for EmailRecord
         use catapulte_domain::entity::email::RecipientKind;
         use catapulte_domain::port::email_repository::EmailStatus;
         let status = match r.status {
+            EmailStatus::Scheduled => "scheduled",
             EmailStatus::Queued => "queued",
             EmailStatus::Sent => "sent",
             EmailStatus::Failed => "failed",
+            EmailStatus::Cancelled => "cancelled",
+            EmailStatus::Suppressed => "suppressed",
         };
         let recipients = r
             .recipients
@@ -750,7 +1368,11 @@ impl From<catapulte_domain::port::email_repository::EmailRecord> for EmailRecord
             sender: r.sender,
             recipients,
             created_at_ms: r.created_at_ms,
+            send_at_ms: r.send_at_ms,
             status: status.to_owned(),
+            template: r.template,
+            template_version: r.template_version,
+            locale: r.locale,
         }
     }
 }
@@ -782,3 +1404,231 @@ pub struct SenderDto {
 pub struct ListSendersResponse {
     pub senders: Vec<SenderDto>,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct ListSuppressionsQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+pub const DEFAULT_SUPPRESSIONS_LIMIT: u32 = 20;
+pub const MAX_SUPPRESSIONS_LIMIT: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct AddSuppressionRequest {
+    pub address: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SuppressionDto {
+    pub address: String,
+    pub reason: String,
+    pub created_at_ms: i64,
+}
+
+impl From<catapulte_domain::port::suppression_list::Suppression> for SuppressionDto {
+    fn from(s: catapulte_domain::port::suppression_list::Suppression) -> Self {
+        Self {
+            address: s.address,
+            reason: s.reason,
+            created_at_ms: s.created_at_ms,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSuppressionsResponse {
+    pub suppressions: Vec<SuppressionDto>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatusDto {
+    Pending,
+    Failed,
+}
+
+impl From<WebhookDeliveryStatusDto>
+    for catapulte_domain::port::webhook_outbox::WebhookDeliveryStatus
+{
+    fn from(s: WebhookDeliveryStatusDto) -> Self {
+        match s {
+            WebhookDeliveryStatusDto::Pending => Self::Pending,
+            WebhookDeliveryStatusDto::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<catapulte_domain::port::webhook_outbox::WebhookDeliveryStatus>
+    for WebhookDeliveryStatusDto
+{
+    fn from(s: catapulte_domain::port::webhook_outbox::WebhookDeliveryStatus) -> Self {
+        match s {
+            catapulte_domain::port::webhook_outbox::WebhookDeliveryStatus::Pending => Self::Pending,
+            catapulte_domain::port::webhook_outbox::WebhookDeliveryStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookDeliveriesQuery {
+    #[serde(default)]
+    pub status: Option<WebhookDeliveryStatusDto>,
+    #[serde(default)]
+    pub subscription_id: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+pub const DEFAULT_WEBHOOK_DELIVERIES_LIMIT: u32 = 20;
+pub const MAX_WEBHOOK_DELIVERIES_LIMIT: u32 = 100;
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryDto {
+    pub id: String,
+    pub event_id: String,
+    /// `webhook` or `nats`.
+    pub target: &'static str,
+    /// `None` for the webhook configured by the operator, and for NATS.
+    pub subscription_id: Option<String>,
+    pub email_id: String,
+    pub event_type: String,
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatusDto,
+    pub attempts: u32,
+    pub next_attempt_at_ms: i64,
+    pub last_error: Option<String>,
+    pub created_at_ms: i64,
+}
+
+impl From<catapulte_domain::port::webhook_outbox::WebhookDelivery> for WebhookDeliveryDto {
+    fn from(d: catapulte_domain::port::webhook_outbox::WebhookDelivery) -> Self {
+        Self {
+            id: d.id.to_string(),
+            event_id: d.event_id.to_string(),
+            target: d.target.as_str(),
+            subscription_id: d.subscription_id.map(|id| id.to_string()),
+            email_id: d.email_id.as_uuid().to_string(),
+            event_type: d.event_type,
+            payload: d.payload,
+            status: d.status.into(),
+            attempts: d.attempts,
+            next_attempt_at_ms: d.next_attempt_at_ms,
+            last_error: d.last_error,
+            created_at_ms: d.created_at_ms,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListWebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryDto>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookSubscriptionsQuery {
+    #[serde(default)]
+    pub limit: Option<u32>,
+    #[serde(default)]
+    pub offset: Option<u32>,
+}
+
+pub const DEFAULT_WEBHOOK_SUBSCRIPTIONS_LIMIT: u32 = 20;
+pub const MAX_WEBHOOK_SUBSCRIPTIONS_LIMIT: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookSubscriptionRequest {
+    pub url: String,
+    pub secret: String,
+    /// Every event type when empty.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    #[serde(default)]
+    pub correlation_id_prefix: Option<String>,
+}
+
+impl From<CreateWebhookSubscriptionRequest>
+    for catapulte_domain::use_case::manage_webhook_subscriptions::CreateWebhookSubscriptionInput
+{
+    fn from(r: CreateWebhookSubscriptionRequest) -> Self {
+        Self {
+            url: r.url,
+            secret: r.secret,
+            filter: catapulte_domain::entity::webhook_subscription::SubscriptionFilter {
+                event_types: r.event_types,
+                correlation_id_prefix: r.correlation_id_prefix,
+            },
+        }
+    }
+}
+
+/// The secret is write-only and never echoed back.
+#[derive(Debug, Serialize)]
+pub struct WebhookSubscriptionDto {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub correlation_id_prefix: Option<String>,
+    pub created_at_ms: i64,
+}
+
+impl From<catapulte_domain::entity::webhook_subscription::WebhookSubscription>
+    for WebhookSubscriptionDto
+{
+    fn from(s: catapulte_domain::entity::webhook_subscription::WebhookSubscription) -> Self {
+        Self {
+            id: s.id.to_string(),
+            url: s.url.to_string(),
+            event_types: s.filter.event_types,
+            correlation_id_prefix: s.filter.correlation_id_prefix,
+            created_at_ms: s.created_at_ms,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListWebhookSubscriptionsResponse {
+    pub subscriptions: Vec<WebhookSubscriptionDto>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GetTemplateQuery {
+    #[serde(default)]
+    pub version: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PutTemplateRequest {
+    pub content: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateDto {
+    pub name: String,
+    pub version: u32,
+    pub content: String,
+    pub created_at_ms: i64,
+}
+
+impl From<catapulte_domain::entity::template::Template> for TemplateDto {
+    fn from(t: catapulte_domain::entity::template::Template) -> Self {
+        Self {
+            name: t.name,
+            version: t.version,
+            content: t.content,
+            created_at_ms: t.created_at_ms,
+        }
+    }
+}
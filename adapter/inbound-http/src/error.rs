@@ -3,10 +3,19 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 
+use catapulte_domain::use_case::cancel_email::CancelEmailError;
 use catapulte_domain::use_case::list_emails::ListEmailsError;
 use catapulte_domain::use_case::list_events::ListEventsError;
 use catapulte_domain::use_case::list_senders::ListSendersError;
+use catapulte_domain::use_case::manage_suppressions::ManageSuppressionsError;
+use catapulte_domain::use_case::manage_templates::ManageTemplatesError;
+use catapulte_domain::use_case::manage_webhook_deliveries::ManageWebhookDeliveriesError;
+use catapulte_domain::use_case::manage_webhook_subscriptions::ManageWebhookSubscriptionsError;
+use catapulte_domain::use_case::process_queued_email::ProcessQueuedEmailError;
+use catapulte_domain::use_case::stream_events::StreamEventsError;
 use catapulte_domain::use_case::submit_email::SubmitEmailError;
+use catapulte_domain::use_case::track_engagement::TrackEngagementError;
+use catapulte_domain::use_case::unsubscribe::UnsubscribeError;
 
 use crate::dto::EnvelopeConversionError;
 
@@ -23,7 +32,25 @@ pub enum AppError {
     #[error(transparent)]
     ListEvents(#[from] ListEventsError),
     #[error(transparent)]
+    StreamEvents(#[from] StreamEventsError),
+    #[error(transparent)]
     ListSenders(#[from] ListSendersError),
+    #[error(transparent)]
+    CancelEmail(#[from] CancelEmailError),
+    #[error(transparent)]
+    Suppressions(#[from] ManageSuppressionsError),
+    #[error(transparent)]
+    Templates(#[from] ManageTemplatesError),
+    #[error(transparent)]
+    Preview(#[from] ProcessQueuedEmailError),
+    #[error(transparent)]
+    Unsubscribe(#[from] UnsubscribeError),
+    #[error(transparent)]
+    TrackEngagement(#[from] TrackEngagementError),
+    #[error(transparent)]
+    WebhookDeliveries(#[from] ManageWebhookDeliveriesError),
+    #[error(transparent)]
+    WebhookSubscriptions(#[from] ManageWebhookSubscriptionsError),
     #[error("invalid email id")]
     InvalidEmailId,
     #[error("invalid error_class value")]
@@ -35,24 +62,88 @@ struct ErrorBody<'a> {
     error: &'a str,
 }
 
+/// Template failures are the caller's to fix, so they are reported in full.
+#[derive(Serialize)]
+struct TemplateErrorBody<'a> {
+    error: &'a str,
+    error_class: &'a str,
+    reason: String,
+}
+
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut reason = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        reason.push_str(": ");
+        reason.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    reason
+}
+
+fn template_error(status: StatusCode, err: &ProcessQueuedEmailError) -> Response {
+    let body = TemplateErrorBody {
+        error: "template error",
+        error_class: err.error_class().as_str(),
+        reason: error_chain(err),
+    };
+    (status, Json(body)).into_response()
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        if let Self::Preview(err) = &self {
+            tracing::info!(error = ?err, "preview failed");
+            return template_error(StatusCode::UNPROCESSABLE_ENTITY, err);
+        }
+        if let Self::Submit(SubmitEmailError::InvalidContent { source }) = &self {
+            tracing::info!(error = ?source, "submitted content rejected");
+            return template_error(StatusCode::BAD_REQUEST, source);
+        }
         let (status, message) = match &self {
             Self::BadRequest(_)
             | Self::BadRequestRaw(_)
             | Self::InvalidEmailId
             | Self::InvalidErrorClass
-            | Self::Submit(SubmitEmailError::AttachmentFetch { .. }) => {
+            | Self::Templates(
+                ManageTemplatesError::InvalidName(_) | ManageTemplatesError::EmptyContent,
+            )
+            | Self::Submit(
+                SubmitEmailError::AttachmentFetch { .. }
+                | SubmitEmailError::ListUnsubscribe(_)
+                | SubmitEmailError::InvalidCalendar(_),
+            )
+            | Self::WebhookSubscriptions(ManageWebhookSubscriptionsError::Invalid(_)) => {
                 (StatusCode::BAD_REQUEST, "invalid request")
             }
+            Self::CancelEmail(CancelEmailError::NotFound)
+            | Self::Suppressions(ManageSuppressionsError::NotFound)
+            | Self::Templates(ManageTemplatesError::NotFound)
+            | Self::Unsubscribe(UnsubscribeError::InvalidToken)
+            | Self::TrackEngagement(TrackEngagementError::InvalidToken)
+            | Self::WebhookDeliveries(ManageWebhookDeliveriesError::NotFound)
+            | Self::WebhookSubscriptions(ManageWebhookSubscriptionsError::NotFound) => {
+                (StatusCode::NOT_FOUND, "not found")
+            }
+            Self::CancelEmail(CancelEmailError::Conflict) => (StatusCode::CONFLICT, "conflict"),
             Self::Submit(
                 SubmitEmailError::Persist(_)
                 | SubmitEmailError::Enqueue(_)
-                | SubmitEmailError::AttachmentStore { .. },
+                | SubmitEmailError::TemplateStore(_)
+                | SubmitEmailError::AttachmentStore { .. }
+                | SubmitEmailError::InvalidContent { .. },
             )
             | Self::ListEmails(_)
             | Self::ListEvents(_)
-            | Self::ListSenders(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal error"),
+            | Self::StreamEvents(_)
+            | Self::ListSenders(_)
+            | Self::CancelEmail(CancelEmailError::Persist(_))
+            | Self::Suppressions(ManageSuppressionsError::Storage(_))
+            | Self::Templates(ManageTemplatesError::Storage(_))
+            | Self::Unsubscribe(UnsubscribeError::Storage(_))
+            | Self::WebhookDeliveries(ManageWebhookDeliveriesError::Storage(_))
+            | Self::WebhookSubscriptions(ManageWebhookSubscriptionsError::Storage(_))
+            | Self::Preview(_) => (StatusCode::INTERNAL_SERVER_ERROR, "internal error"),
         };
         tracing::error!(error = ?self, status = %status.as_u16(), "request failed");
         (status, Json(ErrorBody { error: message })).into_response()
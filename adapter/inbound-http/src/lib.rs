@@ -2,6 +2,11 @@ pub mod dto;
 pub mod error;
 pub mod limited_reader;
 pub mod routes;
+mod signed_token;
+#[cfg(test)]
+mod test_support;
+pub mod tracking;
+pub mod unsubscribe;
 
 use std::net::SocketAddr;
 
@@ -10,12 +15,22 @@ use axum::Router;
 use axum::extract::DefaultBodyLimit;
 use axum::http::StatusCode;
 use axum::middleware::Next;
+use axum::routing::delete;
 use axum::routing::get;
 use axum::routing::post;
+use catapulte_domain::use_case::cancel_email::CancelEmailUseCase;
 use catapulte_domain::use_case::list_emails::ListEmailsUseCase;
 use catapulte_domain::use_case::list_events::ListEventsUseCase;
 use catapulte_domain::use_case::list_senders::ListSendersUseCase;
+use catapulte_domain::use_case::manage_suppressions::ManageSuppressionsUseCase;
+use catapulte_domain::use_case::manage_templates::ManageTemplatesUseCase;
+use catapulte_domain::use_case::manage_webhook_deliveries::ManageWebhookDeliveriesUseCase;
+use catapulte_domain::use_case::manage_webhook_subscriptions::ManageWebhookSubscriptionsUseCase;
+use catapulte_domain::use_case::process_queued_email::PreviewEmailUseCase;
+use catapulte_domain::use_case::stream_events::StreamEventsUseCase;
 use catapulte_domain::use_case::submit_email::SubmitEmailUseCase;
+use catapulte_domain::use_case::track_engagement::TrackEngagementUseCase;
+use catapulte_domain::use_case::unsubscribe::UnsubscribeUseCase;
 use tokio_util::sync::CancellationToken;
 use tower_http::trace::TraceLayer;
 
@@ -35,7 +50,16 @@ pub trait HttpServerState: ReadinessState {
     fn submit_email(&self) -> &impl SubmitEmailUseCase;
     fn list_emails(&self) -> &impl ListEmailsUseCase;
     fn list_events(&self) -> &impl ListEventsUseCase;
+    fn stream_events(&self) -> &impl StreamEventsUseCase;
     fn list_senders(&self) -> &impl ListSendersUseCase;
+    fn cancel_email(&self) -> &impl CancelEmailUseCase;
+    fn suppressions(&self) -> &impl ManageSuppressionsUseCase;
+    fn preview_email(&self) -> &impl PreviewEmailUseCase;
+    fn templates(&self) -> &impl ManageTemplatesUseCase;
+    fn unsubscribe(&self) -> &impl UnsubscribeUseCase;
+    fn track_engagement(&self) -> &impl TrackEngagementUseCase;
+    fn webhook_deliveries(&self) -> &impl ManageWebhookDeliveriesUseCase;
+    fn webhook_subscriptions(&self) -> &impl ManageWebhookSubscriptionsUseCase;
 }
 
 /// Compares two byte slices in constant time to avoid timing side-channels.
@@ -83,9 +107,13 @@ async fn require_api_key(
 
 /// Builds the application router.
 ///
-/// When `api_key` is `Some`, all routes except `/health/live` and
-/// `/health/ready` are gated behind `Authorization: Bearer <key>`.
+/// When `api_key` is `Some`, all routes except `/health/live`,
+/// `/health/ready`, `/unsubscribe/{token}` and `/track/...` are gated behind
+/// `Authorization: Bearer <key>`.
 /// When `api_key` is `None`, no authentication is applied.
+///
+/// `/events/stream` takes a `CancellationToken` request extension, ending
+/// its streams once cancelled; [`InboundHttpServer::run`] layers it on.
 pub fn router<S: HttpServerState>(
     state: S,
     api_key: Option<String>,
@@ -95,7 +123,8 @@ pub fn router<S: HttpServerState>(
     // below: those stream multipart attachment bodies (up to several hundred MiB)
     // and a whole-request deadline would truncate legitimate large uploads over
     // slow links. Submit is instead bounded by the body-size limit and the
-    // per-attachment fetch timeouts. Reads and the health probes are bounded here.
+    // per-attachment fetch timeouts. Reads, previews, cancellation, suppression
+    // and template management and the health probes are bounded here.
     let timeout_layer = tower_http::timeout::TimeoutLayer::with_status_code(
         axum::http::StatusCode::REQUEST_TIMEOUT,
         request_timeout,
@@ -107,14 +136,81 @@ pub fn router<S: HttpServerState>(
         .layer(timeout_layer)
         .with_state(state.clone());
 
+    // Reached from mailbox providers, so never behind the API key: the signed
+    // token is the credential.
+    let unsubscribe_routes = Router::new()
+        .route(
+            "/unsubscribe/{token}",
+            post(crate::routes::unsubscribe::unsubscribe::<S>),
+        )
+        .layer(timeout_layer)
+        .with_state(state.clone());
+
+    // Loaded by mail clients and followed by readers, so public as well.
+    let tracking_routes = Router::new()
+        .route(
+            "/track/open/{token}",
+            get(crate::routes::tracking::open::<S>),
+        )
+        .route(
+            "/track/click/{token}",
+            get(crate::routes::tracking::click::<S>),
+        )
+        .layer(timeout_layer)
+        .with_state(state.clone());
+
     let read_routes = Router::new()
         .route("/emails", get(crate::routes::emails::list_emails::<S>))
+        .route(
+            "/emails/preview",
+            post(crate::routes::emails::preview_email::<S>),
+        )
+        .route(
+            "/emails/{id}",
+            delete(crate::routes::emails::cancel_email::<S>),
+        )
         .route(
             "/emails/{id}/events",
             get(crate::routes::events::list_events_for_email::<S>),
         )
         .route("/events", get(crate::routes::events::list_events::<S>))
+        .route(
+            "/events/stream",
+            get(crate::routes::events::stream_events::<S>),
+        )
         .route("/senders", get(crate::routes::senders::list_senders::<S>))
+        .route(
+            "/suppressions",
+            get(crate::routes::suppressions::list_suppressions::<S>)
+                .post(crate::routes::suppressions::add_suppression::<S>),
+        )
+        .route(
+            "/suppressions/{address}",
+            delete(crate::routes::suppressions::remove_suppression::<S>),
+        )
+        .route(
+            "/templates/{name}",
+            get(crate::routes::templates::get_template::<S>)
+                .put(crate::routes::templates::put_template::<S>)
+                .delete(crate::routes::templates::delete_template::<S>),
+        )
+        .route(
+            "/subscriptions",
+            get(crate::routes::subscriptions::list_subscriptions::<S>)
+                .post(crate::routes::subscriptions::create_subscription::<S>),
+        )
+        .route(
+            "/subscriptions/{id}",
+            delete(crate::routes::subscriptions::delete_subscription::<S>),
+        )
+        .route(
+            "/webhook-deliveries",
+            get(crate::routes::webhook_deliveries::list_webhook_deliveries::<S>),
+        )
+        .route(
+            "/webhook-deliveries/{id}/redeliver",
+            post(crate::routes::webhook_deliveries::redeliver_webhook_delivery::<S>),
+        )
         .layer(timeout_layer);
 
     let submit_routes = Router::new()
@@ -136,6 +232,8 @@ pub fn router<S: HttpServerState>(
     Router::new()
         .merge(protected_routes)
         .merge(health_routes)
+        .merge(unsubscribe_routes)
+        .merge(tracking_routes)
         .layer(TraceLayer::new_for_http())
         .layer(DefaultBodyLimit::max(crate::dto::MAX_REQUEST_BODY_BYTES))
 }
@@ -211,7 +309,11 @@ impl InboundHttpServer {
             .await
             .context("binding http listener")?;
         tracing::info!(address = %self.address, "http server listening");
-        axum::serve(listener, router(state, self.api_key, self.request_timeout))
+        // Lets `/events/stream` end its streams, which would otherwise hold
+        // the graceful shutdown open.
+        let app = router(state, self.api_key, self.request_timeout)
+            .layer(axum::Extension(cancel.clone()));
+        axum::serve(listener, app)
             .with_graceful_shutdown(async move { cancel.cancelled().await })
             .await
             .context("http server stopped")?;
@@ -1,18 +1,23 @@
 use axum::Json;
 use axum::body::Body;
-use axum::extract::{FromRequest, Multipart, Query, Request, State};
+use axum::extract::{FromRequest, Multipart, Path, Query, Request, State};
+use axum::http::StatusCode;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::port::email_repository::ListEmailsParams;
+use catapulte_domain::use_case::cancel_email::CancelEmailUseCase;
 use catapulte_domain::use_case::list_emails::ListEmailsUseCase;
-use catapulte_domain::use_case::submit_email::{AttachmentInput, SubmitEmailUseCase};
+use catapulte_domain::use_case::process_queued_email::PreviewEmailUseCase;
+use catapulte_domain::use_case::submit_email::{
+    AttachmentInput, SubmitEmailError, SubmitEmailUseCase,
+};
 use futures_util::TryStreamExt;
 
 use crate::HttpServerState;
 use crate::dto::{
     BatchItemResultDto, BatchSubmitEmailRequest, BatchSubmitEmailResponse, DEFAULT_EMAILS_LIMIT,
-    EmailRecordDto, EnvelopeCoreDto, ListEmailsQuery, ListEmailsResponse, MAX_ATTACHMENT_BYTES,
-    MAX_ATTACHMENTS_PER_EMAIL, MAX_EMAILS_LIMIT, MAX_EMAILS_PER_BATCH, MAX_ENVELOPE_BYTES,
-    MAX_REQUEST_BODY_BYTES, SubmitEmailRequest, SubmitEmailResponse,
+    EmailPreviewResponse, EmailRecordDto, EnvelopeCoreDto, ListEmailsQuery, ListEmailsResponse,
+    MAX_ATTACHMENT_BYTES, MAX_ATTACHMENTS_PER_EMAIL, MAX_EMAILS_LIMIT, MAX_EMAILS_PER_BATCH,
+    MAX_ENVELOPE_BYTES, MAX_REQUEST_BODY_BYTES, SubmitEmailRequest, SubmitEmailResponse,
 };
 use crate::error::AppError;
 use crate::limited_reader::LimitedReader;
@@ -63,12 +68,23 @@ pub async fn submit_email_batch<S: HttpServerState>(
             Err(validation_err) => results.push(BatchItemResultDto::Rejected {
                 error: validation_err.to_string(),
             }),
-            Ok(input) => {
-                let id = state.submit_email().execute(input).await?;
-                results.push(BatchItemResultDto::Accepted {
+            Ok(input) => match state.submit_email().execute(input).await {
+                Ok(id) => results.push(BatchItemResultDto::Accepted {
                     id: id.as_uuid().to_string(),
-                });
-            }
+                }),
+                // A broken template only rejects its own item.
+                Err(SubmitEmailError::InvalidContent { source }) => {
+                    results.push(BatchItemResultDto::Rejected {
+                        error: format!("template error: {source}"),
+                    });
+                }
+                Err(err @ SubmitEmailError::ListUnsubscribe(_)) => {
+                    results.push(BatchItemResultDto::Rejected {
+                        error: err.to_string(),
+                    });
+                }
+                Err(err) => return Err(err.into()),
+            },
         }
     }
     Ok(Json(BatchSubmitEmailResponse { results }))
@@ -136,6 +152,23 @@ async fn handle_multipart(
                     .unwrap_or("application/octet-stream")
                     .to_owned();
 
+                // A `Content-ID` header on the part sends it inline.
+                let content_id = field
+                    .headers()
+                    .get("content-id")
+                    .map(|value| {
+                        value
+                            .to_str()
+                            .map(|v| v.trim().trim_start_matches('<').trim_end_matches('>'))
+                            .map(str::to_owned)
+                            .map_err(|_| {
+                                AppError::BadRequestRaw(
+                                    "attachment Content-ID must be ASCII".to_owned(),
+                                )
+                            })
+                    })
+                    .transpose()?;
+
                 // Buffer through LimitedReader to enforce the per-attachment size cap.
                 // The axum Field borrows from Multipart and cannot be made 'static, so
                 // we must drain it here before advancing to the next field.
@@ -153,6 +186,7 @@ async fn handle_multipart(
                     filename,
                     content_type,
                     bytes: bytes::Bytes::from(buf),
+                    content_id,
                 });
             }
             _ => {
@@ -211,68 +245,122 @@ pub async fn list_emails<S: HttpServerState>(
     }))
 }
 
+/// # Errors
+///
+/// Returns `AppError::InvalidEmailId` when the path segment is not a valid UUID.
+/// Returns `AppError::CancelEmail` when the email is unknown, no longer
+/// cancellable, or the use case fails.
+#[tracing::instrument(skip_all, fields(email_id = %id))]
+pub async fn cancel_email<S: HttpServerState>(
+    State(state): State<S>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let uuid = uuid::Uuid::parse_str(&id).map_err(|_| AppError::InvalidEmailId)?;
+    state.cancel_email().execute(EmailId::from(uuid)).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Renders the email the way the worker would, without persisting,
+/// enqueuing or sending it. Attachments are validated but ignored.
+///
+/// # Errors
+///
+/// Returns `AppError::BadRequest` when the request fails validation.
+/// Returns `AppError::Preview` when the template fails to resolve,
+/// interpolate or render.
+#[tracing::instrument(skip_all)]
+pub async fn preview_email<S: HttpServerState>(
+    State(state): State<S>,
+    Json(request): Json<SubmitEmailRequest>,
+) -> Result<Json<EmailPreviewResponse>, AppError> {
+    let input = request.into_submit_input()?;
+    let preview = state
+        .preview_email()
+        .preview(input.body, input.subject, input.locale, &input.variables)
+        .await?;
+    Ok(Json(EmailPreviewResponse::from(preview)))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
 
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
+    use catapulte_domain::entity::body::{BodySource, Plain, RenderedBody};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::port::email_repository::{
         EmailRecord, EmailRepositoryError, EmailStatus, ListEmailsParams,
     };
-    use catapulte_domain::port::event_repository::{EventRecord, ListEventsParams};
+    use catapulte_domain::port::template_interpolator::InterpolateError;
+    use catapulte_domain::port::template_renderer::RenderError;
+    use catapulte_domain::use_case::cancel_email::{CancelEmailError, CancelEmailUseCase};
     use catapulte_domain::use_case::list_emails::{ListEmailsError, ListEmailsUseCase};
-    use catapulte_domain::use_case::list_events::{ListEventsError, ListEventsUseCase};
+    use catapulte_domain::use_case::process_queued_email::{
+        EmailPreview, PreviewEmailUseCase, ProcessQueuedEmailError,
+    };
     use catapulte_domain::use_case::submit_email::{
         SubmitEmailError, SubmitEmailInput, SubmitEmailUseCase,
     };
     use http_body_util::BodyExt;
     use tower::ServiceExt;
 
-    use catapulte_domain::use_case::list_senders::{
-        ListSendersError, ListSendersUseCase, SenderSnapshot,
-    };
-
-    use crate::HttpServerState;
     use crate::dto::{DEFAULT_EMAILS_LIMIT, MAX_EMAILS_LIMIT};
     use crate::router;
+    use crate::test_support::TestState;
 
-    struct NoopListSenders;
-
-    #[allow(async_fn_in_trait)]
-    impl ListSendersUseCase for NoopListSenders {
-        async fn execute(&self) -> Result<Vec<SenderSnapshot>, ListSendersError> {
-            Ok(vec![])
-        }
+    #[derive(Clone, Copy)]
+    enum CancelOutcome {
+        Cancelled,
+        NotFound,
+        Conflict,
     }
 
-    struct NoopListEvents;
+    struct FakeCancelEmail(CancelOutcome);
 
-    #[allow(async_fn_in_trait)]
-    impl ListEventsUseCase for NoopListEvents {
-        async fn execute(
-            &self,
-            _params: ListEventsParams,
-        ) -> Result<Vec<EventRecord>, ListEventsError> {
-            Ok(vec![])
+    impl CancelEmailUseCase for FakeCancelEmail {
+        async fn execute(&self, _id: EmailId) -> Result<(), CancelEmailError> {
+            match self.0 {
+                CancelOutcome::Cancelled => Ok(()),
+                CancelOutcome::NotFound => Err(CancelEmailError::NotFound),
+                CancelOutcome::Conflict => Err(CancelEmailError::Conflict),
+            }
         }
     }
 
-    struct NoopReadiness;
-
-    impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase for NoopReadiness {
-        async fn check_readiness(&self) -> catapulte_domain::use_case::check_readiness::Readiness {
-            catapulte_domain::use_case::check_readiness::Readiness::Ready
-        }
+    #[derive(Clone, Copy)]
+    enum PreviewOutcome {
+        Rendered,
+        RenderFails,
     }
 
-    #[derive(Clone)]
-    struct FakeSubmit;
+    struct FakePreview(PreviewOutcome);
 
-    impl SubmitEmailUseCase for FakeSubmit {
-        async fn execute(&self, _input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
-            Ok(EmailId::default())
+    impl PreviewEmailUseCase for FakePreview {
+        async fn preview(
+            &self,
+            _body: BodySource,
+            subject: Option<String>,
+            _locale: Option<String>,
+            _variables: &serde_json::Map<String, serde_json::Value>,
+        ) -> Result<EmailPreview, ProcessQueuedEmailError> {
+            match self.0 {
+                PreviewOutcome::Rendered => {
+                    let plain =
+                        Plain::try_new(Some("text".into()), Some("<p>html</p>".into())).unwrap();
+                    Ok(EmailPreview {
+                        subject,
+                        body: RenderedBody::new(plain)
+                            .with_warnings(vec!["unexpected attribute".into()]),
+                        locale: None,
+                    })
+                }
+                PreviewOutcome::RenderFails => {
+                    Err(ProcessQueuedEmailError::Render(RenderError::Mjml {
+                        source: anyhow::anyhow!("unexpected end of stream"),
+                    }))
+                }
+            }
         }
     }
 
@@ -287,6 +375,24 @@ mod tests {
         }
     }
 
+    /// Rejects emails submitted without variables, like the strict content
+    /// check does for a template using an undefined one.
+    #[derive(Clone)]
+    struct StrictSubmit;
+
+    impl SubmitEmailUseCase for StrictSubmit {
+        async fn execute(&self, input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
+            if input.variables.is_empty() {
+                return Err(SubmitEmailError::InvalidContent {
+                    source: ProcessQueuedEmailError::Interpolate(InterpolateError::Engine {
+                        source: anyhow::anyhow!("undefined value"),
+                    }),
+                });
+            }
+            Ok(EmailId::default())
+        }
+    }
+
     #[derive(Clone)]
     struct FakeListEmails {
         captured_params: Arc<Mutex<Option<ListEmailsParams>>>,
@@ -334,107 +440,9 @@ mod tests {
         }
     }
 
-    #[derive(Clone)]
-    struct TestState {
-        submit: Arc<FakeSubmit>,
-        list_emails: Arc<FakeListEmails>,
-    }
-
-    impl crate::ReadinessState for TestState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for TestState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            self.list_emails.as_ref()
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
-        }
-    }
-
-    #[derive(Clone)]
-    struct FailingTestState {
-        submit: Arc<FailingSubmit>,
-        list_emails: Arc<FakeListEmails>,
-    }
-
-    impl crate::ReadinessState for FailingTestState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for FailingTestState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            self.list_emails.as_ref()
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
-        }
-    }
-
-    #[derive(Clone)]
-    struct FailingEmailRepoState {
-        submit: Arc<FakeSubmit>,
-    }
-
-    impl crate::ReadinessState for FailingEmailRepoState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for FailingEmailRepoState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            &FailingListEmails
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
-        }
-    }
-
     fn make_router() -> axum::Router {
         router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails: Arc::new(FakeListEmails::new()),
-            },
+            TestState::default().with_list_emails(Arc::new(FakeListEmails::new())),
             None,
             std::time::Duration::from_secs(30),
         )
@@ -442,10 +450,19 @@ mod tests {
 
     fn make_failing_router() -> axum::Router {
         router(
-            FailingTestState {
-                submit: Arc::new(FailingSubmit),
-                list_emails: Arc::new(FakeListEmails::new()),
-            },
+            TestState::default()
+                .with_submit_email(Arc::new(FailingSubmit))
+                .with_list_emails(Arc::new(FakeListEmails::new())),
+            None,
+            std::time::Duration::from_secs(30),
+        )
+    }
+
+    fn make_strict_router() -> axum::Router {
+        router(
+            TestState::default()
+                .with_submit_email(Arc::new(StrictSubmit))
+                .with_list_emails(Arc::new(FakeListEmails::new())),
             None,
             std::time::Duration::from_secs(30),
         )
@@ -477,6 +494,10 @@ mod tests {
             recipients: vec![],
             created_at_ms: 1000,
             status: EmailStatus::Queued,
+            send_at_ms: None,
+            template: None,
+            template_version: None,
+            locale: None,
         }
     }
 
@@ -548,10 +569,7 @@ mod tests {
     async fn list_emails_returns_200_with_emails_array() {
         let list_emails = Arc::new(FakeListEmails::with_records(vec![sample_email_record()]));
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -575,10 +593,7 @@ mod tests {
         let list_emails = Arc::new(FakeListEmails::new());
         let captured = list_emails.captured_params.clone();
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -592,10 +607,7 @@ mod tests {
         let list_emails = Arc::new(FakeListEmails::new());
         let captured = list_emails.captured_params.clone();
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -609,10 +621,7 @@ mod tests {
         let list_emails = Arc::new(FakeListEmails::new());
         let captured = list_emails.captured_params.clone();
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -629,10 +638,7 @@ mod tests {
         let list_emails = Arc::new(FakeListEmails::new());
         let captured = list_emails.captured_params.clone();
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -646,10 +652,7 @@ mod tests {
         let list_emails = Arc::new(FakeListEmails::new());
         let captured = list_emails.captured_params.clone();
         let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_emails,
-            },
+            TestState::default().with_list_emails(list_emails),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -661,9 +664,7 @@ mod tests {
     #[tokio::test]
     async fn list_emails_500_when_repository_errors() {
         let app = router(
-            FailingEmailRepoState {
-                submit: Arc::new(FakeSubmit),
-            },
+            TestState::default().with_list_emails(Arc::new(FailingListEmails)),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -671,6 +672,47 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    async fn delete_email(outcome: CancelOutcome, id: &str) -> StatusCode {
+        let app = router(
+            TestState::default().with_cancel_email(Arc::new(FakeCancelEmail(outcome))),
+            None,
+            std::time::Duration::from_secs(30),
+        );
+        let request = Request::builder()
+            .method("DELETE")
+            .uri(format!("/emails/{id}"))
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn cancel_email_returns_204() {
+        let id = EmailId::default().as_uuid().to_string();
+        let status = delete_email(CancelOutcome::Cancelled, &id).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn cancel_unknown_email_returns_404() {
+        let id = EmailId::default().as_uuid().to_string();
+        let status = delete_email(CancelOutcome::NotFound, &id).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn cancel_in_flight_email_returns_409() {
+        let id = EmailId::default().as_uuid().to_string();
+        let status = delete_email(CancelOutcome::Conflict, &id).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn cancel_with_invalid_id_returns_400() {
+        let status = delete_email(CancelOutcome::Cancelled, "not-a-uuid").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn submit_with_invalid_sender_returns_400() {
         let app = make_router();
@@ -1016,6 +1058,55 @@ mod tests {
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[tokio::test]
+    async fn submit_with_invalid_content_returns_400_with_error_class() {
+        let app = make_strict_router();
+        let response = app
+            .oneshot(post_json(Body::from(
+                serde_json::to_vec(&valid_email_payload("r@x.y")).unwrap(),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["error"], "template error");
+        assert_eq!(json["error_class"], "template_interpolate");
+        assert!(
+            json["reason"].as_str().unwrap().contains("undefined value"),
+            "got {json}"
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_submit_rejects_only_the_items_with_invalid_content() {
+        let app = make_strict_router();
+        let mut with_variables = valid_email_payload("r1@x.y");
+        with_variables["variables"] = serde_json::json!({"name": "Ada"});
+        let payload = serde_json::json!({
+            "emails": [with_variables, valid_email_payload("r2@x.y")]
+        });
+        let response = app
+            .oneshot(post_batch_json(Body::from(
+                serde_json::to_vec(&payload).unwrap(),
+            )))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let results = json["results"].as_array().expect("results array");
+        assert_eq!(results[0]["status"], "accepted");
+        assert_eq!(results[1]["status"], "rejected");
+        assert!(
+            results[1]["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("template error"),
+            "got {json}"
+        );
+    }
+
     #[tokio::test]
     async fn batch_submit_validation_errors_do_not_abort_the_batch() {
         let app = make_router();
@@ -1101,12 +1192,14 @@ mod tests {
     #[derive(Clone)]
     struct CapturingSubmit {
         captured_bytes: Arc<Mutex<Vec<Vec<u8>>>>,
+        captured_content_ids: Arc<Mutex<Vec<Option<String>>>>,
     }
 
     impl CapturingSubmit {
         fn new() -> Self {
             Self {
                 captured_bytes: Arc::new(Mutex::new(Vec::new())),
+                captured_content_ids: Arc::new(Mutex::new(Vec::new())),
             }
         }
     }
@@ -1115,46 +1208,18 @@ mod tests {
         async fn execute(&self, input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
             use catapulte_domain::use_case::submit_email::AttachmentInput;
             for att in input.attachments {
-                if let AttachmentInput::Inline { bytes, .. } = att {
+                if let AttachmentInput::Inline {
+                    bytes, content_id, ..
+                } = att
+                {
                     self.captured_bytes.lock().unwrap().push(bytes.to_vec());
+                    self.captured_content_ids.lock().unwrap().push(content_id);
                 }
             }
             Ok(EmailId::default())
         }
     }
 
-    #[derive(Clone)]
-    struct CapturingTestState {
-        submit: Arc<CapturingSubmit>,
-        list_emails: Arc<FakeListEmails>,
-    }
-
-    impl crate::ReadinessState for CapturingTestState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for CapturingTestState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            self.list_emails.as_ref()
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
-        }
-    }
-
     #[tokio::test]
     async fn submit_multipart_with_one_attachment_returns_200() {
         let boundary = "testboundary123";
@@ -1176,10 +1241,9 @@ mod tests {
         let submit = Arc::new(CapturingSubmit::new());
         let captured = submit.captured_bytes.clone();
         let app = router(
-            CapturingTestState {
-                submit,
-                list_emails: Arc::new(FakeListEmails::new()),
-            },
+            TestState::default()
+                .with_submit_email(submit)
+                .with_list_emails(Arc::new(FakeListEmails::new())),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -1192,6 +1256,41 @@ mod tests {
         assert_eq!(blobs[0], file_content);
     }
 
+    #[tokio::test]
+    async fn submit_multipart_content_id_header_sends_the_part_inline() {
+        let boundary = "testboundarycid";
+        let envelope = String::from_utf8(envelope_json()).unwrap();
+        let body = format!(
+            "--{boundary}\r\n\
+             Content-Disposition: form-data; name=\"envelope\"\r\n\
+             Content-Type: application/json\r\n\r\n\
+             {envelope}\r\n\
+             --{boundary}\r\n\
+             Content-Disposition: form-data; name=\"attachment\"; filename=\"logo.png\"\r\n\
+             Content-Type: image/png\r\n\
+             Content-ID: <logo>\r\n\r\n\
+             png\r\n\
+             --{boundary}--\r\n"
+        );
+
+        let submit = Arc::new(CapturingSubmit::new());
+        let captured = submit.captured_content_ids.clone();
+        let app = router(
+            TestState::default()
+                .with_submit_email(submit)
+                .with_list_emails(Arc::new(FakeListEmails::new())),
+            None,
+            std::time::Duration::from_secs(30),
+        );
+
+        let response = app
+            .oneshot(post_multipart(boundary, Body::from(body)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*captured.lock().unwrap(), [Some("logo".to_owned())]);
+    }
+
     #[tokio::test]
     async fn submit_multipart_missing_envelope_returns_400() {
         let boundary = "testboundary456";
@@ -1282,4 +1381,68 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    async fn post_preview(
+        outcome: PreviewOutcome,
+        payload: &serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        let app = router(
+            TestState::default().with_preview_email(Arc::new(FakePreview(outcome))),
+            None,
+            std::time::Duration::from_secs(30),
+        );
+        let request = Request::builder()
+            .method("POST")
+            .uri("/emails/preview")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(payload).unwrap()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    fn preview_payload() -> serde_json::Value {
+        serde_json::json!({
+            "sender": "a@b.c",
+            "recipients": [{"kind": "to", "address": "t@x.y"}],
+            "subject": "Hello",
+            "body": {"kind": "mjml_named", "name": "welcome"}
+        })
+    }
+
+    #[tokio::test]
+    async fn preview_returns_rendered_parts_without_submitting() {
+        // The state's submit use case always fails: a 200 proves preview
+        // never goes through it.
+        let (status, json) = post_preview(PreviewOutcome::Rendered, &preview_payload()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(json["subject"], "Hello");
+        assert_eq!(json["html"], "<p>html</p>");
+        assert_eq!(json["text"], "text");
+        assert_eq!(json["warnings"][0], "unexpected attribute");
+    }
+
+    #[tokio::test]
+    async fn preview_failure_reports_error_class() {
+        let (status, json) = post_preview(PreviewOutcome::RenderFails, &preview_payload()).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(json["error_class"], "template_render");
+        assert!(
+            json["reason"]
+                .as_str()
+                .unwrap()
+                .contains("unexpected end of stream"),
+            "{json}"
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_validates_the_request_like_submit() {
+        let mut payload = preview_payload();
+        payload["sender"] = "not-an-email".into();
+        let (status, _) = post_preview(PreviewOutcome::Rendered, &payload).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
 }
@@ -1,18 +1,28 @@
 use std::str::FromStr;
+use std::time::Duration;
 
-use axum::Json;
 use axum::extract::{Path, Query, State};
+use axum::http::HeaderMap;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::{Extension, Json};
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::error_class::ErrorClass;
-use catapulte_domain::port::event_repository::ListEventsParams;
+use catapulte_domain::port::event_repository::{EventFilter, ListEventsParams};
 use catapulte_domain::use_case::list_events::ListEventsUseCase;
+use catapulte_domain::use_case::stream_events::StreamEventsUseCase;
+use futures_util::{Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
 
 use crate::HttpServerState;
 use crate::dto::{
     DEFAULT_EVENTS_LIMIT, EventRecordDto, ListEventsQuery, ListEventsResponse, MAX_EVENTS_LIMIT,
+    StreamEventsQuery,
 };
 use crate::error::AppError;
 
+/// How often an idle stream looks for new events in storage.
+const STREAM_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 fn parse_error_class(raw: Option<&str>) -> Result<Option<ErrorClass>, AppError> {
     match raw {
         Some(s) => ErrorClass::from_str(s)
@@ -110,6 +120,64 @@ pub async fn list_events_for_email<S: HttpServerState>(
     }))
 }
 
+/// # Errors
+///
+/// Returns `AppError::InvalidEmailId` when the `email_id` query param is not a valid UUID.
+/// Returns `AppError::InvalidErrorClass` when `error_class` is not a recognised value.
+/// Returns `AppError::BadRequestRaw` when the `Last-Event-ID` header is not a valid UUID.
+/// Returns `AppError::StreamEvents` when the starting point cannot be read.
+#[tracing::instrument(skip_all)]
+pub async fn stream_events<S: HttpServerState>(
+    State(state): State<S>,
+    Extension(shutdown): Extension<CancellationToken>,
+    headers: HeaderMap,
+    Query(query): Query<StreamEventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, AppError> {
+    let email_id = match query.email_id.as_deref() {
+        Some(raw) => Some(EmailId::from(
+            uuid::Uuid::parse_str(raw).map_err(|_| AppError::InvalidEmailId)?,
+        )),
+        None => None,
+    };
+    let filter = EventFilter {
+        email_id,
+        event_type: query.event_type,
+        sender_name: query.sender_name,
+        error_class: parse_error_class(query.error_class.as_deref())?,
+    };
+    let last_event_id = headers
+        .get("last-event-id")
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|raw| uuid::Uuid::parse_str(raw.trim()).ok())
+                .ok_or_else(|| AppError::BadRequestRaw("invalid Last-Event-ID".into()))
+        })
+        .transpose()?;
+    let tail = state.stream_events().start(filter, last_event_id).await?;
+
+    let batches = futures_util::stream::unfold((state, tail), |(state, mut tail)| async move {
+        loop {
+            match state.stream_events().next_batch(&mut tail).await {
+                Ok(events) if !events.is_empty() => return Some((events, (state, tail))),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "polling lifecycle events failed"),
+            }
+            tokio::time::sleep(STREAM_POLL_INTERVAL).await;
+        }
+    });
+    let events = batches
+        .flat_map(futures_util::stream::iter)
+        .map(|record| {
+            Event::default()
+                .id(record.id.to_string())
+                .json_data(EventRecordDto::from(record))
+        })
+        .take_until(shutdown.cancelled_owned());
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::{Arc, Mutex};
@@ -117,55 +185,17 @@ mod tests {
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
     use catapulte_domain::entity::email::EmailId;
-    use catapulte_domain::port::email_repository::{EmailRecord, ListEmailsParams};
     use catapulte_domain::port::event_repository::{
-        EventRecord, EventRepositoryError, ListEventsParams,
+        EventRecord, EventRepository, EventRepositoryError, ListEventsAfterParams, ListEventsParams,
     };
-    use catapulte_domain::use_case::list_emails::{ListEmailsError, ListEmailsUseCase};
     use catapulte_domain::use_case::list_events::{ListEventsError, ListEventsUseCase};
-    use catapulte_domain::use_case::list_senders::{
-        ListSendersError, ListSendersUseCase, SenderSnapshot,
-    };
-    use catapulte_domain::use_case::submit_email::{SubmitEmailError, SubmitEmailUseCase};
+    use catapulte_domain::use_case::stream_events::StreamEventsService;
     use http_body_util::BodyExt;
     use tower::ServiceExt;
 
-    use crate::HttpServerState;
     use crate::dto::{DEFAULT_EVENTS_LIMIT, MAX_EVENTS_LIMIT};
     use crate::router;
-
-    struct NoopListSenders;
-
-    #[allow(async_fn_in_trait)]
-    impl ListSendersUseCase for NoopListSenders {
-        async fn execute(&self) -> Result<Vec<SenderSnapshot>, ListSendersError> {
-            Ok(vec![])
-        }
-    }
-
-    struct NoopListEmails;
-
-    #[allow(async_fn_in_trait)]
-    impl ListEmailsUseCase for NoopListEmails {
-        async fn execute(
-            &self,
-            _params: ListEmailsParams,
-        ) -> Result<Vec<EmailRecord>, ListEmailsError> {
-            Ok(vec![])
-        }
-    }
-
-    #[derive(Clone)]
-    struct FakeSubmit;
-
-    impl SubmitEmailUseCase for FakeSubmit {
-        async fn execute(
-            &self,
-            _input: catapulte_domain::use_case::submit_email::SubmitEmailInput,
-        ) -> Result<EmailId, SubmitEmailError> {
-            Ok(EmailId::default())
-        }
-    }
+    use crate::test_support::{NoopListEmails, NoopSubmit, TestState};
 
     #[derive(Clone)]
     struct FakeListEvents {
@@ -214,75 +244,43 @@ mod tests {
         }
     }
 
-    struct NoopReadiness;
-
-    impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase for NoopReadiness {
-        async fn check_readiness(&self) -> catapulte_domain::use_case::check_readiness::Readiness {
-            catapulte_domain::use_case::check_readiness::Readiness::Ready
-        }
+    #[derive(Clone, Default)]
+    struct MemoryEvents {
+        events: Vec<EventRecord>,
     }
 
-    #[derive(Clone)]
-    struct TestState {
-        submit: Arc<FakeSubmit>,
-        list_events: Arc<FakeListEvents>,
-    }
-
-    impl crate::ReadinessState for TestState {
-        fn check_readiness(
+    impl EventRepository for MemoryEvents {
+        async fn list_events(
             &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for TestState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            &NoopListEmails
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            self.list_events.as_ref()
+            _params: ListEventsParams,
+        ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+            Ok(self.events.iter().rev().take(1).cloned().collect())
         }
 
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
+        async fn get_event(
+            &self,
+            id: uuid::Uuid,
+        ) -> Result<Option<EventRecord>, EventRepositoryError> {
+            Ok(self.events.iter().find(|e| e.id == id).cloned())
         }
-    }
-
-    #[derive(Clone)]
-    struct FailingRepoState {
-        submit: Arc<FakeSubmit>,
-    }
 
-    impl crate::ReadinessState for FailingRepoState {
-        fn check_readiness(
+        async fn list_events_after(
             &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
+            params: ListEventsAfterParams,
+        ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+            Ok(self
+                .events
+                .iter()
+                .filter(|e| params.after.is_none_or(|after| e.cursor() > after))
+                .cloned()
+                .collect())
         }
     }
 
-    impl HttpServerState for FailingRepoState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            self.submit.as_ref()
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            &NoopListEmails
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &FailingListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &NoopListSenders
-        }
+    fn state(
+        list_events: Arc<FakeListEvents>,
+    ) -> TestState<NoopSubmit, NoopListEmails, FakeListEvents> {
+        TestState::default().with_list_events(list_events)
     }
 
     fn valid_email_id() -> String {
@@ -318,14 +316,7 @@ mod tests {
             created_at_ms: 1000,
         };
         let list_events = Arc::new(FakeListEvents::with_records(vec![record]));
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         let response = app
             .oneshot(get_events(&email_id.as_uuid().to_string(), ""))
             .await
@@ -341,14 +332,7 @@ mod tests {
     #[tokio::test]
     async fn list_events_with_invalid_uuid_returns_400() {
         let list_events = Arc::new(FakeListEvents::new());
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         let response = app.oneshot(get_events("not-a-uuid", "")).await.unwrap();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
@@ -357,14 +341,7 @@ mod tests {
     async fn list_events_caps_limit_at_max() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_events(&valid_email_id(), "?limit=500"))
             .await
             .unwrap();
@@ -376,14 +353,7 @@ mod tests {
     async fn list_events_applies_default_limit() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_events(&valid_email_id(), ""))
             .await
             .unwrap();
@@ -395,14 +365,7 @@ mod tests {
     async fn list_events_forwards_event_type_filter() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_events(
             &valid_email_id(),
             "?event_type=delivery.succeeded",
@@ -419,9 +382,7 @@ mod tests {
     #[tokio::test]
     async fn list_events_500_when_repository_errors() {
         let app = router(
-            FailingRepoState {
-                submit: Arc::new(FakeSubmit),
-            },
+            TestState::default().with_list_events(Arc::new(FailingListEvents)),
             None,
             std::time::Duration::from_secs(30),
         );
@@ -436,14 +397,7 @@ mod tests {
     async fn list_events_without_email_id_passes_none_to_repo() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_all_events("")).await.unwrap();
         let params = captured.lock().unwrap();
         assert!(params.as_ref().unwrap().email_id.is_none());
@@ -454,14 +408,7 @@ mod tests {
         let uuid = uuid::Uuid::now_v7();
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_all_events(&format!("?email_id={uuid}")))
             .await
             .unwrap();
@@ -472,14 +419,7 @@ mod tests {
     #[tokio::test]
     async fn list_events_with_invalid_email_id_query_returns_400() {
         let list_events = Arc::new(FakeListEvents::new());
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         let response = app
             .oneshot(get_all_events("?email_id=not-a-uuid"))
             .await
@@ -491,14 +431,7 @@ mod tests {
     async fn list_events_global_applies_default_limit() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_all_events("")).await.unwrap();
         let params = captured.lock().unwrap();
         assert_eq!(params.as_ref().unwrap().limit, DEFAULT_EVENTS_LIMIT);
@@ -508,14 +441,7 @@ mod tests {
     async fn list_events_global_caps_limit_at_max() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_all_events("?limit=999")).await.unwrap();
         let params = captured.lock().unwrap();
         assert_eq!(params.as_ref().unwrap().limit, MAX_EVENTS_LIMIT);
@@ -525,14 +451,7 @@ mod tests {
     async fn list_events_forwards_sender_name_filter() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_events(&valid_email_id(), "?sender_name=primary"))
             .await
             .unwrap();
@@ -549,14 +468,7 @@ mod tests {
 
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_events(&valid_email_id(), "?error_class=delivery"))
             .await
             .unwrap();
@@ -570,14 +482,7 @@ mod tests {
     #[tokio::test]
     async fn list_events_invalid_error_class_returns_400() {
         let list_events = Arc::new(FakeListEvents::new());
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         let response = app
             .oneshot(get_events(&valid_email_id(), "?error_class=bogus"))
             .await
@@ -589,14 +494,7 @@ mod tests {
     async fn list_events_global_forwards_sender_name_filter() {
         let list_events = Arc::new(FakeListEvents::new());
         let captured = list_events.captured_params.clone();
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         app.oneshot(get_all_events("?sender_name=backup"))
             .await
             .unwrap();
@@ -610,18 +508,74 @@ mod tests {
     #[tokio::test]
     async fn list_events_global_invalid_error_class_returns_400() {
         let list_events = Arc::new(FakeListEvents::new());
-        let app = router(
-            TestState {
-                submit: Arc::new(FakeSubmit),
-                list_events,
-            },
-            None,
-            std::time::Duration::from_secs(30),
-        );
+        let app = router(state(list_events), None, std::time::Duration::from_secs(30));
         let response = app
             .oneshot(get_all_events("?error_class=unknown_value"))
             .await
             .unwrap();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    fn stream_request(last_event_id: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder().uri("/events/stream");
+        if let Some(id) = last_event_id {
+            request = request.header("last-event-id", id);
+        }
+        request.body(Body::empty()).unwrap()
+    }
+
+    fn event(event_type: &str, created_at_ms: i64) -> EventRecord {
+        EventRecord {
+            id: uuid::Uuid::now_v7(),
+            email_id: EmailId::default(),
+            event_type: event_type.to_owned(),
+            payload: None,
+            sender_name: None,
+            error_class: None,
+            created_at_ms,
+        }
+    }
+
+    #[tokio::test]
+    async fn stream_events_resumes_after_last_event_id_until_shutdown() {
+        let seen = event("queued", 1_000);
+        let next = event("delivery.succeeded", 2_000);
+        let state = TestState::default().with_stream_events(Arc::new(StreamEventsService::new(
+            MemoryEvents {
+                events: vec![seen.clone(), next.clone()],
+            },
+        )));
+        let shutdown = tokio_util::sync::CancellationToken::new();
+        let app = router(state, None, std::time::Duration::from_secs(30))
+            .layer(axum::Extension(shutdown.clone()));
+
+        let response = app
+            .oneshot(stream_request(Some(&seen.id.to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let mut body = response.into_body();
+        let frame = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        let frame = std::str::from_utf8(&frame).unwrap();
+        assert!(frame.contains(&format!("id: {}\n", next.id)));
+        assert!(frame.contains(r#""event_type":"delivery.succeeded""#));
+
+        shutdown.cancel();
+        assert!(body.frame().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_events_with_invalid_last_event_id_returns_400() {
+        let app = router(
+            TestState::default(),
+            None,
+            std::time::Duration::from_secs(30),
+        )
+        .layer(axum::Extension(tokio_util::sync::CancellationToken::new()));
+
+        let response = app.oneshot(stream_request(Some("nope"))).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -2,3 +2,9 @@ pub mod emails;
 pub mod events;
 pub(crate) mod health;
 pub mod senders;
+pub mod subscriptions;
+pub mod suppressions;
+pub mod templates;
+pub mod tracking;
+pub mod unsubscribe;
+pub mod webhook_deliveries;
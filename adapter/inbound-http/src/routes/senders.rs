@@ -41,57 +41,15 @@ mod tests {
 
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
-    use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::sender::{QuotaRange, SenderConfig, SenderName, SenderQuota};
-    use catapulte_domain::port::email_repository::{EmailRecord, ListEmailsParams};
-    use catapulte_domain::port::event_repository::{EventRecord, ListEventsParams};
-    use catapulte_domain::use_case::list_emails::{ListEmailsError, ListEmailsUseCase};
-    use catapulte_domain::use_case::list_events::{ListEventsError, ListEventsUseCase};
     use catapulte_domain::use_case::list_senders::{
         ListSendersError, ListSendersUseCase, SenderSnapshot,
     };
-    use catapulte_domain::use_case::submit_email::{SubmitEmailError, SubmitEmailUseCase};
     use http_body_util::BodyExt;
     use tower::ServiceExt;
 
-    use crate::HttpServerState;
     use crate::router;
-
-    #[derive(Clone)]
-    struct FakeSubmit;
-
-    impl SubmitEmailUseCase for FakeSubmit {
-        async fn execute(
-            &self,
-            _input: catapulte_domain::use_case::submit_email::SubmitEmailInput,
-        ) -> Result<EmailId, SubmitEmailError> {
-            Ok(EmailId::default())
-        }
-    }
-
-    struct NoopListEmails;
-
-    #[allow(async_fn_in_trait)]
-    impl ListEmailsUseCase for NoopListEmails {
-        async fn execute(
-            &self,
-            _params: ListEmailsParams,
-        ) -> Result<Vec<EmailRecord>, ListEmailsError> {
-            Ok(vec![])
-        }
-    }
-
-    struct NoopListEvents;
-
-    #[allow(async_fn_in_trait)]
-    impl ListEventsUseCase for NoopListEvents {
-        async fn execute(
-            &self,
-            _params: ListEventsParams,
-        ) -> Result<Vec<EventRecord>, ListEventsError> {
-            Ok(vec![])
-        }
-    }
+    use crate::test_support::TestState;
 
     #[derive(Clone)]
     struct FakeListSenders {
@@ -131,74 +89,6 @@ mod tests {
         }
     }
 
-    struct NoopReadiness;
-
-    impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase for NoopReadiness {
-        async fn check_readiness(&self) -> catapulte_domain::use_case::check_readiness::Readiness {
-            catapulte_domain::use_case::check_readiness::Readiness::Ready
-        }
-    }
-
-    #[derive(Clone)]
-    struct TestState {
-        list_senders: Arc<FakeListSenders>,
-    }
-
-    impl crate::ReadinessState for TestState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for TestState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            &FakeSubmit
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            &NoopListEmails
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            self.list_senders.as_ref()
-        }
-    }
-
-    #[derive(Clone)]
-    struct FailingState;
-
-    impl crate::ReadinessState for FailingState {
-        fn check_readiness(
-            &self,
-        ) -> &impl catapulte_domain::use_case::check_readiness::CheckReadinessUseCase {
-            &NoopReadiness
-        }
-    }
-
-    impl HttpServerState for FailingState {
-        fn submit_email(&self) -> &impl SubmitEmailUseCase {
-            &FakeSubmit
-        }
-
-        fn list_emails(&self) -> &impl ListEmailsUseCase {
-            &NoopListEmails
-        }
-
-        fn list_events(&self) -> &impl ListEventsUseCase {
-            &NoopListEvents
-        }
-
-        fn list_senders(&self) -> &impl ListSendersUseCase {
-            &FailingListSenders
-        }
-    }
-
     fn get_senders() -> Request<Body> {
         Request::builder()
             .method("GET")
@@ -209,9 +99,7 @@ mod tests {
 
     #[tokio::test]
     async fn list_senders_returns_200_with_empty_array_when_no_senders_configured() {
-        let state = TestState {
-            list_senders: Arc::new(FakeListSenders::empty()),
-        };
+        let state = TestState::default().with_list_senders(Arc::new(FakeListSenders::empty()));
         let app = router(state, None, std::time::Duration::from_secs(30));
         let response = app.oneshot(get_senders()).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
@@ -232,9 +120,8 @@ mod tests {
             sent_in_range: 42,
             failed_in_range: 3,
         }];
-        let state = TestState {
-            list_senders: Arc::new(FakeListSenders::with_usage(usage)),
-        };
+        let state =
+            TestState::default().with_list_senders(Arc::new(FakeListSenders::with_usage(usage)));
         let app = router(state, None, std::time::Duration::from_secs(30));
         let response = app.oneshot(get_senders()).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
@@ -263,9 +150,8 @@ mod tests {
             sent_in_range: 0,
             failed_in_range: 0,
         }];
-        let state = TestState {
-            list_senders: Arc::new(FakeListSenders::with_usage(usage)),
-        };
+        let state =
+            TestState::default().with_list_senders(Arc::new(FakeListSenders::with_usage(usage)));
         let app = router(state, None, std::time::Duration::from_secs(30));
         let response = app.oneshot(get_senders()).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
@@ -279,7 +165,8 @@ mod tests {
 
     #[tokio::test]
     async fn list_senders_returns_500_when_repository_fails() {
-        let app = router(FailingState, None, std::time::Duration::from_secs(30));
+        let state = TestState::default().with_list_senders(Arc::new(FailingListSenders));
+        let app = router(state, None, std::time::Duration::from_secs(30));
         let response = app.oneshot(get_senders()).await.unwrap();
         assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
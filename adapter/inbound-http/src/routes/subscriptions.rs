@@ -0,0 +1,269 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use catapulte_domain::port::webhook_subscription_store::ListWebhookSubscriptionsParams;
+use catapulte_domain::use_case::manage_webhook_subscriptions::ManageWebhookSubscriptionsUseCase;
+
+use crate::HttpServerState;
+use crate::dto::{
+    CreateWebhookSubscriptionRequest, DEFAULT_WEBHOOK_SUBSCRIPTIONS_LIMIT,
+    ListWebhookSubscriptionsQuery, ListWebhookSubscriptionsResponse,
+    MAX_WEBHOOK_SUBSCRIPTIONS_LIMIT, WebhookSubscriptionDto,
+};
+use crate::error::AppError;
+
+/// # Errors
+///
+/// Returns `AppError::WebhookSubscriptions` when the use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn list_subscriptions<S: HttpServerState>(
+    State(state): State<S>,
+    Query(query): Query<ListWebhookSubscriptionsQuery>,
+) -> Result<Json<ListWebhookSubscriptionsResponse>, AppError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_WEBHOOK_SUBSCRIPTIONS_LIMIT)
+        .min(MAX_WEBHOOK_SUBSCRIPTIONS_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let subscriptions = state
+        .webhook_subscriptions()
+        .list(ListWebhookSubscriptionsParams { limit, offset })
+        .await?
+        .into_iter()
+        .map(WebhookSubscriptionDto::from)
+        .collect();
+    Ok(Json(ListWebhookSubscriptionsResponse {
+        subscriptions,
+        limit,
+        offset,
+    }))
+}
+
+/// # Errors
+///
+/// Returns `AppError::WebhookSubscriptions` when the URL, secret or filter is
+/// invalid or the use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn create_subscription<S: HttpServerState>(
+    State(state): State<S>,
+    Json(body): Json<CreateWebhookSubscriptionRequest>,
+) -> Result<(StatusCode, Json<WebhookSubscriptionDto>), AppError> {
+    let subscription = state.webhook_subscriptions().create(body.into()).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookSubscriptionDto::from(subscription)),
+    ))
+}
+
+/// # Errors
+///
+/// Returns `AppError::BadRequestRaw` when the path segment is not a valid UUID.
+/// Returns `AppError::WebhookSubscriptions` when the subscription does not
+/// exist or the use case fails.
+#[tracing::instrument(skip_all, fields(subscription_id = %id))]
+pub async fn delete_subscription<S: HttpServerState>(
+    State(state): State<S>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let id = uuid::Uuid::parse_str(&id)
+        .map_err(|e| AppError::BadRequestRaw(format!("invalid subscription id: {e}")))?;
+    state.webhook_subscriptions().delete(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::entity::webhook_subscription::{
+        InvalidSubscription, WebhookSubscription,
+    };
+    use catapulte_domain::port::webhook_subscription_store::ListWebhookSubscriptionsParams;
+    use catapulte_domain::use_case::manage_webhook_subscriptions::{
+        CreateWebhookSubscriptionInput, ManageWebhookSubscriptionsError,
+        ManageWebhookSubscriptionsUseCase,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    /// Keeps created subscriptions in memory, rejecting `ftp` URLs.
+    #[derive(Default)]
+    struct FakeWebhookSubscriptions {
+        subscriptions: Mutex<Vec<WebhookSubscription>>,
+    }
+
+    impl ManageWebhookSubscriptionsUseCase for FakeWebhookSubscriptions {
+        async fn list(
+            &self,
+            _params: ListWebhookSubscriptionsParams,
+        ) -> Result<Vec<WebhookSubscription>, ManageWebhookSubscriptionsError> {
+            Ok(self.subscriptions.lock().unwrap().clone())
+        }
+
+        async fn create(
+            &self,
+            input: CreateWebhookSubscriptionInput,
+        ) -> Result<WebhookSubscription, ManageWebhookSubscriptionsError> {
+            let url = url::Url::parse(&input.url)
+                .ok()
+                .filter(|u| u.scheme() != "ftp")
+                .ok_or(InvalidSubscription::InvalidUrl)?;
+            let subscription = WebhookSubscription {
+                id: uuid::Uuid::now_v7(),
+                url,
+                secret: input.secret,
+                filter: input.filter,
+                created_at_ms: 42,
+            };
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .push(subscription.clone());
+            Ok(subscription)
+        }
+
+        async fn delete(&self, id: uuid::Uuid) -> Result<(), ManageWebhookSubscriptionsError> {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            let before = subscriptions.len();
+            subscriptions.retain(|s| s.id != id);
+            if subscriptions.len() == before {
+                return Err(ManageWebhookSubscriptionsError::NotFound);
+            }
+            Ok(())
+        }
+    }
+
+    fn app(subscriptions: &Arc<FakeWebhookSubscriptions>) -> axum::Router {
+        let state = TestState::default().with_webhook_subscriptions(Arc::clone(subscriptions));
+        router(
+            state,
+            Some("secret".into()),
+            std::time::Duration::from_secs(30),
+        )
+    }
+
+    fn request(method: &str, uri: &str, body: Option<serde_json::Value>) -> Request<Body> {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", "Bearer secret");
+        match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+            None => builder.body(Body::empty()).unwrap(),
+        }
+    }
+
+    async fn json(response: axum::response::Response) -> serde_json::Value {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn created_subscriptions_are_listed_without_their_secret() {
+        let subscriptions = Arc::new(FakeWebhookSubscriptions::default());
+        let app = app(&subscriptions);
+
+        let created = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/subscriptions",
+                Some(serde_json::json!({
+                    "url": "https://billing.acme.com/hooks",
+                    "secret": "0123456789abcdef0123456789abcdef",
+                    "event_types": ["delivery.failed"],
+                })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(created.status(), StatusCode::CREATED);
+        let created = json(created).await;
+        assert_eq!(created["url"], "https://billing.acme.com/hooks");
+        assert_eq!(
+            created["event_types"],
+            serde_json::json!(["delivery.failed"])
+        );
+        assert!(created.get("secret").is_none());
+
+        let listed = json(
+            app.oneshot(request("GET", "/subscriptions", None))
+                .await
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(listed["subscriptions"][0]["id"], created["id"]);
+        assert!(listed["subscriptions"][0].get("secret").is_none());
+        assert_eq!(
+            subscriptions.subscriptions.lock().unwrap()[0].secret,
+            "0123456789abcdef0123456789abcdef"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_subscriptions_are_rejected() {
+        let subscriptions = Arc::new(FakeWebhookSubscriptions::default());
+        let response = app(&subscriptions)
+            .oneshot(request(
+                "POST",
+                "/subscriptions",
+                Some(serde_json::json!({
+                    "url": "ftp://billing.acme.com",
+                    "secret": "0123456789abcdef0123456789abcdef",
+                })),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(subscriptions.subscriptions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_removes_known_subscriptions_only() {
+        let subscriptions = Arc::new(FakeWebhookSubscriptions::default());
+        let app = app(&subscriptions);
+        let created = json(
+            app.clone()
+                .oneshot(request(
+                    "POST",
+                    "/subscriptions",
+                    Some(serde_json::json!({
+                        "url": "https://crm.acme.com/hooks",
+                        "secret": "0123456789abcdef0123456789abcdef",
+                        "correlation_id_prefix": "marketing:",
+                    })),
+                ))
+                .await
+                .unwrap(),
+        )
+        .await;
+        let uri = format!("/subscriptions/{}", created["id"].as_str().unwrap());
+
+        let deleted = app
+            .clone()
+            .oneshot(request("DELETE", &uri, None))
+            .await
+            .unwrap();
+        let again = app
+            .clone()
+            .oneshot(request("DELETE", &uri, None))
+            .await
+            .unwrap();
+        let malformed = app
+            .oneshot(request("DELETE", "/subscriptions/nope", None))
+            .await
+            .unwrap();
+
+        assert_eq!(deleted.status(), StatusCode::NO_CONTENT);
+        assert_eq!(again.status(), StatusCode::NOT_FOUND);
+        assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+    }
+}
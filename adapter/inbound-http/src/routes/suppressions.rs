@@ -0,0 +1,211 @@
+use std::str::FromStr;
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use catapulte_domain::port::suppression_list::ListSuppressionsParams;
+use catapulte_domain::use_case::manage_suppressions::ManageSuppressionsUseCase;
+
+use crate::HttpServerState;
+use crate::dto::{
+    AddSuppressionRequest, DEFAULT_SUPPRESSIONS_LIMIT, ListSuppressionsQuery,
+    ListSuppressionsResponse, MAX_SUPPRESSIONS_LIMIT, SuppressionDto,
+};
+use crate::error::AppError;
+
+/// # Errors
+///
+/// Returns `AppError::Suppressions` when the use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn list_suppressions<S: HttpServerState>(
+    State(state): State<S>,
+    Query(query): Query<ListSuppressionsQuery>,
+) -> Result<Json<ListSuppressionsResponse>, AppError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SUPPRESSIONS_LIMIT)
+        .min(MAX_SUPPRESSIONS_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let suppressions = state
+        .suppressions()
+        .list(ListSuppressionsParams { limit, offset })
+        .await?
+        .into_iter()
+        .map(SuppressionDto::from)
+        .collect();
+    Ok(Json(ListSuppressionsResponse {
+        suppressions,
+        limit,
+        offset,
+    }))
+}
+
+/// # Errors
+///
+/// Returns `AppError::BadRequestRaw` when `address` is not a valid email address.
+/// Returns `AppError::Suppressions` when the use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn add_suppression<S: HttpServerState>(
+    State(state): State<S>,
+    Json(body): Json<AddSuppressionRequest>,
+) -> Result<StatusCode, AppError> {
+    email_address::EmailAddress::from_str(body.address.trim())
+        .map_err(|e| AppError::BadRequestRaw(format!("invalid address: {e}")))?;
+    state.suppressions().add(body.address, body.reason).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// # Errors
+///
+/// Returns `AppError::Suppressions` when the address is not suppressed or the
+/// use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn remove_suppression<S: HttpServerState>(
+    State(state): State<S>,
+    Path(address): Path<String>,
+) -> Result<StatusCode, AppError> {
+    state.suppressions().remove(address).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::port::suppression_list::{ListSuppressionsParams, Suppression};
+    use catapulte_domain::use_case::manage_suppressions::{
+        ManageSuppressionsError, ManageSuppressionsUseCase,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    #[derive(Default)]
+    struct FakeSuppressions {
+        entries: Vec<Suppression>,
+        list_params: Mutex<Option<ListSuppressionsParams>>,
+        added: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    impl ManageSuppressionsUseCase for FakeSuppressions {
+        async fn list(
+            &self,
+            params: ListSuppressionsParams,
+        ) -> Result<Vec<Suppression>, ManageSuppressionsError> {
+            *self.list_params.lock().unwrap() = Some(params);
+            Ok(self.entries.clone())
+        }
+
+        async fn add(
+            &self,
+            address: String,
+            reason: Option<String>,
+        ) -> Result<(), ManageSuppressionsError> {
+            self.added.lock().unwrap().push((address, reason));
+            Ok(())
+        }
+
+        async fn remove(&self, address: String) -> Result<(), ManageSuppressionsError> {
+            if self.entries.iter().any(|e| e.address == address) {
+                Ok(())
+            } else {
+                Err(ManageSuppressionsError::NotFound)
+            }
+        }
+    }
+
+    fn app(suppressions: &Arc<FakeSuppressions>) -> axum::Router {
+        let state = TestState::default().with_suppressions(Arc::clone(suppressions));
+        router(state, None, std::time::Duration::from_secs(30))
+    }
+
+    fn post_suppression(body: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/suppressions")
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_owned()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn list_suppressions_returns_entries_with_capped_limit() {
+        let suppressions = Arc::new(FakeSuppressions {
+            entries: vec![Suppression {
+                address: "bob@example.com".into(),
+                reason: "manual".into(),
+                created_at_ms: 42,
+            }],
+            ..Default::default()
+        });
+        let response = app(&suppressions)
+            .oneshot(
+                Request::builder()
+                    .uri("/suppressions?limit=500")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["suppressions"][0]["address"], "bob@example.com");
+        assert_eq!(json["suppressions"][0]["reason"], "manual");
+        assert_eq!(json["suppressions"][0]["created_at_ms"], 42);
+        assert_eq!(json["limit"], 100);
+        let params = suppressions.list_params.lock().unwrap();
+        assert_eq!(params.as_ref().unwrap().limit, 100);
+    }
+
+    #[tokio::test]
+    async fn add_suppression_returns_204() {
+        let suppressions = Arc::new(FakeSuppressions::default());
+        let response = app(&suppressions)
+            .oneshot(post_suppression(
+                r#"{"address":"bob@example.com","reason":"complaint"}"#,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            *suppressions.added.lock().unwrap(),
+            vec![("bob@example.com".to_owned(), Some("complaint".to_owned()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn add_suppression_rejects_invalid_address() {
+        let suppressions = Arc::new(FakeSuppressions::default());
+        let response = app(&suppressions)
+            .oneshot(post_suppression(r#"{"address":"not-an-email"}"#))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(suppressions.added.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_unknown_suppression_returns_404() {
+        let suppressions = Arc::new(FakeSuppressions::default());
+        let response = app(&suppressions)
+            .oneshot(
+                Request::builder()
+                    .method("DELETE")
+                    .uri("/suppressions/bob@example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,236 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use catapulte_domain::use_case::manage_templates::ManageTemplatesUseCase;
+
+use crate::HttpServerState;
+use crate::dto::{GetTemplateQuery, PutTemplateRequest, TemplateDto};
+use crate::error::AppError;
+
+/// # Errors
+///
+/// Returns `AppError::Templates` when the template or version does not exist
+/// or the use case fails.
+#[tracing::instrument(skip_all, fields(template = %name))]
+pub async fn get_template<S: HttpServerState>(
+    State(state): State<S>,
+    Path(name): Path<String>,
+    Query(query): Query<GetTemplateQuery>,
+) -> Result<Json<TemplateDto>, AppError> {
+    let template = state.templates().get(name, query.version).await?;
+    Ok(Json(TemplateDto::from(template)))
+}
+
+/// Publishes a new version; earlier versions stay available.
+///
+/// # Errors
+///
+/// Returns `AppError::Templates` when the name or content is invalid or the
+/// use case fails.
+#[tracing::instrument(skip_all, fields(template = %name))]
+pub async fn put_template<S: HttpServerState>(
+    State(state): State<S>,
+    Path(name): Path<String>,
+    Json(body): Json<PutTemplateRequest>,
+) -> Result<(StatusCode, Json<TemplateDto>), AppError> {
+    let template = state.templates().put(name, body.content).await?;
+    Ok((StatusCode::CREATED, Json(TemplateDto::from(template))))
+}
+
+/// # Errors
+///
+/// Returns `AppError::Templates` when the template does not exist or the use
+/// case fails.
+#[tracing::instrument(skip_all, fields(template = %name))]
+pub async fn delete_template<S: HttpServerState>(
+    State(state): State<S>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, AppError> {
+    state.templates().delete(name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::entity::template::Template;
+    use catapulte_domain::use_case::manage_templates::{
+        ManageTemplatesError, ManageTemplatesUseCase,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    /// Holds `welcome` at versions 1 and 2.
+    #[derive(Default)]
+    struct FakeTemplates {
+        requested: Mutex<Vec<(String, Option<u32>)>>,
+        published: Mutex<Vec<(String, String)>>,
+    }
+
+    fn welcome(version: u32) -> Template {
+        Template {
+            name: "welcome".into(),
+            version,
+            content: format!("<mjml>v{version}</mjml>"),
+            created_at_ms: 42,
+        }
+    }
+
+    impl ManageTemplatesUseCase for FakeTemplates {
+        async fn get(
+            &self,
+            name: String,
+            version: Option<u32>,
+        ) -> Result<Template, ManageTemplatesError> {
+            self.requested.lock().unwrap().push((name.clone(), version));
+            match (name.as_str(), version.unwrap_or(2)) {
+                ("welcome", v @ 1..=2) => Ok(welcome(v)),
+                _ => Err(ManageTemplatesError::NotFound),
+            }
+        }
+
+        async fn put(
+            &self,
+            name: String,
+            content: String,
+        ) -> Result<Template, ManageTemplatesError> {
+            if content.is_empty() {
+                return Err(ManageTemplatesError::EmptyContent);
+            }
+            self.published.lock().unwrap().push((name, content));
+            Ok(welcome(3))
+        }
+
+        async fn delete(&self, name: String) -> Result<(), ManageTemplatesError> {
+            if name == "welcome" {
+                Ok(())
+            } else {
+                Err(ManageTemplatesError::NotFound)
+            }
+        }
+    }
+
+    fn app(templates: &Arc<FakeTemplates>) -> axum::Router {
+        let state = TestState::default().with_templates(Arc::clone(templates));
+        router(state, None, std::time::Duration::from_secs(30))
+    }
+
+    fn request(method: &str, uri: &str, body: Body) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(body)
+            .unwrap()
+    }
+
+    async fn json_body(response: axum::response::Response) -> serde_json::Value {
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_template_returns_the_latest_or_the_requested_version() {
+        let templates = Arc::new(FakeTemplates::default());
+
+        let response = app(&templates)
+            .oneshot(request("GET", "/templates/welcome", Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let json = json_body(response).await;
+        assert_eq!(json["name"], "welcome");
+        assert_eq!(json["version"], 2);
+        assert_eq!(json["content"], "<mjml>v2</mjml>");
+        assert_eq!(json["created_at_ms"], 42);
+
+        let response = app(&templates)
+            .oneshot(request(
+                "GET",
+                "/templates/welcome?version=1",
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(json_body(response).await["version"], 1);
+        assert_eq!(
+            *templates.requested.lock().unwrap(),
+            vec![
+                ("welcome".to_owned(), None),
+                ("welcome".to_owned(), Some(1))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn get_unknown_template_returns_404() {
+        let templates = Arc::new(FakeTemplates::default());
+        let response = app(&templates)
+            .oneshot(request(
+                "GET",
+                "/templates/welcome?version=9",
+                Body::empty(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn put_template_publishes_a_new_version() {
+        let templates = Arc::new(FakeTemplates::default());
+        let response = app(&templates)
+            .oneshot(request(
+                "PUT",
+                "/templates/welcome",
+                Body::from(r#"{"content":"<mjml>v3</mjml>"}"#),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(json_body(response).await["version"], 3);
+        assert_eq!(
+            *templates.published.lock().unwrap(),
+            vec![("welcome".to_owned(), "<mjml>v3</mjml>".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn put_empty_template_returns_400() {
+        let templates = Arc::new(FakeTemplates::default());
+        let response = app(&templates)
+            .oneshot(request(
+                "PUT",
+                "/templates/welcome",
+                Body::from(r#"{"content":""}"#),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn delete_template_returns_204_then_404_for_unknown_names() {
+        let templates = Arc::new(FakeTemplates::default());
+        let response = app(&templates)
+            .oneshot(request("DELETE", "/templates/welcome", Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app(&templates)
+            .oneshot(request("DELETE", "/templates/nope", Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
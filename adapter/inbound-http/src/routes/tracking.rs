@@ -0,0 +1,174 @@
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, header};
+use axum::response::{IntoResponse, Redirect, Response};
+
+use crate::HttpServerState;
+use crate::error::AppError;
+use catapulte_domain::use_case::track_engagement::{
+    Engagement, TrackEngagementError, TrackEngagementUseCase,
+};
+
+/// A 1x1 transparent GIF.
+const PIXEL: &[u8] = &[
+    0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x01, 0x00, 0x01, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0xff, 0xff, 0xff, 0x21, 0xf9, 0x04, 0x01, 0x00, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x44, 0x01, 0x00, 0x3b,
+];
+
+fn user_agent(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+}
+
+/// Records an `opened` event and serves the tracking pixel.
+///
+/// # Errors
+///
+/// Returns `AppError::TrackEngagement` when the token is invalid.
+#[tracing::instrument(skip_all)]
+pub async fn open<S: HttpServerState>(
+    State(state): State<S>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    state
+        .track_engagement()
+        .execute(token, user_agent(&headers))
+        .await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/gif"),
+            // Every load is an open; caches would hide the later ones.
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        PIXEL,
+    )
+        .into_response())
+}
+
+/// Records a `clicked` event and redirects to the link target.
+///
+/// # Errors
+///
+/// Returns `AppError::TrackEngagement` when the token is invalid or is an
+/// open token.
+#[tracing::instrument(skip_all)]
+pub async fn click<S: HttpServerState>(
+    State(state): State<S>,
+    Path(token): Path<String>,
+    headers: HeaderMap,
+) -> Result<Redirect, AppError> {
+    match state
+        .track_engagement()
+        .execute(token, user_agent(&headers))
+        .await?
+    {
+        Engagement::Clicked { url } => Ok(Redirect::to(&url)),
+        Engagement::Opened => Err(TrackEngagementError::InvalidToken.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::use_case::track_engagement::{
+        Engagement, TrackEngagementError, TrackEngagementUseCase,
+    };
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    /// Accepts `open` and `click` and records the user agent of every hit.
+    #[derive(Default)]
+    struct FakeTrackEngagement {
+        hits: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    impl TrackEngagementUseCase for FakeTrackEngagement {
+        async fn execute(
+            &self,
+            token: String,
+            user_agent: Option<String>,
+        ) -> Result<Engagement, TrackEngagementError> {
+            let engagement = match token.as_str() {
+                "open" => Engagement::Opened,
+                "click" => Engagement::Clicked {
+                    url: "https://acme.com/pricing".into(),
+                },
+                _ => return Err(TrackEngagementError::InvalidToken),
+            };
+            self.hits.lock().unwrap().push((token, user_agent));
+            Ok(engagement)
+        }
+    }
+
+    /// Built with an API key, which the tracking routes must not require.
+    fn app(tracking: &Arc<FakeTrackEngagement>) -> axum::Router {
+        let state = TestState::default().with_track_engagement(Arc::clone(tracking));
+        router(
+            state,
+            Some("secret".into()),
+            std::time::Duration::from_secs(30),
+        )
+    }
+
+    fn get(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header("user-agent", "Mail/1.0")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn open_serves_the_pixel_without_the_api_key() {
+        let tracking = Arc::new(FakeTrackEngagement::default());
+        let response = app(&tracking)
+            .oneshot(get("/track/open/open"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "image/gif");
+        assert_eq!(response.headers()["cache-control"], "no-store");
+        assert_eq!(
+            *tracking.hits.lock().unwrap(),
+            vec![("open".to_owned(), Some("Mail/1.0".to_owned()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn click_redirects_to_the_signed_target() {
+        let tracking = Arc::new(FakeTrackEngagement::default());
+        let response = app(&tracking)
+            .oneshot(get("/track/click/click"))
+            .await
+            .unwrap();
+
+        assert!(response.status().is_redirection());
+        assert_eq!(response.headers()["location"], "https://acme.com/pricing");
+        assert_eq!(tracking.hits.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_or_mismatched_tokens_return_404() {
+        let tracking = Arc::new(FakeTrackEngagement::default());
+        let app = app(&tracking);
+
+        let forged = app
+            .clone()
+            .oneshot(get("/track/click/forged"))
+            .await
+            .unwrap();
+        let open_as_click = app.oneshot(get("/track/click/open")).await.unwrap();
+
+        assert_eq!(forged.status(), StatusCode::NOT_FOUND);
+        assert_eq!(open_as_click.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,94 @@
+use axum::extract::{Path, State};
+
+use crate::HttpServerState;
+use crate::error::AppError;
+use catapulte_domain::use_case::unsubscribe::UnsubscribeUseCase;
+
+/// RFC 8058 one-click unsubscribe. Mailbox providers POST
+/// `List-Unsubscribe=One-Click` as a form body, which is ignored: the token
+/// alone identifies the recipient and the list.
+///
+/// # Errors
+///
+/// Returns `AppError::Unsubscribe` when the token is invalid or the opt-out
+/// cannot be recorded.
+#[tracing::instrument(skip_all)]
+pub async fn unsubscribe<S: HttpServerState>(
+    State(state): State<S>,
+    Path(token): Path<String>,
+) -> Result<&'static str, AppError> {
+    state.unsubscribe().execute(token).await?;
+    Ok("You have been unsubscribed.")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::use_case::unsubscribe::{UnsubscribeError, UnsubscribeUseCase};
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    /// Accepts the token `valid` and records every accepted call.
+    #[derive(Default)]
+    struct FakeUnsubscribe {
+        accepted: Mutex<Vec<String>>,
+    }
+
+    impl UnsubscribeUseCase for FakeUnsubscribe {
+        async fn execute(&self, token: String) -> Result<(), UnsubscribeError> {
+            if token != "valid" {
+                return Err(UnsubscribeError::InvalidToken);
+            }
+            self.accepted.lock().unwrap().push(token);
+            Ok(())
+        }
+    }
+
+    /// Built with an API key, which the unsubscribe route must not require.
+    fn app(unsubscribe: &Arc<FakeUnsubscribe>) -> axum::Router {
+        let state = TestState::default().with_unsubscribe(Arc::clone(unsubscribe));
+        router(
+            state,
+            Some("secret".into()),
+            std::time::Duration::from_secs(30),
+        )
+    }
+
+    fn one_click(uri: &str) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from("List-Unsubscribe=One-Click"))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn one_click_post_unsubscribes_without_the_api_key() {
+        let unsubscribe = Arc::new(FakeUnsubscribe::default());
+        let response = app(&unsubscribe)
+            .oneshot(one_click("/unsubscribe/valid"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*unsubscribe.accepted.lock().unwrap(), vec!["valid"]);
+    }
+
+    #[tokio::test]
+    async fn unknown_token_returns_404() {
+        let unsubscribe = Arc::new(FakeUnsubscribe::default());
+        let response = app(&unsubscribe)
+            .oneshot(one_click("/unsubscribe/forged"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(unsubscribe.accepted.lock().unwrap().is_empty());
+    }
+}
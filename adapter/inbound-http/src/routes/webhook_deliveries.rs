@@ -0,0 +1,242 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use catapulte_domain::port::webhook_outbox::ListWebhookDeliveriesParams;
+use catapulte_domain::use_case::manage_webhook_deliveries::ManageWebhookDeliveriesUseCase;
+
+use crate::HttpServerState;
+use crate::dto::{
+    DEFAULT_WEBHOOK_DELIVERIES_LIMIT, ListWebhookDeliveriesQuery, ListWebhookDeliveriesResponse,
+    MAX_WEBHOOK_DELIVERIES_LIMIT, WebhookDeliveryDto,
+};
+use crate::error::AppError;
+
+/// Lists the deliveries still waiting in the webhook outbox, pending or
+/// failed.
+///
+/// # Errors
+///
+/// Returns `AppError::BadRequestRaw` when `subscription_id` is not a valid UUID.
+/// Returns `AppError::WebhookDeliveries` when the use case fails.
+#[tracing::instrument(skip_all)]
+pub async fn list_webhook_deliveries<S: HttpServerState>(
+    State(state): State<S>,
+    Query(query): Query<ListWebhookDeliveriesQuery>,
+) -> Result<Json<ListWebhookDeliveriesResponse>, AppError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_WEBHOOK_DELIVERIES_LIMIT)
+        .min(MAX_WEBHOOK_DELIVERIES_LIMIT);
+    let offset = query.offset.unwrap_or(0);
+    let subscription_id = query
+        .subscription_id
+        .as_deref()
+        .map(uuid::Uuid::parse_str)
+        .transpose()
+        .map_err(|e| AppError::BadRequestRaw(format!("invalid subscription id: {e}")))?;
+    let deliveries = state
+        .webhook_deliveries()
+        .list(ListWebhookDeliveriesParams {
+            status: query.status.map(Into::into),
+            subscription_id,
+            limit,
+            offset,
+        })
+        .await?
+        .into_iter()
+        .map(WebhookDeliveryDto::from)
+        .collect();
+    Ok(Json(ListWebhookDeliveriesResponse {
+        deliveries,
+        limit,
+        offset,
+    }))
+}
+
+/// # Errors
+///
+/// Returns `AppError::BadRequestRaw` when the path segment is not a valid UUID.
+/// Returns `AppError::WebhookDeliveries` when the delivery is not in the outbox
+/// or the use case fails.
+#[tracing::instrument(skip_all, fields(delivery_id = %id))]
+pub async fn redeliver_webhook_delivery<S: HttpServerState>(
+    State(state): State<S>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, AppError> {
+    let id = uuid::Uuid::parse_str(&id)
+        .map_err(|e| AppError::BadRequestRaw(format!("invalid delivery id: {e}")))?;
+    state.webhook_deliveries().redeliver(id).await?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::port::webhook_outbox::{
+        DeliveryTarget, ListWebhookDeliveriesParams, WebhookDelivery, WebhookDeliveryStatus,
+    };
+    use catapulte_domain::use_case::manage_webhook_deliveries::{
+        ManageWebhookDeliveriesError, ManageWebhookDeliveriesUseCase,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::router;
+    use crate::test_support::TestState;
+
+    /// Holds a single failed delivery and records the list filters and
+    /// redeliveries it receives.
+    struct FakeWebhookDeliveries {
+        delivery: WebhookDelivery,
+        filters: Mutex<Vec<(Option<WebhookDeliveryStatus>, Option<uuid::Uuid>)>>,
+        redelivered: Mutex<Vec<uuid::Uuid>>,
+    }
+
+    impl FakeWebhookDeliveries {
+        fn new() -> Self {
+            Self {
+                delivery: WebhookDelivery {
+                    id: uuid::Uuid::now_v7(),
+                    event_id: uuid::Uuid::now_v7(),
+                    target: DeliveryTarget::Webhook,
+                    subscription_id: None,
+                    email_id: EmailId::default(),
+                    event_type: "sent".into(),
+                    sender_name: Some("primary".into()),
+                    payload: serde_json::json!({ "sender_name": "primary" }),
+                    status: WebhookDeliveryStatus::Failed,
+                    attempts: 12,
+                    next_attempt_at_ms: 1_000,
+                    last_error: Some("HTTP status server error (503)".into()),
+                    created_at_ms: 500,
+                },
+                filters: Mutex::new(vec![]),
+                redelivered: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    impl ManageWebhookDeliveriesUseCase for FakeWebhookDeliveries {
+        async fn list(
+            &self,
+            params: ListWebhookDeliveriesParams,
+        ) -> Result<Vec<WebhookDelivery>, ManageWebhookDeliveriesError> {
+            self.filters
+                .lock()
+                .unwrap()
+                .push((params.status, params.subscription_id));
+            Ok(vec![self.delivery.clone()])
+        }
+
+        async fn redeliver(&self, id: uuid::Uuid) -> Result<(), ManageWebhookDeliveriesError> {
+            if id != self.delivery.id {
+                return Err(ManageWebhookDeliveriesError::NotFound);
+            }
+            self.redelivered.lock().unwrap().push(id);
+            Ok(())
+        }
+    }
+
+    fn app(deliveries: &Arc<FakeWebhookDeliveries>) -> axum::Router {
+        let state = TestState::default().with_webhook_deliveries(Arc::clone(deliveries));
+        router(
+            state,
+            Some("secret".into()),
+            std::time::Duration::from_secs(30),
+        )
+    }
+
+    fn request(method: &str, uri: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", "Bearer secret")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn list_returns_deliveries_filtered_by_status_and_subscription() {
+        let deliveries = Arc::new(FakeWebhookDeliveries::new());
+        let subscription_id = uuid::Uuid::now_v7();
+        let response = app(&deliveries)
+            .oneshot(request(
+                "GET",
+                &format!("/webhook-deliveries?status=failed&subscription_id={subscription_id}"),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let delivery = &json["deliveries"][0];
+        assert_eq!(delivery["id"], deliveries.delivery.id.to_string());
+        assert_eq!(
+            delivery["event_id"],
+            deliveries.delivery.event_id.to_string()
+        );
+        assert_eq!(delivery["subscription_id"], serde_json::Value::Null);
+        assert_eq!(delivery["status"], "failed");
+        assert_eq!(delivery["attempts"], 12);
+        assert_eq!(delivery["last_error"], "HTTP status server error (503)");
+        assert_eq!(json["limit"], 20);
+        assert_eq!(
+            *deliveries.filters.lock().unwrap(),
+            vec![(Some(WebhookDeliveryStatus::Failed), Some(subscription_id))]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_requires_the_api_key() {
+        let deliveries = Arc::new(FakeWebhookDeliveries::new());
+        let response = app(&deliveries)
+            .oneshot(
+                Request::builder()
+                    .uri("/webhook-deliveries")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn redeliver_accepts_known_deliveries_only() {
+        let deliveries = Arc::new(FakeWebhookDeliveries::new());
+        let app = app(&deliveries);
+        let known = deliveries.delivery.id;
+
+        let accepted = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                &format!("/webhook-deliveries/{known}/redeliver"),
+            ))
+            .await
+            .unwrap();
+        let unknown = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                &format!("/webhook-deliveries/{}/redeliver", uuid::Uuid::now_v7()),
+            ))
+            .await
+            .unwrap();
+        let malformed = app
+            .oneshot(request("POST", "/webhook-deliveries/nope/redeliver"))
+            .await
+            .unwrap();
+
+        assert_eq!(accepted.status(), StatusCode::ACCEPTED);
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        assert_eq!(malformed.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(*deliveries.redelivered.lock().unwrap(), vec![known]);
+    }
+}
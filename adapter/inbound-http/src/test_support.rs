@@ -0,0 +1,688 @@
+//! No-op use cases and a configurable [`HttpServerState`] shared by the route
+//! tests. A test starts from [`TestState::default`] and swaps in the fakes it
+//! exercises with the `with_*` methods.
+
+use std::sync::Arc;
+
+use catapulte_domain::entity::body::{BodySource, Plain, RenderedBody};
+use catapulte_domain::entity::email::EmailId;
+use catapulte_domain::entity::template::Template;
+use catapulte_domain::entity::webhook_subscription::WebhookSubscription;
+use catapulte_domain::port::email_repository::{EmailRecord, ListEmailsParams};
+use catapulte_domain::port::event_repository::{
+    EventFilter, EventRecord, EventRepositoryError, ListEventsParams,
+};
+use catapulte_domain::port::suppression_list::{ListSuppressionsParams, Suppression};
+use catapulte_domain::port::webhook_outbox::{ListWebhookDeliveriesParams, WebhookDelivery};
+use catapulte_domain::port::webhook_subscription_store::ListWebhookSubscriptionsParams;
+use catapulte_domain::use_case::cancel_email::{CancelEmailError, CancelEmailUseCase};
+use catapulte_domain::use_case::check_readiness::{CheckReadinessUseCase, Readiness};
+use catapulte_domain::use_case::list_emails::{ListEmailsError, ListEmailsUseCase};
+use catapulte_domain::use_case::list_events::{ListEventsError, ListEventsUseCase};
+use catapulte_domain::use_case::list_senders::{
+    ListSendersError, ListSendersUseCase, SenderSnapshot,
+};
+use catapulte_domain::use_case::manage_suppressions::{
+    ManageSuppressionsError, ManageSuppressionsUseCase,
+};
+use catapulte_domain::use_case::manage_templates::{ManageTemplatesError, ManageTemplatesUseCase};
+use catapulte_domain::use_case::manage_webhook_deliveries::{
+    ManageWebhookDeliveriesError, ManageWebhookDeliveriesUseCase,
+};
+use catapulte_domain::use_case::manage_webhook_subscriptions::{
+    CreateWebhookSubscriptionInput, ManageWebhookSubscriptionsError,
+    ManageWebhookSubscriptionsUseCase,
+};
+use catapulte_domain::use_case::process_queued_email::{
+    EmailPreview, PreviewEmailUseCase, ProcessQueuedEmailError,
+};
+use catapulte_domain::use_case::stream_events::{
+    EventTail, StreamEventsError, StreamEventsUseCase,
+};
+use catapulte_domain::use_case::submit_email::{
+    SubmitEmailError, SubmitEmailInput, SubmitEmailUseCase,
+};
+use catapulte_domain::use_case::track_engagement::{
+    Engagement, TrackEngagementError, TrackEngagementUseCase,
+};
+use catapulte_domain::use_case::unsubscribe::{UnsubscribeError, UnsubscribeUseCase};
+
+use crate::{HttpServerState, ReadinessState};
+
+pub(crate) struct NoopSubmit;
+
+impl SubmitEmailUseCase for NoopSubmit {
+    async fn execute(&self, _input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
+        Ok(EmailId::default())
+    }
+}
+
+pub(crate) struct NoopListEmails;
+
+impl ListEmailsUseCase for NoopListEmails {
+    async fn execute(
+        &self,
+        _params: ListEmailsParams,
+    ) -> Result<Vec<EmailRecord>, ListEmailsError> {
+        Ok(vec![])
+    }
+}
+
+pub(crate) struct NoopListEvents;
+
+impl ListEventsUseCase for NoopListEvents {
+    async fn execute(
+        &self,
+        _params: ListEventsParams,
+    ) -> Result<Vec<EventRecord>, ListEventsError> {
+        Ok(vec![])
+    }
+}
+
+pub(crate) struct NoopStreamEvents;
+
+impl StreamEventsUseCase for NoopStreamEvents {
+    async fn start(
+        &self,
+        _filter: EventFilter,
+        _last_event_id: Option<uuid::Uuid>,
+    ) -> Result<EventTail, StreamEventsError> {
+        Err(EventRepositoryError::Storage {
+            source: anyhow::anyhow!("not streaming"),
+        }
+        .into())
+    }
+
+    async fn next_batch(
+        &self,
+        _tail: &mut EventTail,
+    ) -> Result<Vec<EventRecord>, StreamEventsError> {
+        Ok(vec![])
+    }
+}
+
+pub(crate) struct NoopListSenders;
+
+impl ListSendersUseCase for NoopListSenders {
+    async fn execute(&self) -> Result<Vec<SenderSnapshot>, ListSendersError> {
+        Ok(vec![])
+    }
+}
+
+pub(crate) struct NoopCancelEmail;
+
+impl CancelEmailUseCase for NoopCancelEmail {
+    async fn execute(&self, _id: EmailId) -> Result<(), CancelEmailError> {
+        Ok(())
+    }
+}
+
+pub(crate) struct NoopSuppressions;
+
+impl ManageSuppressionsUseCase for NoopSuppressions {
+    async fn list(
+        &self,
+        _params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, ManageSuppressionsError> {
+        Ok(vec![])
+    }
+
+    async fn add(
+        &self,
+        _address: String,
+        _reason: Option<String>,
+    ) -> Result<(), ManageSuppressionsError> {
+        Ok(())
+    }
+
+    async fn remove(&self, _address: String) -> Result<(), ManageSuppressionsError> {
+        Ok(())
+    }
+}
+
+pub(crate) struct NoopPreview;
+
+impl PreviewEmailUseCase for NoopPreview {
+    async fn preview(
+        &self,
+        _body: BodySource,
+        subject: Option<String>,
+        _locale: Option<String>,
+        _variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<EmailPreview, ProcessQueuedEmailError> {
+        let plain = Plain::try_new(Some("preview".into()), None).unwrap();
+        Ok(EmailPreview {
+            subject,
+            body: RenderedBody::new(plain),
+            locale: None,
+        })
+    }
+}
+
+pub(crate) struct NoopTemplates;
+
+impl ManageTemplatesUseCase for NoopTemplates {
+    async fn get(
+        &self,
+        _name: String,
+        _version: Option<u32>,
+    ) -> Result<Template, ManageTemplatesError> {
+        Err(ManageTemplatesError::NotFound)
+    }
+
+    async fn put(&self, _name: String, _content: String) -> Result<Template, ManageTemplatesError> {
+        Err(ManageTemplatesError::NotFound)
+    }
+
+    async fn delete(&self, _name: String) -> Result<(), ManageTemplatesError> {
+        Err(ManageTemplatesError::NotFound)
+    }
+}
+
+pub(crate) struct NoopUnsubscribe;
+
+impl UnsubscribeUseCase for NoopUnsubscribe {
+    async fn execute(&self, _token: String) -> Result<(), UnsubscribeError> {
+        Err(UnsubscribeError::InvalidToken)
+    }
+}
+
+pub(crate) struct NoopTrackEngagement;
+
+impl TrackEngagementUseCase for NoopTrackEngagement {
+    async fn execute(
+        &self,
+        _token: String,
+        _user_agent: Option<String>,
+    ) -> Result<Engagement, TrackEngagementError> {
+        Err(TrackEngagementError::InvalidToken)
+    }
+}
+
+pub(crate) struct NoopWebhookDeliveries;
+
+impl ManageWebhookDeliveriesUseCase for NoopWebhookDeliveries {
+    async fn list(
+        &self,
+        _params: ListWebhookDeliveriesParams,
+    ) -> Result<Vec<WebhookDelivery>, ManageWebhookDeliveriesError> {
+        Ok(vec![])
+    }
+
+    async fn redeliver(&self, _id: uuid::Uuid) -> Result<(), ManageWebhookDeliveriesError> {
+        Err(ManageWebhookDeliveriesError::NotFound)
+    }
+}
+
+pub(crate) struct NoopWebhookSubscriptions;
+
+impl ManageWebhookSubscriptionsUseCase for NoopWebhookSubscriptions {
+    async fn list(
+        &self,
+        _params: ListWebhookSubscriptionsParams,
+    ) -> Result<Vec<WebhookSubscription>, ManageWebhookSubscriptionsError> {
+        Ok(vec![])
+    }
+
+    async fn create(
+        &self,
+        _input: CreateWebhookSubscriptionInput,
+    ) -> Result<WebhookSubscription, ManageWebhookSubscriptionsError> {
+        Err(ManageWebhookSubscriptionsError::NotFound)
+    }
+
+    async fn delete(&self, _id: uuid::Uuid) -> Result<(), ManageWebhookSubscriptionsError> {
+        Err(ManageWebhookSubscriptionsError::NotFound)
+    }
+}
+
+pub(crate) struct NoopReadiness;
+
+impl CheckReadinessUseCase for NoopReadiness {
+    async fn check_readiness(&self) -> Readiness {
+        Readiness::Ready
+    }
+}
+
+/// Serves every use case from a no-op, except the ones a test swaps in.
+pub(crate) struct TestState<
+    SE = NoopSubmit,
+    LE = NoopListEmails,
+    LV = NoopListEvents,
+    ST = NoopStreamEvents,
+    LS = NoopListSenders,
+    CE = NoopCancelEmail,
+    SU = NoopSuppressions,
+    PE = NoopPreview,
+    TE = NoopTemplates,
+    UN = NoopUnsubscribe,
+    TR = NoopTrackEngagement,
+    WD = NoopWebhookDeliveries,
+    WS = NoopWebhookSubscriptions,
+> {
+    pub(crate) submit_email: Arc<SE>,
+    pub(crate) list_emails: Arc<LE>,
+    pub(crate) list_events: Arc<LV>,
+    pub(crate) stream_events: Arc<ST>,
+    pub(crate) list_senders: Arc<LS>,
+    pub(crate) cancel_email: Arc<CE>,
+    pub(crate) suppressions: Arc<SU>,
+    pub(crate) preview_email: Arc<PE>,
+    pub(crate) templates: Arc<TE>,
+    pub(crate) unsubscribe: Arc<UN>,
+    pub(crate) track_engagement: Arc<TR>,
+    pub(crate) webhook_deliveries: Arc<WD>,
+    pub(crate) webhook_subscriptions: Arc<WS>,
+}
+
+impl Default for TestState {
+    fn default() -> Self {
+        Self {
+            submit_email: Arc::new(NoopSubmit),
+            list_emails: Arc::new(NoopListEmails),
+            list_events: Arc::new(NoopListEvents),
+            stream_events: Arc::new(NoopStreamEvents),
+            list_senders: Arc::new(NoopListSenders),
+            cancel_email: Arc::new(NoopCancelEmail),
+            suppressions: Arc::new(NoopSuppressions),
+            preview_email: Arc::new(NoopPreview),
+            templates: Arc::new(NoopTemplates),
+            unsubscribe: Arc::new(NoopUnsubscribe),
+            track_engagement: Arc::new(NoopTrackEngagement),
+            webhook_deliveries: Arc::new(NoopWebhookDeliveries),
+            webhook_subscriptions: Arc::new(NoopWebhookSubscriptions),
+        }
+    }
+}
+
+impl<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> Clone
+    for TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS>
+{
+    fn clone(&self) -> Self {
+        Self {
+            submit_email: Arc::clone(&self.submit_email),
+            list_emails: Arc::clone(&self.list_emails),
+            list_events: Arc::clone(&self.list_events),
+            stream_events: Arc::clone(&self.stream_events),
+            list_senders: Arc::clone(&self.list_senders),
+            cancel_email: Arc::clone(&self.cancel_email),
+            suppressions: Arc::clone(&self.suppressions),
+            preview_email: Arc::clone(&self.preview_email),
+            templates: Arc::clone(&self.templates),
+            unsubscribe: Arc::clone(&self.unsubscribe),
+            track_engagement: Arc::clone(&self.track_engagement),
+            webhook_deliveries: Arc::clone(&self.webhook_deliveries),
+            webhook_subscriptions: Arc::clone(&self.webhook_subscriptions),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+impl<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS>
+    TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS>
+{
+    pub(crate) fn with_submit_email<T: SubmitEmailUseCase>(
+        self,
+        submit_email: Arc<T>,
+    ) -> TestState<T, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_list_emails<T: ListEmailsUseCase>(
+        self,
+        list_emails: Arc<T>,
+    ) -> TestState<SE, T, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_list_events<T: ListEventsUseCase>(
+        self,
+        list_events: Arc<T>,
+    ) -> TestState<SE, LE, T, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_stream_events<T: StreamEventsUseCase>(
+        self,
+        stream_events: Arc<T>,
+    ) -> TestState<SE, LE, LV, T, LS, CE, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_list_senders<T: ListSendersUseCase>(
+        self,
+        list_senders: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, T, CE, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_cancel_email<T: CancelEmailUseCase>(
+        self,
+        cancel_email: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, T, SU, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_suppressions<T: ManageSuppressionsUseCase>(
+        self,
+        suppressions: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, T, PE, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_preview_email<T: PreviewEmailUseCase>(
+        self,
+        preview_email: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, T, TE, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_templates<T: ManageTemplatesUseCase>(
+        self,
+        templates: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, PE, T, UN, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_unsubscribe<T: UnsubscribeUseCase>(
+        self,
+        unsubscribe: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, T, TR, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_track_engagement<T: TrackEngagementUseCase>(
+        self,
+        track_engagement: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, T, WD, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_webhook_deliveries<T: ManageWebhookDeliveriesUseCase>(
+        self,
+        webhook_deliveries: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, T, WS> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries,
+            webhook_subscriptions: self.webhook_subscriptions,
+        }
+    }
+
+    pub(crate) fn with_webhook_subscriptions<T: ManageWebhookSubscriptionsUseCase>(
+        self,
+        webhook_subscriptions: Arc<T>,
+    ) -> TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, T> {
+        TestState {
+            submit_email: self.submit_email,
+            list_emails: self.list_emails,
+            list_events: self.list_events,
+            stream_events: self.stream_events,
+            list_senders: self.list_senders,
+            cancel_email: self.cancel_email,
+            suppressions: self.suppressions,
+            preview_email: self.preview_email,
+            templates: self.templates,
+            unsubscribe: self.unsubscribe,
+            track_engagement: self.track_engagement,
+            webhook_deliveries: self.webhook_deliveries,
+            webhook_subscriptions,
+        }
+    }
+}
+
+impl<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> ReadinessState
+    for TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS>
+where
+    SE: SubmitEmailUseCase,
+    LE: ListEmailsUseCase,
+    LV: ListEventsUseCase,
+    ST: StreamEventsUseCase,
+    LS: ListSendersUseCase,
+    CE: CancelEmailUseCase,
+    SU: ManageSuppressionsUseCase,
+    PE: PreviewEmailUseCase,
+    TE: ManageTemplatesUseCase,
+    UN: UnsubscribeUseCase,
+    TR: TrackEngagementUseCase,
+    WD: ManageWebhookDeliveriesUseCase,
+    WS: ManageWebhookSubscriptionsUseCase,
+{
+    fn check_readiness(&self) -> &impl CheckReadinessUseCase {
+        &NoopReadiness
+    }
+}
+
+impl<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS> HttpServerState
+    for TestState<SE, LE, LV, ST, LS, CE, SU, PE, TE, UN, TR, WD, WS>
+where
+    SE: SubmitEmailUseCase,
+    LE: ListEmailsUseCase,
+    LV: ListEventsUseCase,
+    ST: StreamEventsUseCase,
+    LS: ListSendersUseCase,
+    CE: CancelEmailUseCase,
+    SU: ManageSuppressionsUseCase,
+    PE: PreviewEmailUseCase,
+    TE: ManageTemplatesUseCase,
+    UN: UnsubscribeUseCase,
+    TR: TrackEngagementUseCase,
+    WD: ManageWebhookDeliveriesUseCase,
+    WS: ManageWebhookSubscriptionsUseCase,
+{
+    fn submit_email(&self) -> &impl SubmitEmailUseCase {
+        self.submit_email.as_ref()
+    }
+
+    fn list_emails(&self) -> &impl ListEmailsUseCase {
+        self.list_emails.as_ref()
+    }
+
+    fn list_events(&self) -> &impl ListEventsUseCase {
+        self.list_events.as_ref()
+    }
+
+    fn stream_events(&self) -> &impl StreamEventsUseCase {
+        self.stream_events.as_ref()
+    }
+
+    fn list_senders(&self) -> &impl ListSendersUseCase {
+        self.list_senders.as_ref()
+    }
+
+    fn cancel_email(&self) -> &impl CancelEmailUseCase {
+        self.cancel_email.as_ref()
+    }
+
+    fn suppressions(&self) -> &impl ManageSuppressionsUseCase {
+        self.suppressions.as_ref()
+    }
+
+    fn preview_email(&self) -> &impl PreviewEmailUseCase {
+        self.preview_email.as_ref()
+    }
+
+    fn templates(&self) -> &impl ManageTemplatesUseCase {
+        self.templates.as_ref()
+    }
+
+    fn unsubscribe(&self) -> &impl UnsubscribeUseCase {
+        self.unsubscribe.as_ref()
+    }
+
+    fn track_engagement(&self) -> &impl TrackEngagementUseCase {
+        self.track_engagement.as_ref()
+    }
+
+    fn webhook_deliveries(&self) -> &impl ManageWebhookDeliveriesUseCase {
+        self.webhook_deliveries.as_ref()
+    }
+
+    fn webhook_subscriptions(&self) -> &impl ManageWebhookSubscriptionsUseCase {
+        self.webhook_subscriptions.as_ref()
+    }
+}
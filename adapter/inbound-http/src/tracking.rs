@@ -0,0 +1,191 @@
+use catapulte_domain::entity::email::EmailId;
+use catapulte_domain::entity::tracking::{TrackedEmail, TrackingHit};
+use catapulte_domain::port::tracking_links::TrackingLinks;
+use serde::{Deserialize, Serialize};
+
+use crate::signed_token::{TokenKind, TokenSigner, link, link_settings_from_env};
+
+/// Tracked email, plus the target of a click link, as carried by a token.
+#[derive(Serialize, Deserialize)]
+struct TokenPayload {
+    #[serde(rename = "e")]
+    email_id: String,
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+    #[serde(rename = "u", default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+/// Issues `<base_url>/track/open/<token>` pixels and
+/// `<base_url>/track/click/<token>` redirects. Click tokens sign their
+/// target, so the redirect cannot be pointed anywhere else.
+#[derive(Clone)]
+pub struct HmacTrackingLinks {
+    base_url: url::Url,
+    signer: TokenSigner,
+}
+
+impl HmacTrackingLinks {
+    /// # Errors
+    ///
+    /// Returns an error when `secret` is shorter than 32 bytes.
+    pub fn new(base_url: url::Url, secret: &[u8]) -> anyhow::Result<Self> {
+        Ok(Self {
+            base_url,
+            signer: TokenSigner::new(secret)?,
+        })
+    }
+
+    fn token(&self, kind: TokenKind, email: &TrackedEmail, url: Option<&str>) -> Option<String> {
+        self.signer.sign(
+            kind,
+            &TokenPayload {
+                email_id: email.email_id.as_uuid().simple().to_string(),
+                correlation_id: email.correlation_id.clone(),
+                url: url.map(str::to_owned),
+            },
+        )
+    }
+}
+
+impl TrackingLinks for HmacTrackingLinks {
+    fn open_link(&self, email: &TrackedEmail) -> Option<String> {
+        let token = self.token(TokenKind::Open, email, None)?;
+        Some(link(&self.base_url, &format!("track/open/{token}")))
+    }
+
+    fn click_link(&self, email: &TrackedEmail, url: &str) -> Option<String> {
+        let token = self.token(TokenKind::Click, email, Some(url))?;
+        Some(link(&self.base_url, &format!("track/click/{token}")))
+    }
+
+    fn verify(&self, token: &str) -> Option<TrackingHit> {
+        let (kind, payload): (_, TokenPayload) = self.signer.verify(token)?;
+        let email = TrackedEmail {
+            email_id: EmailId::from(uuid::Uuid::parse_str(&payload.email_id).ok()?),
+            correlation_id: payload.correlation_id,
+        };
+        match (kind, payload.url) {
+            (TokenKind::Open, None) => Some(TrackingHit::Open(email)),
+            (TokenKind::Click, Some(url)) => Some(TrackingHit::Click { email, url }),
+            _ => None,
+        }
+    }
+}
+
+pub struct TrackingLinksConfig {
+    /// Public URL the HTTP API is reachable at, e.g. `https://mail.acme.com`.
+    pub base_url: Option<url::Url>,
+    pub secret: Option<String>,
+}
+
+impl TrackingLinksConfig {
+    /// # Errors
+    ///
+    /// Returns an error if `<prefix>_URL` is set but is not an http(s) URL.
+    pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
+        let (base_url, secret) = link_settings_from_env(prefix)?;
+        Ok(Self { base_url, secret })
+    }
+
+    /// Returns `None` when neither the URL nor the secret is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when only one of them is set or the secret is too short.
+    pub fn build(self) -> anyhow::Result<Option<HmacTrackingLinks>> {
+        match (self.base_url, self.secret) {
+            (None, None) => Ok(None),
+            (Some(base_url), Some(secret)) => {
+                HmacTrackingLinks::new(base_url, secret.as_bytes()).map(Some)
+            }
+            _ => anyhow::bail!("tracking links need both a URL and a secret"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::entity::tracking::{TrackedEmail, TrackingHit};
+    use catapulte_domain::entity::unsubscribe::{MailingList, Subscription};
+    use catapulte_domain::port::tracking_links::TrackingLinks;
+    use catapulte_domain::port::unsubscribe_links::UnsubscribeLinks;
+
+    use super::HmacTrackingLinks;
+    use crate::unsubscribe::HmacUnsubscribeLinks;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn links() -> HmacTrackingLinks {
+        HmacTrackingLinks::new("https://mail.acme.com".parse().unwrap(), SECRET).unwrap()
+    }
+
+    #[test]
+    fn open_and_click_links_round_trip_through_verify() {
+        let links = links();
+        let email = TrackedEmail {
+            email_id: EmailId::default(),
+            correlation_id: Some("corr-1".into()),
+        };
+
+        let open = links.open_link(&email).unwrap();
+        let token = open
+            .strip_prefix("https://mail.acme.com/track/open/")
+            .unwrap();
+        assert_eq!(links.verify(token), Some(TrackingHit::Open(email.clone())));
+
+        let click = links
+            .click_link(&email, "https://acme.com/a?x=1&y=2")
+            .unwrap();
+        let token = click
+            .strip_prefix("https://mail.acme.com/track/click/")
+            .unwrap();
+        assert_eq!(
+            links.verify(token),
+            Some(TrackingHit::Click {
+                email,
+                url: "https://acme.com/a?x=1&y=2".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn click_targets_cannot_be_swapped() {
+        let links = links();
+        let email = TrackedEmail {
+            email_id: EmailId::default(),
+            correlation_id: None,
+        };
+        let click = links.click_link(&email, "https://acme.com").unwrap();
+        let (_, signature) = click.rsplit_once('.').unwrap();
+        let evil = links.click_link(&email, "https://evil.example").unwrap();
+        let (evil_payload, _) = evil
+            .strip_prefix("https://mail.acme.com/track/click/")
+            .unwrap()
+            .split_once('.')
+            .unwrap();
+
+        assert_eq!(links.verify(&format!("{evil_payload}.{signature}")), None);
+    }
+
+    #[test]
+    fn unsubscribe_tokens_are_not_tracking_tokens() {
+        let unsubscribe =
+            HmacUnsubscribeLinks::new("https://mail.acme.com".parse().unwrap(), SECRET).unwrap();
+        let link = unsubscribe
+            .link(&Subscription {
+                email_id: EmailId::default(),
+                sender: "news@acme.com".into(),
+                list: MailingList::parse("newsletter").unwrap(),
+                address: "bob@example.com".into(),
+                correlation_id: None,
+            })
+            .unwrap();
+        let token = link
+            .strip_prefix("https://mail.acme.com/unsubscribe/")
+            .unwrap();
+
+        assert_eq!(links().verify(token), None);
+    }
+}
@@ -0,0 +1,193 @@
+use catapulte_domain::entity::email::EmailId;
+use catapulte_domain::entity::unsubscribe::{MailingList, Subscription};
+use catapulte_domain::port::unsubscribe_links::UnsubscribeLinks;
+use serde::{Deserialize, Serialize};
+
+use crate::signed_token::{TokenKind, TokenSigner, link, link_settings_from_env};
+
+/// Subscription as carried by a token. Field names are kept short since the
+/// token ends up in a header line.
+#[derive(Serialize, Deserialize)]
+struct TokenPayload {
+    #[serde(rename = "e")]
+    email_id: String,
+    #[serde(rename = "s")]
+    sender: String,
+    #[serde(rename = "l")]
+    list: String,
+    #[serde(rename = "a")]
+    address: String,
+    #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
+}
+
+/// Issues `<base_url>/unsubscribe/<token>` links, the token being the signed
+/// subscription.
+#[derive(Clone)]
+pub struct HmacUnsubscribeLinks {
+    base_url: url::Url,
+    signer: TokenSigner,
+}
+
+impl HmacUnsubscribeLinks {
+    /// # Errors
+    ///
+    /// Returns an error when `secret` is shorter than 32 bytes.
+    pub fn new(base_url: url::Url, secret: &[u8]) -> anyhow::Result<Self> {
+        Ok(Self {
+            base_url,
+            signer: TokenSigner::new(secret)?,
+        })
+    }
+}
+
+impl UnsubscribeLinks for HmacUnsubscribeLinks {
+    fn link(&self, subscription: &Subscription) -> Option<String> {
+        let token = self.signer.sign(
+            TokenKind::Unsubscribe,
+            &TokenPayload {
+                email_id: subscription.email_id.as_uuid().simple().to_string(),
+                sender: subscription.sender.clone(),
+                list: subscription.list.as_str().to_owned(),
+                address: subscription.address.clone(),
+                correlation_id: subscription.correlation_id.clone(),
+            },
+        )?;
+        Some(link(&self.base_url, &format!("unsubscribe/{token}")))
+    }
+
+    fn verify(&self, token: &str) -> Option<Subscription> {
+        let (kind, payload): (_, TokenPayload) = self.signer.verify(token)?;
+        if kind != TokenKind::Unsubscribe {
+            return None;
+        }
+        Some(Subscription {
+            email_id: EmailId::from(uuid::Uuid::parse_str(&payload.email_id).ok()?),
+            sender: payload.sender,
+            list: MailingList::parse(&payload.list).ok()?,
+            address: payload.address,
+            correlation_id: payload.correlation_id,
+        })
+    }
+}
+
+pub struct UnsubscribeLinksConfig {
+    /// Public URL the HTTP API is reachable at, e.g. `https://mail.acme.com`.
+    pub base_url: Option<url::Url>,
+    pub secret: Option<String>,
+}
+
+impl UnsubscribeLinksConfig {
+    /// # Errors
+    ///
+    /// Returns an error if `<prefix>_URL` is set but is not an http(s) URL.
+    pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
+        let (base_url, secret) = link_settings_from_env(prefix)?;
+        Ok(Self { base_url, secret })
+    }
+
+    /// Returns `None` when neither the URL nor the secret is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when only one of them is set or the secret is too short.
+    pub fn build(self) -> anyhow::Result<Option<HmacUnsubscribeLinks>> {
+        match (self.base_url, self.secret) {
+            (None, None) => Ok(None),
+            (Some(base_url), Some(secret)) => {
+                HmacUnsubscribeLinks::new(base_url, secret.as_bytes()).map(Some)
+            }
+            _ => anyhow::bail!("unsubscribe links need both a URL and a secret"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::entity::unsubscribe::{MailingList, Subscription};
+    use catapulte_domain::port::unsubscribe_links::UnsubscribeLinks;
+
+    use super::{HmacUnsubscribeLinks, UnsubscribeLinksConfig};
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn links() -> HmacUnsubscribeLinks {
+        HmacUnsubscribeLinks::new("https://mail.acme.com/".parse().unwrap(), SECRET).unwrap()
+    }
+
+    fn subscription() -> Subscription {
+        Subscription {
+            email_id: EmailId::default(),
+            sender: "news@acme.com".into(),
+            list: MailingList::parse("newsletter").unwrap(),
+            address: "bob@example.com".into(),
+            correlation_id: Some("corr-1".into()),
+        }
+    }
+
+    fn token_of(link: &str) -> &str {
+        link.strip_prefix("https://mail.acme.com/unsubscribe/")
+            .unwrap()
+    }
+
+    #[test]
+    fn links_round_trip_through_verify() {
+        let links = links();
+        let subscription = subscription();
+        let link = links.link(&subscription).unwrap();
+        let token = token_of(&link);
+        assert!(
+            token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')),
+            "{token}"
+        );
+        assert_eq!(links.verify(token), Some(subscription));
+    }
+
+    #[test]
+    fn tampered_or_foreign_tokens_are_rejected() {
+        let link = links().link(&subscription()).unwrap();
+        let token = token_of(&link);
+
+        let mut other = subscription();
+        other.address = "mallory@example.com".into();
+        let forged_link = links().link(&other).unwrap();
+        let (forged_payload, _) = token_of(&forged_link).split_once('.').unwrap();
+        let (_, signature) = token.split_once('.').unwrap();
+        assert_eq!(
+            links().verify(&format!("{forged_payload}.{signature}")),
+            None
+        );
+
+        let foreign = HmacUnsubscribeLinks::new(
+            "https://mail.acme.com".parse().unwrap(),
+            b"another secret that is long enough",
+        )
+        .unwrap();
+        assert_eq!(foreign.verify(token), None);
+        assert_eq!(links().verify("not-a-token"), None);
+    }
+
+    #[test]
+    fn config_needs_both_url_and_a_long_enough_secret() {
+        let url = || Some("https://mail.acme.com".parse().unwrap());
+        let build = |base_url, secret: Option<&str>| {
+            UnsubscribeLinksConfig {
+                base_url,
+                secret: secret.map(str::to_owned),
+            }
+            .build()
+        };
+        assert!(build(None, None).unwrap().is_none());
+        assert!(build(url(), None).is_err());
+        assert!(build(None, Some("0123456789abcdef0123456789abcdef")).is_err());
+        assert!(build(url(), Some("short")).is_err());
+        assert!(
+            build(url(), Some("0123456789abcdef0123456789abcdef"))
+                .unwrap()
+                .is_some()
+        );
+    }
+}
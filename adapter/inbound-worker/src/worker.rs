@@ -1,15 +1,18 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use catapulte_domain::entity::error_class::ErrorClass;
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+use catapulte_domain::entity::retry_policy::{RetryPolicy, RetryRule};
 use catapulte_domain::port::attachment_store::AttachmentStore;
-use catapulte_domain::port::email_queue::{AckToken, EmailQueue};
+use catapulte_domain::port::email_queue::{DequeuedEmail, EmailQueue};
 use catapulte_domain::port::email_repository::EmailRepository;
 use catapulte_domain::port::event_publisher::EventPublisher;
 use catapulte_domain::use_case::process_queued_email::{
-    ProcessQueuedEmailError, ProcessQueuedEmailUseCase,
+    ProcessOutcome, ProcessQueuedEmailError, ProcessQueuedEmailUseCase,
 };
 use tracing::Instrument as _;
 
-const MAX_ATTEMPTS: u32 = 3;
-
 pub trait WorkerState: Clone + Send + Sync + 'static {
     fn process_queued_email(&self) -> &impl ProcessQueuedEmailUseCase;
     fn email_queue(&self) -> &impl EmailQueue;
@@ -20,11 +23,15 @@ pub trait WorkerState: Clone + Send + Sync + 'static {
 
 pub struct WorkerConfig {
     concurrency: usize,
+    retry_policy: RetryPolicy,
 }
 
 impl Default for WorkerConfig {
     fn default() -> Self {
-        Self { concurrency: 1 }
+        Self {
+            concurrency: 1,
+            retry_policy: RetryPolicy::default(),
+        }
     }
 }
 
@@ -32,7 +39,8 @@ impl WorkerConfig {
     /// # Errors
     ///
     /// Returns an error if `CATAPULTE_WORKER_CONCURRENCY` is set but cannot be
-    /// parsed as a positive integer.
+    /// parsed as a positive integer, or if a `CATAPULTE_WORKER_RETRY_*` variable
+    /// is invalid.
     pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
         use anyhow::Context as _;
 
@@ -49,24 +57,108 @@ impl WorkerConfig {
         if concurrency == 0 {
             anyhow::bail!("{concurrency_key} must be at least 1 (got 0)");
         }
-        Ok(Self { concurrency })
+
+        let retry_prefix = format!("{prefix}_RETRY");
+        let default_rule = retry_rule_from_env(&retry_prefix, RetryRule::default())?;
+        let mut retry_policy = RetryPolicy::new(default_rule.clone());
+        for class in ErrorClass::ALL {
+            let class_prefix = format!("{retry_prefix}_{}", class.as_str().to_ascii_uppercase());
+            let rule = retry_rule_from_env(&class_prefix, default_rule.clone())?;
+            if rule != default_rule {
+                retry_policy = retry_policy.with_rule(class, rule);
+            }
+        }
+
+        Ok(Self {
+            concurrency,
+            retry_policy,
+        })
+    }
+
+    #[must_use]
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
     }
 
     #[must_use]
     pub fn build(self) -> Worker {
         Worker {
             concurrency: self.concurrency,
+            retry_policy: Arc::new(self.retry_policy),
         }
     }
 }
 
+fn env_var(key: &str) -> anyhow::Result<Option<String>> {
+    match std::env::var(key) {
+        Ok(v) => Ok(Some(v)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(e) => Err(anyhow::Error::new(e).context(format!("reading {key}"))),
+    }
+}
+
+/// Reads `{prefix}_MAX_ATTEMPTS`, `{prefix}_BACKOFF`, `{prefix}_JITTER_PERCENT`
+/// and `{prefix}_MAX_AGE_SECS`, keeping the value from `base` for unset ones.
+fn retry_rule_from_env(prefix: &str, base: RetryRule) -> anyhow::Result<RetryRule> {
+    use anyhow::Context as _;
+
+    let mut rule = base;
+
+    let key = format!("{prefix}_MAX_ATTEMPTS");
+    if let Some(v) = env_var(&key)? {
+        rule.max_attempts = v.parse().with_context(|| format!("invalid {key}: {v:?}"))?;
+        if rule.max_attempts == 0 {
+            anyhow::bail!("{key} must be at least 1 (got 0)");
+        }
+    }
+
+    let key = format!("{prefix}_BACKOFF");
+    if let Some(v) = env_var(&key)? {
+        rule.backoff = v
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse::<u64>()
+                    .map(Duration::from_secs)
+                    .with_context(|| format!("invalid value in {key}: {s:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+    }
+
+    let key = format!("{prefix}_JITTER_PERCENT");
+    if let Some(v) = env_var(&key)? {
+        rule.jitter_percent = v.parse().with_context(|| format!("invalid {key}: {v:?}"))?;
+        if rule.jitter_percent > 100 {
+            anyhow::bail!("{key} must be at most 100 (got {})", rule.jitter_percent);
+        }
+    }
+
+    let key = format!("{prefix}_MAX_AGE_SECS");
+    if let Some(v) = env_var(&key)? {
+        let secs: u64 = v.parse().with_context(|| format!("invalid {key}: {v:?}"))?;
+        rule.max_age = (secs > 0).then(|| Duration::from_secs(secs));
+    }
+
+    Ok(rule)
+}
+
 pub struct Worker {
     concurrency: usize,
+    retry_policy: Arc<RetryPolicy>,
 }
 
-fn backoff(attempt: u32) -> std::time::Duration {
-    let secs = (30u64 * (1u64 << attempt.saturating_sub(1))).min(3600);
-    std::time::Duration::from_secs(secs)
+/// How long the email has been due: since it was queued, or since its
+/// scheduled send time when that is later.
+fn due_age(enqueued_at_ms: i64, send_at_ms: Option<i64>) -> Duration {
+    let now_ms = i64::try_from(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+    )
+    .unwrap_or(i64::MAX);
+    let due_ms = send_at_ms.map_or(enqueued_at_ms, |at| at.max(enqueued_at_ms));
+    Duration::from_millis(u64::try_from(now_ms.saturating_sub(due_ms)).unwrap_or(0))
 }
 
 impl Worker {
@@ -77,7 +169,7 @@ impl Worker {
     pub async fn run<S: WorkerState>(self, state: S, cancel: tokio_util::sync::CancellationToken) {
         tracing::info!(concurrency = self.concurrency, "worker started");
 
-        let sem = std::sync::Arc::new(tokio::sync::Semaphore::new(self.concurrency));
+        let sem = Arc::new(tokio::sync::Semaphore::new(self.concurrency));
         let mut tasks: tokio::task::JoinSet<()> = tokio::task::JoinSet::new();
 
         loop {
@@ -98,17 +190,10 @@ impl Worker {
                     // Clone the state for the spawned task. WorkerState impls
                     // hold Arc-wrapped internals so clones are cheap.
                     let st = state.clone();
+                    let policy = Arc::clone(&self.retry_policy);
                     tasks.spawn(async move {
                         let _permit = permit; // holds the slot until the task finishes
-                        process_one(
-                            &st,
-                            dequeued.id,
-                            dequeued.envelope,
-                            dequeued.attempt,
-                            dequeued.token,
-                            dequeued.trace,
-                        )
-                        .await;
+                        process_one(&st, &policy, dequeued).await;
                     });
                     // Reap finished tasks so the JoinSet does not grow unbounded.
                     while tasks.try_join_next().is_some() {}
@@ -133,14 +218,15 @@ impl Worker {
 }
 
 #[allow(clippy::too_many_lines)]
-async fn process_one<S: WorkerState>(
-    state: &S,
-    id: catapulte_domain::entity::email::EmailId,
-    envelope: catapulte_domain::entity::envelope::Envelope,
-    attempt: u32,
-    token: AckToken,
-    trace: catapulte_domain::port::email_queue::TraceCarrier,
-) {
+async fn process_one<S: WorkerState>(state: &S, policy: &RetryPolicy, dequeued: DequeuedEmail) {
+    let DequeuedEmail {
+        id,
+        envelope,
+        attempt,
+        enqueued_at_ms,
+        token,
+        trace,
+    } = dequeued;
     let span = tracing::info_span!(
         "worker.process",
         email_id = %id.as_uuid(),
@@ -155,6 +241,25 @@ async fn process_one<S: WorkerState>(
             tracing::Span::current().record("correlation_id", cid.as_str());
         }
 
+        match state.email_repository().begin_delivery(id).await {
+            Ok(true) => {}
+            Ok(false) => {
+                tracing::info!(email_id = %id.as_uuid(), "email was cancelled, skipping");
+                if let Err(e) = state.email_queue().ack(token).await {
+                    tracing::error!(error = %e, "failed to ack cancelled email");
+                }
+                return;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "failed to mark email as delivering");
+                let delay = policy.default_rule().delay(attempt);
+                if let Err(nack_err) = state.email_queue().nack(token, delay).await {
+                    tracing::error!(error = %nack_err, "failed to nack email");
+                }
+                return;
+            }
+        }
+
         if let Err(e) = state
             .event_publisher()
             .publish(&LifecycleEvent::Sending {
@@ -168,9 +273,10 @@ async fn process_one<S: WorkerState>(
         }
 
         let attachments_for_cleanup = envelope.attachments.clone();
+        let age = due_age(enqueued_at_ms, envelope.send_at_ms);
 
-        match state.process_queued_email().execute(envelope).await {
-            Ok(sender_name) => {
+        match state.process_queued_email().execute(id, envelope).await {
+            Ok(outcome) => {
                 if let Err(e) = state.email_queue().ack(token).await {
                     tracing::error!(error = %e, email_id = %id.as_uuid(), "failed to ack email");
                     return;
@@ -191,16 +297,42 @@ async fn process_one<S: WorkerState>(
                         );
                     }
                 }
-                if let Err(e) = state
-                    .event_publisher()
-                    .publish(&LifecycleEvent::Sent {
-                        id,
+                let events = match outcome {
+                    ProcessOutcome::Sent {
                         sender_name,
-                        correlation_id: correlation_id.clone(),
-                    })
-                    .await
-                {
-                    tracing::error!(error = %e, "failed to publish sent event");
+                        suppressed,
+                    } => {
+                        let mut events = Vec::with_capacity(2);
+                        if !suppressed.is_empty() {
+                            events.push(LifecycleEvent::Suppressed {
+                                id,
+                                recipients: suppressed,
+                                correlation_id: correlation_id.clone(),
+                            });
+                        }
+                        events.push(LifecycleEvent::Sent {
+                            id,
+                            sender_name,
+                            correlation_id: correlation_id.clone(),
+                        });
+                        events
+                    }
+                    ProcessOutcome::Suppressed { recipients } => {
+                        tracing::info!(
+                            email_id = %id.as_uuid(),
+                            "every recipient is suppressed, skipping"
+                        );
+                        vec![LifecycleEvent::Suppressed {
+                            id,
+                            recipients,
+                            correlation_id: correlation_id.clone(),
+                        }]
+                    }
+                };
+                for event in &events {
+                    if let Err(e) = state.event_publisher().publish(event).await {
+                        tracing::error!(error = %e, "failed to publish lifecycle event");
+                    }
                 }
             }
             Err(e) => {
@@ -213,15 +345,25 @@ async fn process_one<S: WorkerState>(
                 let reason = e.to_string();
                 let error_class = e.error_class();
                 let sender_name = e.sender_name().cloned();
-                let is_terminal =
-                    matches!(&e, ProcessQueuedEmailError::Send(s) if !s.is_transient())
-                        || attempt >= MAX_ATTEMPTS;
-                let event = if is_terminal {
-                    if let Err(ack_err) = state.email_queue().ack(token).await {
-                        tracing::error!(error = %ack_err, "failed to ack permanently failed email");
+                let retry_delay = if matches!(&e, ProcessQueuedEmailError::Send(s) if !s.is_transient())
+                {
+                    None
+                } else {
+                    policy.retry_delay(&error_class, attempt, age)
+                };
+                let event = if let Some(delay) = retry_delay {
+                    // Back in the queue: cancellable again until the next attempt.
+                    if let Err(end_err) = state.email_repository().end_delivery(id).await {
+                        tracing::warn!(error = %end_err, "failed to clear delivering mark");
+                    }
+                    if let Err(nack_err) = state.email_queue().nack(token, delay).await {
+                        tracing::error!(
+                            error = %nack_err,
+                            "failed to nack transiently failed email"
+                        );
                         return;
                     }
-                    LifecycleEvent::Failed {
+                    LifecycleEvent::Retrying {
                         id,
                         attempt,
                         reason,
@@ -230,15 +372,11 @@ async fn process_one<S: WorkerState>(
                         correlation_id: correlation_id.clone(),
                     }
                 } else {
-                    let delay = backoff(attempt);
-                    if let Err(nack_err) = state.email_queue().nack(token, delay).await {
-                        tracing::error!(
-                            error = %nack_err,
-                            "failed to nack transiently failed email"
-                        );
+                    if let Err(ack_err) = state.email_queue().ack(token).await {
+                        tracing::error!(error = %ack_err, "failed to ack permanently failed email");
                         return;
                     }
-                    LifecycleEvent::Retrying {
+                    LifecycleEvent::Failed {
                         id,
                         attempt,
                         reason,
@@ -267,6 +405,8 @@ mod tests {
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+    use catapulte_domain::entity::retry_policy::{RetryPolicy, RetryRule};
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::attachment_store::{
         AttachmentReader, AttachmentStore, AttachmentStoreError, PutResult,
@@ -275,15 +415,27 @@ mod tests {
         AckToken, DequeuedEmail, EmailQueue, EmailQueueError, TraceCarrier,
     };
     use catapulte_domain::port::email_repository::{
-        EmailRecord, EmailRepository, EmailRepositoryError, ListEmailsParams, SaveResult,
+        CancelResult, EmailRecord, EmailRepository, EmailRepositoryError, ListEmailsParams,
+        SaveResult,
     };
     use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
     use catapulte_domain::use_case::process_queued_email::{
-        ProcessQueuedEmailError, ProcessQueuedEmailUseCase,
+        ProcessOutcome, ProcessQueuedEmailError, ProcessQueuedEmailUseCase,
     };
 
     use super::{Worker, WorkerState, process_one};
 
+    fn dequeued(id: EmailId, envelope: Envelope, attempt: u32, token: AckToken) -> DequeuedEmail {
+        DequeuedEmail {
+            id,
+            envelope,
+            attempt,
+            enqueued_at_ms: 0,
+            token,
+            trace: TraceCarrier::default(),
+        }
+    }
+
     fn sample_envelope() -> Envelope {
         Envelope {
             idempotency_key: None,
@@ -294,14 +446,27 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hi".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
     struct OkProcessor;
 
     impl ProcessQueuedEmailUseCase for OkProcessor {
-        async fn execute(&self, _: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
-            Ok(SenderName::new("sender"))
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
+            Ok(ProcessOutcome::Sent {
+                sender_name: SenderName::new("sender"),
+                suppressed: vec![],
+            })
         }
     }
 
@@ -431,6 +596,18 @@ mod tests {
         async fn delete(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
             unimplemented!()
         }
+
+        async fn cancel(&self, _: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _: EmailId) -> Result<bool, EmailRepositoryError> {
+            Ok(true)
+        }
+
+        async fn end_delivery(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
+            Ok(())
+        }
     }
 
     /// Noop repository used by tests that don't care about repository calls.
@@ -467,6 +644,18 @@ mod tests {
         async fn delete(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
             unimplemented!()
         }
+
+        async fn cancel(&self, _: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _: EmailId) -> Result<bool, EmailRepositoryError> {
+            Ok(true)
+        }
+
+        async fn end_delivery(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
+            Ok(())
+        }
     }
 
     /// Simple capturing store used by tests that only need to observe deletes.
@@ -490,15 +679,67 @@ mod tests {
         }
     }
 
+    /// Repository reporting every email as cancelled before delivery starts.
     #[derive(Clone)]
-    struct TestState<Q: Clone + Send + Sync + 'static> {
+    struct CancelledRepository;
+
+    impl EmailRepository for CancelledRepository {
+        async fn save(&self, _: EmailId, _: &Envelope) -> Result<SaveResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn list_all_attachment_blobs(
+            &self,
+        ) -> Result<Vec<catapulte_domain::entity::attachment::BlobRef>, EmailRepositoryError>
+        {
+            unimplemented!()
+        }
+
+        async fn list_emails(
+            &self,
+            _: ListEmailsParams,
+        ) -> Result<Vec<EmailRecord>, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn set_attachments(
+            &self,
+            _: EmailId,
+            _: &[AttachmentRef],
+        ) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn delete(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn cancel(&self, _: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _: EmailId) -> Result<bool, EmailRepositoryError> {
+            Ok(false)
+        }
+
+        async fn end_delivery(&self, _: EmailId) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestState<Q: Clone + Send + Sync + 'static, R = NoopRepository> {
         queue: Q,
         publisher: RecordingPublisher,
         store: CapturingDeleteStore,
-        repository: NoopRepository,
+        repository: R,
     }
 
-    impl<Q: EmailQueue + Clone + Send + Sync + 'static> WorkerState for TestState<Q> {
+    impl<Q, R> WorkerState for TestState<Q, R>
+    where
+        Q: EmailQueue + Clone + Send + Sync + 'static,
+        R: EmailRepository + Clone,
+    {
         fn process_queued_email(&self) -> &impl ProcessQueuedEmailUseCase {
             &OkProcessor
         }
@@ -638,7 +879,10 @@ mod tests {
         use tokio_util::sync::CancellationToken;
 
         let cancel = CancellationToken::new();
-        let worker = Worker { concurrency: 1 };
+        let worker = Worker {
+            concurrency: 1,
+            retry_policy: Arc::default(),
+        };
         let cancel_clone = cancel.clone();
 
         tokio::spawn(async move {
@@ -668,11 +912,8 @@ mod tests {
 
         process_one(
             &state,
-            id,
-            sample_envelope(),
-            1,
-            token,
-            TraceCarrier::default(),
+            &RetryPolicy::default(),
+            dequeued(id, sample_envelope(), 1, token),
         )
         .await;
 
@@ -687,6 +928,37 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn cancelled_email_is_acked_without_sending() {
+        let publisher = RecordingPublisher::default();
+        let queue = TrackingQueue::default();
+        let state = TestState {
+            queue: queue.clone(),
+            publisher: publisher.clone(),
+            store: CapturingDeleteStore::default(),
+            repository: CancelledRepository,
+        };
+
+        process_one(
+            &state,
+            &RetryPolicy::default(),
+            dequeued(
+                EmailId::default(),
+                sample_envelope(),
+                1,
+                AckToken::new(vec![0u8; 8]),
+            ),
+        )
+        .await;
+
+        assert_eq!(*queue.acked.lock().unwrap(), 1);
+        assert_eq!(*queue.nacked.lock().unwrap(), 0);
+        assert!(
+            publisher.events.lock().unwrap().is_empty(),
+            "a cancelled email must not emit sending/sent events"
+        );
+    }
+
     #[tokio::test]
     async fn sent_email_blobs_are_deleted() {
         let store = CapturingDeleteStore::default();
@@ -707,12 +979,18 @@ mod tests {
             content_type: "application/pdf".into(),
             size_bytes: 1024,
             blob: blob.clone(),
+            content_id: None,
         }];
 
         let id = EmailId::default();
         let token = AckToken::new(vec![0u8; 8]);
 
-        process_one(&state, id, envelope, 1, token, TraceCarrier::default()).await;
+        process_one(
+            &state,
+            &RetryPolicy::default(),
+            dequeued(id, envelope, 1, token),
+        )
+        .await;
 
         let deleted = store.deleted.lock().unwrap();
         assert!(
@@ -736,10 +1014,16 @@ mod tests {
             content_type: "application/pdf".into(),
             size_bytes: 512,
             blob: blob.clone(),
+            content_id: None,
         }];
 
         let token = AckToken::new(vec![0u8; 8]);
-        process_one(&state, id, envelope, 1, token, TraceCarrier::default()).await;
+        process_one(
+            &state,
+            &RetryPolicy::default(),
+            dequeued(id, envelope, 1, token),
+        )
+        .await;
 
         let ops = state.log.snapshot();
         let set_pos = ops
@@ -759,7 +1043,11 @@ mod tests {
     struct NoMatchingRouteProcessor;
 
     impl ProcessQueuedEmailUseCase for NoMatchingRouteProcessor {
-        async fn execute(&self, _: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
             Err(ProcessQueuedEmailError::Send(
                 catapulte_domain::port::email_sender::SendError::NoMatchingRoute {
                     sender_domain: "example.com".to_owned(),
@@ -772,6 +1060,7 @@ mod tests {
     struct TrackingQueue {
         acked: Arc<Mutex<u32>>,
         nacked: Arc<Mutex<u32>>,
+        nack_delays: Arc<Mutex<Vec<Duration>>>,
     }
 
     impl EmailQueue for TrackingQueue {
@@ -788,8 +1077,9 @@ mod tests {
             Ok(())
         }
 
-        async fn nack(&self, _: AckToken, _: Duration) -> Result<(), EmailQueueError> {
+        async fn nack(&self, _: AckToken, delay: Duration) -> Result<(), EmailQueueError> {
             *self.nacked.lock().unwrap() += 1;
+            self.nack_delays.lock().unwrap().push(delay);
             Ok(())
         }
     }
@@ -822,6 +1112,77 @@ mod tests {
         }
     }
 
+    struct SuppressedProcessor;
+
+    impl ProcessQueuedEmailUseCase for SuppressedProcessor {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
+            Ok(ProcessOutcome::Suppressed {
+                recipients: vec!["to@example.com".to_owned()],
+            })
+        }
+    }
+
+    #[derive(Clone)]
+    struct SuppressedState {
+        queue: TrackingQueue,
+        publisher: RecordingPublisher,
+    }
+
+    impl WorkerState for SuppressedState {
+        fn process_queued_email(&self) -> &impl ProcessQueuedEmailUseCase {
+            &SuppressedProcessor
+        }
+
+        fn email_queue(&self) -> &impl EmailQueue {
+            &self.queue
+        }
+
+        fn event_publisher(&self) -> &impl EventPublisher {
+            &self.publisher
+        }
+
+        fn attachment_store(&self) -> &impl AttachmentStore {
+            &NoopStore
+        }
+
+        fn email_repository(&self) -> &impl EmailRepository {
+            &NoopRepository
+        }
+    }
+
+    #[tokio::test]
+    async fn fully_suppressed_email_is_acked_with_suppressed_event() {
+        let queue = TrackingQueue::default();
+        let publisher = RecordingPublisher::default();
+        let state = SuppressedState {
+            queue: queue.clone(),
+            publisher: publisher.clone(),
+        };
+
+        process_one(
+            &state,
+            &RetryPolicy::default(),
+            dequeued(
+                EmailId::default(),
+                sample_envelope(),
+                1,
+                AckToken::new(vec![0u8; 8]),
+            ),
+        )
+        .await;
+
+        assert_eq!(*queue.acked.lock().unwrap(), 1);
+        assert_eq!(*queue.nacked.lock().unwrap(), 0);
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec!["sending", "suppressed"]
+        );
+    }
+
     #[tokio::test]
     async fn non_transient_send_error_fails_immediately_without_retry() {
         let queue = TrackingQueue::default();
@@ -834,14 +1195,11 @@ mod tests {
         let id = EmailId::default();
         let token = AckToken::new(vec![0u8; 8]);
 
-        // attempt = 1, well below MAX_ATTEMPTS=3, so only the non-transient check drives failure
+        // attempt = 1, well below the default 3 attempts, so only the non-transient check drives failure
         process_one(
             &state,
-            id,
-            sample_envelope(),
-            1,
-            token,
-            TraceCarrier::default(),
+            &RetryPolicy::default(),
+            dequeued(id, sample_envelope(), 1, token),
         )
         .await;
 
@@ -923,11 +1281,8 @@ mod tests {
 
         process_one(
             &state,
-            id,
-            sample_envelope(),
-            1,
-            token,
-            TraceCarrier::default(),
+            &RetryPolicy::default(),
+            dequeued(id, sample_envelope(), 1, token),
         )
         .await;
 
@@ -942,6 +1297,227 @@ mod tests {
         );
     }
 
+    struct RejectedProcessor;
+
+    impl ProcessQueuedEmailUseCase for RejectedProcessor {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
+            Err(ProcessQueuedEmailError::Send(
+                catapulte_domain::port::email_sender::SendError::Rejected {
+                    sender_name: SenderName::new("primary"),
+                    reply: catapulte_domain::port::email_transport::SmtpReply::new(
+                        550,
+                        "5.1.1 <to@example.com>: user unknown",
+                    ),
+                    recipients: vec!["to@example.com".to_owned()],
+                },
+            ))
+        }
+    }
+
+    #[derive(Clone)]
+    struct RejectedState {
+        queue: TrackingQueue,
+        publisher: CapturingEventPublisher,
+    }
+
+    impl WorkerState for RejectedState {
+        fn process_queued_email(&self) -> &impl ProcessQueuedEmailUseCase {
+            &RejectedProcessor
+        }
+
+        fn email_queue(&self) -> &impl EmailQueue {
+            &self.queue
+        }
+
+        fn event_publisher(&self) -> &impl EventPublisher {
+            &self.publisher
+        }
+
+        fn attachment_store(&self) -> &impl AttachmentStore {
+            &NoopStore
+        }
+
+        fn email_repository(&self) -> &impl EmailRepository {
+            &NoopRepository
+        }
+    }
+
+    #[tokio::test]
+    async fn permanent_rejection_fails_on_first_attempt_with_server_reply() {
+        use catapulte_domain::entity::error_class::ErrorClass;
+
+        let queue = TrackingQueue::default();
+        let publisher = CapturingEventPublisher::default();
+        let state = RejectedState {
+            queue: queue.clone(),
+            publisher: publisher.clone(),
+        };
+
+        process_one(
+            &state,
+            &RetryPolicy::default(),
+            dequeued(
+                EmailId::default(),
+                sample_envelope(),
+                1,
+                AckToken::new(vec![0u8; 8]),
+            ),
+        )
+        .await;
+
+        assert_eq!(*queue.acked.lock().unwrap(), 1);
+        assert_eq!(*queue.nacked.lock().unwrap(), 0);
+        let events = publisher.events.lock().unwrap();
+        let Some(LifecycleEvent::Failed {
+            reason,
+            error_class,
+            ..
+        }) = events.last()
+        else {
+            panic!("expected a Failed event, got: {events:?}");
+        };
+        assert_eq!(*error_class, ErrorClass::Rejected);
+        assert!(
+            reason.contains("550 5.1.1 <to@example.com>: user unknown"),
+            "reason must carry the server reply, got: {reason}"
+        );
+    }
+
+    struct UnreachableProcessor;
+
+    impl ProcessQueuedEmailUseCase for UnreachableProcessor {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
+            Err(ProcessQueuedEmailError::Send(
+                catapulte_domain::port::email_sender::SendError::Send {
+                    sender_name: SenderName::new("primary"),
+                    source: anyhow::anyhow!("connection refused"),
+                },
+            ))
+        }
+    }
+
+    #[derive(Clone)]
+    struct UnreachableState {
+        queue: TrackingQueue,
+        publisher: RecordingPublisher,
+    }
+
+    impl WorkerState for UnreachableState {
+        fn process_queued_email(&self) -> &impl ProcessQueuedEmailUseCase {
+            &UnreachableProcessor
+        }
+
+        fn email_queue(&self) -> &impl EmailQueue {
+            &self.queue
+        }
+
+        fn event_publisher(&self) -> &impl EventPublisher {
+            &self.publisher
+        }
+
+        fn attachment_store(&self) -> &impl AttachmentStore {
+            &NoopStore
+        }
+
+        fn email_repository(&self) -> &impl EmailRepository {
+            &NoopRepository
+        }
+    }
+
+    fn now_ms() -> i64 {
+        i64::try_from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn transient_failure_is_retried_per_its_class_rule() {
+        use catapulte_domain::entity::error_class::ErrorClass;
+
+        let queue = TrackingQueue::default();
+        let publisher = RecordingPublisher::default();
+        let state = UnreachableState {
+            queue: queue.clone(),
+            publisher: publisher.clone(),
+        };
+        let policy = RetryPolicy::default().with_rule(
+            ErrorClass::Delivery,
+            RetryRule {
+                max_attempts: 10,
+                backoff: vec![Duration::from_secs(5), Duration::from_secs(90)],
+                ..RetryRule::default()
+            },
+        );
+        let mut email = dequeued(
+            EmailId::default(),
+            sample_envelope(),
+            4,
+            AckToken::new(vec![0u8; 8]),
+        );
+        email.enqueued_at_ms = now_ms();
+
+        // Attempt 4 is past the default rule's 3 attempts.
+        process_one(&state, &policy, email).await;
+
+        assert_eq!(*queue.acked.lock().unwrap(), 0);
+        assert_eq!(
+            *queue.nack_delays.lock().unwrap(),
+            vec![Duration::from_secs(90)]
+        );
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec!["sending", "retrying"]
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_failure_past_max_age_fails_for_good() {
+        use catapulte_domain::entity::error_class::ErrorClass;
+
+        let queue = TrackingQueue::default();
+        let publisher = RecordingPublisher::default();
+        let state = UnreachableState {
+            queue: queue.clone(),
+            publisher: publisher.clone(),
+        };
+        let policy = RetryPolicy::default().with_rule(
+            ErrorClass::Delivery,
+            RetryRule {
+                max_attempts: 100,
+                max_age: Some(Duration::from_hours(1)),
+                ..RetryRule::default()
+            },
+        );
+        let mut email = dequeued(
+            EmailId::default(),
+            sample_envelope(),
+            2,
+            AckToken::new(vec![0u8; 8]),
+        );
+        email.enqueued_at_ms = now_ms() - 2 * 3_600_000;
+
+        process_one(&state, &policy, email).await;
+
+        assert_eq!(*queue.acked.lock().unwrap(), 1);
+        assert_eq!(*queue.nacked.lock().unwrap(), 0);
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec!["sending", "delivery.failed"]
+        );
+    }
+
     // -------------------------------------------------------------------------
     // Helpers for Worker::run concurrency tests
     // -------------------------------------------------------------------------
@@ -979,6 +1555,7 @@ mod tests {
                         id,
                         envelope: sample_envelope(),
                         attempt: 1,
+                        enqueued_at_ms: 0,
                         token: AckToken::new(vec![0u8; 8]),
                         trace: TraceCarrier::default(),
                     });
@@ -1009,7 +1586,11 @@ mod tests {
     }
 
     impl ProcessQueuedEmailUseCase for PeakTrackingProcessor {
-        async fn execute(&self, _: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
             let current = self
                 .in_flight
                 .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
@@ -1026,7 +1607,10 @@ mod tests {
                 .fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
             self.processed
                 .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            Ok(SenderName::new("sender"))
+            Ok(ProcessOutcome::Sent {
+                sender_name: SenderName::new("sender"),
+                suppressed: vec![],
+            })
         }
     }
 
@@ -1081,7 +1665,11 @@ mod tests {
     }
 
     impl ProcessQueuedEmailUseCase for GateProcessor {
-        async fn execute(&self, _: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
+        async fn execute(
+            &self,
+            _: EmailId,
+            _: Envelope,
+        ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
             let current = self
                 .in_flight
                 .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
@@ -1098,7 +1686,10 @@ mod tests {
                 .fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
             self.processed
                 .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-            Ok(SenderName::new("sender"))
+            Ok(ProcessOutcome::Sent {
+                sender_name: SenderName::new("sender"),
+                suppressed: vec![],
+            })
         }
     }
 
@@ -1151,6 +1742,7 @@ mod tests {
         let run_task = tokio::spawn(
             Worker {
                 concurrency: CONCURRENCY,
+                retry_policy: Arc::default(),
             }
             .run(state, cancel.clone()),
         );
@@ -1253,7 +1845,11 @@ mod tests {
 
         tokio::time::timeout(
             std::time::Duration::from_secs(5),
-            Worker { concurrency: 1 }.run(state, cancel),
+            Worker {
+                concurrency: 1,
+                retry_policy: Arc::default(),
+            }
+            .run(state, cancel),
         )
         .await
         .expect("worker must finish within the timeout");
@@ -1278,12 +1874,19 @@ mod tests {
         }
 
         impl ProcessQueuedEmailUseCase for SlowProcessor {
-            async fn execute(&self, _: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
+            async fn execute(
+                &self,
+                _: EmailId,
+                _: Envelope,
+            ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
                 self.started.notify_one();
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
                 self.finished
                     .store(true, std::sync::atomic::Ordering::SeqCst);
-                Ok(SenderName::new("sender"))
+                Ok(ProcessOutcome::Sent {
+                    sender_name: SenderName::new("sender"),
+                    suppressed: vec![],
+                })
             }
         }
 
@@ -1334,7 +1937,11 @@ mod tests {
 
         tokio::time::timeout(
             std::time::Duration::from_secs(5),
-            Worker { concurrency: 2 }.run(state, cancel),
+            Worker {
+                concurrency: 2,
+                retry_policy: Arc::default(),
+            }
+            .run(state, cancel),
         )
         .await
         .expect("worker must finish within the timeout");
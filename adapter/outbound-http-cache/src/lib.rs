@@ -0,0 +1,660 @@
+use std::collections::HashMap;
+use std::env::VarError;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use reqwest::StatusCode;
+use reqwest::header::{ETAG, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+
+const DEFAULT_TTL: Duration = Duration::from_mins(5);
+const DEFAULT_STALE_IF_ERROR: Duration = Duration::from_hours(1);
+const DEFAULT_NOT_FOUND_TTL: Duration = Duration::from_mins(1);
+const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+pub struct RemoteCacheConfig {
+    /// How long a fetched body is served without asking the origin again.
+    /// Zero disables caching.
+    pub ttl: Duration,
+    /// How long past its TTL a body is still served when the origin fails.
+    pub stale_if_error: Duration,
+    /// How long a `404 Not Found` is remembered without asking the origin
+    /// again. Zero disables it.
+    pub not_found_ttl: Duration,
+    /// Total size of the cached bodies; least recently used ones are evicted
+    /// past it.
+    pub max_bytes: usize,
+}
+
+impl Default for RemoteCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: DEFAULT_TTL,
+            stale_if_error: DEFAULT_STALE_IF_ERROR,
+            not_found_ttl: DEFAULT_NOT_FOUND_TTL,
+            max_bytes: DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+impl RemoteCacheConfig {
+    /// Reads:
+    /// - `{prefix}_TTL_SECS` — freshness lifetime (default 300); `0` disables caching
+    /// - `{prefix}_STALE_IF_ERROR_SECS` — extra lifetime when the origin fails (default 3600)
+    /// - `{prefix}_NOT_FOUND_TTL_SECS` — lifetime of a `404` answer (default 60); `0` disables it
+    /// - `{prefix}_MAX_BYTES` — total size of cached bodies (default 64 MiB)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of them is set but is not a number.
+    pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
+        Self::from_lookup(prefix, |key| std::env::var(key))
+    }
+
+    fn from_lookup<F>(prefix: &str, lookup: F) -> anyhow::Result<Self>
+    where
+        F: Fn(&str) -> Result<String, VarError>,
+    {
+        let number = |suffix: &str| -> anyhow::Result<Option<u64>> {
+            let key = format!("{prefix}_{suffix}");
+            lookup(&key)
+                .ok()
+                .map(|raw| {
+                    raw.trim()
+                        .parse::<u64>()
+                        .with_context(|| format!("parsing {key}={raw}"))
+                })
+                .transpose()
+        };
+        let defaults = Self::default();
+        Ok(Self {
+            ttl: number("TTL_SECS")?.map_or(defaults.ttl, Duration::from_secs),
+            stale_if_error: number("STALE_IF_ERROR_SECS")?
+                .map_or(defaults.stale_if_error, Duration::from_secs),
+            not_found_ttl: number("NOT_FOUND_TTL_SECS")?
+                .map_or(defaults.not_found_ttl, Duration::from_secs),
+            max_bytes: number("MAX_BYTES")?
+                .map_or(Ok(defaults.max_bytes), usize::try_from)
+                .context("cache size does not fit in memory")?,
+        })
+    }
+
+    #[must_use]
+    pub fn build(&self) -> RemoteCache {
+        RemoteCache {
+            ttl: self.ttl,
+            stale_if_error: self.stale_if_error,
+            not_found_ttl: self.not_found_ttl,
+            max_bytes: self.max_bytes,
+            entries: Mutex::default(),
+            in_flight: Mutex::default(),
+            tick: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            revalidated: AtomicU64::new(0),
+            stale: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Lookup outcomes since startup, and the current cache size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RemoteCacheStats {
+    /// Served from the cache without contacting the origin, including
+    /// remembered `404` answers.
+    pub hits: u64,
+    /// Fetched in full from the origin.
+    pub misses: u64,
+    /// Confirmed unchanged by the origin (`304 Not Modified`).
+    pub revalidated: u64,
+    /// Served past their TTL because the origin failed.
+    pub stale: u64,
+    pub bytes: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: String,
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    fetched_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    by_url: HashMap<String, Entry>,
+    bytes: usize,
+    /// When each URL last answered `404 Not Found`.
+    not_found: HashMap<String, Instant>,
+}
+
+impl Entries {
+    fn remove(&mut self, url: &str) {
+        if let Some(old) = self.by_url.remove(url) {
+            self.bytes -= old.body.len();
+        }
+    }
+
+    fn evict_to(&mut self, max_bytes: usize) {
+        while self.bytes > max_bytes {
+            let Some(oldest) = self
+                .by_url
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(url, _)| url.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+    }
+}
+
+/// The origin answered `404 Not Found` for `url`, now or recently enough for
+/// the answer to still be cached.
+#[derive(Debug)]
+pub struct NotFound {
+    pub url: String,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} was not found", self.url)
+    }
+}
+
+impl std::error::Error for NotFound {}
+
+/// The cached copy of a URL, as read at the start of a lookup.
+struct Cached {
+    body: String,
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    age: Duration,
+}
+
+enum Fetched {
+    NotModified,
+    Body {
+        body: String,
+        etag: Option<HeaderValue>,
+        last_modified: Option<HeaderValue>,
+    },
+}
+
+/// In-process cache of remote template sources keyed by URL.
+///
+/// A body younger than the TTL is served as is. An older one is revalidated
+/// with `If-None-Match` / `If-Modified-Since`, and still served for a while
+/// if the origin errors or is unreachable. A `404` is remembered for its own,
+/// usually shorter, TTL.
+///
+/// Concurrent lookups of a URL that is not fresh in the cache wait for a
+/// single request to the origin and share its outcome.
+#[derive(Debug)]
+pub struct RemoteCache {
+    ttl: Duration,
+    stale_if_error: Duration,
+    not_found_ttl: Duration,
+    max_bytes: usize,
+    entries: Mutex<Entries>,
+    /// One lock per URL being fetched, held for the duration of the fetch.
+    in_flight: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    /// Orders entries by last use, for eviction.
+    tick: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    revalidated: AtomicU64,
+    stale: AtomicU64,
+}
+
+impl RemoteCache {
+    /// A cache that stores nothing; every lookup goes to the origin.
+    #[must_use]
+    pub fn disabled() -> Self {
+        RemoteCacheConfig {
+            ttl: Duration::ZERO,
+            stale_if_error: Duration::ZERO,
+            not_found_ttl: Duration::ZERO,
+            max_bytes: 0,
+        }
+        .build()
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> RemoteCacheStats {
+        let bytes = self.lock().bytes;
+        RemoteCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+            stale: self.stale.load(Ordering::Relaxed),
+            bytes: u64::try_from(bytes).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// Returns the body at `url`, sending `request` (a GET for `url`) only
+    /// when there is no fresh cached copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the origin fails and no cached copy is recent
+    /// enough to stand in for it. The error is a [`NotFound`] when the origin
+    /// answered, or recently answered, `404 Not Found`.
+    pub async fn fetch(
+        &self,
+        url: &str,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<String> {
+        if self.ttl.is_zero() {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return match send(request).await? {
+                Fetched::Body { body, .. } => Ok(body),
+                Fetched::NotModified => {
+                    anyhow::bail!("unexpected 304 for an unconditional request")
+                }
+            };
+        }
+
+        if let Some(answer) = self.cached_answer(url) {
+            return answer;
+        }
+        let flight = self.join_flight(url);
+        let _turn = flight.lock.lock().await;
+        // The lookup that held the turn before this one may have fetched `url`.
+        if let Some(answer) = self.cached_answer(url) {
+            return answer;
+        }
+
+        let cached = self.lookup(url);
+        let mut request = request;
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(IF_NONE_MATCH, etag.clone());
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified.clone());
+            }
+        }
+
+        match (send(request).await, cached) {
+            (Ok(Fetched::NotModified), Some(cached)) => {
+                self.revalidated.fetch_add(1, Ordering::Relaxed);
+                self.refresh(url);
+                Ok(cached.body)
+            }
+            (Ok(Fetched::NotModified), None) => {
+                anyhow::bail!("unexpected 304 for an unconditional request")
+            }
+            (
+                Ok(Fetched::Body {
+                    body,
+                    etag,
+                    last_modified,
+                }),
+                _,
+            ) => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                self.store(url, &body, etag, last_modified);
+                Ok(body)
+            }
+            (Err(err), Some(cached)) if cached.age < self.ttl + self.stale_if_error => {
+                tracing::warn!(url, error = ?err, "remote template fetch failed; serving the cached copy");
+                self.stale.fetch_add(1, Ordering::Relaxed);
+                Ok(cached.body)
+            }
+            (Err(err), _) => {
+                if err.is::<NotFound>() {
+                    self.remember_missing(url);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Answers from the cache alone: a fresh body, or a remembered `404`.
+    fn cached_answer(&self, url: &str) -> Option<anyhow::Result<String>> {
+        if self.known_missing(url) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(Err(NotFound {
+                url: url.to_owned(),
+            }
+            .into()));
+        }
+        let cached = self.lookup(url).filter(|cached| cached.age < self.ttl)?;
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(Ok(cached.body))
+    }
+
+    fn join_flight(&self, url: &str) -> Flight<'_> {
+        let lock = self
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(url.to_owned())
+            .or_default()
+            .clone();
+        Flight {
+            cache: self,
+            url: url.to_owned(),
+            lock,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn next_tick(&self) -> u64 {
+        self.tick.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn lookup(&self, url: &str) -> Option<Cached> {
+        let tick = self.next_tick();
+        let mut entries = self.lock();
+        let entry = entries.by_url.get_mut(url)?;
+        entry.last_used = tick;
+        Some(Cached {
+            body: entry.body.clone(),
+            etag: entry.etag.clone(),
+            last_modified: entry.last_modified.clone(),
+            age: entry.fetched_at.elapsed(),
+        })
+    }
+
+    fn known_missing(&self, url: &str) -> bool {
+        self.lock()
+            .not_found
+            .get(url)
+            .is_some_and(|at| at.elapsed() < self.not_found_ttl)
+    }
+
+    fn remember_missing(&self, url: &str) {
+        if self.not_found_ttl.is_zero() {
+            return;
+        }
+        let mut entries = self.lock();
+        entries
+            .not_found
+            .retain(|_, at| at.elapsed() < self.not_found_ttl);
+        entries.not_found.insert(url.to_owned(), Instant::now());
+    }
+
+    fn refresh(&self, url: &str) {
+        if let Some(entry) = self.lock().by_url.get_mut(url) {
+            entry.fetched_at = Instant::now();
+        }
+    }
+
+    fn store(
+        &self,
+        url: &str,
+        body: &str,
+        etag: Option<HeaderValue>,
+        last_modified: Option<HeaderValue>,
+    ) {
+        let tick = self.next_tick();
+        let mut entries = self.lock();
+        entries.remove(url);
+        if body.len() > self.max_bytes {
+            return;
+        }
+        entries.bytes += body.len();
+        entries.by_url.insert(
+            url.to_owned(),
+            Entry {
+                body: body.to_owned(),
+                etag,
+                last_modified,
+                fetched_at: Instant::now(),
+                last_used: tick,
+            },
+        );
+        entries.evict_to(self.max_bytes);
+    }
+}
+
+/// A lookup's place in the queue of lookups fetching the same URL.
+struct Flight<'a> {
+    cache: &'a RemoteCache,
+    url: String,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Drop for Flight<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self
+            .cache
+            .in_flight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        // Only the map and this flight hold the lock: nobody else is waiting.
+        if Arc::strong_count(&self.lock) == 2 {
+            in_flight.remove(&self.url);
+        }
+    }
+}
+
+async fn send(request: reqwest::RequestBuilder) -> anyhow::Result<Fetched> {
+    let response = request.send().await.context("http request failed")?;
+    match response.status() {
+        StatusCode::NOT_MODIFIED => return Ok(Fetched::NotModified),
+        StatusCode::NOT_FOUND => {
+            return Err(NotFound {
+                url: response.url().to_string(),
+            }
+            .into());
+        }
+        _ => {}
+    }
+    let response = response.error_for_status().context("http error response")?;
+    let etag = response.headers().get(ETAG).cloned();
+    let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+    let body = response.text().await.context("reading response body")?;
+    Ok(Fetched::Body {
+        body,
+        etag,
+        last_modified,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env::VarError;
+    use std::time::Duration;
+
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::{NotFound, RemoteCache, RemoteCacheConfig, RemoteCacheStats};
+
+    fn cache(ttl: Duration, max_bytes: usize) -> RemoteCache {
+        RemoteCacheConfig {
+            ttl,
+            stale_if_error: Duration::from_mins(1),
+            not_found_ttl: Duration::from_mins(1),
+            max_bytes,
+        }
+        .build()
+    }
+
+    async fn get(cache: &RemoteCache, url: &str) -> anyhow::Result<String> {
+        cache.fetch(url, reqwest::Client::new().get(url)).await
+    }
+
+    #[tokio::test]
+    async fn fresh_entries_are_served_without_a_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/a.mjml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mjml/>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_mins(1), 1024);
+        let url = format!("{}/a.mjml", server.uri());
+
+        assert_eq!(get(&cache, &url).await.unwrap(), "<mjml/>");
+        assert_eq!(get(&cache, &url).await.unwrap(), "<mjml/>");
+        assert_eq!(
+            cache.stats(),
+            RemoteCacheStats {
+                hits: 1,
+                misses: 1,
+                bytes: 7,
+                ..RemoteCacheStats::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_revalidated_with_their_etag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("etag", "\"v1\"")
+                    .set_body_string("<mjml/>"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_millis(1), 1024);
+        let url = format!("{}/a.mjml", server.uri());
+
+        get(&cache, &url).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(get(&cache, &url).await.unwrap(), "<mjml/>");
+        assert_eq!(cache.stats().revalidated, 1);
+    }
+
+    #[tokio::test]
+    async fn stale_entries_stand_in_when_the_origin_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mjml/>"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_millis(1), 1024);
+        let url = format!("{}/a.mjml", server.uri());
+
+        get(&cache, &url).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(get(&cache, &url).await.unwrap(), "<mjml/>");
+        assert_eq!(cache.stats().stale, 1);
+
+        let uncached = format!("{}/b.mjml", server.uri());
+        assert!(get(&cache, &uncached).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn not_found_answers_are_remembered() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_mins(1), 1024);
+        let url = format!("{}/a.fr.mjml", server.uri());
+
+        for _ in 0..2 {
+            let err = get(&cache, &url).await.unwrap_err();
+            assert!(err.is::<NotFound>());
+        }
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_lookups_share_one_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("<mjml/>")
+                    .set_delay(Duration::from_millis(50)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_mins(1), 1024);
+        let url = format!("{}/a.mjml", server.uri());
+
+        let (a, b, c) = tokio::join!(get(&cache, &url), get(&cache, &url), get(&cache, &url));
+        for body in [a, b, c] {
+            assert_eq!(body.unwrap(), "<mjml/>");
+        }
+        assert_eq!((cache.stats().hits, cache.stats().misses), (2, 1));
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entries_are_evicted_past_max_bytes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("12345"))
+            .mount(&server)
+            .await;
+        let cache = cache(Duration::from_mins(1), 10);
+        let [a, b, c] = ["a", "b", "c"].map(|name| format!("{}/{name}", server.uri()));
+
+        get(&cache, &a).await.unwrap();
+        get(&cache, &b).await.unwrap();
+        get(&cache, &a).await.unwrap();
+        get(&cache, &c).await.unwrap();
+        assert_eq!(cache.stats().bytes, 10);
+
+        get(&cache, &a).await.unwrap();
+        get(&cache, &b).await.unwrap();
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 4));
+    }
+
+    #[tokio::test]
+    async fn disabled_cache_always_fetches() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mjml/>"))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let cache = RemoteCache::disabled();
+        let url = format!("{}/a.mjml", server.uri());
+
+        get(&cache, &url).await.unwrap();
+        get(&cache, &url).await.unwrap();
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn config_from_lookup_reads_overrides() {
+        let vars: HashMap<&str, &str> =
+            HashMap::from([("CACHE_TTL_SECS", "0"), ("CACHE_MAX_BYTES", "1024")]);
+        let config = RemoteCacheConfig::from_lookup("CACHE", |key| {
+            vars.get(key)
+                .map(ToString::to_string)
+                .ok_or(VarError::NotPresent)
+        })
+        .unwrap();
+
+        assert_eq!(config.ttl, Duration::ZERO);
+        assert_eq!(config.stale_if_error, Duration::from_hours(1));
+        assert_eq!(config.not_found_ttl, Duration::from_mins(1));
+        assert_eq!(config.max_bytes, 1024);
+    }
+}
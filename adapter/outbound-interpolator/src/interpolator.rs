@@ -1,13 +1,15 @@
 use anyhow::Context;
 use catapulte_domain::entity::body::{InterpolatedBody, Plain, ResolvedBody};
 use catapulte_domain::port::template_interpolator::{InterpolateError, TemplateInterpolator};
-use minijinja::Environment;
+use minijinja::{Environment, UndefinedBehavior};
 
 fn render_str(
     source: &str,
     variables: &serde_json::Map<String, serde_json::Value>,
+    undefined: UndefinedBehavior,
 ) -> Result<String, InterpolateError> {
     let mut env = Environment::new();
+    env.set_undefined_behavior(undefined);
     env.add_template("t", source)
         .context("adding template")
         .map_err(|source| InterpolateError::Engine { source })?;
@@ -20,12 +22,25 @@ fn render_str(
         .map_err(|source| InterpolateError::Engine { source })
 }
 
-pub struct MiniJinjaInterpolator;
+pub struct MiniJinjaInterpolator {
+    undefined: UndefinedBehavior,
+}
 
 impl MiniJinjaInterpolator {
+    /// Undefined variables render as empty strings.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self {
+            undefined: UndefinedBehavior::Lenient,
+        }
+    }
+
+    /// Any use of an undefined variable is an error.
+    #[must_use]
+    pub fn strict() -> Self {
+        Self {
+            undefined: UndefinedBehavior::Strict,
+        }
     }
 }
 
@@ -38,10 +53,15 @@ impl Default for MiniJinjaInterpolator {
 fn interpolate_plain(
     plain: Plain,
     variables: &serde_json::Map<String, serde_json::Value>,
+    undefined: UndefinedBehavior,
 ) -> Result<InterpolatedBody, InterpolateError> {
     let (text, html) = plain.into_parts();
-    let text = text.map(|t| render_str(&t, variables)).transpose()?;
-    let html = html.map(|h| render_str(&h, variables)).transpose()?;
+    let text = text
+        .map(|t| render_str(&t, variables, undefined))
+        .transpose()?;
+    let html = html
+        .map(|h| render_str(&h, variables, undefined))
+        .transpose()?;
     let plain = Plain::try_new(text, html)
         .context("reconstructing plain body")
         .map_err(|source| InterpolateError::Engine { source })?;
@@ -56,11 +76,19 @@ impl TemplateInterpolator for MiniJinjaInterpolator {
     ) -> Result<InterpolatedBody, InterpolateError> {
         match body {
             ResolvedBody::Mjml(source) => {
-                render_str(&source, variables).map(InterpolatedBody::Mjml)
+                render_str(&source, variables, self.undefined).map(InterpolatedBody::Mjml)
             }
-            ResolvedBody::Plain(plain) => interpolate_plain(plain, variables),
+            ResolvedBody::Plain(plain) => interpolate_plain(plain, variables, self.undefined),
         }
     }
+
+    fn interpolate_subject(
+        &self,
+        subject: &str,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<String, InterpolateError> {
+        render_str(subject, variables, self.undefined)
+    }
 }
 
 #[cfg(test)]
@@ -177,6 +205,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn interpolate_subject_substitutes_variable() {
+        let interpolator = MiniJinjaInterpolator::new();
+        let mut variables = Map::new();
+        variables.insert("order_id".to_string(), Value::from(42));
+
+        let subject = interpolator
+            .interpolate_subject("Your order {{ order_id }} shipped", &variables)
+            .unwrap();
+
+        assert_eq!(subject, "Your order 42 shipped");
+    }
+
     #[test]
     fn interpolate_invalid_template_returns_error() {
         let interpolator = MiniJinjaInterpolator::new();
@@ -188,4 +229,27 @@ mod tests {
 
         assert!(matches!(result, Err(InterpolateError::Engine { .. })));
     }
+
+    #[test]
+    fn strict_interpolator_rejects_undefined_variables() {
+        let lenient = MiniJinjaInterpolator::new();
+        let strict = MiniJinjaInterpolator::strict();
+        let variables = Map::new();
+
+        assert_eq!(
+            lenient
+                .interpolate_subject("Hi {{ name }}", &variables)
+                .unwrap(),
+            "Hi "
+        );
+        assert!(matches!(
+            strict.interpolate_subject("Hi {{ name }}", &variables),
+            Err(InterpolateError::Engine { .. })
+        ));
+        let body = ResolvedBody::Mjml("<mj-text>{{ msg }}</mj-text>".to_string());
+        assert!(matches!(
+            strict.interpolate(body, &variables),
+            Err(InterpolateError::Engine { .. })
+        ));
+    }
 }
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Context;
-use mrml::prelude::parser::http_loader::{AsyncReqwestFetcher, HttpIncludeLoader};
+use catapulte_outbound_http_cache::RemoteCache;
 use mrml::prelude::parser::loader::{AsyncIncludeLoader, IncludeLoader, IncludeLoaderError};
 use mrml::prelude::parser::local_loader::LocalIncludeLoader;
 use mrml::prelude::parser::multi_loader::MultiIncludeLoaderAsync;
@@ -43,10 +43,64 @@ enum HttpOriginConfig {
     Deny(HashSet<String>),
 }
 
+impl HttpOriginConfig {
+    fn is_allowed(&self, origin: &str) -> bool {
+        match self {
+            Self::Allow(set) => set.contains(origin),
+            Self::Deny(set) => !set.contains(origin),
+        }
+    }
+}
+
+/// Fetches `http(s)://` includes from allowed origins through a
+/// [`RemoteCache`], so a template rendered for every email of a batch does
+/// not refetch its partials each time.
+#[derive(Clone, Debug)]
+struct CachedHttpLoader {
+    origin: Arc<HttpOriginConfig>,
+    http_client: reqwest::Client,
+    cache: Arc<RemoteCache>,
+}
+
+impl CachedHttpLoader {
+    fn check_url(&self, path: &str) -> Result<(), IncludeLoaderError> {
+        let url = url::Url::parse(path).map_err(|err| {
+            IncludeLoaderError::new(path, std::io::ErrorKind::InvalidInput)
+                .with_message("unable to parse the provided url")
+                .with_cause(Arc::new(err))
+        })?;
+        if self.origin.is_allowed(&url.origin().ascii_serialization()) {
+            Ok(())
+        } else {
+            Err(
+                IncludeLoaderError::new(path, std::io::ErrorKind::InvalidInput)
+                    .with_message("the path is not allowed by the defined list of domains"),
+            )
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncIncludeLoader for CachedHttpLoader {
+    async fn async_resolve(&self, path: &str) -> Result<String, IncludeLoaderError> {
+        self.check_url(path)?;
+        self.cache
+            .fetch(path, self.http_client.get(path))
+            .await
+            .map_err(|err| {
+                let cause: Box<dyn std::error::Error + Send + Sync> = err.into();
+                IncludeLoaderError::new(path, std::io::ErrorKind::NotFound)
+                    .with_message("unable to fetch template")
+                    .with_cause(Arc::from(cause))
+            })
+    }
+}
+
 #[derive(Default)]
 pub struct IncludeLoaderConfig {
     pub fs_root: Option<PathBuf>,
     http_origin: Option<HttpOriginConfig>,
+    cache: Option<Arc<RemoteCache>>,
 }
 
 impl IncludeLoaderConfig {
@@ -104,31 +158,43 @@ impl IncludeLoaderConfig {
         Ok(Self {
             fs_root,
             http_origin,
+            cache: None,
         })
     }
 
+    /// Serves HTTP includes through `cache`; without one, every render
+    /// fetches them again.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Arc<RemoteCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     pub fn build(self) -> Box<dyn AsyncIncludeLoader + Send + Sync> {
         let fs: Option<SpawnBlockingLocalLoader> = self.fs_root.map(SpawnBlockingLocalLoader::new);
-        let http_origin = self.http_origin;
+        let http = self.http_origin.map(|origin| CachedHttpLoader {
+            origin: Arc::new(origin),
+            http_client: reqwest::Client::new(),
+            cache: self
+                .cache
+                .unwrap_or_else(|| Arc::new(RemoteCache::disabled())),
+        });
 
-        match (fs, http_origin) {
+        match (fs, http) {
             (None, None) => Box::new(NoopIncludeLoader),
             (Some(fs), None) => Box::new(fs),
-            (None, Some(origin)) => {
-                // Two separate loaders (http and https) because HttpIncludeLoader is not Clone.
-                let (loader_http, loader_https) = build_http_loader_pair(origin);
+            (None, Some(http)) => {
                 let multi = MultiIncludeLoaderAsync::new()
-                    .with_starts_with("http://", Box::new(loader_http))
-                    .with_starts_with("https://", Box::new(loader_https))
+                    .with_starts_with("http://", Box::new(http.clone()))
+                    .with_starts_with("https://", Box::new(http))
                     .with_any(Box::<NoopIncludeLoader>::default());
                 Box::new(multi)
             }
-            (Some(fs), Some(origin)) => {
-                let (loader_http, loader_https) = build_http_loader_pair(origin);
+            (Some(fs), Some(http)) => {
                 let multi = MultiIncludeLoaderAsync::new()
                     .with_starts_with("file://", Box::new(fs))
-                    .with_starts_with("http://", Box::new(loader_http))
-                    .with_starts_with("https://", Box::new(loader_https))
+                    .with_starts_with("http://", Box::new(http.clone()))
+                    .with_starts_with("https://", Box::new(http))
                     .with_any(Box::<NoopIncludeLoader>::default());
                 Box::new(multi)
             }
@@ -136,24 +202,6 @@ impl IncludeLoaderConfig {
     }
 }
 
-fn build_http_loader_pair(
-    origin: HttpOriginConfig,
-) -> (
-    HttpIncludeLoader<AsyncReqwestFetcher>,
-    HttpIncludeLoader<AsyncReqwestFetcher>,
-) {
-    match origin {
-        HttpOriginConfig::Allow(set) => (
-            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(set.clone()),
-            HttpIncludeLoader::<AsyncReqwestFetcher>::new_allow(set),
-        ),
-        HttpOriginConfig::Deny(set) => (
-            HttpIncludeLoader::<AsyncReqwestFetcher>::new_deny(set.clone()),
-            HttpIncludeLoader::<AsyncReqwestFetcher>::new_deny(set),
-        ),
-    }
-}
-
 fn parse_origin_set(
     prefix: &str,
     env_name: &str,
@@ -341,4 +389,49 @@ mod tests {
             "error message should mention the offending entry, got: {msg}"
         );
     }
+
+    #[tokio::test]
+    async fn http_includes_are_served_from_the_cache() {
+        use std::sync::Arc;
+
+        use catapulte_outbound_http_cache::RemoteCacheConfig;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/header.mjml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mj-text>hi</mj-text>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let origin = server.uri();
+        let lookup = move |key: &str| -> Result<String, VarError> {
+            if key == "CATAPULTE_INCLUDE_LOADER_HTTP_ALLOW" {
+                Ok(origin.clone())
+            } else {
+                Err(VarError::NotPresent)
+            }
+        };
+        let cache = Arc::new(RemoteCacheConfig::default().build());
+        let loader = IncludeLoaderConfig::from_lookup("CATAPULTE_INCLUDE_LOADER", lookup)
+            .unwrap()
+            .with_cache(Arc::clone(&cache))
+            .build();
+
+        let url = format!("{}/header.mjml", server.uri());
+        for _ in 0..2 {
+            assert_eq!(
+                loader.async_resolve(&url).await.unwrap(),
+                "<mj-text>hi</mj-text>"
+            );
+        }
+        assert_eq!(cache.stats().hits, 1);
+        assert!(
+            loader
+                .async_resolve("https://elsewhere.example.com/header.mjml")
+                .await
+                .is_err()
+        );
+    }
 }
@@ -1,2 +1,3 @@
 pub mod include_loader;
 pub mod renderer;
+pub mod text;
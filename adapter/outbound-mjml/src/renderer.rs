@@ -6,8 +6,11 @@ use catapulte_domain::port::template_renderer::{RenderError, TemplateRenderer};
 use mrml::prelude::parser::AsyncParserOptions;
 use mrml::prelude::parser::loader::AsyncIncludeLoader;
 
+use crate::text::{TextAlternative, html_to_text};
+
 pub struct MjmlRenderer {
     parser_options: Arc<AsyncParserOptions>,
+    text_alternative: TextAlternative,
 }
 
 impl MjmlRenderer {
@@ -15,8 +18,15 @@ impl MjmlRenderer {
     pub fn new(include_loader: Box<dyn AsyncIncludeLoader + Send + Sync>) -> Self {
         Self {
             parser_options: Arc::new(AsyncParserOptions { include_loader }),
+            text_alternative: TextAlternative::default(),
         }
     }
+
+    #[must_use]
+    pub fn with_text_alternative(mut self, text_alternative: TextAlternative) -> Self {
+        self.text_alternative = text_alternative;
+        self
+    }
 }
 
 impl TemplateRenderer for MjmlRenderer {
@@ -26,17 +36,48 @@ impl TemplateRenderer for MjmlRenderer {
     #[tracing::instrument(skip_all, name = "template.render")]
     async fn render(&self, body: InterpolatedBody) -> Result<RenderedBody, RenderError> {
         match body {
-            InterpolatedBody::Plain(plain) => Ok(RenderedBody::new(plain)),
+            InterpolatedBody::Plain(plain) => match self.text_alternative {
+                TextAlternative::FromHtml => with_html_text(plain),
+                TextAlternative::Preview => Ok(RenderedBody::new(plain)),
+            },
             InterpolatedBody::Mjml(source) => {
-                render_mjml(&source, Arc::clone(&self.parser_options)).await
+                render_mjml(
+                    &source,
+                    Arc::clone(&self.parser_options),
+                    self.text_alternative,
+                )
+                .await
             }
         }
     }
 }
 
+/// Fills in the text part of an HTML-only body. The body keeps no text part
+/// when the conversion fails or yields nothing.
+fn with_html_text(plain: Plain) -> Result<RenderedBody, RenderError> {
+    let (text, html) = plain.into_parts();
+    let text = text.or_else(|| html.as_deref().and_then(text_from_html));
+    let plain = Plain::try_new(text, html)
+        .context("plain body has no parts")
+        .map_err(|source| RenderError::Mjml { source })?;
+    Ok(RenderedBody::new(plain))
+}
+
+fn text_from_html(html: &str) -> Option<String> {
+    match html_to_text(html) {
+        Ok(text) if !text.is_empty() => Some(text),
+        Ok(_) => None,
+        Err(error) => {
+            tracing::warn!(error = ?error, "unable to convert html to text");
+            None
+        }
+    }
+}
+
 async fn render_mjml(
     source: &str,
     opts: Arc<AsyncParserOptions>,
+    text_alternative: TextAlternative,
 ) -> Result<RenderedBody, RenderError> {
     let parsed = mrml::async_parse_with_options(source, opts)
         .await
@@ -51,12 +92,20 @@ async fn render_mjml(
         .map_err(|source| RenderError::Mjml { source })?;
 
     let preview = parsed.element.get_preview();
+    let text = match text_alternative {
+        TextAlternative::FromHtml => text_from_html(&html).or(preview),
+        TextAlternative::Preview => preview,
+    };
+    let title = parsed.element.get_title();
+    let warnings = parsed.warnings.iter().map(ToString::to_string).collect();
 
-    let plain = Plain::try_new(preview, Some(html))
+    let plain = Plain::try_new(text, Some(html))
         .context("rendered mjml has no body parts")
         .map_err(|source| RenderError::Mjml { source })?;
 
-    Ok(RenderedBody::new(plain))
+    Ok(RenderedBody::new(plain)
+        .with_title(title)
+        .with_warnings(warnings))
 }
 
 #[cfg(test)]
@@ -66,6 +115,7 @@ mod tests {
     use mrml::prelude::parser::noop_loader::NoopIncludeLoader;
 
     use super::MjmlRenderer;
+    use crate::text::TextAlternative;
 
     fn noop_renderer() -> MjmlRenderer {
         MjmlRenderer::new(Box::new(NoopIncludeLoader))
@@ -95,8 +145,64 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn render_mjml_with_preview_produces_text_and_html() {
+    async fn render_plain_html_only_gets_text_from_html() {
         let renderer = noop_renderer();
+        let plain = Plain::try_new(
+            None,
+            Some(r#"<p>Track it <a href="https://example.com/t/1">here</a></p>"#.to_string()),
+        )
+        .unwrap();
+        let result = renderer
+            .render(InterpolatedBody::Plain(plain))
+            .await
+            .unwrap();
+        let text = result.text().unwrap();
+        assert!(text.contains("[here][1]"), "{text}");
+        assert!(text.contains("[1]: https://example.com/t/1"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn render_plain_html_only_in_preview_mode_has_no_text() {
+        let renderer = noop_renderer().with_text_alternative(TextAlternative::Preview);
+        let plain = Plain::try_new(None, Some("<p>html</p>".to_string())).unwrap();
+        let result = renderer
+            .render(InterpolatedBody::Plain(plain))
+            .await
+            .unwrap();
+        assert_eq!(result.text(), None);
+    }
+
+    #[tokio::test]
+    async fn render_mjml_text_comes_from_the_rendered_html() {
+        let renderer = noop_renderer();
+        let source = r#"<mjml>
+  <mj-head>
+    <mj-preview>preview text</mj-preview>
+  </mj-head>
+  <mj-body>
+    <mj-section>
+      <mj-column>
+        <mj-text>Hello world</mj-text>
+        <mj-button href="https://example.com/confirm">Confirm</mj-button>
+      </mj-column>
+    </mj-section>
+  </mj-body>
+</mjml>"#;
+        let result = renderer
+            .render(InterpolatedBody::Mjml(source.to_string()))
+            .await
+            .unwrap();
+        let text = result.text().unwrap();
+        assert!(text.starts_with("Hello world"), "{text}");
+        assert!(text.contains("[Confirm][1]"), "{text}");
+        assert!(text.contains("[1]: https://example.com/confirm"), "{text}");
+        assert!(!text.contains("preview text"), "{text}");
+        assert!(!text.contains('<'), "{text}");
+    }
+
+    #[tokio::test]
+    async fn render_mjml_with_preview_produces_text_and_html() {
+        let renderer = noop_renderer().with_text_alternative(TextAlternative::Preview);
         let source = r"<mjml>
   <mj-head>
     <mj-preview>preview text</mj-preview>
@@ -119,9 +225,37 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn render_mjml_without_preview_produces_html_only() {
+    async fn render_mjml_exposes_title() {
         let renderer = noop_renderer();
         let source = r"<mjml>
+  <mj-head>
+    <mj-title>Your order shipped</mj-title>
+  </mj-head>
+  <mj-body></mj-body>
+</mjml>";
+        let result = renderer
+            .render(InterpolatedBody::Mjml(source.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(result.title(), Some("Your order shipped"));
+    }
+
+    #[tokio::test]
+    async fn render_mjml_reports_warnings() {
+        let renderer = noop_renderer();
+        let source = r#"<mjml><mj-head><mj-style unknown="x"></mj-style></mj-head><mj-body></mj-body></mjml>"#;
+        let result = renderer
+            .render(InterpolatedBody::Mjml(source.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(result.warnings().len(), 1, "{:?}", result.warnings());
+        assert!(result.warnings()[0].contains("unexpected attribute"));
+    }
+
+    #[tokio::test]
+    async fn render_mjml_without_preview_produces_html_only() {
+        let renderer = noop_renderer().with_text_alternative(TextAlternative::Preview);
+        let source = r"<mjml>
   <mj-body>
     <mj-section>
       <mj-column>
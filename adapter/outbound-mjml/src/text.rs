@@ -0,0 +1,118 @@
+use std::str::FromStr;
+
+use anyhow::Context;
+
+/// Column the text part is wrapped at.
+const TEXT_WIDTH: usize = 78;
+
+/// Where the text part of an email without an explicit one comes from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlternative {
+    /// Convert the rendered HTML to text.
+    #[default]
+    FromHtml,
+    /// Use the `<mj-preview>` of MJML templates and leave HTML-only plain
+    /// bodies without a text part.
+    Preview,
+}
+
+impl FromStr for TextAlternative {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "html" => Ok(Self::FromHtml),
+            "preview" => Ok(Self::Preview),
+            other => Err(anyhow::anyhow!(
+                "unknown text alternative {other:?}, expected \"html\" or \"preview\""
+            )),
+        }
+    }
+}
+
+/// Converts rendered HTML into the text alternative of an email.
+///
+/// Links are listed as numbered footnotes, tables (MJML layouts are made of
+/// them) are read cell by cell, and elements hidden with `display: none`,
+/// such as the `<mj-preview>` preheader, are dropped.
+///
+/// # Errors
+///
+/// Returns an error when the HTML cannot be converted.
+pub fn html_to_text(html: &str) -> anyhow::Result<String> {
+    let text = html2text::config::plain()
+        .raw_mode(true)
+        .use_doc_css()
+        .allow_width_overflow()
+        .string_from_read(html.as_bytes(), TEXT_WIDTH)
+        .context("converting html to text")?;
+    Ok(tidy(&text))
+}
+
+/// Drops trailing spaces and runs of blank lines left by layout elements.
+fn tidy(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut blank = true;
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            if !blank {
+                out.push('\n');
+            }
+            blank = true;
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        blank = false;
+    }
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TextAlternative, html_to_text};
+
+    #[test]
+    fn text_alternative_parses_known_values() {
+        assert_eq!(
+            "html".parse::<TextAlternative>().unwrap(),
+            TextAlternative::FromHtml
+        );
+        assert_eq!(
+            " Preview ".parse::<TextAlternative>().unwrap(),
+            TextAlternative::Preview
+        );
+        assert!("markdown".parse::<TextAlternative>().is_err());
+    }
+
+    #[test]
+    fn links_become_footnotes() {
+        let text =
+            html_to_text(r#"<p>Read the <a href="https://example.com/terms">terms</a> first.</p>"#)
+                .unwrap();
+        assert!(text.contains("[terms][1]"), "{text}");
+        assert!(text.contains("[1]: https://example.com/terms"), "{text}");
+    }
+
+    #[test]
+    fn tables_are_flattened() {
+        let text = html_to_text(
+            "<table><tr><td>Item</td><td>Price</td></tr><tr><td>Mug</td><td>$12</td></tr></table>",
+        )
+        .unwrap();
+        assert!(!text.contains('|') && !text.contains('─'), "{text}");
+        let words: Vec<&str> = text.split_whitespace().collect();
+        assert_eq!(words, ["Item", "Price", "Mug", "$12"]);
+    }
+
+    #[test]
+    fn hidden_elements_and_blank_runs_are_dropped() {
+        let text = html_to_text(
+            r#"<div style="display:none">preheader</div><p>Hello</p><div><br><br><br></div><p>Bye</p>"#,
+        )
+        .unwrap();
+        assert_eq!(text, "Hello\n\nBye");
+    }
+}
@@ -1,8 +1,14 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
 use catapulte_domain::entity::body::{BodySource, MjmlSource, Plain};
+use catapulte_domain::entity::calendar::Calendar;
 use catapulte_domain::entity::email::{EmailId, RecipientKind};
 use catapulte_domain::entity::envelope::Envelope;
+use catapulte_domain::entity::message_headers::MessageHeaders;
+use catapulte_domain::entity::tracking::Tracking;
+use catapulte_domain::entity::unsubscribe::MailingList;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -17,6 +23,8 @@ pub struct AttachmentRefDto {
     pub content_type: String,
     pub size_bytes: u64,
     pub blob: BlobRefDto,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_id: Option<String>,
 }
 
 impl From<&AttachmentRef> for AttachmentRefDto {
@@ -29,6 +37,7 @@ impl From<&AttachmentRef> for AttachmentRefDto {
                 backend: a.blob.backend.clone(),
                 key: a.blob.key.clone(),
             },
+            content_id: a.content_id.clone(),
         }
     }
 }
@@ -43,6 +52,7 @@ impl From<AttachmentRefDto> for AttachmentRef {
                 backend: dto.blob.backend,
                 key: dto.blob.key,
             },
+            content_id: dto.content_id,
         }
     }
 }
@@ -65,6 +75,26 @@ pub struct EnvelopeDto {
     pub variables: serde_json::Map<String, serde_json::Value>,
     #[serde(default)]
     pub attachments: Vec<AttachmentRefDto>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub send_at_ms: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub display_names: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Raw iCalendar object of an invitation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar: Option<String>,
+    /// Mailing list the email belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub track_opens: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub track_clicks: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -177,6 +207,15 @@ impl From<(&EmailId, &Envelope)> for QueuedEmailPayload {
                     .iter()
                     .map(AttachmentRefDto::from)
                     .collect(),
+                send_at_ms: envelope.send_at_ms,
+                reply_to: envelope.headers.reply_to.clone(),
+                display_names: envelope.headers.display_names.clone(),
+                headers: envelope.headers.custom.clone(),
+                locale: envelope.locale.clone(),
+                calendar: envelope.calendar.as_ref().map(|c| c.content().to_owned()),
+                list: envelope.list.as_ref().map(|l| l.as_str().to_owned()),
+                track_opens: envelope.tracking.opens,
+                track_clicks: envelope.tracking.clicks,
             },
         }
     }
@@ -208,6 +247,31 @@ impl TryFrom<QueuedEmailPayload> for (EmailId, Envelope) {
             body,
             variables: payload.envelope.variables,
             attachments,
+            send_at_ms: payload.envelope.send_at_ms,
+            headers: MessageHeaders {
+                reply_to: payload.envelope.reply_to,
+                display_names: payload.envelope.display_names,
+                custom: payload.envelope.headers,
+            },
+            locale: payload.envelope.locale,
+            calendar: payload
+                .envelope
+                .calendar
+                .as_deref()
+                .map(Calendar::parse)
+                .transpose()
+                .context("parsing calendar")?,
+            list: payload
+                .envelope
+                .list
+                .as_deref()
+                .map(MailingList::parse)
+                .transpose()
+                .context("parsing list")?,
+            tracking: Tracking {
+                opens: payload.envelope.track_opens,
+                clicks: payload.envelope.track_clicks,
+            },
         };
         Ok((EmailId::from(payload.id), envelope))
     }
@@ -217,8 +281,12 @@ impl TryFrom<QueuedEmailPayload> for (EmailId, Envelope) {
 mod tests {
     use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
     use catapulte_domain::entity::body::{BodySource, Plain};
+    use catapulte_domain::entity::calendar::Calendar;
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+    use catapulte_domain::entity::tracking::Tracking;
+    use catapulte_domain::entity::unsubscribe::MailingList;
 
     use super::QueuedEmailPayload;
 
@@ -233,6 +301,7 @@ mod tests {
                     backend: "fs".into(),
                     key: uuid::Uuid::now_v7().simple().to_string(),
                 },
+                content_id: None,
             })
             .collect();
 
@@ -246,6 +315,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("Hello world".into()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments,
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: Tracking::default(),
         };
 
         let payload = QueuedEmailPayload::from((&id, &envelope));
@@ -256,4 +331,47 @@ mod tests {
             bytes.len()
         );
     }
+
+    #[test]
+    fn message_headers_locale_calendar_list_and_tracking_survive_the_round_trip() {
+        let mut headers = MessageHeaders {
+            reply_to: Some("support@example.com".into()),
+            ..MessageHeaders::default()
+        };
+        headers
+            .display_names
+            .insert("sender@example.com".into(), "Acme Billing".into());
+        headers.custom.insert("Importance".into(), "high".into());
+        let envelope = Envelope {
+            idempotency_key: None,
+            correlation_id: None,
+            subject: None,
+            sender: "sender@example.com".into(),
+            recipients: vec![(RecipientKind::To, "to@example.com".into())],
+            body: BodySource::Plain(Plain::try_new(Some("Hello".into()), None).unwrap()),
+            variables: serde_json::Map::new(),
+            attachments: vec![],
+            send_at_ms: None,
+            headers,
+            locale: Some("fr-CA".into()),
+            calendar: Some(
+                Calendar::parse("BEGIN:VCALENDAR\r\nMETHOD:CANCEL\r\nEND:VCALENDAR\r\n").unwrap(),
+            ),
+            list: Some(MailingList::parse("newsletter").unwrap()),
+            tracking: Tracking {
+                opens: false,
+                clicks: true,
+            },
+        };
+
+        let payload = QueuedEmailPayload::from((&EmailId::default(), &envelope));
+        let bytes = serde_json::to_vec(&payload).unwrap();
+        let decoded: QueuedEmailPayload = serde_json::from_slice(&bytes).unwrap();
+        let (_, decoded) = <(EmailId, Envelope)>::try_from(decoded).unwrap();
+        assert_eq!(decoded.headers, envelope.headers);
+        assert_eq!(decoded.locale, envelope.locale);
+        assert_eq!(decoded.calendar, envelope.calendar);
+        assert_eq!(decoded.list, envelope.list);
+        assert_eq!(decoded.tracking, envelope.tracking);
+    }
 }
@@ -97,6 +97,53 @@ fn nak_payload(delay: std::time::Duration) -> String {
     format!("-NAK {{\"delay\":{delay_ns}}}")
 }
 
+fn now_ms() -> i64 {
+    i64::try_from(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+    )
+    .unwrap_or(i64::MAX)
+}
+
+/// Returns how long a message scheduled for `send_at_ms` must still wait, or
+/// `None` when it is due (or unscheduled).
+fn remaining_delay(send_at_ms: Option<i64>, now_ms: i64) -> Option<Duration> {
+    let send_at_ms = send_at_ms?;
+    let remaining = u64::try_from(send_at_ms.saturating_sub(now_ms)).ok()?;
+    (remaining > 0).then(|| Duration::from_millis(remaining))
+}
+
+/// Marks the copy of a scheduled message published once it is due.
+const RELEASED_HEADER: &str = "Catapulte-Released";
+
+/// What to do with one delivery of a queued message.
+#[derive(Debug, PartialEq, Eq)]
+enum Delivery {
+    /// Hand the email to the worker.
+    Send,
+    /// Not due yet: NAK it back for the remaining delay.
+    Park(Duration),
+    /// Replace the message with a new copy, `released` once it is due.
+    Republish { released: bool },
+}
+
+/// Plans a delivery so that only send attempts use up the consumer's
+/// deliveries. A scheduled message is parked on the first delivery of each
+/// copy; a copy that comes back early is replaced by a new one, and a due copy
+/// by a released one, whose deliveries are all send attempts.
+fn plan_delivery(send_at_ms: Option<i64>, released: bool, delivered: i64, now_ms: i64) -> Delivery {
+    if released || send_at_ms.is_none() {
+        return Delivery::Send;
+    }
+    match remaining_delay(send_at_ms, now_ms) {
+        Some(delay) if delivered <= 1 => Delivery::Park(delay),
+        Some(_) => Delivery::Republish { released: false },
+        None => Delivery::Republish { released: true },
+    }
+}
+
 impl EmailQueue for NatsAdapter {
     async fn enqueue(&self, id: EmailId, envelope: &Envelope) -> Result<(), EmailQueueError> {
         let payload = QueuedEmailPayload::from((&id, envelope));
@@ -166,6 +213,44 @@ impl EmailQueue for NatsAdapter {
                 let (email_id, envelope) = <(EmailId, Envelope)>::try_from(payload)
                     .map_err(|source| EmailQueueError::Storage { source })?;
 
+                // JetStream has no per-message delivery time: a scheduled message
+                // waits in delayed NAKs and is republished once due.
+                let released = msg
+                    .headers
+                    .as_ref()
+                    .is_some_and(|headers| headers.get(RELEASED_HEADER).is_some());
+                match plan_delivery(envelope.send_at_ms, released, info.delivered, now_ms()) {
+                    Delivery::Send => {}
+                    Delivery::Park(delay) => {
+                        self.nack(token, delay).await?;
+                        continue;
+                    }
+                    Delivery::Republish { released } => {
+                        let mut headers = msg.headers.clone().unwrap_or_default();
+                        if released {
+                            headers.insert(RELEASED_HEADER, "true");
+                        }
+                        // Deduplicates the copy when the ack below is lost and
+                        // this delivery comes back.
+                        let msg_id = format!("{}-{}", email_id.as_uuid(), info.stream_sequence);
+                        headers.insert(async_nats::header::NATS_MESSAGE_ID, msg_id.as_str());
+                        self.client()
+                            .publish_with_headers(
+                                self.subject().to_owned(),
+                                headers,
+                                msg.payload.clone(),
+                            )
+                            .await
+                            .map_err(nats_err)
+                            .context("republishing to NATS")
+                            .map_err(|source| EmailQueueError::Storage { source })?;
+                        self.ack(token).await?;
+                        continue;
+                    }
+                }
+                let published_ms = i64::try_from(info.published.unix_timestamp_nanos() / 1_000_000)
+                    .unwrap_or(i64::MAX);
+
                 let trace_pairs = msg
                     .headers
                     .as_ref()
@@ -176,6 +261,7 @@ impl EmailQueue for NatsAdapter {
                     id: email_id,
                     envelope,
                     attempt,
+                    enqueued_at_ms: published_ms,
                     token,
                     trace: TraceCarrier::new(trace_pairs),
                 });
@@ -211,6 +297,63 @@ impl EmailQueue for NatsAdapter {
     }
 }
 
+#[cfg(test)]
+mod schedule_tests {
+    use std::time::Duration;
+
+    use super::{Delivery, plan_delivery, remaining_delay};
+
+    #[test]
+    fn unscheduled_message_is_due() {
+        assert_eq!(remaining_delay(None, 1_000), None);
+    }
+
+    #[test]
+    fn past_send_at_is_due() {
+        assert_eq!(remaining_delay(Some(500), 1_000), None);
+        assert_eq!(remaining_delay(Some(1_000), 1_000), None);
+    }
+
+    #[test]
+    fn unscheduled_and_released_messages_are_sent() {
+        assert_eq!(plan_delivery(None, false, 3, 1_000), Delivery::Send);
+        assert_eq!(plan_delivery(Some(61_000), true, 2, 1_000), Delivery::Send);
+    }
+
+    #[test]
+    fn two_deferrals_leave_the_first_send_attempt_uncharged() {
+        // First copy: parked, then redelivered early (say after a consumer
+        // recreate) and replaced instead of being parked again.
+        assert_eq!(
+            plan_delivery(Some(61_000), false, 1, 1_000),
+            Delivery::Park(Duration::from_mins(1))
+        );
+        assert_eq!(
+            plan_delivery(Some(61_000), false, 2, 31_000),
+            Delivery::Republish { released: false }
+        );
+        // Second copy: parked again, then released once due.
+        assert_eq!(
+            plan_delivery(Some(61_000), false, 1, 31_000),
+            Delivery::Park(Duration::from_secs(30))
+        );
+        assert_eq!(
+            plan_delivery(Some(61_000), false, 2, 61_000),
+            Delivery::Republish { released: true }
+        );
+        // The released copy is sent on its first delivery.
+        assert_eq!(plan_delivery(Some(61_000), true, 1, 61_000), Delivery::Send);
+    }
+
+    #[test]
+    fn future_send_at_returns_remaining_delay() {
+        assert_eq!(
+            remaining_delay(Some(61_000), 1_000),
+            Some(Duration::from_mins(1))
+        );
+    }
+}
+
 #[cfg(test)]
 mod header_filter_tests {
     use async_nats::HeaderMap;
@@ -305,43 +448,17 @@ mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::port::email_queue::EmailQueue;
     use testcontainers::GenericImage;
-    use testcontainers::ImageExt;
-    use testcontainers::core::WaitFor;
-    use testcontainers::runners::AsyncRunner;
 
+    use crate::testing::start_nats;
     use crate::{NatsAdapter, NatsConfig};
 
-    async fn wait_for_tcp(port: u16, timeout: Duration) {
-        let deadline = tokio::time::Instant::now() + timeout;
-        loop {
-            if tokio::net::TcpStream::connect(("127.0.0.1", port))
-                .await
-                .is_ok()
-            {
-                return;
-            }
-            assert!(
-                tokio::time::Instant::now() < deadline,
-                "127.0.0.1:{port} did not accept connections within {timeout:?}"
-            );
-            tokio::time::sleep(Duration::from_millis(50)).await;
-        }
-    }
-
     async fn fresh_adapter() -> (NatsAdapter, testcontainers::ContainerAsync<GenericImage>) {
-        let nats = GenericImage::new("nats", "2-alpine")
-            .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
-            .with_cmd(["--js".to_owned()])
-            .start()
-            .await
-            .expect("failed to start NATS container; ensure Docker is running");
-
-        let port = nats.get_host_port_ipv4(4222).await.unwrap();
-        wait_for_tcp(port, Duration::from_secs(15)).await;
+        let (url, nats) = start_nats().await;
         let adapter = NatsConfig {
-            url: format!("nats://127.0.0.1:{port}"),
+            url,
             stream: "TEST".to_owned(),
             subject: "test.queued".to_owned(),
             consumer: "worker".to_owned(),
@@ -365,6 +482,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -404,6 +527,26 @@ mod tests {
         assert_eq!(dequeued.attempt, 1);
     }
 
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn scheduled_message_is_released_as_attempt_one() {
+        let (adapter, _nats) = fresh_adapter().await;
+        let id = EmailId::default();
+        let mut envelope = sample_envelope();
+        envelope.send_at_ms = Some(super::now_ms() + 1_500);
+
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = tokio::time::timeout(Duration::from_secs(15), adapter.dequeue())
+            .await
+            .expect("dequeue timed out")
+            .unwrap();
+
+        assert_eq!(dequeued.id, id);
+        assert_eq!(dequeued.attempt, 1);
+        assert!(super::now_ms() >= envelope.send_at_ms.unwrap());
+    }
+
     #[serial_test::serial]
     #[tokio::test]
     async fn ack_removes_message() {
@@ -1,80 +1,362 @@
 use anyhow::Context;
+use async_nats::header::NATS_MESSAGE_ID;
+use async_nats::jetstream;
+use catapulte_domain::entity::cloud_event::{
+    CloudEvent, CloudEventsFormat, CloudEventsMode, DATA_CONTENT_TYPE, STRUCTURED_CONTENT_TYPE,
+};
+use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+use catapulte_domain::entity::sender::SenderName;
+use catapulte_domain::port::clock::{Clock, SystemClock};
 use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
+use catapulte_domain::port::webhook_outbox::WebhookDelivery;
+
+const EVENT_TYPE_PLACEHOLDER: &str = "{event_type}";
+const SENDER_PLACEHOLDER: &str = "{sender}";
+/// How long a created stream remembers message ids. Covers the outbox lease
+/// and the first retries of a delivery whose ack was lost.
+const DUPLICATE_WINDOW: std::time::Duration = std::time::Duration::from_hours(1);
+/// Stands for the sender of events that have none.
+const NO_SENDER: &str = "_";
+
+/// Subject of each event, e.g. `catapulte.lifecycle.{event_type}.{sender}`.
+/// The event type is inserted as is: `delivery.failed` spans two tokens, so
+/// `catapulte.lifecycle.delivery.>` matches both delivery outcomes.
+#[derive(Clone, Debug)]
+pub struct SubjectTemplate(String);
+
+impl SubjectTemplate {
+    /// # Errors
+    ///
+    /// Returns an error for a placeholder other than `{event_type}` or
+    /// `{sender}`.
+    pub fn parse(template: &str) -> anyhow::Result<Self> {
+        let fixed = template
+            .replace(EVENT_TYPE_PLACEHOLDER, "")
+            .replace(SENDER_PLACEHOLDER, "");
+        anyhow::ensure!(
+            !fixed.contains(['{', '}']),
+            "unknown placeholder in NATS subject {template:?}, expected {EVENT_TYPE_PLACEHOLDER} or {SENDER_PLACEHOLDER}"
+        );
+        Ok(Self(template.to_owned()))
+    }
+
+    fn render(&self, event_type: &str, sender_name: Option<&str>) -> String {
+        let sender = sender_name.map_or_else(|| NO_SENDER.to_owned(), token);
+        self.0
+            .replace(EVENT_TYPE_PLACEHOLDER, event_type)
+            .replace(SENDER_PLACEHOLDER, &sender)
+    }
+
+    /// Subject filter matching every rendered subject: the tokens before the
+    /// first placeholder, followed by `>`.
+    fn stream_subject(&self) -> anyhow::Result<String> {
+        let Some(first) = self.0.find('{') else {
+            return Ok(self.0.clone());
+        };
+        let Some(end) = self.0[..first].rfind('.') else {
+            anyhow::bail!(
+                "NATS subject {:?} must start with a fixed token to be stored in a stream",
+                self.0
+            );
+        };
+        Ok(format!("{}.>", &self.0[..end]))
+    }
+}
+
+/// `value` as a single subject token.
+fn token(value: &str) -> String {
+    let token: String = value
+        .chars()
+        .map(|c| {
+            if matches!(c, '.' | '*' | '>') || c.is_whitespace() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    if token.is_empty() {
+        NO_SENDER.to_owned()
+    } else {
+        token
+    }
+}
 
 #[derive(Clone)]
 pub struct NatsEventPublisher {
     client: async_nats::Client,
-    subject: String,
+    /// Publishes through `JetStream`, waiting for the ack, when set.
+    jetstream: Option<jetstream::Context>,
+    subject: SubjectTemplate,
+    cloud_events: Option<CloudEventsFormat>,
 }
 
 impl NatsEventPublisher {
     #[must_use]
-    pub fn new(client: async_nats::Client, subject: String) -> Self {
-        Self { client, subject }
+    pub fn new(client: async_nats::Client, subject: SubjectTemplate) -> Self {
+        Self {
+            client,
+            jetstream: None,
+            subject,
+            cloud_events: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_jetstream(mut self, context: jetstream::Context) -> Self {
+        self.jetstream = Some(context);
+        self
+    }
+
+    /// Wraps every event in a `CloudEvents` envelope.
+    #[must_use]
+    pub fn with_cloud_events(mut self, format: Option<CloudEventsFormat>) -> Self {
+        self.cloud_events = format;
+        self
+    }
+
+    /// Whether messages are stored in a stream, in which case events are
+    /// pushed from the outbox, with [`Self::deliver`], until the stream acks.
+    #[must_use]
+    pub fn uses_jetstream(&self) -> bool {
+        self.jetstream.is_some()
+    }
+
+    /// Publishes `event`, stored under `id`, right away.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the event cannot be serialized or published.
+    pub async fn publish_event(
+        &self,
+        id: uuid::Uuid,
+        event: &LifecycleEvent,
+    ) -> anyhow::Result<()> {
+        let event_type = event.event_type();
+        let subject = self
+            .subject
+            .render(event_type, event.sender_name().map(SenderName::as_str));
+        let (headers, body) = encode(
+            event_type,
+            *event.email_id(),
+            &event.payload(),
+            self.cloud_events.as_ref(),
+            id,
+            SystemClock.now_ms(),
+        )?;
+        self.send(subject, id, headers, body).await
+    }
+
+    /// Pushes an outbox delivery, under the id and time of its stored event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the message cannot be serialized, or is not
+    /// acked by the stream.
+    pub async fn deliver(&self, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+        let subject = self
+            .subject
+            .render(&delivery.event_type, delivery.sender_name.as_deref());
+        let (headers, body) = encode(
+            &delivery.event_type,
+            delivery.email_id,
+            &delivery.payload,
+            self.cloud_events.as_ref(),
+            delivery.event_id,
+            delivery.created_at_ms,
+        )?;
+        self.send(subject, delivery.event_id, headers, body).await
+    }
+
+    async fn send(
+        &self,
+        subject: String,
+        id: uuid::Uuid,
+        mut headers: async_nats::HeaderMap,
+        body: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let Some(jetstream) = &self.jetstream else {
+            self.client
+                .publish_with_headers(subject, headers, body.into())
+                .await
+                .context("publishing event to NATS")?;
+            return Ok(());
+        };
+        // The stream drops a message it already stored under this id, e.g.
+        // by an earlier attempt whose ack was lost.
+        headers.insert(NATS_MESSAGE_ID, id.hyphenated().to_string().as_str());
+        jetstream
+            .publish_with_headers(subject, headers, body.into())
+            .await
+            .context("publishing event to JetStream")?
+            .await
+            .context("waiting for the JetStream ack")?;
+        Ok(())
     }
 }
 
-fn event_to_json(event: &LifecycleEvent) -> serde_json::Value {
+fn body_json(
+    event_type: &str,
+    email_id: EmailId,
+    payload: &serde_json::Value,
+) -> serde_json::Value {
     serde_json::json!({
-        "event_type": event.event_type(),
-        "email_id": event.email_id().as_uuid().to_string(),
-        "payload": event.payload(),
+        "event_type": event_type,
+        "email_id": email_id.as_uuid().to_string(),
+        "payload": payload,
     })
 }
 
+/// The message headers and body of an event.
+fn encode(
+    event_type: &str,
+    email_id: EmailId,
+    payload: &serde_json::Value,
+    format: Option<&CloudEventsFormat>,
+    id: uuid::Uuid,
+    time_ms: i64,
+) -> anyhow::Result<(async_nats::HeaderMap, Vec<u8>)> {
+    let mut headers = async_nats::HeaderMap::new();
+    let Some(format) = format else {
+        let body = serde_json::to_vec(&body_json(event_type, email_id, payload))
+            .context("serializing event")?;
+        return Ok((headers, body));
+    };
+    let event = CloudEvent {
+        id,
+        source: format.source.clone(),
+        event_type: event_type.to_owned(),
+        email_id,
+        time_ms,
+        data: payload.clone(),
+    };
+    let body = match format.mode {
+        CloudEventsMode::Structured => {
+            headers.insert("content-type", STRUCTURED_CONTENT_TYPE);
+            serde_json::to_vec(&event.to_structured_json())
+        }
+        CloudEventsMode::Binary => {
+            headers.insert("content-type", DATA_CONTENT_TYPE);
+            for (name, value) in event.attributes() {
+                headers.insert(format!("ce-{name}").as_str(), value.as_str());
+            }
+            serde_json::to_vec(&event.data)
+        }
+    }
+    .context("serializing event")?;
+    Ok((headers, body))
+}
+
 impl EventPublisher for NatsEventPublisher {
     async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
-        let body = serde_json::to_vec(&event_to_json(event))
-            .context("serializing event")
-            .map_err(|source| EventPublisherError::Publish { source })?;
-        self.client
-            .publish(self.subject.clone(), body.into())
+        self.publish_event(uuid::Uuid::now_v7(), event)
             .await
-            .context("publishing event to NATS")
-            .map_err(|source| EventPublisherError::Publish { source })?;
-        Ok(())
+            .map_err(|source| EventPublisherError::Publish { source })
     }
 }
 
 pub struct NatsEventConfig {
     pub url: Option<String>,
+    /// Subject template, see [`SubjectTemplate`].
     pub subject: String,
+    /// `JetStream` stream storing the events, created when missing. Events are
+    /// published with core NATS, and dropped when nobody listens, when `None`.
+    pub stream: Option<String>,
+    /// Envelope of every message; the plain `{event_type, email_id, payload}`
+    /// body when `None`.
+    pub cloud_events: Option<CloudEventsFormat>,
 }
 
 impl NatsEventConfig {
     /// # Errors
     ///
-    /// Never fails; env vars are optional.
+    /// Returns an error if the `CloudEvents` mode is unknown.
     pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
         let url = std::env::var(format!("{prefix}_URL")).ok();
         let subject = std::env::var(format!("{prefix}_SUBJECT"))
             .unwrap_or_else(|_| "catapulte.lifecycle".to_owned());
-        Ok(Self { url, subject })
+        let stream = std::env::var(format!("{prefix}_STREAM"))
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+        let cloud_events = CloudEventsFormat::parse(
+            std::env::var(format!("{prefix}_CLOUDEVENTS"))
+                .ok()
+                .as_deref(),
+            std::env::var(format!("{prefix}_CLOUDEVENTS_SOURCE"))
+                .ok()
+                .as_deref(),
+        )
+        .context("parsing NATS CloudEvents mode")?;
+        Ok(Self {
+            url,
+            subject,
+            stream,
+            cloud_events,
+        })
     }
 
     /// # Errors
     ///
-    /// Returns an error if the NATS connection fails.
+    /// Returns an error if the subject template is invalid, or the NATS
+    /// connection or stream setup fails.
     pub async fn build(self) -> anyhow::Result<Option<NatsEventPublisher>> {
         let Some(url) = self.url else {
             return Ok(None);
         };
+        let subject = SubjectTemplate::parse(&self.subject)?;
         let client = async_nats::connect(&url)
             .await
             .context("connecting to NATS for event publisher")?;
-        Ok(Some(NatsEventPublisher::new(client, self.subject)))
+        let publisher = NatsEventPublisher::new(client.clone(), subject.clone())
+            .with_cloud_events(self.cloud_events);
+        let Some(stream_name) = self.stream else {
+            return Ok(Some(publisher));
+        };
+        let js = jetstream::new(client);
+        let stream_subject = subject.stream_subject()?;
+        let mut stream = js
+            .get_or_create_stream(jetstream::stream::Config {
+                name: stream_name.clone(),
+                subjects: vec![stream_subject.clone()],
+                storage: jetstream::stream::StorageType::File,
+                duplicate_window: DUPLICATE_WINDOW,
+                ..Default::default()
+            })
+            .await
+            .context("creating NATS events stream")?;
+        let stream_info = stream
+            .info()
+            .await
+            .context("fetching NATS events stream info")?;
+        anyhow::ensure!(
+            stream_info.config.subjects.contains(&stream_subject),
+            "NATS stream {:?} does not include subject {:?}; found: {:?}",
+            stream_name,
+            stream_subject,
+            stream_info.config.subjects,
+        );
+        Ok(Some(publisher.with_jetstream(js)))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use catapulte_domain::entity::cloud_event::{CloudEventsFormat, CloudEventsMode};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::error_class::ErrorClass;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
     use catapulte_domain::entity::sender::SenderName;
+    use catapulte_domain::port::webhook_outbox::{
+        DeliveryTarget, WebhookDelivery, WebhookDeliveryStatus,
+    };
 
-    use super::event_to_json;
+    use super::{NatsEventConfig, SubjectTemplate, body_json, encode};
+    use crate::testing::start_nats;
+
+    fn event_to_json(event: &LifecycleEvent) -> serde_json::Value {
+        body_json(event.event_type(), *event.email_id(), &event.payload())
+    }
 
     /// Build the canonical expected body for a given event — shared between the
     /// webhook and NATS contract-lock tests so they are provably identical.
@@ -134,4 +416,135 @@ mod tests {
         assert_eq!(event_to_json(&event), expected);
         assert_eq!(event_to_json(&event), expected_body(&event));
     }
+
+    #[test]
+    fn plain_messages_carry_no_headers() {
+        let event = LifecycleEvent::Queued {
+            id: EmailId::default(),
+            correlation_id: None,
+        };
+        let (headers, body) = encode(
+            event.event_type(),
+            *event.email_id(),
+            &event.payload(),
+            None,
+            uuid::Uuid::now_v7(),
+            0,
+        )
+        .unwrap();
+        assert!(headers.is_empty());
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, expected_body(&event));
+    }
+
+    #[test]
+    fn binary_cloud_events_move_the_attributes_to_headers() {
+        let id = EmailId::default();
+        let event = LifecycleEvent::Cancelled {
+            id,
+            correlation_id: Some("corr".to_owned()),
+        };
+        let format = CloudEventsFormat {
+            mode: CloudEventsMode::Binary,
+            source: "/catapulte".into(),
+        };
+        let (headers, body) = encode(
+            event.event_type(),
+            id,
+            &event.payload(),
+            Some(&format),
+            uuid::Uuid::now_v7(),
+            1_700_000_000_050,
+        )
+        .unwrap();
+        let header = |name: &str| headers.get(name).map(|v| v.as_str().to_owned());
+        assert_eq!(header("ce-specversion").as_deref(), Some("1.0"));
+        assert_eq!(header("ce-type").as_deref(), Some("io.catapulte.cancelled"));
+        assert_eq!(header("ce-subject"), Some(id.as_uuid().to_string()));
+        assert_eq!(
+            header("ce-time").as_deref(),
+            Some("2023-11-14T22:13:20.050Z")
+        );
+        assert_eq!(header("content-type").as_deref(), Some("application/json"));
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body, event.payload());
+    }
+
+    #[test]
+    fn subjects_follow_the_event_type_and_sender() {
+        let template = SubjectTemplate::parse("catapulte.lifecycle.{event_type}.{sender}").unwrap();
+
+        assert_eq!(
+            template.render("delivery.failed", Some("eu primary.1")),
+            "catapulte.lifecycle.delivery.failed.eu_primary_1"
+        );
+        assert_eq!(
+            template.render("queued", None),
+            "catapulte.lifecycle.queued._"
+        );
+        assert_eq!(template.stream_subject().unwrap(), "catapulte.lifecycle.>");
+        assert!(SubjectTemplate::parse("catapulte.{email_id}").is_err());
+        assert!(
+            SubjectTemplate::parse("{event_type}.catapulte")
+                .unwrap()
+                .stream_subject()
+                .is_err()
+        );
+    }
+
+    #[serial_test::serial]
+    #[tokio::test]
+    async fn redelivered_events_are_stored_once() {
+        let (url, _nats) = start_nats().await;
+        let publisher = NatsEventConfig {
+            url: Some(url.clone()),
+            subject: "test.events.{event_type}".to_owned(),
+            stream: Some("TEST_EVENTS".to_owned()),
+            cloud_events: None,
+        }
+        .build()
+        .await
+        .expect("failed to build NATS event publisher")
+        .unwrap();
+        let event = LifecycleEvent::Cancelled {
+            id: EmailId::default(),
+            correlation_id: None,
+        };
+        let delivery = WebhookDelivery {
+            id: uuid::Uuid::now_v7(),
+            event_id: uuid::Uuid::now_v7(),
+            target: DeliveryTarget::Nats,
+            subscription_id: None,
+            email_id: *event.email_id(),
+            event_type: event.event_type().to_owned(),
+            sender_name: None,
+            payload: event.payload(),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at_ms: 0,
+            last_error: None,
+            created_at_ms: 0,
+        };
+
+        // As if the ack of the first attempt was lost.
+        publisher.deliver(&delivery).await.unwrap();
+        publisher.deliver(&delivery).await.unwrap();
+
+        let client = async_nats::connect(&url).await.unwrap();
+        let mut stream = async_nats::jetstream::new(client)
+            .get_stream("TEST_EVENTS")
+            .await
+            .unwrap();
+        assert_eq!(stream.info().await.unwrap().state.messages, 1);
+        let stored = stream
+            .get_last_raw_message_by_subject("test.events.cancelled")
+            .await
+            .unwrap();
+        assert_eq!(
+            stored.headers.get("Nats-Msg-Id").unwrap().as_str(),
+            delivery.event_id.to_string()
+        );
+        let body: serde_json::Value = serde_json::from_slice(&stored.payload).unwrap();
+        assert_eq!(body, expected_body(&event));
+    }
 }
@@ -5,6 +5,7 @@ mod health;
 
 use anyhow::Context;
 use async_nats::jetstream;
+use catapulte_domain::entity::retry_policy::RetryPolicy;
 
 #[derive(Clone)]
 pub struct NatsAdapter {
@@ -139,36 +140,35 @@ impl NatsConfig {
                 .with_context(|| format!("invalid {ack_wait_key}: {v:?}"))?,
         };
 
-        let max_deliver_key = format!("{prefix}_MAX_DELIVER");
-        let max_deliver = match std::env::var(&max_deliver_key) {
-            Err(_) => 3i64,
-            Ok(v) => v
-                .parse::<i64>()
-                .with_context(|| format!("invalid {max_deliver_key}: {v:?}"))?,
-        };
-
-        let backoff_key = format!("{prefix}_BACKOFF");
-        let backoff_secs = match std::env::var(&backoff_key) {
-            Err(_) => vec![30u64, 60, 120],
-            Ok(v) => v
-                .split(',')
-                .map(|s| {
-                    s.trim()
-                        .parse::<u64>()
-                        .with_context(|| format!("invalid value in {backoff_key}: {s:?}"))
-                })
-                .collect::<anyhow::Result<Vec<_>>>()?,
-        };
-
         Ok(Self {
             url,
             stream,
             subject,
             consumer,
             ack_wait_secs,
-            max_deliver,
-            backoff_secs,
-        })
+            max_deliver: 0,
+            backoff_secs: Vec::new(),
+        }
+        .with_retry_policy(&RetryPolicy::default()))
+    }
+
+    /// Derives the consumer's redelivery limits from the worker's retry
+    /// policy, so the consumer never drops a message the worker would retry.
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: &RetryPolicy) -> Self {
+        // A parked copy of a scheduled message needs a second delivery to
+        // come back; it is then republished, so it is never sent.
+        let max_deliver = policy.max_attempts().max(2);
+        self.max_deliver = i64::from(max_deliver);
+        // The server requires fewer backoff steps than deliveries.
+        self.backoff_secs = policy
+            .default_rule()
+            .backoff
+            .iter()
+            .map(std::time::Duration::as_secs)
+            .take(usize::try_from(max_deliver - 1).unwrap_or(usize::MAX))
+            .collect();
+        self
     }
 
     /// # Errors
@@ -178,3 +178,43 @@ impl NatsConfig {
         NatsAdapter::connect(&self).await
     }
 }
+
+#[cfg(test)]
+pub(crate) mod testing {
+    use std::time::Duration;
+
+    use testcontainers::core::WaitFor;
+    use testcontainers::runners::AsyncRunner;
+    use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+
+    async fn wait_for_tcp(port: u16, timeout: Duration) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if tokio::net::TcpStream::connect(("127.0.0.1", port))
+                .await
+                .is_ok()
+            {
+                return;
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "127.0.0.1:{port} did not accept connections within {timeout:?}"
+            );
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Starts a NATS server with `JetStream`, returning its URL.
+    pub(crate) async fn start_nats() -> (String, ContainerAsync<GenericImage>) {
+        let nats = GenericImage::new("nats", "2-alpine")
+            .with_wait_for(WaitFor::message_on_stderr("Server is ready"))
+            .with_cmd(["--js".to_owned()])
+            .start()
+            .await
+            .expect("failed to start NATS container; ensure Docker is running");
+
+        let port = nats.get_host_port_ipv4(4222).await.unwrap();
+        wait_for_tcp(port, Duration::from_secs(15)).await;
+        (format!("nats://127.0.0.1:{port}"), nats)
+    }
+}
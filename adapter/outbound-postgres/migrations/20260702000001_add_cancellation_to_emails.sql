@@ -0,0 +1,2 @@
+ALTER TABLE emails ADD COLUMN cancelled_at_ms BIGINT;
+ALTER TABLE emails ADD COLUMN delivery_started_at_ms BIGINT;
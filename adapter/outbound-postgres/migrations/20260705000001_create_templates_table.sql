@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS templates (
+    name TEXT NOT NULL,
+    version BIGINT NOT NULL,
+    content TEXT NOT NULL,
+    created_at_ms BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT,
+    deleted_at_ms BIGINT,
+    PRIMARY KEY (name, version)
+);
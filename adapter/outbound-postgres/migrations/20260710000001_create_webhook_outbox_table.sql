@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS webhook_outbox (
+    event_id UUID PRIMARY KEY NOT NULL REFERENCES lifecycle_events(id),
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at_ms BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT,
+    last_error TEXT,
+    created_at_ms BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS webhook_outbox_due
+    ON webhook_outbox(next_attempt_at_ms) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS webhook_outbox_created_at_ms ON webhook_outbox(created_at_ms);
@@ -0,0 +1,22 @@
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id UUID PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    -- JSON array of event types, empty for every type
+    event_types JSONB NOT NULL DEFAULT '[]',
+    correlation_id_prefix TEXT,
+    created_at_ms BIGINT NOT NULL DEFAULT (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT
+);
+
+-- The outbox now holds one delivery per event and endpoint. Deliveries to
+-- the webhook configured by the operator have no subscription; the ones
+-- queued before this migration keep their event id as delivery id.
+ALTER TABLE webhook_outbox ADD COLUMN id UUID;
+UPDATE webhook_outbox SET id = event_id;
+ALTER TABLE webhook_outbox ALTER COLUMN id SET NOT NULL;
+ALTER TABLE webhook_outbox DROP CONSTRAINT webhook_outbox_pkey;
+ALTER TABLE webhook_outbox ADD PRIMARY KEY (id);
+ALTER TABLE webhook_outbox
+    ADD COLUMN subscription_id UUID REFERENCES webhook_subscriptions(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS webhook_outbox_subscription_id ON webhook_outbox(subscription_id);
@@ -0,0 +1,2 @@
+-- Deliveries to the NATS JetStream stream share the outbox with webhooks.
+ALTER TABLE webhook_outbox ADD COLUMN target TEXT NOT NULL DEFAULT 'webhook';
@@ -0,0 +1,8 @@
+-- Template a named MJML body uses, without its pinned version or locale
+-- variant, and the locale of that variant: `welcome.fr@2` is `welcome` in `fr`.
+ALTER TABLE emails ADD COLUMN template_name TEXT;
+ALTER TABLE emails ADD COLUMN template_locale TEXT;
+
+UPDATE emails
+SET template_name = split_part(body->'source'->>'name', '@', 1)
+WHERE body->'source'->>'kind' = 'mjml_named';
@@ -2,16 +2,22 @@ use std::time::Duration;
 
 use anyhow::Context;
 use catapulte_domain::entity::body::BodySource;
+use catapulte_domain::entity::calendar::Calendar;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::envelope::Envelope;
+use catapulte_domain::entity::tracking::Tracking;
+use catapulte_domain::entity::unsubscribe::MailingList;
 use catapulte_domain::port::email_queue::{
     AckToken, DequeuedEmail, EmailQueue, EmailQueueError, TraceCarrier,
 };
 
 use crate::PostgresAdapter;
-use crate::dto::{EnvelopeBodyDtoDeser, RecipientDto, recipients_from_dto};
+use crate::dto::{EnvelopeBodyDtoDeser, MessageHeadersDto, RecipientDto, recipients_from_dto};
 
-fn now_ms() -> i64 {
+/// How long a dequeued email stays claimed before another worker may take it.
+pub(crate) const PROCESSING_TIMEOUT_MS: i64 = 300_000;
+
+pub(crate) fn now_ms() -> i64 {
     i64::try_from(
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -41,6 +47,28 @@ fn parse_envelope(row: &sqlx::postgres::PgRow) -> anyhow::Result<Envelope> {
     let correlation_id: Option<String> = row
         .try_get("correlation_id")
         .context("reading correlation_id")?;
+    let send_at_ms: Option<i64> = row.try_get("send_at_ms").context("reading send_at_ms")?;
+    let headers: Option<sqlx::types::Json<MessageHeadersDto>> =
+        row.try_get("headers").context("reading headers")?;
+    let locale: Option<String> = row.try_get("locale").context("reading locale")?;
+    let calendar: Option<String> = row.try_get("calendar").context("reading calendar")?;
+    let calendar = calendar
+        .as_deref()
+        .map(Calendar::parse)
+        .transpose()
+        .context("parsing calendar")?;
+    let list: Option<String> = row.try_get("list_name").context("reading list_name")?;
+    let list = list
+        .as_deref()
+        .map(MailingList::parse)
+        .transpose()
+        .context("parsing list_name")?;
+    let tracking = Tracking {
+        opens: row.try_get("track_opens").context("reading track_opens")?,
+        clicks: row
+            .try_get("track_clicks")
+            .context("reading track_clicks")?,
+    };
     let subject = row.try_get("subject").context("reading subject")?;
     let sender = row.try_get("sender").context("reading sender")?;
     Ok(Envelope {
@@ -52,6 +80,12 @@ fn parse_envelope(row: &sqlx::postgres::PgRow) -> anyhow::Result<Envelope> {
         body,
         variables: variables.0,
         attachments,
+        send_at_ms,
+        headers: headers.map(|h| h.0).unwrap_or_default().into(),
+        locale,
+        calendar,
+        list,
+        tracking,
     })
 }
 
@@ -96,7 +130,7 @@ impl PostgresAdapter {
             .map_err(|source| EmailQueueError::Storage { source })?;
 
         let maybe = sqlx::query(
-            "SELECT id, email_id, attempt_count, trace_context FROM email_queue \
+            "SELECT id, email_id, attempt_count, enqueued_at, trace_context FROM email_queue \
              WHERE claimed_until IS NULL OR claimed_until < $1 \
              ORDER BY enqueued_at ASC LIMIT 1 FOR UPDATE SKIP LOCKED",
         )
@@ -106,10 +140,11 @@ impl PostgresAdapter {
         .context("finding next queue entry")
         .map_err(|source| EmailQueueError::Storage { source })?;
 
-        let (entry_id, email_id_uuid, current_attempt, trace_raw): (
+        let (entry_id, email_id_uuid, current_attempt, enqueued_at_ms, trace_raw): (
             uuid::Uuid,
             uuid::Uuid,
             u32,
+            i64,
             Option<String>,
         ) = match maybe {
             None => {
@@ -136,17 +171,20 @@ impl PostgresAdapter {
                 let attempt_u32 = u32::try_from(attempt)
                     .context("attempt_count out of range")
                     .map_err(|source| EmailQueueError::Storage { source })?;
+                let enqueued_at_ms: i64 = row
+                    .try_get("enqueued_at")
+                    .context("reading enqueued_at")
+                    .map_err(|source| EmailQueueError::Storage { source })?;
                 let trace_raw: Option<String> = row
                     .try_get("trace_context")
                     .context("reading trace_context")
                     .map_err(|source| EmailQueueError::Storage { source })?;
-                (entry_id, email_id, attempt_u32, trace_raw)
+                (entry_id, email_id, attempt_u32, enqueued_at_ms, trace_raw)
             }
         };
 
         let new_attempt = current_attempt + 1;
-        let processing_timeout_ms: i64 = 300_000;
-        let claim_until = now + processing_timeout_ms;
+        let claim_until = now + PROCESSING_TIMEOUT_MS;
 
         sqlx::query(
             "UPDATE email_queue SET claimed_until = $1, attempt_count = attempt_count + 1 WHERE id = $2",
@@ -159,7 +197,7 @@ impl PostgresAdapter {
         .map_err(|source| EmailQueueError::Storage { source })?;
 
         let maybe_row = sqlx::query(
-            "SELECT idempotency_key, correlation_id, subject, sender, recipients, body, variables FROM emails WHERE id = $1",
+            "SELECT idempotency_key, correlation_id, subject, sender, recipients, body, variables, send_at_ms, headers, locale, calendar, list_name, track_opens, track_clicks FROM emails WHERE id = $1",
         )
         .bind(email_id_uuid)
         .fetch_optional(&mut *tx)
@@ -194,6 +232,7 @@ impl PostgresAdapter {
                     id: EmailId::from(email_id_uuid),
                     envelope,
                     attempt: new_attempt,
+                    enqueued_at_ms,
                     token,
                     trace,
                 }))
@@ -203,7 +242,7 @@ impl PostgresAdapter {
 }
 
 impl EmailQueue for PostgresAdapter {
-    async fn enqueue(&self, id: EmailId, _envelope: &Envelope) -> Result<(), EmailQueueError> {
+    async fn enqueue(&self, id: EmailId, envelope: &Envelope) -> Result<(), EmailQueueError> {
         let entry_id = uuid::Uuid::now_v7();
         let email_id = id.as_uuid();
         let pairs = catapulte_telemetry::propagation::inject_current();
@@ -216,14 +255,19 @@ impl EmailQueue for PostgresAdapter {
                     .map_err(|source| EmailQueueError::Storage { source })?,
             )
         };
-        sqlx::query("INSERT INTO email_queue (id, email_id, trace_context) VALUES ($1, $2, $3)")
-            .bind(entry_id)
-            .bind(email_id)
-            .bind(trace_context)
-            .execute(self.pool())
-            .await
-            .context("inserting into email_queue")
-            .map_err(|source| EmailQueueError::Storage { source })?;
+        // A scheduled entry starts out "claimed" until its send time, so the
+        // regular `claimed_until < now` check keeps it invisible until due.
+        sqlx::query(
+            "INSERT INTO email_queue (id, email_id, trace_context, claimed_until) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(entry_id)
+        .bind(email_id)
+        .bind(trace_context)
+        .bind(envelope.send_at_ms)
+        .execute(self.pool())
+        .await
+        .context("inserting into email_queue")
+        .map_err(|source| EmailQueueError::Storage { source })?;
         Ok(())
     }
 
@@ -272,6 +316,7 @@ mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::port::email_queue::EmailQueue;
     use catapulte_domain::port::email_repository::EmailRepository;
 
@@ -334,6 +379,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -352,6 +403,21 @@ mod tests {
         assert_eq!(dequeued.id, id);
     }
 
+    #[tokio::test]
+    async fn scheduled_email_is_not_dequeued_before_send_at() {
+        let (adapter, _container) = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            send_at_ms: Some(super::now_ms() + 60_000),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        assert!(adapter.try_dequeue().await.unwrap().is_none());
+        assert_eq!(adapter.pending().await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn ack_removes_item_from_queue() {
         let (adapter, _container) = fresh_adapter().await;
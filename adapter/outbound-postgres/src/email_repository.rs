@@ -1,9 +1,13 @@
 use anyhow::Context;
 use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
+use catapulte_domain::entity::calendar::Calendar;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::envelope::Envelope;
+use catapulte_domain::entity::template::parse_template_ref;
+use catapulte_domain::entity::unsubscribe::MailingList;
 use catapulte_domain::port::email_repository::{
-    EmailRecord, EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams, SaveResult,
+    CancelResult, EmailRecord, EmailRepository, EmailRepositoryError, EmailStatus,
+    ListEmailsParams, SaveResult,
 };
 use sqlx::QueryBuilder;
 use sqlx::Row;
@@ -11,7 +15,8 @@ use sqlx::types::Json;
 
 use crate::PostgresAdapter;
 use crate::dto::{
-    AttachmentRefDto, BodySourceDto, EnvelopeBodyDto, EnvelopeBodyDtoDeser, recipients_to_dto,
+    AttachmentRefDto, BodySourceDto, EnvelopeBodyDto, EnvelopeBodyDtoDeser, MessageHeadersDto,
+    recipients_to_dto,
 };
 
 impl EmailRepository for PostgresAdapter {
@@ -33,10 +38,11 @@ impl EmailRepository for PostgresAdapter {
                 .collect(),
         };
         let recipients_dto = recipients_to_dto(&envelope.recipients);
+        let (template_name, template_locale) = envelope.template().unzip();
 
         let result = sqlx::query(
-            "INSERT INTO emails (id, idempotency_key, correlation_id, subject, sender, recipients, body, variables) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+            "INSERT INTO emails (id, idempotency_key, correlation_id, subject, sender, recipients, body, variables, send_at_ms, headers, locale, calendar, list_name, track_opens, track_clicks, template_name, template_locale) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17) \
              ON CONFLICT (idempotency_key) WHERE idempotency_key IS NOT NULL DO NOTHING",
         )
         .bind(id_uuid)
@@ -47,6 +53,15 @@ impl EmailRepository for PostgresAdapter {
         .bind(Json(&recipients_dto))
         .bind(Json(&body_dto))
         .bind(Json(&envelope.variables))
+        .bind(envelope.send_at_ms)
+        .bind(Json(MessageHeadersDto::from(&envelope.headers)))
+        .bind(envelope.locale.as_deref())
+        .bind(envelope.calendar.as_ref().map(Calendar::content))
+        .bind(envelope.list.as_ref().map(MailingList::as_str))
+        .bind(envelope.tracking.opens)
+        .bind(envelope.tracking.clicks)
+        .bind(template_name)
+        .bind(template_locale.flatten())
         .execute(self.pool())
         .await
         .context("inserting email")
@@ -75,10 +90,14 @@ impl EmailRepository for PostgresAdapter {
         Ok(SaveResult::Duplicate(EmailId::from(existing_uuid)))
     }
 
+    #[allow(clippy::too_many_lines)]
     async fn list_emails(
         &self,
         params: ListEmailsParams,
     ) -> Result<Vec<EmailRecord>, EmailRepositoryError> {
+        let now = crate::email_queue::now_ms();
+        // Engagement events (`unsubscribed`, `opened`, `clicked`) come after
+        // delivery and never change the status.
         let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
             "WITH email_status AS (\
                 SELECT \
@@ -88,17 +107,24 @@ impl EmailRepository for PostgresAdapter {
                     e.sender, \
                     e.recipients, \
                     e.created_at, \
+                    e.send_at_ms, \
+                    e.cancelled_at_ms, \
+                    e.locale, \
+                    e.template_name, \
+                    e.template_locale, \
+                    CASE WHEN e.body->'source'->>'kind' = 'mjml_named' THEN e.body->'source'->>'name' END AS template_ref, \
                     COALESCE(\
                         (SELECT le.event_type \
                          FROM lifecycle_events le \
                          WHERE le.email_id = e.id \
+                           AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
                          ORDER BY le.created_at DESC, le.id DESC \
                          LIMIT 1),\
                         'queued'\
                     ) AS latest_event_type \
                 FROM emails e\
             ) \
-            SELECT id, idempotency_key, subject, sender, recipients, created_at, latest_event_type \
+            SELECT id, idempotency_key, subject, sender, recipients, created_at, send_at_ms, cancelled_at_ms, template_ref, template_name, template_locale, locale, latest_event_type \
             FROM email_status \
             WHERE 1=1",
         );
@@ -123,9 +149,20 @@ impl EmailRepository for PostgresAdapter {
             qb.push(" || '%')");
         }
         if let Some(template) = params.template {
-            qb.push(" AND id IN (SELECT id FROM emails WHERE body->'source'->>'kind' = 'mjml_named' AND body->'source'->>'name' = ");
+            // Pinned versions (`welcome@3`) and locale variants
+            // (`welcome.fr`) are stored under their bare name.
+            qb.push(" AND template_name = ");
             qb.push_bind(template);
-            qb.push(")");
+        }
+        // A cancellation outranks whatever the event log says.
+        match params.status {
+            Some(EmailStatus::Cancelled) => {
+                qb.push(" AND cancelled_at_ms IS NOT NULL");
+            }
+            Some(_) => {
+                qb.push(" AND cancelled_at_ms IS NULL");
+            }
+            None => {}
         }
         match params.status {
             Some(EmailStatus::Sent) => {
@@ -136,10 +173,21 @@ impl EmailRepository for PostgresAdapter {
                 qb.push(" AND latest_event_type = ");
                 qb.push_bind("delivery.failed");
             }
+            Some(EmailStatus::Suppressed) => {
+                qb.push(" AND latest_event_type = ");
+                qb.push_bind("suppressed");
+            }
+            Some(EmailStatus::Scheduled) => {
+                qb.push(" AND latest_event_type = 'queued' AND send_at_ms > ");
+                qb.push_bind(now);
+            }
             Some(EmailStatus::Queued) => {
-                qb.push(" AND latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed')");
+                qb.push(" AND latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed')");
+                qb.push(" AND NOT (latest_event_type = 'queued' AND COALESCE(send_at_ms, 0) > ");
+                qb.push_bind(now);
+                qb.push(")");
             }
-            None => {}
+            Some(EmailStatus::Cancelled) | None => {}
         }
 
         qb.push(" ORDER BY created_at DESC, id DESC LIMIT ");
@@ -155,7 +203,7 @@ impl EmailRepository for PostgresAdapter {
             .map_err(|source| EmailRepositoryError::Storage { source })?;
 
         rows.iter()
-            .map(PostgresAdapter::row_to_email_record)
+            .map(|row| PostgresAdapter::row_to_email_record(row, now))
             .collect::<anyhow::Result<Vec<_>>>()
             .map_err(|source| EmailRepositoryError::Storage { source })
     }
@@ -229,19 +277,112 @@ impl EmailRepository for PostgresAdapter {
         Ok(())
     }
 
+    async fn cancel(&self, id: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+        let id_uuid = id.as_uuid();
+        let now = crate::email_queue::now_ms();
+
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .context("starting transaction")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let row: Option<(Option<String>, Json<EnvelopeBodyDtoDeser>)> = sqlx::query_as(
+            "UPDATE emails SET cancelled_at_ms = $1 \
+             WHERE id = $2 \
+               AND cancelled_at_ms IS NULL \
+               AND (delivery_started_at_ms IS NULL OR delivery_started_at_ms < $3) \
+               AND COALESCE(\
+                   (SELECT le.event_type FROM lifecycle_events le \
+                    WHERE le.email_id = emails.id \
+                      AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
+                    ORDER BY le.created_at DESC, le.id DESC LIMIT 1),\
+                   'queued'\
+               ) NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed') \
+             RETURNING correlation_id, body",
+        )
+        .bind(now)
+        .bind(id_uuid)
+        .bind(now - crate::email_queue::PROCESSING_TIMEOUT_MS)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("cancelling email")
+        .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let Some((correlation_id, body)) = row else {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM emails WHERE id = $1)")
+                    .bind(id_uuid)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .context("checking email existence")
+                    .map_err(|source| EmailRepositoryError::Storage { source })?;
+            return Ok(if exists {
+                CancelResult::NotCancellable
+            } else {
+                CancelResult::NotFound
+            });
+        };
+
+        sqlx::query("DELETE FROM email_queue WHERE email_id = $1")
+            .bind(id_uuid)
+            .execute(&mut *tx)
+            .await
+            .context("dropping queue entry of cancelled email")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        tx.commit()
+            .await
+            .context("committing cancel transaction")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let (_, attachments) = body.0.split();
+        let attachments = attachments.into_iter().map(AttachmentRef::from).collect();
+        Ok(CancelResult::Cancelled {
+            correlation_id,
+            attachments,
+        })
+    }
+
+    async fn begin_delivery(&self, id: EmailId) -> Result<bool, EmailRepositoryError> {
+        let result = sqlx::query(
+            "UPDATE emails SET delivery_started_at_ms = $1 WHERE id = $2 AND cancelled_at_ms IS NULL",
+        )
+        .bind(crate::email_queue::now_ms())
+        .bind(id.as_uuid())
+        .execute(self.pool())
+        .await
+        .context("marking delivery started")
+        .map_err(|source| EmailRepositoryError::Storage { source })?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn end_delivery(&self, id: EmailId) -> Result<(), EmailRepositoryError> {
+        sqlx::query("UPDATE emails SET delivery_started_at_ms = NULL WHERE id = $1")
+            .bind(id.as_uuid())
+            .execute(self.pool())
+            .await
+            .context("clearing delivery started")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+        Ok(())
+    }
+
     async fn list_all_attachment_blobs(&self) -> Result<Vec<BlobRef>, EmailRepositoryError> {
         let rows: Vec<serde_json::Value> = sqlx::query_scalar(
             "WITH email_status AS (\
-                SELECT e.body, COALESCE(\
+                SELECT e.body, e.cancelled_at_ms, COALESCE(\
                     (SELECT event_type FROM lifecycle_events le \
                      WHERE le.email_id = e.id \
+                       AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
                      ORDER BY le.created_at DESC, le.id DESC LIMIT 1),\
                     'queued'\
                 ) AS latest_event_type \
                 FROM emails e\
             ) \
             SELECT body FROM email_status \
-            WHERE latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed')",
+            WHERE latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed') \
+              AND cancelled_at_ms IS NULL",
         )
         .fetch_all(self.pool())
         .await
@@ -266,7 +407,10 @@ impl EmailRepository for PostgresAdapter {
 }
 
 impl PostgresAdapter {
-    fn row_to_email_record(row: &sqlx::postgres::PgRow) -> anyhow::Result<EmailRecord> {
+    fn row_to_email_record(
+        row: &sqlx::postgres::PgRow,
+        now_ms: i64,
+    ) -> anyhow::Result<EmailRecord> {
         let id: uuid::Uuid = row.try_get("id").context("reading id")?;
         let idempotency_key: Option<String> = row
             .try_get("idempotency_key")
@@ -279,9 +423,33 @@ impl PostgresAdapter {
         let latest_event_type: String = row
             .try_get("latest_event_type")
             .context("reading latest_event_type")?;
+        let send_at_ms: Option<i64> = row.try_get("send_at_ms").context("reading send_at_ms")?;
+        let cancelled_at_ms: Option<i64> = row
+            .try_get("cancelled_at_ms")
+            .context("reading cancelled_at_ms")?;
+        let template_ref: Option<String> = row
+            .try_get("template_ref")
+            .context("reading template_ref")?;
+        let template: Option<String> = row
+            .try_get("template_name")
+            .context("reading template_name")?;
+        let template_version = template_ref
+            .as_deref()
+            .and_then(|reference| parse_template_ref(reference).1);
+        // A pinned template has its variant picked already; otherwise it is
+        // picked when the email is rendered, for the requested locale.
+        let locale: Option<String> = if template_version.is_some() {
+            row.try_get("template_locale")
+                .context("reading template_locale")?
+        } else {
+            row.try_get("locale").context("reading locale")?
+        };
         let status = match latest_event_type.as_str() {
+            _ if cancelled_at_ms.is_some() => EmailStatus::Cancelled,
             "delivery.succeeded" => EmailStatus::Sent,
             "delivery.failed" => EmailStatus::Failed,
+            "suppressed" => EmailStatus::Suppressed,
+            "queued" if send_at_ms.is_some_and(|at| at > now_ms) => EmailStatus::Scheduled,
             _ => EmailStatus::Queued,
         };
         Ok(EmailRecord {
@@ -291,7 +459,11 @@ impl PostgresAdapter {
             sender,
             recipients: crate::dto::recipients_from_dto(recipients_json.0),
             created_at_ms,
+            send_at_ms,
             status,
+            template,
+            template_version,
+            locale,
         })
     }
 }
@@ -303,9 +475,11 @@ mod tests {
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::email_repository::{
-        EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams, SaveResult,
+        CancelResult, EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams,
+        SaveResult,
     };
     use catapulte_domain::port::event_publisher::EventPublisher;
 
@@ -368,6 +542,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -718,6 +898,7 @@ mod tests {
                     backend: "s3".to_owned(),
                     key: "uploads/invoice.pdf".to_owned(),
                 },
+                content_id: None,
             },
             AttachmentRef {
                 filename: "photo.png".to_owned(),
@@ -727,6 +908,7 @@ mod tests {
                     backend: "gcs".to_owned(),
                     key: "media/photo.png".to_owned(),
                 },
+                content_id: None,
             },
         ];
         adapter.set_attachments(id, &attachments).await.unwrap();
@@ -786,6 +968,7 @@ mod tests {
                 backend: "s3".to_owned(),
                 key: "docs/doc.pdf".to_owned(),
             },
+            content_id: None,
         }];
         adapter.set_attachments(id, &attachments).await.unwrap();
 
@@ -840,6 +1023,72 @@ mod tests {
         adapter.delete(id).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn cancel_marks_email_cancelled_and_drops_queue_entry() {
+        use catapulte_domain::port::email_queue::EmailQueue;
+
+        let (adapter, _container) = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        adapter.enqueue(id, &sample_envelope()).await.unwrap();
+
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::Cancelled { .. }));
+
+        let queued: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM email_queue")
+            .fetch_one(adapter.pool())
+            .await
+            .unwrap();
+        assert_eq!(queued, 0);
+
+        let cancelled = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Cancelled),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(cancelled.len(), 1);
+        assert!(!adapter.begin_delivery(id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cancel_ignores_a_stale_delivery_mark() {
+        let (adapter, _container) = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+
+        // The worker died between begin_delivery and end_delivery and its
+        // lease ran out: the email is back in the queue.
+        assert!(adapter.begin_delivery(id).await.unwrap());
+        sqlx::query(
+            "UPDATE emails SET delivery_started_at_ms = delivery_started_at_ms - $1 WHERE id = $2",
+        )
+        .bind(crate::email_queue::PROCESSING_TIMEOUT_MS + 1)
+        .bind(id.as_uuid())
+        .execute(adapter.pool())
+        .await
+        .unwrap();
+
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::Cancelled { .. }));
+    }
+
+    #[tokio::test]
+    async fn cancel_is_rejected_while_delivery_is_in_flight() {
+        let (adapter, _container) = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+
+        assert!(adapter.begin_delivery(id).await.unwrap());
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::NotCancellable));
+
+        adapter.end_delivery(id).await.unwrap();
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::Cancelled { .. }));
+    }
+
     #[tokio::test]
     async fn list_emails_template_filter_matches_only_named_mjml() {
         let (adapter, _container) = fresh_adapter().await;
@@ -897,6 +1146,7 @@ mod tests {
                             backend: "fs".into(),
                             key: "key-a".into(),
                         },
+                        content_id: None,
                     },
                     AttachmentRef {
                         filename: "b.pdf".into(),
@@ -906,6 +1156,7 @@ mod tests {
                             backend: "fs".into(),
                             key: "key-b".into(),
                         },
+                        content_id: None,
                     },
                 ],
             )
@@ -926,6 +1177,7 @@ mod tests {
                         backend: "fs".into(),
                         key: "key-sent".into(),
                     },
+                    content_id: None,
                 }],
             )
             .await
@@ -953,6 +1205,7 @@ mod tests {
                         backend: "fs".into(),
                         key: "key-failed".into(),
                     },
+                    content_id: None,
                 }],
             )
             .await
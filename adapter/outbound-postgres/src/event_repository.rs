@@ -2,37 +2,51 @@ use anyhow::Context;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::sender::SenderName;
 use catapulte_domain::port::event_repository::{
-    EventRecord, EventRepository, EventRepositoryError, ListEventsParams,
+    EventFilter, EventRecord, EventRepository, EventRepositoryError, ListEventsAfterParams,
+    ListEventsParams,
 };
 use sqlx::QueryBuilder;
 use sqlx::Row;
 
 use crate::PostgresAdapter;
 
+const SELECT_EVENTS: &str = "SELECT id, email_id, event_type, payload, sender_name, error_class, \
+     created_at FROM lifecycle_events WHERE 1=1";
+
+fn push_filter(qb: &mut QueryBuilder<sqlx::Postgres>, filter: &EventFilter) {
+    if let Some(email_id) = filter.email_id {
+        qb.push(" AND email_id = ");
+        qb.push_bind(email_id.as_uuid());
+    }
+    if let Some(event_type) = filter.event_type.as_deref() {
+        qb.push(" AND event_type = ");
+        qb.push_bind(event_type.to_owned());
+    }
+    if let Some(sender_name) = filter.sender_name.as_deref() {
+        qb.push(" AND sender_name = ");
+        qb.push_bind(sender_name.to_owned());
+    }
+    if let Some(error_class) = filter.error_class.as_ref() {
+        qb.push(" AND error_class = ");
+        qb.push_bind(error_class.as_str().to_owned());
+    }
+}
+
 impl EventRepository for PostgresAdapter {
     async fn list_events(
         &self,
         params: ListEventsParams,
     ) -> Result<Vec<EventRecord>, EventRepositoryError> {
-        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(
-            "SELECT id, email_id, event_type, payload, sender_name, error_class, created_at FROM lifecycle_events WHERE 1=1",
+        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(SELECT_EVENTS);
+        push_filter(
+            &mut qb,
+            &EventFilter {
+                email_id: params.email_id,
+                event_type: params.event_type,
+                sender_name: params.sender_name,
+                error_class: params.error_class,
+            },
         );
-        if let Some(email_id) = params.email_id {
-            qb.push(" AND email_id = ");
-            qb.push_bind(email_id.as_uuid());
-        }
-        if let Some(event_type) = params.event_type.as_deref() {
-            qb.push(" AND event_type = ");
-            qb.push_bind(event_type.to_owned());
-        }
-        if let Some(sender_name) = params.sender_name.as_deref() {
-            qb.push(" AND sender_name = ");
-            qb.push_bind(sender_name.to_owned());
-        }
-        if let Some(error_class) = params.error_class.as_ref() {
-            qb.push(" AND error_class = ");
-            qb.push_bind(error_class.as_str().to_owned());
-        }
         if let Some(after) = params.after_ms {
             qb.push(" AND created_at > ");
             qb.push_bind(after);
@@ -58,6 +72,51 @@ impl EventRepository for PostgresAdapter {
             .collect::<anyhow::Result<Vec<_>>>()
             .map_err(|source| EventRepositoryError::Storage { source })
     }
+
+    async fn get_event(&self, id: uuid::Uuid) -> Result<Option<EventRecord>, EventRepositoryError> {
+        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(SELECT_EVENTS);
+        qb.push(" AND id = ");
+        qb.push_bind(id);
+        let row = qb
+            .build()
+            .fetch_optional(self.pool())
+            .await
+            .context("reading lifecycle event")
+            .map_err(|source| EventRepositoryError::Storage { source })?;
+        row.as_ref()
+            .map(PostgresAdapter::row_to_event_record)
+            .transpose()
+            .map_err(|source| EventRepositoryError::Storage { source })
+    }
+
+    async fn list_events_after(
+        &self,
+        params: ListEventsAfterParams,
+    ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+        let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new(SELECT_EVENTS);
+        push_filter(&mut qb, &params.filter);
+        if let Some(after) = params.after {
+            qb.push(" AND (created_at, id) > (");
+            qb.push_bind(after.created_at_ms);
+            qb.push(", ");
+            qb.push_bind(after.id);
+            qb.push(")");
+        }
+        qb.push(" ORDER BY created_at, id LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing lifecycle events after a cursor")
+            .map_err(|source| EventRepositoryError::Storage { source })?;
+
+        rows.iter()
+            .map(PostgresAdapter::row_to_event_record)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|source| EventRepositoryError::Storage { source })
+    }
 }
 
 impl PostgresAdapter {
@@ -90,6 +149,7 @@ mod tests {
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::email_repository::EmailRepository;
     use catapulte_domain::port::event_publisher::EventPublisher;
@@ -152,6 +212,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
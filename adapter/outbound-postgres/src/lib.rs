@@ -5,6 +5,10 @@ pub mod event_publisher;
 pub mod event_repository;
 mod health;
 pub mod sender_usage;
+pub mod suppression_list;
+pub mod template_store;
+pub mod webhook_outbox;
+pub mod webhook_subscription_store;
 
 use anyhow::Context;
 use sqlx::PgPool;
@@ -13,6 +17,8 @@ use sqlx::postgres::PgPoolOptions;
 #[derive(Clone, Debug)]
 pub struct PostgresAdapter {
     pool: PgPool,
+    webhook_outbox: bool,
+    nats_outbox: bool,
 }
 
 impl PostgresAdapter {
@@ -30,7 +36,11 @@ impl PostgresAdapter {
             .connect(url)
             .await
             .context("failed to open postgres pool")?;
-        Ok(Self { pool })
+        Ok(Self {
+            pool,
+            webhook_outbox: false,
+            nats_outbox: false,
+        })
     }
 
     /// # Errors
@@ -44,6 +54,22 @@ impl PostgresAdapter {
         Ok(())
     }
 
+    /// Queues every published event in the webhook outbox, in the same
+    /// transaction as the event.
+    #[must_use]
+    pub fn with_webhook_outbox(mut self) -> Self {
+        self.webhook_outbox = true;
+        self
+    }
+
+    /// Queues every published event for the NATS `JetStream` stream in the
+    /// outbox, in the same transaction as the event.
+    #[must_use]
+    pub fn with_nats_outbox(mut self) -> Self {
+        self.nats_outbox = true;
+        self
+    }
+
     pub(crate) fn pool(&self) -> &PgPool {
         &self.pool
     }
@@ -0,0 +1,132 @@
+use anyhow::Context;
+use catapulte_domain::entity::unsubscribe::MailingList;
+use catapulte_domain::port::suppression_list::{
+    ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+};
+use sqlx::Row;
+
+use crate::PostgresAdapter;
+
+impl SuppressionList for PostgresAdapter {
+    async fn add(&self, address: &str, reason: &str) -> Result<(), SuppressionListError> {
+        sqlx::query(
+            "INSERT INTO suppressions (address, reason) VALUES ($1, $2) \
+             ON CONFLICT (address) DO NOTHING",
+        )
+        .bind(address)
+        .bind(reason)
+        .execute(self.pool())
+        .await
+        .context("inserting suppression")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(())
+    }
+
+    async fn remove(&self, address: &str) -> Result<bool, SuppressionListError> {
+        let result = sqlx::query("DELETE FROM suppressions WHERE address = $1")
+            .bind(address)
+            .execute(self.pool())
+            .await
+            .context("deleting suppression")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, SuppressionListError> {
+        let rows = sqlx::query(
+            "SELECT address, reason, created_at_ms FROM suppressions \
+             ORDER BY created_at_ms DESC, address LIMIT $1 OFFSET $2",
+        )
+        .bind(i64::from(params.limit))
+        .bind(i64::from(params.offset))
+        .fetch_all(self.pool())
+        .await
+        .context("listing suppressions")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| -> anyhow::Result<Suppression> {
+                Ok(Suppression {
+                    address: row.try_get("address").context("reading address")?,
+                    reason: row.try_get("reason").context("reading reason")?,
+                    created_at_ms: row
+                        .try_get("created_at_ms")
+                        .context("reading created_at_ms")?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+
+    async fn find_suppressed(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rows = sqlx::query("SELECT address FROM suppressions WHERE address = ANY($1)")
+            .bind(addresses)
+            .fetch_all(self.pool())
+            .await
+            .context("querying suppressions")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| row.try_get("address").context("reading address"))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+
+    async fn opt_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        address: &str,
+    ) -> Result<bool, SuppressionListError> {
+        let result = sqlx::query(
+            "INSERT INTO list_opt_outs (sender, list_name, address) VALUES ($1, $2, $3) \
+             ON CONFLICT (sender, list_name, address) DO NOTHING",
+        )
+        .bind(sender)
+        .bind(list.as_str())
+        .bind(address)
+        .execute(self.pool())
+        .await
+        .context("inserting list opt-out")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_opted_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let rows = sqlx::query(
+            "SELECT address FROM list_opt_outs \
+             WHERE sender = $1 AND list_name = $2 AND address = ANY($3)",
+        )
+        .bind(sender)
+        .bind(list.as_str())
+        .bind(addresses)
+        .fetch_all(self.pool())
+        .await
+        .context("querying list opt-outs")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| row.try_get("address").context("reading address"))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+}
@@ -0,0 +1,80 @@
+use anyhow::Context;
+use catapulte_domain::entity::template::Template;
+use catapulte_domain::port::template_store::{TemplateStore, TemplateStoreError};
+use sqlx::Row;
+use sqlx::postgres::PgRow;
+
+use crate::PostgresAdapter;
+
+fn row_to_template(name: &str, row: &PgRow) -> anyhow::Result<Template> {
+    let version: i64 = row.try_get("version").context("reading version")?;
+    Ok(Template {
+        name: name.to_owned(),
+        version: u32::try_from(version).context("template version out of range")?,
+        content: row.try_get("content").context("reading content")?,
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
+
+impl TemplateStore for PostgresAdapter {
+    async fn publish(&self, name: &str, content: &str) -> Result<Template, TemplateStoreError> {
+        // Deleted versions still count, so a version number is never reused.
+        // Concurrent publishes of one name race on the primary key; the loser
+        // fails and can be retried.
+        let row = sqlx::query(
+            "INSERT INTO templates (name, version, content) \
+             SELECT $1, COALESCE(MAX(version), 0) + 1, $2 FROM templates WHERE name = $1 \
+             RETURNING version, content, created_at_ms",
+        )
+        .bind(name)
+        .bind(content)
+        .fetch_one(self.pool())
+        .await
+        .context("inserting template version")
+        .map_err(|source| TemplateStoreError::Storage { source })?;
+        row_to_template(name, &row).map_err(|source| TemplateStoreError::Storage { source })
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<Template>, TemplateStoreError> {
+        let query = match version {
+            Some(version) => sqlx::query(
+                "SELECT version, content, created_at_ms FROM templates \
+                 WHERE name = $1 AND version = $2",
+            )
+            .bind(name)
+            .bind(i64::from(version)),
+            None => sqlx::query(
+                "SELECT version, content, created_at_ms FROM templates \
+                 WHERE name = $1 AND deleted_at_ms IS NULL ORDER BY version DESC LIMIT 1",
+            )
+            .bind(name),
+        };
+        let row = query
+            .fetch_optional(self.pool())
+            .await
+            .context("fetching template")
+            .map_err(|source| TemplateStoreError::Storage { source })?;
+        row.map(|row| row_to_template(name, &row))
+            .transpose()
+            .map_err(|source| TemplateStoreError::Storage { source })
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool, TemplateStoreError> {
+        let result = sqlx::query(
+            "UPDATE templates SET deleted_at_ms = (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT \
+             WHERE name = $1 AND deleted_at_ms IS NULL",
+        )
+        .bind(name)
+        .execute(self.pool())
+        .await
+        .context("deleting template")
+        .map_err(|source| TemplateStoreError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Context;
+use catapulte_domain::entity::email::EmailId;
+use catapulte_domain::port::webhook_outbox::{
+    DeliveryTarget, ListWebhookDeliveriesParams, WebhookDelivery, WebhookDeliveryStatus,
+    WebhookOutbox, WebhookOutboxError,
+};
+use sqlx::{Postgres, QueryBuilder, Row};
+
+use crate::PostgresAdapter;
+
+fn storage_error(source: anyhow::Error) -> WebhookOutboxError {
+    WebhookOutboxError::Storage { source }
+}
+
+impl WebhookOutbox for PostgresAdapter {
+    async fn claim_due(
+        &self,
+        now_ms: i64,
+        lease_until_ms: i64,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        // SKIP LOCKED lets several instances dispatch without claiming the
+        // same delivery twice.
+        let rows = sqlx::query(
+            "WITH due AS ( \
+                 SELECT id FROM webhook_outbox \
+                 WHERE status = 'pending' AND next_attempt_at_ms <= $1 \
+                 ORDER BY next_attempt_at_ms, id LIMIT $3 \
+                 FOR UPDATE SKIP LOCKED \
+             ), leased AS ( \
+                 UPDATE webhook_outbox o SET next_attempt_at_ms = $2 \
+                 FROM due WHERE o.id = due.id \
+                 RETURNING o.id, o.event_id, o.target, o.subscription_id, o.status, o.attempts, \
+                     o.next_attempt_at_ms, o.last_error, o.created_at_ms \
+             ) \
+             SELECT l.id, l.event_id, l.target, l.subscription_id, e.email_id, e.event_type, \
+                 e.sender_name, e.payload, \
+                 l.status, l.attempts, l.next_attempt_at_ms, l.last_error, l.created_at_ms \
+             FROM leased l JOIN lifecycle_events e ON e.id = l.event_id \
+             ORDER BY l.created_at_ms, l.id",
+        )
+        .bind(now_ms)
+        .bind(lease_until_ms)
+        .bind(i64::from(limit))
+        .fetch_all(self.pool())
+        .await
+        .context("claiming due webhook deliveries")
+        .map_err(storage_error)?;
+        rows.iter()
+            .map(row_to_delivery)
+            .collect::<anyhow::Result<_>>()
+            .map_err(storage_error)
+    }
+
+    async fn complete(&self, id: uuid::Uuid) -> Result<(), WebhookOutboxError> {
+        sqlx::query("DELETE FROM webhook_outbox WHERE id = $1")
+            .bind(id)
+            .execute(self.pool())
+            .await
+            .context("deleting delivered webhook")
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: uuid::Uuid,
+        error: &str,
+        next_attempt_at_ms: Option<i64>,
+    ) -> Result<(), WebhookOutboxError> {
+        let status = match next_attempt_at_ms {
+            Some(_) => WebhookDeliveryStatus::Pending,
+            None => WebhookDeliveryStatus::Failed,
+        };
+        sqlx::query(
+            "UPDATE webhook_outbox SET status = $1, attempts = attempts + 1, last_error = $2, \
+             next_attempt_at_ms = COALESCE($3, next_attempt_at_ms) WHERE id = $4",
+        )
+        .bind(status.as_str())
+        .bind(error)
+        .bind(next_attempt_at_ms)
+        .bind(id)
+        .execute(self.pool())
+        .await
+        .context("recording webhook failure")
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT o.id, o.event_id, o.target, o.subscription_id, e.email_id, e.event_type, \
+             e.sender_name, e.payload, o.status, o.attempts, o.next_attempt_at_ms, o.last_error, \
+             o.created_at_ms \
+             FROM webhook_outbox o JOIN lifecycle_events e ON e.id = o.event_id WHERE 1 = 1",
+        );
+        if let Some(status) = params.status {
+            qb.push(" AND o.status = ");
+            qb.push_bind(status.as_str());
+        }
+        if let Some(subscription_id) = params.subscription_id {
+            qb.push(" AND o.subscription_id = ");
+            qb.push_bind(subscription_id);
+        }
+        qb.push(" ORDER BY o.created_at_ms DESC, o.id DESC LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+        qb.push(" OFFSET ");
+        qb.push_bind(i64::from(params.offset));
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing webhook deliveries")
+            .map_err(storage_error)?;
+        rows.iter()
+            .map(row_to_delivery)
+            .collect::<anyhow::Result<_>>()
+            .map_err(storage_error)
+    }
+
+    async fn redeliver(&self, id: uuid::Uuid, now_ms: i64) -> Result<bool, WebhookOutboxError> {
+        let result = sqlx::query(
+            "UPDATE webhook_outbox SET status = 'pending', attempts = 0, next_attempt_at_ms = $1 \
+             WHERE id = $2",
+        )
+        .bind(now_ms)
+        .bind(id)
+        .execute(self.pool())
+        .await
+        .context("rescheduling webhook delivery")
+        .map_err(storage_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn row_to_delivery(row: &sqlx::postgres::PgRow) -> anyhow::Result<WebhookDelivery> {
+    let email_id: uuid::Uuid = row.try_get("email_id").context("reading email_id")?;
+    let payload: Option<sqlx::types::Json<serde_json::Value>> =
+        row.try_get("payload").context("reading payload")?;
+    let status: String = row.try_get("status").context("reading status")?;
+    let attempts: i32 = row.try_get("attempts").context("reading attempts")?;
+    Ok(WebhookDelivery {
+        id: row.try_get("id").context("reading id")?,
+        event_id: row.try_get("event_id").context("reading event_id")?,
+        subscription_id: row
+            .try_get("subscription_id")
+            .context("reading subscription_id")?,
+        target: parse_target(
+            &row.try_get::<String, _>("target")
+                .context("reading target")?,
+        )?,
+        email_id: EmailId::from(email_id),
+        event_type: row.try_get("event_type").context("reading event_type")?,
+        sender_name: row.try_get("sender_name").context("reading sender_name")?,
+        payload: payload.map_or(serde_json::Value::Null, |j| j.0),
+        status: match status.as_str() {
+            "failed" => WebhookDeliveryStatus::Failed,
+            _ => WebhookDeliveryStatus::Pending,
+        },
+        attempts: u32::try_from(attempts).context("reading attempts")?,
+        next_attempt_at_ms: row
+            .try_get("next_attempt_at_ms")
+            .context("reading next_attempt_at_ms")?,
+        last_error: row.try_get("last_error").context("reading last_error")?,
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
+
+fn parse_target(target: &str) -> anyhow::Result<DeliveryTarget> {
+    match target {
+        "webhook" => Ok(DeliveryTarget::Webhook),
+        "nats" => Ok(DeliveryTarget::Nats),
+        other => anyhow::bail!("unknown delivery target {other:?}"),
+    }
+}
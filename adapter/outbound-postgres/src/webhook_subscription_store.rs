@@ -0,0 +1,121 @@
+use anyhow::Context;
+use catapulte_domain::entity::webhook_subscription::{SubscriptionFilter, WebhookSubscription};
+use catapulte_domain::port::webhook_subscription_store::{
+    ListWebhookSubscriptionsParams, WebhookSubscriptionStore, WebhookSubscriptionStoreError,
+};
+use sqlx::{PgConnection, Postgres, QueryBuilder, Row};
+
+use crate::PostgresAdapter;
+
+const SELECT_SUBSCRIPTIONS: &str = "SELECT id, url, secret, event_types, correlation_id_prefix, \
+     created_at_ms FROM webhook_subscriptions";
+
+fn storage_error(source: anyhow::Error) -> WebhookSubscriptionStoreError {
+    WebhookSubscriptionStoreError::Storage { source }
+}
+
+impl WebhookSubscriptionStore for PostgresAdapter {
+    async fn insert(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), WebhookSubscriptionStoreError> {
+        sqlx::query(
+            "INSERT INTO webhook_subscriptions \
+             (id, url, secret, event_types, correlation_id_prefix, created_at_ms) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(subscription.id)
+        .bind(subscription.url.as_str())
+        .bind(subscription.secret.as_str())
+        .bind(sqlx::types::Json(&subscription.filter.event_types))
+        .bind(subscription.filter.correlation_id_prefix.as_deref())
+        .bind(subscription.created_at_ms)
+        .execute(self.pool())
+        .await
+        .context("inserting webhook subscription")
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_SUBSCRIPTIONS);
+        qb.push(" WHERE id = ");
+        qb.push_bind(id);
+        let row = qb
+            .build()
+            .fetch_optional(self.pool())
+            .await
+            .context("reading webhook subscription")
+            .map_err(storage_error)?;
+        row.as_ref()
+            .map(row_to_subscription)
+            .transpose()
+            .map_err(storage_error)
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> Result<Vec<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        let mut qb: QueryBuilder<Postgres> = QueryBuilder::new(SELECT_SUBSCRIPTIONS);
+        qb.push(" ORDER BY created_at_ms, id LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+        qb.push(" OFFSET ");
+        qb.push_bind(i64::from(params.offset));
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing webhook subscriptions")
+            .map_err(storage_error)?;
+        rows.iter()
+            .map(row_to_subscription)
+            .collect::<anyhow::Result<_>>()
+            .map_err(storage_error)
+    }
+
+    async fn delete(&self, id: uuid::Uuid) -> Result<bool, WebhookSubscriptionStoreError> {
+        // Pending deliveries go along through the ON DELETE CASCADE.
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(self.pool())
+            .await
+            .context("deleting webhook subscription")
+            .map_err(storage_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Every subscription, read within the transaction inserting an event.
+pub(crate) async fn all_subscriptions(
+    conn: &mut PgConnection,
+) -> anyhow::Result<Vec<WebhookSubscription>> {
+    let rows = sqlx::query(SELECT_SUBSCRIPTIONS)
+        .fetch_all(conn)
+        .await
+        .context("reading webhook subscriptions")?;
+    rows.iter().map(row_to_subscription).collect()
+}
+
+fn row_to_subscription(row: &sqlx::postgres::PgRow) -> anyhow::Result<WebhookSubscription> {
+    let url: String = row.try_get("url").context("reading url")?;
+    let event_types: sqlx::types::Json<Vec<String>> =
+        row.try_get("event_types").context("reading event_types")?;
+    Ok(WebhookSubscription {
+        id: row.try_get("id").context("reading id")?,
+        url: url::Url::parse(&url).context("parsing url")?,
+        secret: row.try_get("secret").context("reading secret")?,
+        filter: SubscriptionFilter {
+            event_types: event_types.0,
+            correlation_id_prefix: row
+                .try_get("correlation_id_prefix")
+                .context("reading correlation_id_prefix")?,
+        },
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
@@ -9,7 +9,8 @@ use catapulte_domain::port::email_queue::{
     AckToken, DequeuedEmail, EmailQueue, EmailQueueError, TraceCarrier,
 };
 
-type QueueItem = (EmailId, Envelope, u32, TraceCarrier);
+/// `(id, envelope, attempt, enqueued_at_ms, trace)`
+type QueueItem = (EmailId, Envelope, u32, i64, TraceCarrier);
 type InFlight = Arc<Mutex<HashMap<u64, QueueItem>>>;
 type RxGuard = Arc<tokio::sync::Mutex<tokio::sync::mpsc::UnboundedReceiver<QueueItem>>>;
 
@@ -42,6 +43,23 @@ impl MemoryQueue {
     }
 }
 
+/// Returns how long an envelope scheduled for `send_at_ms` must still wait, or
+/// `None` when it is due (or unscheduled).
+fn remaining_delay(send_at_ms: Option<i64>) -> Option<Duration> {
+    let remaining = u64::try_from(send_at_ms?.saturating_sub(now_ms())).ok()?;
+    (remaining > 0).then(|| Duration::from_millis(remaining))
+}
+
+fn now_ms() -> i64 {
+    i64::try_from(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis(),
+    )
+    .unwrap_or(i64::MAX)
+}
+
 impl Default for MemoryQueue {
     fn default() -> Self {
         Self::new()
@@ -52,12 +70,32 @@ impl EmailQueue for MemoryQueue {
     async fn enqueue(&self, id: EmailId, envelope: &Envelope) -> Result<(), EmailQueueError> {
         let pairs = catapulte_telemetry::propagation::inject_current();
         let trace = TraceCarrier::new(pairs);
+        let enqueued_at_ms = now_ms();
+        if let Some(delay) = remaining_delay(envelope.send_at_ms) {
+            // Scheduled for later: hold the item back the same way nack does.
+            // It only counts as ready once it reaches the channel.
+            let tx = self.tx.clone();
+            let ready_count = Arc::clone(&self.ready_count);
+            let envelope = envelope.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                ready_count.fetch_add(1, Ordering::Relaxed);
+                if tx.send((id, envelope, 1, enqueued_at_ms, trace)).is_err() {
+                    ready_count.fetch_sub(1, Ordering::Relaxed);
+                }
+            });
+            return Ok(());
+        }
         // Increment before the send: a concurrent dequeue can only observe the
         // item after it is sent, so the matching decrement can never run before
         // this increment (which would underflow the counter). Roll back if the
         // send fails.
         self.ready_count.fetch_add(1, Ordering::Relaxed);
-        if self.tx.send((id, envelope.clone(), 1, trace)).is_err() {
+        if self
+            .tx
+            .send((id, envelope.clone(), 1, enqueued_at_ms, trace))
+            .is_err()
+        {
             self.ready_count.fetch_sub(1, Ordering::Relaxed);
             return Err(EmailQueueError::Storage {
                 source: anyhow::anyhow!("memory queue channel closed"),
@@ -67,27 +105,28 @@ impl EmailQueue for MemoryQueue {
     }
 
     async fn dequeue(&self) -> Result<DequeuedEmail, EmailQueueError> {
-        let (id, envelope, attempt, trace) =
-            self.rx
-                .lock()
-                .await
-                .recv()
-                .await
-                .ok_or_else(|| EmailQueueError::Storage {
-                    source: anyhow::anyhow!("memory queue channel closed"),
-                })?;
+        let (id, envelope, attempt, enqueued_at_ms, trace) = self
+            .rx
+            .lock()
+            .await
+            .recv()
+            .await
+            .ok_or_else(|| EmailQueueError::Storage {
+                source: anyhow::anyhow!("memory queue channel closed"),
+            })?;
         self.ready_count.fetch_sub(1, Ordering::Relaxed);
 
         let token_id = self.next_token.fetch_add(1, Ordering::Relaxed);
         let token = AckToken::new(token_id.to_le_bytes().to_vec());
-        self.pending
-            .lock()
-            .unwrap()
-            .insert(token_id, (id, envelope.clone(), attempt, trace.clone()));
+        self.pending.lock().unwrap().insert(
+            token_id,
+            (id, envelope.clone(), attempt, enqueued_at_ms, trace.clone()),
+        );
         Ok(DequeuedEmail {
             id,
             envelope,
             attempt,
+            enqueued_at_ms,
             token,
             trace,
         })
@@ -108,14 +147,17 @@ impl EmailQueue for MemoryQueue {
         })?;
         let token_id = u64::from_le_bytes(bytes);
         let entry = self.pending.lock().unwrap().remove(&token_id);
-        if let Some((id, envelope, attempt, trace)) = entry {
+        if let Some((id, envelope, attempt, enqueued_at_ms, trace)) = entry {
             let tx = self.tx.clone();
             let ready_count = Arc::clone(&self.ready_count);
             tokio::spawn(async move {
                 tokio::time::sleep(delay).await;
                 // Increment before the re-send for the same reason as enqueue.
                 ready_count.fetch_add(1, Ordering::Relaxed);
-                if tx.send((id, envelope, attempt + 1, trace)).is_err() {
+                if tx
+                    .send((id, envelope, attempt + 1, enqueued_at_ms, trace))
+                    .is_err()
+                {
                     ready_count.fetch_sub(1, Ordering::Relaxed);
                 }
             });
@@ -129,6 +171,7 @@ mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::port::email_queue::EmailQueue;
 
     use super::MemoryQueue;
@@ -143,6 +186,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -203,6 +252,30 @@ mod tests {
         assert_eq!(dequeued2.attempt, 2);
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn scheduled_item_is_held_until_send_at() {
+        let queue = MemoryQueue::new();
+        let id = EmailId::default();
+        let now_ms = i64::try_from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_millis(),
+        )
+        .unwrap();
+        let mut envelope = sample_envelope();
+        envelope.send_at_ms = Some(now_ms + 60_000);
+
+        queue.enqueue(id, &envelope).await.unwrap();
+        assert_eq!(queue.pending(), 0);
+
+        tokio::time::advance(std::time::Duration::from_secs(61)).await;
+
+        let dequeued = queue.dequeue().await.unwrap();
+        assert_eq!(dequeued.id, id);
+        assert_eq!(dequeued.attempt, 1);
+    }
+
     #[tokio::test]
     async fn pending_reflects_channel_depth() {
         let queue = MemoryQueue::new();
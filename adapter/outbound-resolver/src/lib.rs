@@ -1 +1,2 @@
 pub mod resolver;
+pub mod watcher;
@@ -1,10 +1,19 @@
 use std::collections::{HashMap, HashSet};
 use std::env::VarError;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
-use catapulte_domain::entity::body::{BodySource, MjmlSource, ResolvedBody};
+use catapulte_domain::entity::body::{BodySource, MjmlSource, ResolvedBody, ResolvedTemplate};
+use catapulte_domain::entity::template::{
+    locale_fallbacks, localized_template_name, parse_template_ref, variant_locale,
+};
 use catapulte_domain::port::template_resolver::{ResolveError, TemplateResolver};
+use catapulte_domain::port::template_store::{NoopTemplateStore, TemplateStore};
+use catapulte_outbound_http_cache::{NotFound, RemoteCache};
+
+use crate::watcher::TemplateDirectory;
 
 pub struct ResolverAuthEntry {
     pub host: String,
@@ -12,11 +21,20 @@ pub struct ResolverAuthEntry {
     pub headers: Vec<(String, String)>,
 }
 
-pub struct TemplateResolverAdapter {
-    templates: HashMap<String, String>,
+/// Named templates are looked up in the template store first, then in the
+/// templates directory. A pinned `name@version` only comes from the store.
+///
+/// With a locale such as `fr-CA`, `welcome.fr-CA`, then `welcome.fr`, then
+/// `welcome` is used, whichever exists first. Remote templates work the same
+/// way on the file name: `welcome.fr-CA.mjml`, `welcome.fr.mjml`,
+/// `welcome.mjml`, moving on when a variant answers `404`.
+pub struct TemplateResolverAdapter<S = NoopTemplateStore> {
+    templates: TemplateDirectory,
+    store: S,
     allowed_domains: HashSet<String>,
     http_client: reqwest::Client,
     auth_headers: HashMap<String, reqwest::header::HeaderMap>,
+    remote_cache: Arc<RemoteCache>,
 }
 
 impl TemplateResolverAdapter {
@@ -81,12 +99,44 @@ impl TemplateResolverAdapter {
         }
 
         Ok(Self {
-            templates,
+            templates: TemplateDirectory::new(templates),
+            store: NoopTemplateStore,
             allowed_domains,
             http_client,
             auth_headers,
+            remote_cache: Arc::new(RemoteCache::disabled()),
         })
     }
+}
+
+impl<S: TemplateStore> TemplateResolverAdapter<S> {
+    /// Resolves named templates from `store` before the templates directory.
+    #[must_use]
+    pub fn with_template_store<S2: TemplateStore>(self, store: S2) -> TemplateResolverAdapter<S2> {
+        TemplateResolverAdapter {
+            templates: self.templates,
+            store,
+            allowed_domains: self.allowed_domains,
+            http_client: self.http_client,
+            auth_headers: self.auth_headers,
+            remote_cache: self.remote_cache,
+        }
+    }
+
+    /// Serves `mjml_remote` templates through `cache` instead of fetching
+    /// them for every email.
+    #[must_use]
+    pub fn with_remote_cache(mut self, cache: Arc<RemoteCache>) -> Self {
+        self.remote_cache = cache;
+        self
+    }
+
+    /// Handle on the templates directory set, for a
+    /// [`TemplateDirWatcher`](crate::watcher::TemplateDirWatcher) to reload.
+    #[must_use]
+    pub fn templates(&self) -> TemplateDirectory {
+        self.templates.clone()
+    }
 
     fn check_domain(&self, url: &url::Url) -> Result<(), ResolveError> {
         let host = url.host_str().unwrap_or("");
@@ -99,63 +149,155 @@ impl TemplateResolverAdapter {
         }
     }
 
-    async fn resolve_remote(&self, url: url::Url) -> Result<String, ResolveError> {
+    async fn fetch_remote(&self, url: url::Url) -> anyhow::Result<String> {
         let url_str = url.to_string();
         let host = url.host_str().map(str::to_owned);
         let mut request = self.http_client.get(url);
         if let Some(headers) = host.as_deref().and_then(|h| self.auth_headers.get(h)) {
             request = request.headers(headers.clone());
         }
-        let response = request
-            .send()
-            .await
-            .context("http request failed")
-            .map_err(|source| ResolveError::Fetch {
-                url: url_str.clone(),
-                source,
-            })?
-            .error_for_status()
-            .context("http error response")
-            .map_err(|source| ResolveError::Fetch {
-                url: url_str.clone(),
-                source,
-            })?;
-        response
-            .text()
-            .await
-            .context("reading response body")
-            .map_err(|source| ResolveError::Fetch {
+        self.remote_cache.fetch(&url_str, request).await
+    }
+
+    async fn resolve_remote(
+        &self,
+        url: url::Url,
+        locale: Option<&str>,
+    ) -> Result<(String, Option<String>), ResolveError> {
+        let variants = locale
+            .into_iter()
+            .flat_map(locale_fallbacks)
+            .filter_map(|locale| localized_url(&url, locale).map(|url| (url, locale)));
+        for (variant, locale) in variants {
+            match self.fetch_remote(variant.clone()).await {
+                Ok(content) => return Ok((content, Some(locale.to_owned()))),
+                Err(err) if is_not_found(&err) => {}
+                Err(source) => {
+                    return Err(ResolveError::Fetch {
+                        url: variant.to_string(),
+                        source,
+                    });
+                }
+            }
+        }
+        let url_str = url.to_string();
+        match self.fetch_remote(url).await {
+            Ok(content) => Ok((content, None)),
+            Err(source) => Err(ResolveError::Fetch {
                 url: url_str,
                 source,
+            }),
+        }
+    }
+
+    async fn get_stored(
+        &self,
+        reference: &str,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<String>, ResolveError> {
+        self.store
+            .get(name, version)
+            .await
+            .map(|stored| stored.map(|template| template.content))
+            .map_err(|err| ResolveError::Store {
+                name: reference.to_owned(),
+                source: anyhow::Error::new(err),
             })
     }
 
-    async fn resolve_mjml(&self, source: MjmlSource) -> Result<String, ResolveError> {
+    async fn resolve_named(
+        &self,
+        reference: String,
+        locale: Option<&str>,
+    ) -> Result<(String, Option<String>), ResolveError> {
+        let (name, version) = parse_template_ref(&reference);
+        if version.is_some() {
+            // Submissions are pinned to the variant picked at submit time.
+            let variant = locale.and_then(|locale| variant_locale(name, locale));
+            return match self.get_stored(&reference, name, version).await? {
+                Some(content) => Ok((content, variant.map(str::to_owned))),
+                None => Err(ResolveError::NotFound { name: reference }),
+            };
+        }
+        let candidates: Vec<(String, Option<&str>)> = locale
+            .into_iter()
+            .flat_map(locale_fallbacks)
+            .map(|locale| (localized_template_name(name, locale), Some(locale)))
+            .chain(std::iter::once((name.to_owned(), None)))
+            .collect();
+        for (candidate, locale) in &candidates {
+            if let Some(content) = self.get_stored(&reference, candidate, None).await? {
+                return Ok((content, locale.map(str::to_owned)));
+            }
+        }
+        candidates
+            .iter()
+            .find_map(|(candidate, locale)| {
+                self.templates
+                    .get(candidate)
+                    .map(|content| (content, locale.map(str::to_owned)))
+            })
+            .ok_or(ResolveError::NotFound { name: reference })
+    }
+
+    async fn resolve_mjml(
+        &self,
+        source: MjmlSource,
+        locale: Option<&str>,
+    ) -> Result<(String, Option<String>), ResolveError> {
         match source {
-            MjmlSource::Inline(s) => Ok(s),
-            MjmlSource::Named(name) => self
-                .templates
-                .get(&name)
-                .cloned()
-                .ok_or_else(|| ResolveError::NotFound { name }),
+            MjmlSource::Inline(s) => Ok((s, None)),
+            MjmlSource::Named(reference) => self.resolve_named(reference, locale).await,
             MjmlSource::Remote(url) => {
                 self.check_domain(&url)?;
-                self.resolve_remote(url).await
+                self.resolve_remote(url, locale).await
             }
         }
     }
 }
 
-impl TemplateResolver for TemplateResolverAdapter {
+impl<S: TemplateStore> TemplateResolver for TemplateResolverAdapter<S> {
     #[tracing::instrument(skip_all, name = "template.resolve")]
-    async fn resolve(&self, body: BodySource) -> Result<ResolvedBody, ResolveError> {
+    async fn resolve(
+        &self,
+        body: BodySource,
+        locale: Option<String>,
+    ) -> Result<ResolvedTemplate, ResolveError> {
         match body {
-            BodySource::Plain(plain) => Ok(ResolvedBody::Plain(plain)),
-            BodySource::Mjml(source) => self.resolve_mjml(source).await.map(ResolvedBody::Mjml),
+            BodySource::Plain(plain) => Ok(ResolvedBody::Plain(plain).into()),
+            BodySource::Mjml(source) => {
+                let (content, locale) = self.resolve_mjml(source, locale.as_deref()).await?;
+                Ok(ResolvedTemplate {
+                    body: ResolvedBody::Mjml(content),
+                    locale,
+                })
+            }
         }
     }
 }
 
+/// `url` with `locale` inserted before the file extension:
+/// `.../welcome.mjml` → `.../welcome.fr.mjml`. `None` when the path has no
+/// file name.
+fn localized_url(url: &url::Url, locale: &str) -> Option<url::Url> {
+    let (dir, file) = url.path().rsplit_once('/')?;
+    if file.is_empty() {
+        return None;
+    }
+    let localized = match file.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{stem}.{locale}.{extension}"),
+        _ => format!("{file}.{locale}"),
+    };
+    let mut localized_url = url.clone();
+    localized_url.set_path(&format!("{dir}/{localized}"));
+    Some(localized_url)
+}
+
+fn is_not_found(err: &anyhow::Error) -> bool {
+    err.is::<NotFound>()
+}
+
 fn load_template_entry(
     raw: std::io::Result<std::fs::DirEntry>,
 ) -> anyhow::Result<Option<(String, String)>> {
@@ -181,9 +323,14 @@ fn load_templates_from_dir(dir: &std::path::Path) -> anyhow::Result<HashMap<Stri
         .collect()
 }
 
+const DEFAULT_TEMPLATES_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
 pub struct TemplateResolverConfig {
     pub allowed_domains: HashSet<String>,
     pub templates_dir: Option<PathBuf>,
+    /// How often the templates directory is checked for edits; `None` loads
+    /// it once at startup.
+    pub templates_reload_interval: Option<Duration>,
     pub auth_entries: Vec<ResolverAuthEntry>,
 }
 
@@ -193,6 +340,8 @@ impl TemplateResolverConfig {
     /// Reads:
     /// - `{prefix}_ALLOWED_DOMAINS` — comma-separated hostnames allowed for remote fetches
     /// - `{prefix}_TEMPLATES_DIR` — directory of named `.mjml` templates
+    /// - `{prefix}_TEMPLATES_RELOAD_INTERVAL_MS` — how often the directory is checked for edits
+    ///   (default 5000); `0` disables reloading
     /// - `{prefix}_TOKENS` — comma-separated entry names (e.g. `github,gitlab`); absent/empty
     ///   means no auth entries
     /// - `{prefix}_TOKEN_<NAME>_HOST` — exact host for the named entry
@@ -214,6 +363,7 @@ impl TemplateResolverConfig {
     /// - a named entry has neither a bearer token nor any headers
     /// - a header listed in `_HEADERS` is missing its `_VALUE` variable
     /// - two header names in `_HEADERS` map to the same FRAGMENT (would silently shadow)
+    /// - `_TEMPLATES_RELOAD_INTERVAL_MS` is not a number
     pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
         Self::from_lookup(prefix, |key| std::env::var(key))
     }
@@ -237,6 +387,19 @@ impl TemplateResolverConfig {
             .ok()
             .map(PathBuf::from);
 
+        let reload_key = format!("{prefix}_TEMPLATES_RELOAD_INTERVAL_MS");
+        let templates_reload_interval = match lookup(&reload_key).ok() {
+            None => Some(DEFAULT_TEMPLATES_RELOAD_INTERVAL),
+            Some(raw) => match raw
+                .trim()
+                .parse::<u64>()
+                .with_context(|| format!("parsing {reload_key}={raw}"))?
+            {
+                0 => None,
+                ms => Some(Duration::from_millis(ms)),
+            },
+        };
+
         let auth_entries = lookup(&format!("{prefix}_TOKENS"))
             .ok()
             .map(|v| {
@@ -314,6 +477,7 @@ impl TemplateResolverConfig {
         Ok(Self {
             allowed_domains,
             templates_dir,
+            templates_reload_interval,
             auth_entries,
         })
     }
@@ -338,11 +502,15 @@ mod tests {
     use std::collections::{HashMap, HashSet};
     use std::env::VarError;
     use std::path::PathBuf;
+    use std::sync::Arc;
 
     use catapulte_domain::entity::body::{BodySource, MjmlSource, Plain, ResolvedBody};
+    use catapulte_domain::entity::template::Template;
     use catapulte_domain::port::template_resolver::{ResolveError, TemplateResolver};
+    use catapulte_domain::port::template_store::{TemplateStore, TemplateStoreError};
 
     use super::{ResolverAuthEntry, TemplateResolverAdapter, TemplateResolverConfig};
+    use catapulte_outbound_http_cache::RemoteCacheConfig;
 
     fn make_lookup(
         vars: HashMap<&'static str, &'static str>,
@@ -361,7 +529,7 @@ mod tests {
         let plain = Plain::try_new(Some("hello".to_owned()), None).unwrap();
         let body = BodySource::Plain(plain);
 
-        let result = adapter.resolve(body).await.unwrap();
+        let result = adapter.resolve(body, None).await.unwrap().body;
 
         match result {
             ResolvedBody::Plain(p) => {
@@ -378,7 +546,7 @@ mod tests {
             TemplateResolverAdapter::new(HashMap::new(), HashSet::new(), Vec::new()).unwrap();
         let body = BodySource::Mjml(MjmlSource::Inline("source".to_owned()));
 
-        let result = adapter.resolve(body).await.unwrap();
+        let result = adapter.resolve(body, None).await.unwrap().body;
 
         match result {
             ResolvedBody::Mjml(s) => assert_eq!(s, "source"),
@@ -393,7 +561,7 @@ mod tests {
         let adapter = TemplateResolverAdapter::new(templates, HashSet::new(), Vec::new()).unwrap();
         let body = BodySource::Mjml(MjmlSource::Named("welcome".to_owned()));
 
-        let result = adapter.resolve(body).await.unwrap();
+        let result = adapter.resolve(body, None).await.unwrap().body;
 
         match result {
             ResolvedBody::Mjml(s) => assert_eq!(s, "<mjml/>"),
@@ -407,7 +575,7 @@ mod tests {
             TemplateResolverAdapter::new(HashMap::new(), HashSet::new(), Vec::new()).unwrap();
         let body = BodySource::Mjml(MjmlSource::Named("missing".to_owned()));
 
-        let result = adapter.resolve(body).await;
+        let result = adapter.resolve(body, None).await;
 
         assert!(matches!(
             result,
@@ -422,7 +590,7 @@ mod tests {
         let url = url::Url::parse("https://example.com/template.mjml").unwrap();
         let body = BodySource::Mjml(MjmlSource::Remote(url));
 
-        let result = adapter.resolve(body).await;
+        let result = adapter.resolve(body, None).await;
 
         assert!(matches!(result, Err(ResolveError::DomainNotAllowed { .. })));
     }
@@ -435,7 +603,7 @@ mod tests {
         let url = url::Url::parse("https://example.com/template.mjml").unwrap();
         let body = BodySource::Mjml(MjmlSource::Remote(url));
 
-        let result = adapter.resolve(body).await;
+        let result = adapter.resolve(body, None).await;
 
         assert!(
             !matches!(result, Err(ResolveError::DomainNotAllowed { .. })),
@@ -450,6 +618,10 @@ mod tests {
             TemplateResolverConfig::from_lookup("RESOLVER_TEST_EMPTY", make_lookup(vars)).unwrap();
         assert!(config.allowed_domains.is_empty());
         assert!(config.templates_dir.is_none());
+        assert_eq!(
+            config.templates_reload_interval,
+            Some(std::time::Duration::from_secs(5))
+        );
         assert!(config.auth_entries.is_empty());
     }
 
@@ -640,6 +812,7 @@ mod tests {
         let config = TemplateResolverConfig {
             allowed_domains: HashSet::new(),
             templates_dir: None,
+            templates_reload_interval: None,
             auth_entries: Vec::new(),
         };
         assert!(config.build().is_ok());
@@ -650,6 +823,7 @@ mod tests {
         let config = TemplateResolverConfig {
             allowed_domains: HashSet::new(),
             templates_dir: Some(PathBuf::from("/nonexistent/resolver/templates")),
+            templates_reload_interval: None,
             auth_entries: Vec::new(),
         };
         assert!(config.build().is_err());
@@ -663,12 +837,13 @@ mod tests {
         let config = TemplateResolverConfig {
             allowed_domains: HashSet::new(),
             templates_dir: Some(dir.path().to_owned()),
+            templates_reload_interval: None,
             auth_entries: Vec::new(),
         };
         let adapter = config.build().unwrap();
         let rt = tokio::runtime::Runtime::new().unwrap();
         let body = BodySource::Mjml(MjmlSource::Named("welcome".to_owned()));
-        let result = rt.block_on(adapter.resolve(body)).unwrap();
+        let result = rt.block_on(adapter.resolve(body, None)).unwrap().body;
         match result {
             ResolvedBody::Mjml(s) => assert_eq!(s, "<mjml/>"),
             ResolvedBody::Plain(_) => panic!("expected Mjml variant"),
@@ -720,7 +895,7 @@ mod tests {
 
         let url = url::Url::parse(&format!("http://{host}:{port}/template.mjml")).unwrap();
         let body = BodySource::Mjml(MjmlSource::Remote(url));
-        let result = adapter.resolve(body).await.unwrap();
+        let result = adapter.resolve(body, None).await.unwrap().body;
 
         match result {
             ResolvedBody::Mjml(s) => assert_eq!(s, "<mjml/>"),
@@ -757,11 +932,187 @@ mod tests {
 
         let url = url::Url::parse(&format!("http://{host}:{port}/template.mjml")).unwrap();
         let body = BodySource::Mjml(MjmlSource::Remote(url));
-        let result = adapter.resolve(body).await.unwrap();
+        let result = adapter.resolve(body, None).await.unwrap().body;
 
         match result {
             ResolvedBody::Mjml(s) => assert_eq!(s, "<mjml/>"),
             ResolvedBody::Plain(_) => panic!("expected Mjml variant"),
         }
     }
+
+    struct VersionedStore;
+
+    impl TemplateStore for VersionedStore {
+        async fn publish(
+            &self,
+            _name: &str,
+            _content: &str,
+        ) -> Result<Template, TemplateStoreError> {
+            unimplemented!()
+        }
+
+        async fn get(
+            &self,
+            name: &str,
+            version: Option<u32>,
+        ) -> Result<Option<Template>, TemplateStoreError> {
+            let version = version.unwrap_or(2);
+            Ok(
+                (name == "welcome" && (1..=2).contains(&version)).then(|| Template {
+                    name: name.to_owned(),
+                    version,
+                    content: format!("<mjml>v{version}</mjml>"),
+                    created_at_ms: 0,
+                }),
+            )
+        }
+
+        async fn delete(&self, _name: &str) -> Result<bool, TemplateStoreError> {
+            unimplemented!()
+        }
+    }
+
+    async fn resolve_named(
+        adapter: &TemplateResolverAdapter<VersionedStore>,
+        reference: &str,
+    ) -> Result<String, ResolveError> {
+        let body = BodySource::Mjml(MjmlSource::Named(reference.to_owned()));
+        match adapter.resolve(body, None).await?.body {
+            ResolvedBody::Mjml(s) => Ok(s),
+            ResolvedBody::Plain(_) => panic!("expected Mjml variant"),
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_named_prefers_the_template_store() {
+        let mut templates = HashMap::new();
+        templates.insert("welcome".to_owned(), "<mjml>dir</mjml>".to_owned());
+        templates.insert("legal".to_owned(), "<mjml>legal</mjml>".to_owned());
+        let adapter = TemplateResolverAdapter::new(templates, HashSet::new(), Vec::new())
+            .unwrap()
+            .with_template_store(VersionedStore);
+
+        assert_eq!(
+            resolve_named(&adapter, "welcome").await.unwrap(),
+            "<mjml>v2</mjml>"
+        );
+        assert_eq!(
+            resolve_named(&adapter, "welcome@1").await.unwrap(),
+            "<mjml>v1</mjml>"
+        );
+        assert_eq!(
+            resolve_named(&adapter, "legal").await.unwrap(),
+            "<mjml>legal</mjml>"
+        );
+        assert!(matches!(
+            resolve_named(&adapter, "welcome@7").await,
+            Err(ResolveError::NotFound { name }) if name == "welcome@7"
+        ));
+        assert!(matches!(
+            resolve_named(&adapter, "legal@1").await,
+            Err(ResolveError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolve_named_falls_back_to_less_specific_locales() {
+        let mut templates = HashMap::new();
+        templates.insert("welcome".to_owned(), "<mjml>en</mjml>".to_owned());
+        templates.insert("welcome.fr".to_owned(), "<mjml>fr</mjml>".to_owned());
+        let adapter = TemplateResolverAdapter::new(templates, HashSet::new(), Vec::new()).unwrap();
+
+        for (locale, content, chosen) in [
+            (Some("fr-CA"), "<mjml>fr</mjml>", Some("fr")),
+            (Some("de"), "<mjml>en</mjml>", None),
+            (None, "<mjml>en</mjml>", None),
+        ] {
+            let body = BodySource::Mjml(MjmlSource::Named("welcome".to_owned()));
+            let resolved = adapter
+                .resolve(body, locale.map(str::to_owned))
+                .await
+                .unwrap();
+            assert!(matches!(resolved.body, ResolvedBody::Mjml(s) if s == content));
+            assert_eq!(resolved.locale.as_deref(), chosen);
+        }
+    }
+
+    #[tokio::test]
+    async fn resolve_remote_tries_localized_file_names() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/t/welcome.fr-CA.mjml"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/t/welcome.fr.mjml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mjml>fr</mjml>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut allowed = HashSet::new();
+        allowed.insert(server.address().ip().to_string());
+        let adapter = TemplateResolverAdapter::new(HashMap::new(), allowed, Vec::new()).unwrap();
+        let url = url::Url::parse(&format!("{}/t/welcome.mjml", server.uri())).unwrap();
+        let body = BodySource::Mjml(MjmlSource::Remote(url));
+
+        let resolved = adapter.resolve(body, Some("fr-CA".into())).await.unwrap();
+
+        assert!(matches!(resolved.body, ResolvedBody::Mjml(s) if s == "<mjml>fr</mjml>"));
+        assert_eq!(resolved.locale.as_deref(), Some("fr"));
+    }
+
+    #[tokio::test]
+    async fn resolve_remote_remembers_missing_localized_file_names() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/t/welcome.fr.mjml"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/t/welcome.mjml"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<mjml/>"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut allowed = HashSet::new();
+        allowed.insert(server.address().ip().to_string());
+        let adapter = TemplateResolverAdapter::new(HashMap::new(), allowed, Vec::new())
+            .unwrap()
+            .with_remote_cache(Arc::new(RemoteCacheConfig::default().build()));
+        let url = url::Url::parse(&format!("{}/t/welcome.mjml", server.uri())).unwrap();
+
+        for _ in 0..2 {
+            let body = BodySource::Mjml(MjmlSource::Remote(url.clone()));
+            let resolved = adapter.resolve(body, Some("fr".into())).await.unwrap();
+            assert_eq!(resolved.locale, None);
+        }
+    }
+
+    #[test]
+    fn localized_url_inserts_the_locale_before_the_extension() {
+        let localized = |raw: &str| {
+            super::localized_url(&url::Url::parse(raw).unwrap(), "fr").map(String::from)
+        };
+        assert_eq!(
+            localized("https://cdn.test/t/welcome.mjml?ref=main").as_deref(),
+            Some("https://cdn.test/t/welcome.fr.mjml?ref=main")
+        );
+        assert_eq!(
+            localized("https://cdn.test/welcome").as_deref(),
+            Some("https://cdn.test/welcome.fr")
+        );
+        assert_eq!(localized("https://cdn.test/t/"), None);
+    }
 }
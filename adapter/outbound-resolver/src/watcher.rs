@@ -0,0 +1,471 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
+use catapulte_domain::entity::body::ResolvedBody;
+use catapulte_domain::port::template_interpolator::TemplateInterpolator;
+use catapulte_domain::port::template_renderer::TemplateRenderer;
+use tokio_util::sync::CancellationToken;
+
+/// Named templates loaded from the templates directory. Clones share the same
+/// set, and a reload replaces it as a whole so a lookup never sees half of a
+/// change.
+#[derive(Clone, Default)]
+pub struct TemplateDirectory {
+    current: Arc<RwLock<Arc<HashMap<String, String>>>>,
+}
+
+impl TemplateDirectory {
+    #[must_use]
+    pub fn new(templates: HashMap<String, String>) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(templates))),
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.snapshot().get(name).cloned()
+    }
+
+    fn snapshot(&self) -> Arc<HashMap<String, String>> {
+        Arc::clone(&self.current.read().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn replace(&self, templates: HashMap<String, String>) {
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(templates);
+    }
+}
+
+/// Modification time and size of a file, compared between polls.
+type Stamp = (Option<SystemTime>, u64);
+
+#[derive(Default, PartialEq)]
+struct Scan {
+    /// Template name → path and stamp of its `.mjml` file.
+    templates: HashMap<String, (PathBuf, Stamp)>,
+    /// Every file under the `mj-include` root.
+    partials: HashMap<PathBuf, Stamp>,
+}
+
+/// Name of the template stored at `path`: its file stem when it is a `.mjml`
+/// file.
+fn template_name(path: &Path) -> Option<&str> {
+    if path.extension().and_then(|e| e.to_str()) != Some("mjml") {
+        return None;
+    }
+    path.file_stem().and_then(|s| s.to_str())
+}
+
+fn stamp(metadata: &std::fs::Metadata) -> Stamp {
+    (metadata.modified().ok(), metadata.len())
+}
+
+fn scan_templates(dir: &Path) -> anyhow::Result<HashMap<String, (PathBuf, Stamp)>> {
+    let mut templates = HashMap::new();
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("reading templates directory {}", dir.display()))?
+    {
+        let path = entry.context("reading directory entry")?.path();
+        let Some(name) = template_name(&path).map(str::to_owned) else {
+            continue;
+        };
+        let metadata =
+            std::fs::metadata(&path).with_context(|| format!("reading {}", path.display()))?;
+        templates.insert(name, (path, stamp(&metadata)));
+    }
+    Ok(templates)
+}
+
+fn scan_partials(root: &Path, out: &mut HashMap<PathBuf, Stamp>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(root)
+        .with_context(|| format!("reading include directory {}", root.display()))?
+    {
+        let path = entry.context("reading directory entry")?.path();
+        let metadata =
+            std::fs::metadata(&path).with_context(|| format!("reading {}", path.display()))?;
+        if metadata.is_dir() {
+            scan_partials(&path, out)?;
+        } else {
+            out.insert(path, stamp(&metadata));
+        }
+    }
+    Ok(())
+}
+
+fn scan(templates_dir: &Path, include_root: Option<&Path>) -> anyhow::Result<Scan> {
+    let templates = scan_templates(templates_dir)?;
+    let mut partials = HashMap::new();
+    if let Some(root) = include_root {
+        scan_partials(root, &mut partials)?;
+    }
+    Ok(Scan {
+        templates,
+        partials,
+    })
+}
+
+/// Polls the templates directory, and the `mj-include` root when set, and
+/// swaps edited templates into a [`TemplateDirectory`].
+///
+/// A changed file is rendered with no variables before it is accepted; one
+/// that fails is logged and the previous version stays live. A change under
+/// the include root re-checks every template, but partials are read at render
+/// time, so a broken partial can only be reported, not held back.
+pub struct TemplateDirWatcher<I, R> {
+    templates_dir: PathBuf,
+    include_root: Option<PathBuf>,
+    interval: Duration,
+    directory: TemplateDirectory,
+    interpolator: I,
+    renderer: R,
+}
+
+impl<I, R> TemplateDirWatcher<I, R>
+where
+    I: TemplateInterpolator,
+    R: TemplateRenderer,
+{
+    #[must_use]
+    pub fn new(
+        templates_dir: PathBuf,
+        directory: TemplateDirectory,
+        interpolator: I,
+        renderer: R,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            templates_dir,
+            include_root: None,
+            interval,
+            directory,
+            interpolator,
+            renderer,
+        }
+    }
+
+    /// Also watches `root`, the directory `mj-include` partials are read from.
+    #[must_use]
+    pub fn with_include_root(mut self, root: Option<PathBuf>) -> Self {
+        self.include_root = root;
+        self
+    }
+
+    pub async fn run(self, cancel: CancellationToken) {
+        // The first poll checks everything loaded at startup, so broken
+        // templates are reported early.
+        let mut seen = Scan::default();
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(self.interval) => {
+                    if let Err(e) = self.poll(&mut seen).await {
+                        tracing::warn!(error = %e, "template directory scan failed");
+                    }
+                }
+            }
+        }
+        tracing::info!("template watcher stopped");
+    }
+
+    async fn check(&self, content: &str) -> anyhow::Result<()> {
+        let interpolated = self
+            .interpolator
+            .interpolate(
+                ResolvedBody::Mjml(content.to_owned()),
+                &serde_json::Map::new(),
+            )
+            .context("interpolating template")?;
+        self.renderer
+            .render(interpolated)
+            .await
+            .context("rendering template")?;
+        Ok(())
+    }
+
+    /// Applies whatever changed since `seen`, then records the new state.
+    async fn poll(&self, seen: &mut Scan) -> anyhow::Result<()> {
+        let templates_dir = self.templates_dir.clone();
+        let include_root = self.include_root.clone();
+        let current =
+            tokio::task::spawn_blocking(move || scan(&templates_dir, include_root.as_deref()))
+                .await
+                .context("template scan task panicked")??;
+        if current == *seen {
+            return Ok(());
+        }
+
+        let recheck_all = current.partials != seen.partials;
+        let live = self.directory.snapshot();
+        let mut next = HashMap::with_capacity(current.templates.len());
+        for (name, (path, stamp)) in &current.templates {
+            let previous = live.get(name);
+            let unchanged = seen.templates.get(name).map(|(_, s)| s) == Some(stamp);
+            if unchanged && !recheck_all {
+                if let Some(content) = previous {
+                    next.insert(name.clone(), content.clone());
+                }
+                continue;
+            }
+            let content = match read(path).await {
+                Ok(content) => content,
+                Err(e) => {
+                    tracing::error!(template = %name, error = ?e, "failed to read template; keeping the previous version");
+                    if let Some(content) = previous {
+                        next.insert(name.clone(), content.clone());
+                    }
+                    continue;
+                }
+            };
+            match self.check(&content).await {
+                Ok(()) => {
+                    if previous != Some(&content) {
+                        tracing::info!(template = %name, "template reloaded");
+                    }
+                    next.insert(name.clone(), content);
+                }
+                Err(e) => match previous {
+                    Some(previous) => {
+                        tracing::error!(template = %name, error = ?e, "template does not render; keeping the previous version");
+                        next.insert(name.clone(), previous.clone());
+                    }
+                    None => {
+                        tracing::error!(template = %name, error = ?e, "template does not render; not loading it");
+                    }
+                },
+            }
+        }
+        for name in live
+            .keys()
+            .filter(|name| !current.templates.contains_key(*name))
+        {
+            tracing::info!(template = %name, "template removed");
+        }
+
+        self.directory.replace(next);
+        *seen = current;
+        Ok(())
+    }
+}
+
+async fn read(path: &Path) -> anyhow::Result<String> {
+    let path = path.to_owned();
+    tokio::task::spawn_blocking(move || {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("reading template {}", path.display()))
+    })
+    .await
+    .context("template read task panicked")?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{Duration, SystemTime};
+
+    use catapulte_domain::entity::body::{InterpolatedBody, Plain, RenderedBody, ResolvedBody};
+    use catapulte_domain::port::template_interpolator::{InterpolateError, TemplateInterpolator};
+    use catapulte_domain::port::template_renderer::{RenderError, TemplateRenderer};
+
+    use super::{Scan, TemplateDirWatcher, TemplateDirectory};
+
+    struct PassThrough;
+
+    impl TemplateInterpolator for PassThrough {
+        fn interpolate(
+            &self,
+            body: ResolvedBody,
+            _variables: &serde_json::Map<String, serde_json::Value>,
+        ) -> Result<InterpolatedBody, InterpolateError> {
+            match body {
+                ResolvedBody::Plain(plain) => Ok(InterpolatedBody::Plain(plain)),
+                ResolvedBody::Mjml(source) => Ok(InterpolatedBody::Mjml(source)),
+            }
+        }
+
+        fn interpolate_subject(
+            &self,
+            subject: &str,
+            _variables: &serde_json::Map<String, serde_json::Value>,
+        ) -> Result<String, InterpolateError> {
+            Ok(subject.to_owned())
+        }
+    }
+
+    /// Rejects any template containing `broken`, or including a partial that
+    /// does.
+    struct FakeRenderer {
+        include_root: std::path::PathBuf,
+    }
+
+    impl TemplateRenderer for FakeRenderer {
+        async fn render(&self, body: InterpolatedBody) -> Result<RenderedBody, RenderError> {
+            let InterpolatedBody::Mjml(source) = body else {
+                unreachable!()
+            };
+            let partial =
+                std::fs::read_to_string(self.include_root.join("header.mjml")).unwrap_or_default();
+            if source.contains("broken")
+                || (source.contains("mj-include") && partial.contains("broken"))
+            {
+                return Err(RenderError::Mjml {
+                    source: anyhow::anyhow!("broken"),
+                });
+            }
+            Ok(RenderedBody::new(
+                Plain::try_new(None, Some(source)).unwrap(),
+            ))
+        }
+    }
+
+    static TICK: AtomicU64 = AtomicU64::new(1);
+
+    /// Writes `content`, moving the modification time forward so the change
+    /// is seen even on filesystems with coarse timestamps.
+    fn write(path: &Path, content: &str) {
+        std::fs::write(path, content).unwrap();
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        let tick = TICK.fetch_add(1, Ordering::Relaxed);
+        file.set_modified(SystemTime::now() + Duration::from_secs(tick))
+            .unwrap();
+    }
+
+    struct Fixture {
+        templates: tempfile::TempDir,
+        partials: tempfile::TempDir,
+        directory: TemplateDirectory,
+        watcher: TemplateDirWatcher<PassThrough, FakeRenderer>,
+        seen: Scan,
+    }
+
+    impl Fixture {
+        fn new(initial: &[(&str, &str)]) -> Self {
+            let templates = tempfile::tempdir().unwrap();
+            let partials = tempfile::tempdir().unwrap();
+            let mut loaded = HashMap::new();
+            for (name, content) in initial {
+                write(&templates.path().join(format!("{name}.mjml")), content);
+                loaded.insert((*name).to_owned(), (*content).to_owned());
+            }
+            let directory = TemplateDirectory::new(loaded);
+            let watcher = TemplateDirWatcher::new(
+                templates.path().to_owned(),
+                directory.clone(),
+                PassThrough,
+                FakeRenderer {
+                    include_root: partials.path().to_owned(),
+                },
+                Duration::from_secs(1),
+            )
+            .with_include_root(Some(partials.path().to_owned()));
+            Self {
+                templates,
+                partials,
+                directory,
+                watcher,
+                seen: Scan::default(),
+            }
+        }
+
+        fn template(&self, name: &str) -> std::path::PathBuf {
+            self.templates.path().join(format!("{name}.mjml"))
+        }
+
+        async fn poll(&mut self) {
+            self.watcher.poll(&mut self.seen).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn edited_and_new_templates_are_swapped_in() {
+        let mut fixture = Fixture::new(&[("welcome", "<mjml>v1</mjml>")]);
+        fixture.poll().await;
+
+        write(&fixture.template("welcome"), "<mjml>v2</mjml>");
+        write(&fixture.template("receipt"), "<mjml>receipt</mjml>");
+        fixture.poll().await;
+
+        assert_eq!(
+            fixture.directory.get("welcome").as_deref(),
+            Some("<mjml>v2</mjml>")
+        );
+        assert_eq!(
+            fixture.directory.get("receipt").as_deref(),
+            Some("<mjml>receipt</mjml>")
+        );
+    }
+
+    #[tokio::test]
+    async fn broken_templates_keep_the_previous_version_live() {
+        let mut fixture = Fixture::new(&[("welcome", "<mjml>v1</mjml>")]);
+        fixture.poll().await;
+
+        write(&fixture.template("welcome"), "<mjml>broken</mjml>");
+        write(&fixture.template("receipt"), "<mjml>broken</mjml>");
+        fixture.poll().await;
+
+        assert_eq!(
+            fixture.directory.get("welcome").as_deref(),
+            Some("<mjml>v1</mjml>")
+        );
+        assert!(fixture.directory.get("receipt").is_none());
+
+        write(&fixture.template("welcome"), "<mjml>fixed</mjml>");
+        fixture.poll().await;
+        assert_eq!(
+            fixture.directory.get("welcome").as_deref(),
+            Some("<mjml>fixed</mjml>")
+        );
+    }
+
+    #[tokio::test]
+    async fn deleted_templates_are_dropped() {
+        let mut fixture = Fixture::new(&[("welcome", "<mjml>v1</mjml>"), ("legal", "<mjml/>")]);
+        fixture.poll().await;
+
+        std::fs::remove_file(fixture.template("legal")).unwrap();
+        fixture.poll().await;
+
+        assert!(fixture.directory.get("legal").is_none());
+        assert!(fixture.directory.get("welcome").is_some());
+    }
+
+    #[tokio::test]
+    async fn partial_changes_recheck_templates_without_dropping_them() {
+        let mut fixture =
+            Fixture::new(&[("welcome", "<mjml><mj-include path=\"header.mjml\"/></mjml>")]);
+        write(
+            &fixture.partials.path().join("header.mjml"),
+            "<mj-text>hi</mj-text>",
+        );
+        fixture.poll().await;
+
+        write(
+            &fixture.partials.path().join("header.mjml"),
+            "<mj-text>broken</mj-text>",
+        );
+        fixture.poll().await;
+
+        assert!(fixture.directory.get("welcome").is_some());
+    }
+
+    #[tokio::test]
+    async fn unreadable_directory_is_an_error_and_keeps_templates() {
+        let mut fixture = Fixture::new(&[("welcome", "<mjml>v1</mjml>")]);
+        fixture.poll().await;
+        let path = fixture.templates.path().to_owned();
+        drop(std::mem::replace(
+            &mut fixture.templates,
+            tempfile::tempdir().unwrap(),
+        ));
+        assert!(!path.exists());
+
+        assert!(fixture.watcher.poll(&mut fixture.seen).await.is_err());
+        assert!(fixture.directory.get("welcome").is_some());
+    }
+}
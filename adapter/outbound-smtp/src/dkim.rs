@@ -0,0 +1,548 @@
+use std::collections::HashMap;
+use std::env::VarError;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use anyhow::Context;
+use lettre::message::dkim::{
+    DkimCanonicalization, DkimCanonicalizationType, DkimSigningAlgorithm, DkimSigningKey,
+};
+use lettre::message::header::HeaderName;
+
+const DEFAULT_HEADERS: [&str; 11] = [
+    "From",
+    "To",
+    "Cc",
+    "Subject",
+    "Date",
+    "Message-ID",
+    "Reply-To",
+    "MIME-Version",
+    "Content-Type",
+    // RFC 8058 one-click unsubscribe only counts when both are signed.
+    "List-Unsubscribe",
+    "List-Unsubscribe-Post",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DkimAlgorithm {
+    Rsa,
+    Ed25519,
+}
+
+impl DkimAlgorithm {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Rsa => "rsa",
+            Self::Ed25519 => "ed25519",
+        }
+    }
+}
+
+fn parse_algorithm(val: &str, key: &str) -> anyhow::Result<DkimAlgorithm> {
+    match val {
+        "rsa" => Ok(DkimAlgorithm::Rsa),
+        "ed25519" => Ok(DkimAlgorithm::Ed25519),
+        other => anyhow::bail!("unknown value for env var {key}: {other}"),
+    }
+}
+
+/// A private key and the DNS selector its public half is published under.
+#[derive(Debug, Clone)]
+pub struct DkimKey {
+    pub selector: String,
+    pub algorithm: DkimAlgorithm,
+    /// PKCS#1 PEM for RSA, base64 of the 32-byte secret key for Ed25519.
+    pub private_key: String,
+}
+
+impl DkimKey {
+    fn signing_key(&self) -> anyhow::Result<DkimSigningKey> {
+        let algorithm = match self.algorithm {
+            DkimAlgorithm::Rsa => DkimSigningAlgorithm::Rsa,
+            DkimAlgorithm::Ed25519 => DkimSigningAlgorithm::Ed25519,
+        };
+        DkimSigningKey::new(self.private_key.trim(), algorithm).with_context(|| {
+            format!(
+                "invalid {} DKIM key for selector {:?}",
+                self.algorithm.as_str(),
+                self.selector
+            )
+        })
+    }
+}
+
+/// DKIM settings of one sender.
+///
+/// Environment variables, for a sender `NAME`:
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_SELECTOR`: selector of the sender-wide key
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_PRIVATE_KEY` or
+///   `CATAPULTE_SENDER_{NAME}_DKIM_PRIVATE_KEY_FILE`: the sender-wide key
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_ALGORITHM` (optional: "rsa" or "ed25519",
+///   default "rsa")
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_DOMAIN` (optional, default the `From`
+///   domain): signing domain of the sender-wide key
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_KEY_DIR` (optional): per-domain keys, one
+///   `{domain}/{selector}.{rsa|ed25519}` file each
+/// - `CATAPULTE_SENDER_{NAME}_DKIM_HEADERS` (optional): comma-separated header
+///   fields covered by the signature
+#[derive(Debug, Clone)]
+pub struct DkimConfig {
+    /// Signs messages whose `From` domain has no key in `key_dir`.
+    pub key: Option<DkimKey>,
+    /// Signing domain (`d=`) for `key`; the `From` domain when unset.
+    pub domain: Option<String>,
+    pub key_dir: Option<PathBuf>,
+    pub headers: Vec<String>,
+}
+
+impl DkimConfig {
+    /// Reads `{prefix}_DKIM_*`. Returns `None` when neither a key nor a key
+    /// directory is configured.
+    pub(crate) fn from_lookup<F>(prefix: &str, lookup: &F) -> anyhow::Result<Option<Self>>
+    where
+        F: Fn(&str) -> Result<String, VarError>,
+    {
+        let var = |name: &str| {
+            lookup(&format!("{prefix}_DKIM_{name}"))
+                .ok()
+                .map(|v| v.trim().to_owned())
+                .filter(|v| !v.is_empty())
+        };
+
+        let key_file_key = format!("{prefix}_DKIM_PRIVATE_KEY_FILE");
+        let private_key = match (var("PRIVATE_KEY"), var("PRIVATE_KEY_FILE")) {
+            (Some(_), Some(_)) => anyhow::bail!(
+                "env vars {prefix}_DKIM_PRIVATE_KEY and {key_file_key} are mutually exclusive"
+            ),
+            (Some(key), None) => Some(key),
+            (None, Some(path)) => Some(
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading {key_file_key} {path:?}"))?,
+            ),
+            (None, None) => None,
+        };
+        let selector_key = format!("{prefix}_DKIM_SELECTOR");
+        let key = match (var("SELECTOR"), private_key) {
+            (Some(selector), Some(private_key)) => {
+                let algorithm = match var("ALGORITHM") {
+                    Some(val) => parse_algorithm(&val, &format!("{prefix}_DKIM_ALGORITHM"))?,
+                    None => DkimAlgorithm::Rsa,
+                };
+                Some(DkimKey {
+                    selector,
+                    algorithm,
+                    private_key,
+                })
+            }
+            (Some(_), None) => {
+                anyhow::bail!("env var {selector_key} is set but no DKIM private key is")
+            }
+            (None, Some(_)) => {
+                anyhow::bail!("a DKIM private key is set but env var {selector_key} is missing")
+            }
+            (None, None) => None,
+        };
+        let key_dir = var("KEY_DIR").map(PathBuf::from);
+
+        if key.is_none() && key_dir.is_none() {
+            return Ok(None);
+        }
+
+        let headers = var("HEADERS").map_or_else(
+            || DEFAULT_HEADERS.iter().map(|&h| h.to_owned()).collect(),
+            |v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|h| !h.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            },
+        );
+
+        Ok(Some(Self {
+            key,
+            domain: var("DOMAIN"),
+            key_dir,
+            headers,
+        }))
+    }
+
+    /// # Errors
+    ///
+    /// Returns an error if the sender-wide key or a header name is invalid, or
+    /// if the key directory is not a directory.
+    pub fn build(self) -> anyhow::Result<DkimSigner> {
+        if let Some(key) = &self.key {
+            key.signing_key()?;
+        }
+        if let Some(dir) = &self.key_dir {
+            anyhow::ensure!(
+                dir.is_dir(),
+                "DKIM key directory {} is not a directory",
+                dir.display()
+            );
+        }
+        let headers = self
+            .headers
+            .into_iter()
+            .map(|name| {
+                HeaderName::new_from_ascii(name.clone())
+                    .map_err(|_| anyhow::anyhow!("invalid DKIM header name {name:?}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(
+            headers.iter().any(|h| h.eq_ignore_ascii_case("From")),
+            "DKIM signed headers must include From"
+        );
+        Ok(DkimSigner {
+            key: self.key,
+            domain: self.domain,
+            key_dir: self.key_dir,
+            headers,
+            cache: Mutex::default(),
+        })
+    }
+}
+
+/// Picks the key for a message's `From` domain and turns it into a signing
+/// configuration.
+///
+/// The key directory is listed on every message, so keys can be added or
+/// rotated without a restart, but a key file is only read and parsed again
+/// when its path or modification time changes.
+pub struct DkimSigner {
+    key: Option<DkimKey>,
+    domain: Option<String>,
+    key_dir: Option<PathBuf>,
+    headers: Vec<HeaderName>,
+    /// Signing configurations by `From` domain.
+    cache: Mutex<HashMap<String, CachedConfig>>,
+}
+
+struct CachedConfig {
+    /// Key file the configuration was built from, `None` for the sender-wide
+    /// key.
+    source: Option<KeyFile>,
+    config: Arc<lettre::message::dkim::DkimConfig>,
+}
+
+/// A key file of the key directory, as last seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyFile {
+    path: PathBuf,
+    modified: SystemTime,
+}
+
+impl DkimSigner {
+    /// # Errors
+    ///
+    /// Returns an error if no key covers `from_domain`, or if its key file
+    /// cannot be read or parsed.
+    pub(crate) async fn config_for(
+        &self,
+        from_domain: &str,
+    ) -> anyhow::Result<Arc<lettre::message::dkim::DkimConfig>> {
+        let from_domain = from_domain.to_ascii_lowercase();
+        let source = match &self.key_dir {
+            Some(dir) => find_domain_key(dir, &from_domain).await?,
+            None => None,
+        };
+        if let Some(config) = self.cached(&from_domain, source.as_ref()) {
+            return Ok(config);
+        }
+        let config = if let Some(file) = &source {
+            let key = read_key_file(file).await?;
+            self.signing_config(&key, &from_domain)?
+        } else {
+            let key = self
+                .key
+                .as_ref()
+                .with_context(|| format!("no DKIM key for domain {from_domain:?}"))?;
+            self.signing_config(key, self.domain.as_deref().unwrap_or(&from_domain))?
+        };
+        let config = Arc::new(config);
+        self.cache.lock().unwrap().insert(
+            from_domain,
+            CachedConfig {
+                source,
+                config: Arc::clone(&config),
+            },
+        );
+        Ok(config)
+    }
+
+    fn cached(
+        &self,
+        from_domain: &str,
+        source: Option<&KeyFile>,
+    ) -> Option<Arc<lettre::message::dkim::DkimConfig>> {
+        self.cache
+            .lock()
+            .unwrap()
+            .get(from_domain)
+            .filter(|cached| cached.source.as_ref() == source)
+            .map(|cached| Arc::clone(&cached.config))
+    }
+
+    fn signing_config(
+        &self,
+        key: &DkimKey,
+        domain: &str,
+    ) -> anyhow::Result<lettre::message::dkim::DkimConfig> {
+        Ok(lettre::message::dkim::DkimConfig::new(
+            key.selector.clone(),
+            domain.to_owned(),
+            key.signing_key()?,
+            self.headers.clone(),
+            DkimCanonicalization {
+                header: DkimCanonicalizationType::Relaxed,
+                body: DkimCanonicalizationType::Relaxed,
+            },
+        ))
+    }
+}
+
+/// Finds the single `{selector}.{algorithm}` file under `dir/{domain}`, or
+/// `None` when the domain has no directory.
+async fn find_domain_key(dir: &Path, domain: &str) -> anyhow::Result<Option<KeyFile>> {
+    anyhow::ensure!(
+        !domain.starts_with('.') && !domain.contains(['/', '\\']),
+        "invalid sender domain {domain:?}"
+    );
+    let domain_dir = dir.join(domain);
+    let mut entries = match tokio::fs::read_dir(&domain_dir).await {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        other => {
+            other.with_context(|| format!("reading DKIM key directory {}", domain_dir.display()))?
+        }
+    };
+    let mut files = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .with_context(|| format!("reading DKIM key directory {}", domain_dir.display()))?
+    {
+        let path = entry.path();
+        // Follows symlinks, so a key mounted from a secret is seen as a file
+        // and its rotation as a new modification time.
+        let metadata = tokio::fs::metadata(&path)
+            .await
+            .with_context(|| format!("reading DKIM key file {}", path.display()))?;
+        if metadata.is_file() {
+            let modified = metadata
+                .modified()
+                .with_context(|| format!("reading DKIM key file {}", path.display()))?;
+            files.push(KeyFile { path, modified });
+        }
+    }
+    match files.len() {
+        1 => Ok(files.pop()),
+        0 => anyhow::bail!("no DKIM key in {}", domain_dir.display()),
+        _ => anyhow::bail!("more than one DKIM key in {}", domain_dir.display()),
+    }
+}
+
+async fn read_key_file(file: &KeyFile) -> anyhow::Result<DkimKey> {
+    let path = &file.path;
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid DKIM key file name {}", path.display()))?;
+    let (selector, algorithm) = file_name.rsplit_once('.').with_context(|| {
+        format!(
+            "DKIM key file {} must be named {{selector}}.{{rsa|ed25519}}",
+            path.display()
+        )
+    })?;
+    let algorithm = parse_algorithm(algorithm, &path.display().to_string())?;
+    let private_key = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("reading DKIM key file {}", path.display()))?;
+    Ok(DkimKey {
+        selector: selector.to_owned(),
+        algorithm,
+        private_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::env::VarError;
+    use std::sync::Arc;
+
+    use lettre::message::header::{HeaderName, HeaderValue};
+
+    use super::{DkimAlgorithm, DkimConfig, DkimSigner};
+
+    // Base64 of a 32-byte Ed25519 secret key.
+    const ED25519_KEY: &str = "nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=";
+
+    fn make_lookup(
+        vars: HashMap<&'static str, &'static str>,
+    ) -> impl Fn(&str) -> Result<String, VarError> {
+        move |key: &str| {
+            vars.get(key)
+                .map(|v| (*v).to_owned())
+                .ok_or(VarError::NotPresent)
+        }
+    }
+
+    async fn sign_from(signer: &DkimSigner, domain: &str) -> String {
+        let mut message = lettre::Message::builder()
+            .from(format!("from@{domain}").parse().unwrap())
+            .to("to@example.com".parse().unwrap())
+            .body(String::from("hello"))
+            .unwrap();
+        message.sign(&signer.config_for(domain).await.unwrap());
+        String::from_utf8(message.formatted()).unwrap()
+    }
+
+    #[test]
+    fn no_dkim_vars_means_no_config() {
+        let config = DkimConfig::from_lookup("P", &make_lookup(HashMap::new())).unwrap();
+        assert!(config.is_none());
+    }
+
+    #[test]
+    fn sender_wide_key_parsed_with_defaults() {
+        let mut vars = HashMap::new();
+        vars.insert("P_DKIM_SELECTOR", "mail");
+        vars.insert("P_DKIM_PRIVATE_KEY", ED25519_KEY);
+        vars.insert("P_DKIM_ALGORITHM", "ed25519");
+        let config = DkimConfig::from_lookup("P", &make_lookup(vars))
+            .unwrap()
+            .unwrap();
+        let key = config.key.as_ref().unwrap();
+        assert_eq!(key.selector, "mail");
+        assert_eq!(key.algorithm, DkimAlgorithm::Ed25519);
+        assert!(config.domain.is_none());
+        assert!(config.headers.iter().any(|h| h == "From"));
+        config.build().unwrap();
+    }
+
+    #[test]
+    fn selector_without_key_is_an_error() {
+        let mut vars = HashMap::new();
+        vars.insert("P_DKIM_SELECTOR", "mail");
+        assert!(DkimConfig::from_lookup("P", &make_lookup(vars)).is_err());
+    }
+
+    #[test]
+    fn signed_headers_must_include_from() {
+        let mut vars = HashMap::new();
+        vars.insert("P_DKIM_SELECTOR", "mail");
+        vars.insert("P_DKIM_PRIVATE_KEY", ED25519_KEY);
+        vars.insert("P_DKIM_ALGORITHM", "ed25519");
+        vars.insert("P_DKIM_HEADERS", "To,Subject");
+        let config = DkimConfig::from_lookup("P", &make_lookup(vars))
+            .unwrap()
+            .unwrap();
+        assert!(config.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn key_dir_key_wins_over_the_sender_wide_key() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("example.org")).unwrap();
+        std::fs::write(
+            dir.path().join("example.org").join("s2026.ed25519"),
+            ED25519_KEY,
+        )
+        .unwrap();
+        let signer = DkimConfig {
+            key: Some(super::DkimKey {
+                selector: "fallback".to_owned(),
+                algorithm: DkimAlgorithm::Ed25519,
+                private_key: ED25519_KEY.to_owned(),
+            }),
+            domain: Some("example.net".to_owned()),
+            key_dir: Some(dir.path().to_path_buf()),
+            headers: vec!["From".to_owned()],
+        }
+        .build()
+        .unwrap();
+
+        let own_key = sign_from(&signer, "Example.org").await;
+        assert!(own_key.contains("d=example.org"), "{own_key}");
+        assert!(own_key.contains("s=s2026"), "{own_key}");
+        let fallback = sign_from(&signer, "example.com").await;
+        assert!(fallback.contains("d=example.net"), "{fallback}");
+        assert!(fallback.contains("s=fallback"), "{fallback}");
+    }
+
+    #[tokio::test]
+    async fn rotated_key_file_is_picked_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let domain_dir = dir.path().join("example.org");
+        std::fs::create_dir(&domain_dir).unwrap();
+        std::fs::write(domain_dir.join("s2025.ed25519"), ED25519_KEY).unwrap();
+        let signer = DkimConfig {
+            key: None,
+            domain: None,
+            key_dir: Some(dir.path().to_path_buf()),
+            headers: vec!["From".to_owned()],
+        }
+        .build()
+        .unwrap();
+
+        let first = signer.config_for("example.org").await.unwrap();
+        let again = signer.config_for("example.org").await.unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        std::fs::remove_file(domain_dir.join("s2025.ed25519")).unwrap();
+        std::fs::write(domain_dir.join("s2026.ed25519"), ED25519_KEY).unwrap();
+        let rotated = sign_from(&signer, "example.org").await;
+        assert!(rotated.contains("s=s2026"), "{rotated}");
+    }
+
+    #[tokio::test]
+    async fn default_headers_sign_one_click_unsubscribe() {
+        let mut vars = HashMap::new();
+        vars.insert("P_DKIM_SELECTOR", "mail");
+        vars.insert("P_DKIM_PRIVATE_KEY", ED25519_KEY);
+        vars.insert("P_DKIM_ALGORITHM", "ed25519");
+        let signer = DkimConfig::from_lookup("P", &make_lookup(vars))
+            .unwrap()
+            .unwrap()
+            .build()
+            .unwrap();
+        let mut message = lettre::Message::builder()
+            .from("from@example.com".parse().unwrap())
+            .to("to@example.com".parse().unwrap())
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                "<https://mail.example.com/unsubscribe/abc>".to_owned(),
+            ))
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                "List-Unsubscribe=One-Click".to_owned(),
+            ))
+            .body(String::from("hello"))
+            .unwrap();
+        message.sign(&signer.config_for("example.com").await.unwrap());
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        let unfolded = formatted.replace("\r\n", "").replace([' ', '\t'], "");
+        assert!(
+            unfolded.contains("list-unsubscribe:list-unsubscribe-post"),
+            "{formatted}"
+        );
+    }
+
+    #[tokio::test]
+    async fn domain_without_any_key_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let signer = DkimConfig {
+            key: None,
+            domain: None,
+            key_dir: Some(dir.path().to_path_buf()),
+            headers: vec!["From".to_owned()],
+        }
+        .build()
+        .unwrap();
+        let err = signer.config_for("example.com").await.unwrap_err();
+        assert!(err.to_string().contains("no DKIM key"), "{err}");
+    }
+}
@@ -1,2 +1,3 @@
+pub mod dkim;
 pub mod multi_sender;
 pub mod transport;
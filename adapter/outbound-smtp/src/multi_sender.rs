@@ -3,6 +3,7 @@ use std::env::VarError;
 use anyhow::Context;
 use catapulte_domain::entity::sender::{QuotaRange, SenderName, SenderQuota};
 
+use crate::dkim::DkimConfig;
 use crate::transport::{SmtpConfig, SmtpTransport};
 
 pub struct SmtpTransportEntry {
@@ -19,6 +20,7 @@ struct SingleSenderConfig {
     priority: u8,
     quota: Option<SenderQuota>,
     match_sender_domain: Option<String>,
+    dkim: Option<DkimConfig>,
 }
 
 /// Configuration for a collection of SMTP senders.
@@ -35,6 +37,7 @@ struct SingleSenderConfig {
 ///   - `CATAPULTE_SENDER_{NAME}_QUOTA_COUNT` (optional)
 ///   - `CATAPULTE_SENDER_{NAME}_QUOTA_RANGE` (optional: "hourly", "daily",
 ///     "weekly", "monthly")
+///   - `CATAPULTE_SENDER_{NAME}_DKIM_*` (optional, see [`DkimConfig`])
 pub struct MultiSenderConfig {
     senders: Vec<SingleSenderConfig>,
 }
@@ -51,6 +54,7 @@ impl MultiSenderConfig {
                 priority: 100,
                 quota: None,
                 match_sender_domain: None,
+                dkim: None,
             }],
         }
     }
@@ -77,6 +81,7 @@ impl MultiSenderConfig {
             priority,
             quota,
             match_sender_domain: None,
+            dkim: None,
         });
         self.senders.sort_by_key(|s| s.priority);
         self
@@ -98,6 +103,7 @@ impl MultiSenderConfig {
             priority,
             quota,
             match_sender_domain,
+            dkim: None,
         });
         self.senders.sort_by_key(|s| s.priority);
         self
@@ -136,12 +142,14 @@ impl MultiSenderConfig {
                     .ok()
                     .map(|v| v.trim().to_owned())
                     .filter(|v| !v.is_empty());
+                let dkim = DkimConfig::from_lookup(&prefix, &lookup)?;
                 Ok(SingleSenderConfig {
                     name: SenderName::new(raw_name),
                     smtp,
                     priority,
                     quota,
                     match_sender_domain,
+                    dkim,
                 })
             })
             .collect::<anyhow::Result<Vec<_>>>()?;
@@ -153,12 +161,19 @@ impl MultiSenderConfig {
 
     /// # Errors
     ///
-    /// Returns an error if any SMTP transport cannot be constructed.
+    /// Returns an error if any SMTP transport or DKIM signer cannot be
+    /// constructed.
     pub fn build(self) -> anyhow::Result<Vec<SmtpTransportEntry>> {
         self.senders
             .into_iter()
             .map(|cfg| {
-                let transport = cfg.smtp.build()?;
+                let mut transport = cfg.smtp.build()?;
+                if let Some(dkim) = cfg.dkim {
+                    let signer = dkim
+                        .build()
+                        .with_context(|| format!("building DKIM signer for sender {}", cfg.name))?;
+                    transport = transport.with_dkim(signer);
+                }
                 Ok(SmtpTransportEntry {
                     name: cfg.name,
                     priority: cfg.priority,
@@ -329,4 +344,38 @@ mod tests {
         let config = MultiSenderConfig::from_lookup(make_lookup(vars)).unwrap();
         assert!(config.senders[0].match_sender_domain.is_none());
     }
+
+    #[test]
+    fn dkim_is_configured_per_sender() {
+        let mut vars = HashMap::new();
+        vars.insert("CATAPULTE_SENDERS", "signed,plain");
+        vars.insert("CATAPULTE_SENDER_SIGNED_HOST", "smtp.example.com");
+        vars.insert("CATAPULTE_SENDER_SIGNED_DKIM_SELECTOR", "s2026");
+        vars.insert(
+            "CATAPULTE_SENDER_SIGNED_DKIM_PRIVATE_KEY",
+            "nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=",
+        );
+        vars.insert("CATAPULTE_SENDER_SIGNED_DKIM_ALGORITHM", "ed25519");
+        vars.insert("CATAPULTE_SENDER_PLAIN_HOST", "smtp.example.com");
+        let config = MultiSenderConfig::from_lookup(make_lookup(vars)).unwrap();
+        let signed = config
+            .senders
+            .iter()
+            .find(|s| s.name.as_str() == "signed")
+            .unwrap();
+        assert_eq!(
+            signed
+                .dkim
+                .as_ref()
+                .and_then(|d| d.key.as_ref())
+                .map(|k| k.selector.as_str()),
+            Some("s2026")
+        );
+        let plain = config
+            .senders
+            .iter()
+            .find(|s| s.name.as_str() == "plain")
+            .unwrap();
+        assert!(plain.dkim.is_none());
+    }
 }
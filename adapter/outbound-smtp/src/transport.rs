@@ -1,14 +1,18 @@
 use anyhow::Context;
 use catapulte_domain::entity::attachment::ResolvedAttachment;
 use catapulte_domain::entity::body::RenderedBody;
+use catapulte_domain::entity::calendar::Calendar;
 use catapulte_domain::entity::email::RecipientKind;
+use catapulte_domain::entity::message_headers::MessageHeaders;
 use catapulte_domain::port::email_sender::OutboundEmail;
-use catapulte_domain::port::email_transport::EmailTransport;
-use lettre::message::header::{ContentDisposition, ContentType};
-use lettre::message::{Mailbox, MultiPart, SinglePart};
+use catapulte_domain::port::email_transport::{EmailTransport, SmtpReply, TransportError};
+use lettre::message::header::{ContentDisposition, ContentType, HeaderName, HeaderValue};
+use lettre::message::{Attachment, Mailbox, MultiPart, MultiPartBuilder, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
 
+use crate::dkim::DkimSigner;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SmtpTls {
     Starttls,
@@ -49,10 +53,10 @@ fn parse_tls(raw: Option<String>, key: &str) -> anyhow::Result<SmtpTls> {
     }
 }
 
-fn parse_mailbox(addr: &str) -> anyhow::Result<Mailbox> {
+fn parse_mailbox(addr: &str, name: Option<&str>) -> anyhow::Result<Mailbox> {
     addr.parse::<Address>()
         .with_context(|| format!("invalid address: {addr}"))
-        .map(|a| Mailbox::new(None, a))
+        .map(|a| Mailbox::new(name.map(str::to_owned), a))
 }
 
 fn add_recipient(
@@ -70,101 +74,208 @@ fn add_recipient(
 fn apply_recipients(
     mut builder: lettre::message::MessageBuilder,
     recipients: &[(RecipientKind, String)],
+    headers: &MessageHeaders,
 ) -> anyhow::Result<lettre::message::MessageBuilder> {
     for (kind, address) in recipients {
-        let mailbox = parse_mailbox(address)?;
+        let mailbox = parse_mailbox(address, headers.display_name(address))?;
         builder = add_recipient(builder, *kind, mailbox);
     }
     Ok(builder)
 }
 
-fn build_body_part(body: &RenderedBody) -> MultiPart {
-    match (body.text(), body.html()) {
-        (Some(text), Some(html)) => MultiPart::alternative()
-            .singlepart(
-                SinglePart::builder()
-                    .header(ContentType::TEXT_PLAIN)
-                    .body(text.to_owned()),
-            )
-            .singlepart(
-                SinglePart::builder()
-                    .header(ContentType::TEXT_HTML)
-                    .body(html.to_owned()),
-            ),
-        (Some(text), None) => MultiPart::alternative().singlepart(
-            SinglePart::builder()
-                .header(ContentType::TEXT_PLAIN)
-                .body(text.to_owned()),
-        ),
-        (None, Some(html)) => MultiPart::alternative().singlepart(
-            SinglePart::builder()
-                .header(ContentType::TEXT_HTML)
-                .body(html.to_owned()),
-        ),
-        (None, None) => {
-            unreachable!("Plain invariant: at least one of text or html must be provided")
+/// Adds `Reply-To` and the custom header fields. Values are RFC 2047 encoded
+/// and folded by lettre.
+fn apply_headers(
+    mut builder: lettre::message::MessageBuilder,
+    headers: &MessageHeaders,
+) -> anyhow::Result<lettre::message::MessageBuilder> {
+    if let Some(address) = &headers.reply_to {
+        builder = builder.reply_to(parse_mailbox(address, headers.display_name(address))?);
+    }
+    for (name, value) in &headers.custom {
+        let name = HeaderName::new_from_ascii(name.clone())
+            .with_context(|| format!("invalid header name: {name}"))?;
+        builder = builder.raw_header(HeaderValue::new(name, value.clone()));
+    }
+    Ok(builder)
+}
+
+/// A node of the MIME tree of a message.
+enum Part {
+    Single(SinglePart),
+    Multi(MultiPart),
+}
+
+fn nest(parent: MultiPartBuilder, child: Part) -> MultiPart {
+    match child {
+        Part::Single(part) => parent.singlepart(part),
+        Part::Multi(part) => parent.multipart(part),
+    }
+}
+
+fn append(parent: MultiPart, child: Part) -> MultiPart {
+    match child {
+        Part::Single(part) => parent.singlepart(part),
+        Part::Multi(part) => parent.multipart(part),
+    }
+}
+
+fn text_part(text: &str) -> SinglePart {
+    SinglePart::builder()
+        .header(ContentType::TEXT_PLAIN)
+        .body(text.to_owned())
+}
+
+fn html_part(html: &str) -> SinglePart {
+    SinglePart::builder()
+        .header(ContentType::TEXT_HTML)
+        .body(html.to_owned())
+}
+
+/// `text/calendar` part whose `method` parameter matches the calendar's, so
+/// that clients offer to accept or decline the invitation.
+fn calendar_part(calendar: &Calendar) -> anyhow::Result<SinglePart> {
+    let content_type = ContentType::parse(&format!(
+        "text/calendar; method={}; charset=utf-8",
+        calendar.method()
+    ))
+    .context("invalid calendar method")?;
+    Ok(SinglePart::builder()
+        .header(content_type)
+        .body(calendar.content().to_owned()))
+}
+
+fn attachment_content_type(att: &ResolvedAttachment) -> anyhow::Result<ContentType> {
+    ContentType::parse(&att.content_type)
+        .with_context(|| format!("invalid attachment content-type: {}", att.content_type))
+}
+
+/// Builds `multipart/mixed` around the body when there are attachments,
+/// `multipart/alternative` around the text, HTML and calendar parts, and
+/// `multipart/related` around the HTML and the inline parts it references by
+/// Content-ID. Inline parts of a body without HTML are attached instead.
+fn build_body(
+    body: &RenderedBody,
+    calendar: Option<&Calendar>,
+    attachments: &[ResolvedAttachment],
+) -> anyhow::Result<Part> {
+    let (inline, attached): (Vec<_>, Vec<_>) = attachments
+        .iter()
+        .partition(|att| att.content_id.is_some() && body.html().is_some());
+
+    let html = match body.html() {
+        Some(html) if inline.is_empty() => Some(Part::Single(html_part(html))),
+        Some(html) => {
+            let mut related = MultiPart::related().singlepart(html_part(html));
+            for att in inline {
+                let content_id = att.content_id.clone().unwrap_or_default();
+                related = related.singlepart(
+                    Attachment::new_inline_with_name(content_id, att.filename.clone())
+                        .body(att.bytes.to_vec(), attachment_content_type(att)?),
+                );
+            }
+            Some(Part::Multi(related))
         }
+        None => None,
+    };
+    let calendar = calendar.map(calendar_part).transpose()?;
+    let mut alternatives = body
+        .text()
+        .map(|text| Part::Single(text_part(text)))
+        .into_iter()
+        .chain(html)
+        .chain(calendar.map(Part::Single));
+    let Some(first) = alternatives.next() else {
+        unreachable!("Plain invariant: at least one of text or html must be provided")
+    };
+    let content = match alternatives.next() {
+        None => first,
+        Some(second) => Part::Multi(alternatives.fold(
+            append(nest(MultiPart::alternative(), first), second),
+            append,
+        )),
+    };
+    if attached.is_empty() {
+        return Ok(content);
     }
+
+    let mut mixed = nest(MultiPart::mixed(), content);
+    for att in attached {
+        mixed = mixed.singlepart(
+            SinglePart::builder()
+                .header(attachment_content_type(att)?)
+                .header(ContentDisposition::attachment(&att.filename))
+                .body(att.bytes.to_vec()),
+        );
+    }
+    Ok(Part::Multi(mixed))
 }
 
 fn finalize_message(
     builder: lettre::message::MessageBuilder,
     subject: Option<&str>,
     body: &RenderedBody,
+    calendar: Option<&Calendar>,
     attachments: &[ResolvedAttachment],
+    dkim: Option<&lettre::message::dkim::DkimConfig>,
 ) -> anyhow::Result<Message> {
     let builder = match subject {
         Some(s) => builder.subject(s),
         None => builder,
     };
 
-    if attachments.is_empty() {
-        let msg = match (body.text(), body.html()) {
-            (Some(text), Some(html)) => builder.multipart(
-                MultiPart::alternative()
-                    .singlepart(
-                        SinglePart::builder()
-                            .header(ContentType::TEXT_PLAIN)
-                            .body(text.to_owned()),
-                    )
-                    .singlepart(
-                        SinglePart::builder()
-                            .header(ContentType::TEXT_HTML)
-                            .body(html.to_owned()),
-                    ),
-            ),
-            (Some(text), None) => builder.singlepart(
-                SinglePart::builder()
-                    .header(ContentType::TEXT_PLAIN)
-                    .body(text.to_owned()),
-            ),
-            (None, Some(html)) => builder.singlepart(
-                SinglePart::builder()
-                    .header(ContentType::TEXT_HTML)
-                    .body(html.to_owned()),
-            ),
-            (None, None) => {
-                unreachable!("Plain invariant: at least one of text or html must be provided")
-            }
-        };
-        msg.context("building email message")
-    } else {
-        let body_part = build_body_part(body);
-        let mut mixed = MultiPart::mixed().multipart(body_part);
-        for att in attachments {
-            let content_type = ContentType::parse(&att.content_type).with_context(|| {
-                format!("invalid attachment content-type: {}", att.content_type)
-            })?;
-            mixed = mixed.singlepart(
-                SinglePart::builder()
-                    .header(content_type)
-                    .header(ContentDisposition::attachment(&att.filename))
-                    .body(att.bytes.to_vec()),
-            );
-        }
-        builder
-            .multipart(mixed)
-            .context("building email message with attachments")
+    let message = match build_body(body, calendar, attachments)? {
+        Part::Single(part) => builder.singlepart(part),
+        Part::Multi(part) => builder.multipart(part),
+    };
+    let mut message = message.context("building email message")?;
+    if let Some(dkim) = dkim {
+        message.sign(dkim);
+    }
+    Ok(message)
+}
+
+/// Replies refusing the mailbox, the sender or the message become
+/// `TransportError::Rejected`; anything else, authentication and server
+/// configuration failures included, is left for the caller to retry, possibly
+/// on another sender.
+fn classify_send_error(
+    err: lettre::transport::smtp::Error,
+    recipients: &[(RecipientKind, String)],
+) -> TransportError {
+    use std::error::Error as _;
+
+    let rejected = err
+        .status()
+        .map(u16::from)
+        .filter(|&code| is_rejection_code(code))
+        .and_then(|code| {
+            let text = err.source().map(ToString::to_string).unwrap_or_default();
+            rejection(SmtpReply::new(code, text), recipients)
+        });
+    rejected.unwrap_or_else(|| {
+        TransportError::Other(anyhow::Error::new(err).context("smtp send failed"))
+    })
+}
+
+/// `550` to `553` (RFC 5321 §4.2.3): the mailbox is unavailable or its name is
+/// not allowed, which another attempt or sender will not change. Other `5xx`,
+/// such as `530` and `535` on authentication, depend on the sender.
+fn is_rejection_code(code: u16) -> bool {
+    matches!(code, 550..=553)
+}
+
+/// A mailbox rejection is only final when it can be pinned on a recipient:
+/// the message has exactly one and the reply blames its mailbox. Otherwise the
+/// reply does not say which `RCPT TO` failed, and failing the email would fail
+/// its other recipients too, so it is left to retries and other senders.
+fn rejection(reply: SmtpReply, recipients: &[(RecipientKind, String)]) -> Option<TransportError> {
+    match recipients {
+        [(_, address)] if reply.is_recipient_rejection() => Some(TransportError::Rejected {
+            reply,
+            recipients: vec![address.clone()],
+        }),
+        _ => None,
     }
 }
 
@@ -235,34 +346,61 @@ impl SmtpConfig {
         let builder = build_transport_builder(&self.tls, &self.host)?.port(self.port);
         let builder = apply_credentials(builder, self.username, self.password);
         let transport = builder.build();
-        Ok(SmtpTransport { transport })
+        Ok(SmtpTransport {
+            transport,
+            dkim: None,
+        })
     }
 }
 
 pub struct SmtpTransport {
     transport: AsyncSmtpTransport<Tokio1Executor>,
+    dkim: Option<DkimSigner>,
 }
 
 impl SmtpTransport {
-    pub(crate) async fn send_inner(&self, email: &OutboundEmail) -> anyhow::Result<()> {
-        let from = parse_mailbox(&email.sender)?;
-        let builder = apply_recipients(Message::builder().from(from), &email.recipients)?;
+    /// Signs every message sent through this transport.
+    #[must_use]
+    pub fn with_dkim(mut self, signer: DkimSigner) -> Self {
+        self.dkim = Some(signer);
+        self
+    }
+
+    pub(crate) async fn send_inner(&self, email: &OutboundEmail) -> Result<(), TransportError> {
+        let from = parse_mailbox(&email.sender, email.headers.display_name(&email.sender))?;
+        let dkim = match &self.dkim {
+            Some(signer) => Some(
+                signer
+                    .config_for(from.email.domain())
+                    .await
+                    .map_err(TransportError::Signing)?,
+            ),
+            None => None,
+        };
+        let builder = apply_recipients(
+            Message::builder().from(from),
+            &email.recipients,
+            &email.headers,
+        )?;
+        let builder = apply_headers(builder, &email.headers)?;
         let message = finalize_message(
             builder,
             email.subject.as_deref(),
             &email.body,
+            email.calendar.as_ref(),
             &email.attachments,
+            dkim.as_deref(),
         )?;
         self.transport
             .send(message)
             .await
-            .context("smtp send failed")?;
+            .map_err(|err| classify_send_error(err, &email.recipients))?;
         Ok(())
     }
 }
 
 impl EmailTransport for SmtpTransport {
-    async fn deliver<'a>(&'a self, email: &'a OutboundEmail) -> Result<(), anyhow::Error> {
+    async fn deliver<'a>(&'a self, email: &'a OutboundEmail) -> Result<(), TransportError> {
         self.send_inner(email).await
     }
 }
@@ -274,9 +412,17 @@ mod tests {
 
     use catapulte_domain::entity::attachment::ResolvedAttachment;
     use catapulte_domain::entity::body::{Plain, RenderedBody};
+    use catapulte_domain::entity::calendar::Calendar;
+    use catapulte_domain::entity::email::RecipientKind;
+    use catapulte_domain::port::email_transport::{SmtpReply, TransportError};
     use lettre::Address;
 
-    use super::{SmtpConfig, SmtpTls, finalize_message, parse_port, parse_tls};
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+
+    use super::{
+        SmtpConfig, SmtpTls, apply_headers, apply_recipients, finalize_message, is_rejection_code,
+        parse_port, parse_tls, rejection,
+    };
     use crate::transport::parse_mailbox;
 
     fn make_lookup(
@@ -381,20 +527,24 @@ mod tests {
     #[test]
     fn finalize_message_text_only() {
         let body = plain_text_body();
-        assert!(finalize_message(base_builder(), Some("Test Subject"), &body, &[]).is_ok());
+        assert!(
+            finalize_message(base_builder(), Some("Test Subject"), &body, None, &[], None).is_ok()
+        );
     }
 
     #[test]
     fn finalize_message_html_only() {
         let plain = Plain::try_new(None, Some("<p>hi</p>".to_string())).unwrap();
         let body = RenderedBody::new(plain);
-        assert!(finalize_message(base_builder(), None, &body, &[]).is_ok());
+        assert!(finalize_message(base_builder(), None, &body, None, &[], None).is_ok());
     }
 
     #[test]
     fn finalize_message_multipart() {
         let body = multipart_body();
-        assert!(finalize_message(base_builder(), Some("Test Subject"), &body, &[]).is_ok());
+        assert!(
+            finalize_message(base_builder(), Some("Test Subject"), &body, None, &[], None).is_ok()
+        );
     }
 
     #[test]
@@ -404,8 +554,19 @@ mod tests {
             filename: "test.txt".to_owned(),
             content_type: "text/plain".to_owned(),
             bytes: bytes::Bytes::from_static(b"hello attachment"),
+            content_id: None,
         };
-        assert!(finalize_message(base_builder(), Some("With Attachment"), &body, &[att]).is_ok());
+        assert!(
+            finalize_message(
+                base_builder(),
+                Some("With Attachment"),
+                &body,
+                None,
+                &[att],
+                None
+            )
+            .is_ok()
+        );
     }
 
     #[test]
@@ -415,13 +576,25 @@ mod tests {
             filename: "a.txt".to_owned(),
             content_type: "text/plain".to_owned(),
             bytes: bytes::Bytes::from_static(b"file a"),
+            content_id: None,
         };
         let att2 = ResolvedAttachment {
             filename: "b.txt".to_owned(),
             content_type: "text/plain".to_owned(),
             bytes: bytes::Bytes::from_static(b"file b"),
+            content_id: None,
         };
-        assert!(finalize_message(base_builder(), Some("Multi"), &body, &[att1, att2]).is_ok());
+        assert!(
+            finalize_message(
+                base_builder(),
+                Some("Multi"),
+                &body,
+                None,
+                &[att1, att2],
+                None
+            )
+            .is_ok()
+        );
     }
 
     #[test]
@@ -431,17 +604,305 @@ mod tests {
             filename: "bad.bin".to_owned(),
             content_type: "not a content type///".to_owned(),
             bytes: bytes::Bytes::from_static(b"data"),
+            content_id: None,
+        };
+        assert!(finalize_message(base_builder(), None, &body, None, &[att], None).is_err());
+    }
+
+    fn logo() -> ResolvedAttachment {
+        ResolvedAttachment {
+            filename: "logo.png".to_owned(),
+            content_type: "image/png".to_owned(),
+            bytes: bytes::Bytes::from_static(b"png"),
+            content_id: Some("logo".to_owned()),
+        }
+    }
+
+    #[test]
+    fn inline_parts_are_related_to_the_html() {
+        let plain = Plain::try_new(
+            Some("hi".to_string()),
+            Some(r#"<img src="cid:logo">"#.to_string()),
+        )
+        .unwrap();
+        let body = RenderedBody::new(plain);
+        let message = finalize_message(base_builder(), None, &body, None, &[logo()], None).unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(
+            formatted.contains("Content-Type: multipart/alternative"),
+            "{formatted}"
+        );
+        assert!(
+            formatted.contains("Content-Type: multipart/related"),
+            "{formatted}"
+        );
+        assert!(!formatted.contains("multipart/mixed"), "{formatted}");
+        assert!(formatted.contains("Content-ID: <logo>"), "{formatted}");
+        assert!(
+            formatted.contains("Content-Disposition: inline; filename=\"logo.png\""),
+            "{formatted}"
+        );
+        let related = formatted.find("multipart/related").unwrap();
+        assert!(
+            formatted.find("text/plain").unwrap() < related,
+            "{formatted}"
+        );
+    }
+
+    #[test]
+    fn inline_parts_and_attachments_are_mixed() {
+        let plain = Plain::try_new(None, Some(r#"<img src="cid:logo">"#.to_string())).unwrap();
+        let body = RenderedBody::new(plain);
+        let invoice = ResolvedAttachment {
+            filename: "invoice.pdf".to_owned(),
+            content_type: "application/pdf".to_owned(),
+            bytes: bytes::Bytes::from_static(b"%PDF"),
+            content_id: None,
         };
-        assert!(finalize_message(base_builder(), None, &body, &[att]).is_err());
+        let message =
+            finalize_message(base_builder(), None, &body, None, &[logo(), invoice], None).unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        let mixed = formatted.find("multipart/mixed").unwrap();
+        let related = formatted.find("multipart/related").unwrap();
+        let invoice = formatted
+            .find("Content-Disposition: attachment; filename=\"invoice.pdf\"")
+            .unwrap();
+        assert!(mixed < related && related < invoice, "{formatted}");
+        assert!(formatted.contains("Content-ID: <logo>"), "{formatted}");
+    }
+
+    #[test]
+    fn inline_parts_without_html_are_attached() {
+        let message = finalize_message(
+            base_builder(),
+            None,
+            &plain_text_body(),
+            None,
+            &[logo()],
+            None,
+        )
+        .unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(formatted.contains("multipart/mixed"), "{formatted}");
+        assert!(!formatted.contains("multipart/related"), "{formatted}");
+        assert!(
+            formatted.contains("Content-Disposition: attachment; filename=\"logo.png\""),
+            "{formatted}"
+        );
+    }
+
+    #[test]
+    fn calendar_is_the_last_alternative() {
+        let calendar = Calendar::parse(
+            "BEGIN:VCALENDAR\r\nMETHOD:REQUEST\r\nBEGIN:VEVENT\r\nUID:kickoff\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+        )
+        .unwrap();
+        let message = finalize_message(
+            base_builder(),
+            Some("Kickoff"),
+            &multipart_body(),
+            Some(&calendar),
+            &[],
+            None,
+        )
+        .unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        let alternative = formatted.find("multipart/alternative").unwrap();
+        let html = formatted.find("text/html").unwrap();
+        let ics = formatted
+            .find("Content-Type: text/calendar; method=REQUEST; charset=utf-8")
+            .unwrap();
+        assert!(alternative < html && html < ics, "{formatted}");
+        assert!(formatted.contains("UID:kickoff"), "{formatted}");
+    }
+
+    #[test]
+    fn calendar_makes_a_text_only_body_an_alternative() {
+        let calendar =
+            Calendar::parse("BEGIN:VCALENDAR\r\nMETHOD:CANCEL\r\nEND:VCALENDAR\r\n").unwrap();
+        let message = finalize_message(
+            base_builder(),
+            None,
+            &plain_text_body(),
+            Some(&calendar),
+            &[],
+            None,
+        )
+        .unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(formatted.contains("multipart/alternative"), "{formatted}");
+        assert!(
+            formatted.contains("text/calendar; method=CANCEL"),
+            "{formatted}"
+        );
+    }
+
+    fn ed25519_signer(key_dir: Option<std::path::PathBuf>) -> crate::dkim::DkimSigner {
+        crate::dkim::DkimConfig {
+            key: Some(crate::dkim::DkimKey {
+                selector: "mail".to_owned(),
+                algorithm: crate::dkim::DkimAlgorithm::Ed25519,
+                private_key: "nWGxne/9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A=".to_owned(),
+            }),
+            domain: None,
+            key_dir,
+            headers: vec!["From".to_owned(), "Subject".to_owned()],
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn finalize_message_signs_with_dkim() {
+        let body = plain_text_body();
+        let att = ResolvedAttachment {
+            filename: "report.pdf".to_owned(),
+            content_type: "application/pdf".to_owned(),
+            bytes: bytes::Bytes::from_static(b"%PDF"),
+            content_id: None,
+        };
+        let dkim = ed25519_signer(None)
+            .config_for("example.com")
+            .await
+            .unwrap();
+        let message = finalize_message(
+            base_builder(),
+            Some("Signed"),
+            &body,
+            None,
+            &[att],
+            Some(&dkim),
+        )
+        .unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(formatted.contains("DKIM-Signature: "), "{formatted}");
+        assert!(formatted.contains("a=ed25519-sha256"), "{formatted}");
+        assert!(formatted.contains("d=example.com"), "{formatted}");
+        assert!(formatted.contains("h=from:subject"), "{formatted}");
+    }
+
+    #[test]
+    fn display_names_reply_to_and_custom_headers_are_written() {
+        let mut headers = MessageHeaders {
+            reply_to: Some("support@acme.com".to_owned()),
+            ..MessageHeaders::default()
+        };
+        headers
+            .display_names
+            .insert("billing@acme.com".to_owned(), "Acme Billing".to_owned());
+        headers
+            .display_names
+            .insert("jane@example.com".to_owned(), "Jane Doe".to_owned());
+        headers
+            .custom
+            .insert("X-Campaign-Id".to_owned(), "spring-sale".to_owned());
+        headers
+            .custom
+            .insert("Importance".to_owned(), "high".to_owned());
+        let from =
+            parse_mailbox("billing@acme.com", headers.display_name("billing@acme.com")).unwrap();
+        let builder = apply_recipients(
+            lettre::Message::builder().from(from),
+            &[(RecipientKind::To, "jane@example.com".to_owned())],
+            &headers,
+        )
+        .unwrap();
+        let builder = apply_headers(builder, &headers).unwrap();
+        let message = finalize_message(builder, None, &plain_text_body(), None, &[], None).unwrap();
+        let formatted = String::from_utf8(message.formatted()).unwrap();
+        assert!(
+            formatted.contains("From: \"Acme Billing\" <billing@acme.com>"),
+            "{formatted}"
+        );
+        assert!(
+            formatted.contains("To: \"Jane Doe\" <jane@example.com>"),
+            "{formatted}"
+        );
+        assert!(
+            formatted.contains("Reply-To: support@acme.com"),
+            "{formatted}"
+        );
+        assert!(
+            formatted.contains("X-Campaign-Id: spring-sale"),
+            "{formatted}"
+        );
+        assert!(formatted.contains("Importance: high"), "{formatted}");
+    }
+
+    #[tokio::test]
+    async fn missing_dkim_key_fails_before_connecting() {
+        use catapulte_domain::port::email_sender::OutboundEmail;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("example.com")).unwrap();
+        let transport = SmtpConfig {
+            host: "127.0.0.1".to_owned(),
+            port: 1,
+            username: None,
+            password: None,
+            tls: SmtpTls::None,
+        }
+        .build()
+        .unwrap()
+        .with_dkim(ed25519_signer(Some(dir.path().to_path_buf())));
+        let email = OutboundEmail {
+            sender: "from@example.com".to_owned(),
+            subject: None,
+            recipients: vec![(RecipientKind::To, "to@example.com".to_owned())],
+            body: plain_text_body(),
+            attachments: vec![],
+            headers: MessageHeaders::default(),
+            calendar: None,
+        };
+
+        let err = transport.send_inner(&email).await.unwrap_err();
+        assert!(matches!(err, TransportError::Signing(_)), "{err:?}");
+    }
+
+    #[test]
+    fn mailbox_rejection_names_the_single_recipient() {
+        let recipients = vec![(RecipientKind::To, "bob@example.com".to_owned())];
+        let err = rejection(
+            SmtpReply::new(550, "5.1.1 <bob@example.com>: user unknown"),
+            &recipients,
+        );
+        let Some(TransportError::Rejected { reply, recipients }) = err else {
+            panic!("expected a rejection");
+        };
+        assert_eq!(reply.code, 550);
+        assert_eq!(recipients, vec!["bob@example.com".to_owned()]);
+    }
+
+    #[test]
+    fn rejections_that_cannot_be_pinned_stay_retryable() {
+        let several = vec![
+            (RecipientKind::To, "bob@example.com".to_owned()),
+            (RecipientKind::Cc, "alice@example.com".to_owned()),
+        ];
+        assert!(rejection(SmtpReply::new(550, "5.1.1 user unknown"), &several).is_none());
+
+        let single = vec![(RecipientKind::To, "bob@example.com".to_owned())];
+        assert!(rejection(SmtpReply::new(553, "5.1.8 sender domain unknown"), &single).is_none());
+        assert!(rejection(SmtpReply::new(550, "mailbox unavailable"), &single).is_none());
+    }
+
+    #[test]
+    fn only_mailbox_replies_are_rejections() {
+        for code in [550, 551, 552, 553] {
+            assert!(is_rejection_code(code), "{code}");
+        }
+        for code in [421, 450, 530, 535, 554, 555] {
+            assert!(!is_rejection_code(code), "{code}");
+        }
     }
 
     #[test]
     fn parse_mailbox_valid_address() {
-        assert!(parse_mailbox("user@example.com").is_ok());
+        assert!(parse_mailbox("user@example.com", None).is_ok());
     }
 
     #[test]
     fn parse_mailbox_invalid_address() {
-        assert!(parse_mailbox("not-an-email").is_err());
+        assert!(parse_mailbox("not-an-email", None).is_err());
     }
 }
@@ -0,0 +1 @@
+ALTER TABLE emails ADD COLUMN send_at_ms INTEGER;
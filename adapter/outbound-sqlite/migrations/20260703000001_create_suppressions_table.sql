@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS suppressions (
+    address TEXT PRIMARY KEY NOT NULL,
+    reason TEXT NOT NULL,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000)
+);
+
+CREATE INDEX IF NOT EXISTS suppressions_created_at_ms ON suppressions(created_at_ms);
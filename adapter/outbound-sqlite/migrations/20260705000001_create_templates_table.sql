@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS templates (
+    name TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    content TEXT NOT NULL,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    deleted_at_ms INTEGER,
+    PRIMARY KEY (name, version)
+);
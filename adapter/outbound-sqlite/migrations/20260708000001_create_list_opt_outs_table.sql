@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS list_opt_outs (
+    sender TEXT NOT NULL,
+    list_name TEXT NOT NULL,
+    address TEXT NOT NULL,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    PRIMARY KEY (sender, list_name, address)
+);
@@ -0,0 +1 @@
+ALTER TABLE emails ADD COLUMN list_name TEXT;
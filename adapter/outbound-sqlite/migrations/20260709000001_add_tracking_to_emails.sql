@@ -0,0 +1,2 @@
+ALTER TABLE emails ADD COLUMN track_opens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE emails ADD COLUMN track_clicks INTEGER NOT NULL DEFAULT 0;
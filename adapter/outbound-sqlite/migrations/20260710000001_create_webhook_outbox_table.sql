@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS webhook_outbox (
+    event_id BLOB PRIMARY KEY NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    last_error TEXT,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    FOREIGN KEY (event_id) REFERENCES lifecycle_events(id)
+);
+
+CREATE INDEX IF NOT EXISTS webhook_outbox_due
+    ON webhook_outbox(next_attempt_at_ms) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS webhook_outbox_created_at_ms ON webhook_outbox(created_at_ms);
@@ -0,0 +1,37 @@
+CREATE TABLE IF NOT EXISTS webhook_subscriptions (
+    id BLOB PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    -- JSON array of event types, empty for every type
+    event_types TEXT NOT NULL DEFAULT '[]',
+    correlation_id_prefix TEXT,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000)
+);
+
+-- The outbox now holds one delivery per event and endpoint. Deliveries to
+-- the webhook configured by the operator have no subscription; the ones
+-- queued before this migration keep their event id as delivery id.
+CREATE TABLE webhook_outbox_new (
+    id BLOB PRIMARY KEY NOT NULL,
+    event_id BLOB NOT NULL,
+    subscription_id BLOB,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    last_error TEXT,
+    created_at_ms INTEGER NOT NULL DEFAULT (unixepoch('now', 'subsec') * 1000),
+    FOREIGN KEY (event_id) REFERENCES lifecycle_events(id),
+    FOREIGN KEY (subscription_id) REFERENCES webhook_subscriptions(id) ON DELETE CASCADE
+);
+
+INSERT INTO webhook_outbox_new (id, event_id, status, attempts, next_attempt_at_ms, last_error, created_at_ms)
+    SELECT event_id, event_id, status, attempts, next_attempt_at_ms, last_error, created_at_ms
+    FROM webhook_outbox;
+
+DROP TABLE webhook_outbox;
+ALTER TABLE webhook_outbox_new RENAME TO webhook_outbox;
+
+CREATE INDEX IF NOT EXISTS webhook_outbox_due
+    ON webhook_outbox(next_attempt_at_ms) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS webhook_outbox_created_at_ms ON webhook_outbox(created_at_ms);
+CREATE INDEX IF NOT EXISTS webhook_outbox_subscription_id ON webhook_outbox(subscription_id);
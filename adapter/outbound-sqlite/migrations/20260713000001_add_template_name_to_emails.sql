@@ -0,0 +1,12 @@
+-- Template a named MJML body uses, without its pinned version or locale
+-- variant, and the locale of that variant: `welcome.fr@2` is `welcome` in `fr`.
+ALTER TABLE emails ADD COLUMN template_name TEXT;
+ALTER TABLE emails ADD COLUMN template_locale TEXT;
+
+UPDATE emails
+SET template_name = CASE
+    WHEN instr(json_extract(body, '$.source.name'), '@') > 0
+        THEN substr(json_extract(body, '$.source.name'), 1, instr(json_extract(body, '$.source.name'), '@') - 1)
+    ELSE json_extract(body, '$.source.name')
+END
+WHERE json_extract(body, '$.source.kind') = 'mjml_named';
@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context;
 use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
 use catapulte_domain::entity::body::{BodySource, MjmlSource, Plain};
 use catapulte_domain::entity::email::RecipientKind;
+use catapulte_domain::entity::message_headers::MessageHeaders;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -108,6 +111,38 @@ pub fn recipients_from_dto(dtos: Vec<RecipientDto>) -> Vec<(RecipientKind, Strin
         .collect()
 }
 
+/// Stored in the nullable `headers` column; rows written before it existed
+/// read back as empty headers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MessageHeadersDto {
+    #[serde(default)]
+    pub reply_to: Option<String>,
+    #[serde(default)]
+    pub display_names: BTreeMap<String, String>,
+    #[serde(default)]
+    pub custom: BTreeMap<String, String>,
+}
+
+impl From<&MessageHeaders> for MessageHeadersDto {
+    fn from(headers: &MessageHeaders) -> Self {
+        Self {
+            reply_to: headers.reply_to.clone(),
+            display_names: headers.display_names.clone(),
+            custom: headers.custom.clone(),
+        }
+    }
+}
+
+impl From<MessageHeadersDto> for MessageHeaders {
+    fn from(dto: MessageHeadersDto) -> Self {
+        Self {
+            reply_to: dto.reply_to,
+            display_names: dto.display_names,
+            custom: dto.custom,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlobRefDto {
     pub backend: String,
@@ -120,6 +155,8 @@ pub struct AttachmentRefDto {
     pub content_type: String,
     pub size_bytes: u64,
     pub blob: BlobRefDto,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_id: Option<String>,
 }
 
 impl From<&AttachmentRef> for AttachmentRefDto {
@@ -132,6 +169,7 @@ impl From<&AttachmentRef> for AttachmentRefDto {
                 backend: a.blob.backend.clone(),
                 key: a.blob.key.clone(),
             },
+            content_id: a.content_id.clone(),
         }
     }
 }
@@ -146,6 +184,7 @@ impl From<AttachmentRefDto> for AttachmentRef {
                 backend: dto.blob.backend,
                 key: dto.blob.key,
             },
+            content_id: dto.content_id,
         }
     }
 }
@@ -229,6 +268,7 @@ mod tests {
                         backend: "s3".to_owned(),
                         key: "uploads/invoice.pdf".to_owned(),
                     },
+                    content_id: None,
                 },
                 AttachmentRefDto {
                     filename: "photo.png".to_owned(),
@@ -238,6 +278,7 @@ mod tests {
                         backend: "gcs".to_owned(),
                         key: "media/photo.png".to_owned(),
                     },
+                    content_id: None,
                 },
             ],
         };
@@ -8,9 +8,12 @@ use catapulte_domain::port::email_queue::{
 };
 
 use crate::SqliteAdapter;
-use crate::dto::{EnvelopeBodyDtoDeser, RecipientDto, recipients_from_dto};
+use crate::dto::{EnvelopeBodyDtoDeser, MessageHeadersDto, RecipientDto, recipients_from_dto};
 
 use catapulte_domain::entity::body::BodySource;
+use catapulte_domain::entity::calendar::Calendar;
+use catapulte_domain::entity::tracking::Tracking;
+use catapulte_domain::entity::unsubscribe::MailingList;
 
 fn parse_id(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<EmailId> {
     use sqlx::Row;
@@ -50,6 +53,28 @@ fn parse_envelope(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<Envelope> {
     let correlation_id: Option<String> = row
         .try_get("correlation_id")
         .context("reading correlation_id")?;
+    let send_at_ms: Option<i64> = row.try_get("send_at_ms").context("reading send_at_ms")?;
+    let headers: Option<sqlx::types::Json<MessageHeadersDto>> =
+        row.try_get("headers").context("reading headers")?;
+    let locale: Option<String> = row.try_get("locale").context("reading locale")?;
+    let calendar: Option<String> = row.try_get("calendar").context("reading calendar")?;
+    let calendar = calendar
+        .as_deref()
+        .map(Calendar::parse)
+        .transpose()
+        .context("parsing calendar")?;
+    let list: Option<String> = row.try_get("list_name").context("reading list_name")?;
+    let list = list
+        .as_deref()
+        .map(MailingList::parse)
+        .transpose()
+        .context("parsing list_name")?;
+    let tracking = Tracking {
+        opens: row.try_get("track_opens").context("reading track_opens")?,
+        clicks: row
+            .try_get("track_clicks")
+            .context("reading track_clicks")?,
+    };
     Ok(Envelope {
         idempotency_key,
         correlation_id,
@@ -59,6 +84,12 @@ fn parse_envelope(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<Envelope> {
         body,
         variables: variables.0,
         attachments,
+        send_at_ms,
+        headers: headers.map(|h| h.0).unwrap_or_default().into(),
+        locale,
+        calendar,
+        list,
+        tracking,
     })
 }
 
@@ -68,7 +99,10 @@ fn parse_row(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<(EmailId, Envelope
     Ok((id, envelope))
 }
 
-fn now_ms() -> i64 {
+/// How long a dequeued email stays claimed before another worker may take it.
+pub(crate) const PROCESSING_TIMEOUT_MS: i64 = 300_000;
+
+pub(crate) fn now_ms() -> i64 {
     i64::try_from(
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -110,8 +144,7 @@ impl SqliteAdapter {
     async fn try_dequeue(&self) -> Result<Option<DequeuedEmail>, EmailQueueError> {
         use sqlx::Row;
         let now = now_ms();
-        let processing_timeout_ms: i64 = 300_000;
-        let claim_until = now + processing_timeout_ms;
+        let claim_until = now + PROCESSING_TIMEOUT_MS;
 
         let maybe = sqlx::query(
             "UPDATE email_queue \
@@ -122,7 +155,7 @@ impl SqliteAdapter {
                  ORDER BY enqueued_at ASC \
                  LIMIT 1 \
              ) \
-             RETURNING id, email_id, attempt_count, trace_context",
+             RETURNING id, email_id, attempt_count, CAST(enqueued_at AS INTEGER) AS enqueued_at, trace_context",
         )
         .bind(claim_until)
         .bind(now)
@@ -147,6 +180,10 @@ impl SqliteAdapter {
         let new_attempt = u32::try_from(attempt)
             .context("attempt_count out of range")
             .map_err(|source| EmailQueueError::Storage { source })?;
+        let enqueued_at_ms: i64 = row
+            .try_get("enqueued_at")
+            .context("reading enqueued_at")
+            .map_err(|source| EmailQueueError::Storage { source })?;
         let trace_raw: Option<String> = row
             .try_get("trace_context")
             .context("reading trace_context")
@@ -154,7 +191,7 @@ impl SqliteAdapter {
         let trace = deserialize_trace_context(trace_raw);
 
         let maybe_row = sqlx::query(
-            "SELECT id, idempotency_key, correlation_id, subject, sender, recipients, body, variables FROM emails WHERE id = ?",
+            "SELECT id, idempotency_key, correlation_id, subject, sender, recipients, body, variables, send_at_ms, headers, locale, calendar, list_name, track_opens, track_clicks FROM emails WHERE id = ?",
         )
         .bind(&email_id_bytes)
         .fetch_optional(self.pool())
@@ -174,6 +211,7 @@ impl SqliteAdapter {
                         id,
                         envelope,
                         attempt: new_attempt,
+                        enqueued_at_ms,
                         token: AckToken::new(entry_id_bytes.clone()),
                         trace,
                     })
@@ -184,7 +222,7 @@ impl SqliteAdapter {
 }
 
 impl EmailQueue for SqliteAdapter {
-    async fn enqueue(&self, id: EmailId, _envelope: &Envelope) -> Result<(), EmailQueueError> {
+    async fn enqueue(&self, id: EmailId, envelope: &Envelope) -> Result<(), EmailQueueError> {
         let entry_id = uuid::Uuid::now_v7().as_bytes().to_vec();
         let email_id_bytes = id.as_uuid().as_bytes().to_vec();
         let pairs = catapulte_telemetry::propagation::inject_current();
@@ -197,10 +235,15 @@ impl EmailQueue for SqliteAdapter {
                     .map_err(|source| EmailQueueError::Storage { source })?,
             )
         };
-        sqlx::query("INSERT INTO email_queue (id, email_id, trace_context) VALUES (?, ?, ?)")
-            .bind(entry_id)
-            .bind(email_id_bytes)
-            .bind(trace_context)
+        // A scheduled entry starts out "claimed" until its send time, so the
+        // regular `claimed_until < now` check keeps it invisible until due.
+        sqlx::query(
+            "INSERT INTO email_queue (id, email_id, trace_context, claimed_until) VALUES (?, ?, ?, ?)",
+        )
+        .bind(entry_id)
+        .bind(email_id_bytes)
+        .bind(trace_context)
+        .bind(envelope.send_at_ms)
             .execute(self.pool())
             .await
             .context("inserting into email_queue")
@@ -249,8 +292,9 @@ mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::port::email_queue::EmailQueue;
-    use catapulte_domain::port::email_repository::EmailRepository;
+    use catapulte_domain::port::email_repository::{EmailRepository, ListEmailsParams};
 
     use crate::SqliteAdapter;
 
@@ -270,6 +314,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -305,6 +355,148 @@ mod tests {
         assert_eq!(dequeued.id, id);
     }
 
+    #[tokio::test]
+    async fn scheduled_email_is_not_dequeued_before_send_at() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            send_at_ms: Some(super::now_ms() + 60_000),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        assert!(adapter.try_dequeue().await.unwrap().is_none());
+        assert_eq!(adapter.pending().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn scheduled_email_is_dequeued_once_due() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            send_at_ms: Some(super::now_ms() - 1),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.id, id);
+        assert_eq!(dequeued.attempt, 1);
+        assert_eq!(dequeued.envelope.send_at_ms, envelope.send_at_ms);
+    }
+
+    #[tokio::test]
+    async fn message_headers_survive_the_round_trip() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let mut headers = MessageHeaders {
+            reply_to: Some("support@example.com".to_owned()),
+            ..MessageHeaders::default()
+        };
+        headers
+            .display_names
+            .insert("sender@example.com".to_owned(), "Acme Billing".to_owned());
+        headers
+            .custom
+            .insert("X-Campaign-Id".to_owned(), "spring-sale".to_owned());
+        let envelope = Envelope {
+            headers,
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.envelope.headers, envelope.headers);
+    }
+
+    #[tokio::test]
+    async fn list_survives_the_round_trip() {
+        use catapulte_domain::entity::unsubscribe::MailingList;
+
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            list: Some(MailingList::parse("newsletter").unwrap()),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.envelope.list, envelope.list);
+    }
+
+    #[tokio::test]
+    async fn tracking_survives_the_round_trip() {
+        use catapulte_domain::entity::tracking::Tracking;
+
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            tracking: Tracking {
+                opens: true,
+                clicks: false,
+            },
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.envelope.tracking, envelope.tracking);
+    }
+
+    #[tokio::test]
+    async fn calendar_survives_the_round_trip() {
+        use catapulte_domain::entity::calendar::Calendar;
+
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let calendar =
+            Calendar::parse("BEGIN:VCALENDAR\r\nMETHOD:REQUEST\r\nEND:VCALENDAR\r\n").unwrap();
+        let envelope = Envelope {
+            calendar: Some(calendar.clone()),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.envelope.calendar, Some(calendar));
+    }
+
+    #[tokio::test]
+    async fn locale_survives_the_round_trip() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let envelope = Envelope {
+            locale: Some("fr-CA".to_owned()),
+            ..sample_envelope()
+        };
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let dequeued = adapter.try_dequeue().await.unwrap().unwrap();
+        assert_eq!(dequeued.envelope.locale.as_deref(), Some("fr-CA"));
+        let listed = adapter
+            .list_emails(ListEmailsParams {
+                status: None,
+                after_ms: None,
+                before_ms: None,
+                recipient: None,
+                template: None,
+                id: Some(id),
+                limit: 1,
+                offset: 0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(listed[0].locale.as_deref(), Some("fr-CA"));
+    }
+
     #[tokio::test]
     async fn ack_removes_email_from_queue() {
         let adapter = fresh_adapter().await;
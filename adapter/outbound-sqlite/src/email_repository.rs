@@ -1,9 +1,13 @@
 use anyhow::Context;
 use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
+use catapulte_domain::entity::calendar::Calendar;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::envelope::Envelope;
+use catapulte_domain::entity::template::parse_template_ref;
+use catapulte_domain::entity::unsubscribe::MailingList;
 use catapulte_domain::port::email_repository::{
-    EmailRecord, EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams, SaveResult,
+    CancelResult, EmailRecord, EmailRepository, EmailRepositoryError, EmailStatus,
+    ListEmailsParams, SaveResult,
 };
 use sqlx::QueryBuilder;
 use sqlx::Row;
@@ -12,7 +16,8 @@ use sqlx::types::Json;
 
 use crate::SqliteAdapter;
 use crate::dto::{
-    AttachmentRefDto, BodySourceDto, EnvelopeBodyDto, EnvelopeBodyDtoDeser, recipients_to_dto,
+    AttachmentRefDto, BodySourceDto, EnvelopeBodyDto, EnvelopeBodyDtoDeser, MessageHeadersDto,
+    recipients_to_dto,
 };
 
 impl EmailRepository for SqliteAdapter {
@@ -34,10 +39,11 @@ impl EmailRepository for SqliteAdapter {
                 .collect(),
         };
         let recipients_dto = recipients_to_dto(&envelope.recipients);
+        let (template_name, template_locale) = envelope.template().unzip();
 
         let result = sqlx::query(
-            "INSERT OR IGNORE INTO emails (id, idempotency_key, correlation_id, subject, sender, recipients, body, variables) \
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT OR IGNORE INTO emails (id, idempotency_key, correlation_id, subject, sender, recipients, body, variables, send_at_ms, headers, locale, calendar, list_name, track_opens, track_clicks, template_name, template_locale) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&id_bytes)
         .bind(envelope.idempotency_key.as_deref())
@@ -47,6 +53,15 @@ impl EmailRepository for SqliteAdapter {
         .bind(Json(&recipients_dto))
         .bind(Json(&body_dto))
         .bind(Json(&envelope.variables))
+        .bind(envelope.send_at_ms)
+        .bind(Json(MessageHeadersDto::from(&envelope.headers)))
+        .bind(envelope.locale.as_deref())
+        .bind(envelope.calendar.as_ref().map(Calendar::content))
+        .bind(envelope.list.as_ref().map(MailingList::as_str))
+        .bind(envelope.tracking.opens)
+        .bind(envelope.tracking.clicks)
+        .bind(template_name)
+        .bind(template_locale.flatten())
         .execute(self.pool())
         .await
         .context("inserting email")
@@ -84,10 +99,14 @@ impl EmailRepository for SqliteAdapter {
         Ok(SaveResult::Duplicate(EmailId::from(existing_uuid)))
     }
 
+    #[allow(clippy::too_many_lines)]
     async fn list_emails(
         &self,
         params: ListEmailsParams,
     ) -> Result<Vec<EmailRecord>, EmailRepositoryError> {
+        let now = crate::email_queue::now_ms();
+        // Engagement events (`unsubscribed`, `opened`, `clicked`) come after
+        // delivery and never change the status.
         let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
             "WITH email_status AS (\
                 SELECT \
@@ -97,17 +116,24 @@ impl EmailRepository for SqliteAdapter {
                     e.sender, \
                     e.recipients, \
                     e.created_at_ms, \
+                    e.send_at_ms, \
+                    e.cancelled_at_ms, \
+                    e.locale, \
+                    e.template_name, \
+                    e.template_locale, \
+                    CASE WHEN json_extract(e.body, '$.source.kind') = 'mjml_named' THEN json_extract(e.body, '$.source.name') END AS template_ref, \
                     COALESCE(\
                         (SELECT le.event_type \
                          FROM lifecycle_events le \
                          WHERE le.email_id = e.id \
+                           AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
                          ORDER BY le.created_at DESC, le.id DESC \
                          LIMIT 1),\
                         'queued'\
                     ) AS latest_event_type \
                 FROM emails e\
             ) \
-            SELECT id, idempotency_key, subject, sender, recipients, created_at_ms, latest_event_type \
+            SELECT id, idempotency_key, subject, sender, recipients, created_at_ms, send_at_ms, cancelled_at_ms, template_ref, template_name, template_locale, locale, latest_event_type \
             FROM email_status \
             WHERE 1=1",
         );
@@ -132,9 +158,20 @@ impl EmailRepository for SqliteAdapter {
             qb.push(" || '%')");
         }
         if let Some(template) = params.template {
-            qb.push(" AND id IN (SELECT id FROM emails WHERE json_extract(body, '$.source.kind') = 'mjml_named' AND json_extract(body, '$.source.name') = ");
+            // Pinned versions (`welcome@3`) and locale variants
+            // (`welcome.fr`) are stored under their bare name.
+            qb.push(" AND template_name = ");
             qb.push_bind(template);
-            qb.push(")");
+        }
+        // A cancellation outranks whatever the event log says.
+        match params.status {
+            Some(EmailStatus::Cancelled) => {
+                qb.push(" AND cancelled_at_ms IS NOT NULL");
+            }
+            Some(_) => {
+                qb.push(" AND cancelled_at_ms IS NULL");
+            }
+            None => {}
         }
         match params.status {
             Some(EmailStatus::Sent) => {
@@ -145,10 +182,21 @@ impl EmailRepository for SqliteAdapter {
                 qb.push(" AND latest_event_type = ");
                 qb.push_bind("delivery.failed");
             }
+            Some(EmailStatus::Suppressed) => {
+                qb.push(" AND latest_event_type = ");
+                qb.push_bind("suppressed");
+            }
+            Some(EmailStatus::Scheduled) => {
+                qb.push(" AND latest_event_type = 'queued' AND send_at_ms > ");
+                qb.push_bind(now);
+            }
             Some(EmailStatus::Queued) => {
-                qb.push(" AND latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed')");
+                qb.push(" AND latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed')");
+                qb.push(" AND NOT (latest_event_type = 'queued' AND COALESCE(send_at_ms, 0) > ");
+                qb.push_bind(now);
+                qb.push(")");
             }
-            None => {}
+            Some(EmailStatus::Cancelled) | None => {}
         }
 
         qb.push(" ORDER BY created_at_ms DESC, id DESC LIMIT ");
@@ -164,7 +212,7 @@ impl EmailRepository for SqliteAdapter {
             .map_err(|source| EmailRepositoryError::Storage { source })?;
 
         rows.iter()
-            .map(SqliteAdapter::row_to_email_record)
+            .map(|row| SqliteAdapter::row_to_email_record(row, now))
             .collect::<anyhow::Result<Vec<_>>>()
             .map_err(|source| EmailRepositoryError::Storage { source })
     }
@@ -238,19 +286,112 @@ impl EmailRepository for SqliteAdapter {
         Ok(())
     }
 
+    async fn cancel(&self, id: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+        let id_bytes = id.as_uuid().as_bytes().to_vec();
+        let now = crate::email_queue::now_ms();
+
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .context("starting transaction")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let row: Option<(Option<String>, Json<EnvelopeBodyDtoDeser>)> = sqlx::query_as(
+            "UPDATE emails SET cancelled_at_ms = ? \
+             WHERE id = ? \
+               AND cancelled_at_ms IS NULL \
+               AND (delivery_started_at_ms IS NULL OR delivery_started_at_ms < ?) \
+               AND COALESCE(\
+                   (SELECT le.event_type FROM lifecycle_events le \
+                    WHERE le.email_id = emails.id \
+                      AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
+                    ORDER BY le.created_at DESC, le.id DESC LIMIT 1),\
+                   'queued'\
+               ) NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed') \
+             RETURNING correlation_id, body",
+        )
+        .bind(now)
+        .bind(&id_bytes)
+        .bind(now - crate::email_queue::PROCESSING_TIMEOUT_MS)
+        .fetch_optional(&mut *tx)
+        .await
+        .context("cancelling email")
+        .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let Some((correlation_id, body)) = row else {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM emails WHERE id = ?)")
+                    .bind(&id_bytes)
+                    .fetch_one(&mut *tx)
+                    .await
+                    .context("checking email existence")
+                    .map_err(|source| EmailRepositoryError::Storage { source })?;
+            return Ok(if exists {
+                CancelResult::NotCancellable
+            } else {
+                CancelResult::NotFound
+            });
+        };
+
+        sqlx::query("DELETE FROM email_queue WHERE email_id = ?")
+            .bind(&id_bytes)
+            .execute(&mut *tx)
+            .await
+            .context("dropping queue entry of cancelled email")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        tx.commit()
+            .await
+            .context("committing cancel transaction")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+
+        let (_, attachments) = body.0.split();
+        let attachments = attachments.into_iter().map(AttachmentRef::from).collect();
+        Ok(CancelResult::Cancelled {
+            correlation_id,
+            attachments,
+        })
+    }
+
+    async fn begin_delivery(&self, id: EmailId) -> Result<bool, EmailRepositoryError> {
+        let result = sqlx::query(
+            "UPDATE emails SET delivery_started_at_ms = ? WHERE id = ? AND cancelled_at_ms IS NULL",
+        )
+        .bind(crate::email_queue::now_ms())
+        .bind(id.as_uuid().as_bytes().to_vec())
+        .execute(self.pool())
+        .await
+        .context("marking delivery started")
+        .map_err(|source| EmailRepositoryError::Storage { source })?;
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn end_delivery(&self, id: EmailId) -> Result<(), EmailRepositoryError> {
+        sqlx::query("UPDATE emails SET delivery_started_at_ms = NULL WHERE id = ?")
+            .bind(id.as_uuid().as_bytes().to_vec())
+            .execute(self.pool())
+            .await
+            .context("clearing delivery started")
+            .map_err(|source| EmailRepositoryError::Storage { source })?;
+        Ok(())
+    }
+
     async fn list_all_attachment_blobs(&self) -> Result<Vec<BlobRef>, EmailRepositoryError> {
         let rows: Vec<String> = sqlx::query_scalar(
             "WITH email_status AS (\
-                SELECT e.body, COALESCE(\
+                SELECT e.body, e.cancelled_at_ms, COALESCE(\
                     (SELECT event_type FROM lifecycle_events le \
                      WHERE le.email_id = e.id \
+                       AND le.event_type NOT IN ('unsubscribed', 'opened', 'clicked') \
                      ORDER BY le.created_at DESC, le.id DESC LIMIT 1),\
                     'queued'\
                 ) AS latest_event_type \
                 FROM emails e\
             ) \
             SELECT body FROM email_status \
-            WHERE latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed')",
+            WHERE latest_event_type NOT IN ('delivery.succeeded', 'delivery.failed', 'suppressed') \
+              AND cancelled_at_ms IS NULL",
         )
         .fetch_all(self.pool())
         .await
@@ -275,7 +416,10 @@ impl EmailRepository for SqliteAdapter {
 }
 
 impl SqliteAdapter {
-    fn row_to_email_record(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<EmailRecord> {
+    fn row_to_email_record(
+        row: &sqlx::sqlite::SqliteRow,
+        now_ms: i64,
+    ) -> anyhow::Result<EmailRecord> {
         let id_bytes: Vec<u8> = row.try_get("id").context("reading id")?;
         let id = uuid::Uuid::from_slice(&id_bytes).context("parsing id")?;
         let idempotency_key: Option<String> = row
@@ -291,9 +435,33 @@ impl SqliteAdapter {
         let latest_event_type: String = row
             .try_get("latest_event_type")
             .context("reading latest_event_type")?;
+        let send_at_ms: Option<i64> = row.try_get("send_at_ms").context("reading send_at_ms")?;
+        let cancelled_at_ms: Option<i64> = row
+            .try_get("cancelled_at_ms")
+            .context("reading cancelled_at_ms")?;
+        let template_ref: Option<String> = row
+            .try_get("template_ref")
+            .context("reading template_ref")?;
+        let template: Option<String> = row
+            .try_get("template_name")
+            .context("reading template_name")?;
+        let template_version = template_ref
+            .as_deref()
+            .and_then(|reference| parse_template_ref(reference).1);
+        // A pinned template has its variant picked already; otherwise it is
+        // picked when the email is rendered, for the requested locale.
+        let locale: Option<String> = if template_version.is_some() {
+            row.try_get("template_locale")
+                .context("reading template_locale")?
+        } else {
+            row.try_get("locale").context("reading locale")?
+        };
         let status = match latest_event_type.as_str() {
+            _ if cancelled_at_ms.is_some() => EmailStatus::Cancelled,
             "delivery.succeeded" => EmailStatus::Sent,
             "delivery.failed" => EmailStatus::Failed,
+            "suppressed" => EmailStatus::Suppressed,
+            "queued" if send_at_ms.is_some_and(|at| at > now_ms) => EmailStatus::Scheduled,
             _ => EmailStatus::Queued,
         };
         Ok(EmailRecord {
@@ -303,7 +471,11 @@ impl SqliteAdapter {
             sender,
             recipients: crate::dto::recipients_from_dto(recipients_json.0),
             created_at_ms,
+            send_at_ms,
             status,
+            template,
+            template_version,
+            locale,
         })
     }
 }
@@ -315,9 +487,11 @@ mod tests {
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::email_repository::{
-        EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams, SaveResult,
+        CancelResult, EmailRepository, EmailRepositoryError, EmailStatus, ListEmailsParams,
+        SaveResult,
     };
     use catapulte_domain::port::event_publisher::EventPublisher;
 
@@ -339,6 +513,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -489,6 +669,54 @@ mod tests {
         assert_eq!(emails.len(), 1);
     }
 
+    #[tokio::test]
+    async fn engagement_events_keep_the_sent_status() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        adapter
+            .publish(&LifecycleEvent::Sent {
+                id,
+                sender_name: SenderName::new("test"),
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+        adapter
+            .publish(&LifecycleEvent::Opened {
+                id,
+                user_agent: None,
+                at_ms: 1,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+        adapter
+            .publish(&LifecycleEvent::Clicked {
+                id,
+                url: "https://acme.com".into(),
+                user_agent: None,
+                at_ms: 2,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let emails = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Sent),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].status, EmailStatus::Sent);
+        assert!(matches!(
+            adapter.cancel(id).await.unwrap(),
+            CancelResult::NotCancellable
+        ));
+    }
+
     #[tokio::test]
     async fn list_emails_status_failed_filter() {
         let adapter = fresh_adapter().await;
@@ -516,6 +744,40 @@ mod tests {
         assert_eq!(emails.len(), 1);
     }
 
+    #[tokio::test]
+    async fn list_emails_reports_suppressed_status() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        adapter
+            .publish(&LifecycleEvent::Suppressed {
+                id,
+                recipients: vec!["bob@example.com".into()],
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let emails = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Suppressed),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].status, EmailStatus::Suppressed);
+
+        let queued = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Queued),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert!(queued.is_empty());
+    }
+
     #[tokio::test]
     async fn list_emails_status_queued_filter_includes_no_events_and_processing() {
         let adapter = fresh_adapter().await;
@@ -557,6 +819,42 @@ mod tests {
         assert_eq!(emails.len(), 2);
     }
 
+    #[tokio::test]
+    async fn list_emails_reports_future_send_at_as_scheduled() {
+        let adapter = fresh_adapter().await;
+        let scheduled = EmailId::default();
+        let mut envelope = sample_envelope();
+        envelope.send_at_ms = Some(crate::email_queue::now_ms() + 60_000);
+        adapter.save(scheduled, &envelope).await.unwrap();
+        let due = EmailId::default();
+        let mut envelope = sample_envelope();
+        envelope.send_at_ms = Some(crate::email_queue::now_ms() - 1);
+        adapter.save(due, &envelope).await.unwrap();
+
+        let emails = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Scheduled),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].id, scheduled);
+        assert_eq!(emails[0].status, EmailStatus::Scheduled);
+        assert!(emails[0].send_at_ms.is_some());
+
+        let emails = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Queued),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(emails.len(), 1);
+        assert_eq!(emails[0].id, due);
+        assert_eq!(emails[0].status, EmailStatus::Queued);
+    }
+
     #[tokio::test]
     async fn list_emails_filters_by_id() {
         let adapter = fresh_adapter().await;
@@ -726,6 +1024,7 @@ mod tests {
                     backend: "s3".to_owned(),
                     key: "uploads/invoice.pdf".to_owned(),
                 },
+                content_id: None,
             },
             AttachmentRef {
                 filename: "photo.png".to_owned(),
@@ -735,6 +1034,7 @@ mod tests {
                     backend: "gcs".to_owned(),
                     key: "media/photo.png".to_owned(),
                 },
+                content_id: None,
             },
         ];
         adapter.set_attachments(id, &attachments).await.unwrap();
@@ -794,6 +1094,7 @@ mod tests {
                 backend: "s3".to_owned(),
                 key: "docs/doc.pdf".to_owned(),
             },
+            content_id: None,
         }];
         adapter.set_attachments(id, &attachments).await.unwrap();
 
@@ -848,6 +1149,122 @@ mod tests {
         adapter.delete(id).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn cancel_marks_email_cancelled_and_drops_queue_entry() {
+        use catapulte_domain::port::email_queue::EmailQueue;
+
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        let mut envelope = sample_envelope();
+        envelope.correlation_id = Some("order-1".to_owned());
+        adapter.save(id, &envelope).await.unwrap();
+        adapter.enqueue(id, &envelope).await.unwrap();
+
+        let result = adapter.cancel(id).await.unwrap();
+        let CancelResult::Cancelled {
+            correlation_id,
+            attachments,
+        } = result
+        else {
+            panic!("expected the email to be cancelled");
+        };
+        assert_eq!(correlation_id.as_deref(), Some("order-1"));
+        assert!(attachments.is_empty());
+
+        let queued: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM email_queue")
+            .fetch_one(adapter.pool())
+            .await
+            .unwrap();
+        assert_eq!(queued, 0);
+
+        let all = adapter.list_emails(default_list_params()).await.unwrap();
+        assert_eq!(all[0].status, EmailStatus::Cancelled);
+        let queued = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Queued),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert!(queued.is_empty());
+        let cancelled = adapter
+            .list_emails(ListEmailsParams {
+                status: Some(EmailStatus::Cancelled),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+        assert_eq!(cancelled.len(), 1);
+
+        // A second cancel finds nothing left to cancel.
+        let again = adapter.cancel(id).await.unwrap();
+        assert!(matches!(again, CancelResult::NotCancellable));
+        assert!(!adapter.begin_delivery(id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn cancel_unknown_email_returns_not_found() {
+        let adapter = fresh_adapter().await;
+        let result = adapter.cancel(EmailId::default()).await.unwrap();
+        assert!(matches!(result, CancelResult::NotFound));
+    }
+
+    #[tokio::test]
+    async fn cancel_ignores_a_stale_delivery_mark() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+
+        // The worker died between begin_delivery and end_delivery and its
+        // lease ran out: the email is back in the queue.
+        assert!(adapter.begin_delivery(id).await.unwrap());
+        sqlx::query(
+            "UPDATE emails SET delivery_started_at_ms = delivery_started_at_ms - ? WHERE id = ?",
+        )
+        .bind(crate::email_queue::PROCESSING_TIMEOUT_MS + 1)
+        .bind(id.as_uuid().as_bytes().to_vec())
+        .execute(adapter.pool())
+        .await
+        .unwrap();
+
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::Cancelled { .. }));
+    }
+
+    #[tokio::test]
+    async fn cancel_is_rejected_while_delivery_is_in_flight() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+
+        assert!(adapter.begin_delivery(id).await.unwrap());
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::NotCancellable));
+
+        // Back in the queue after a transient failure: cancellable again.
+        adapter.end_delivery(id).await.unwrap();
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::Cancelled { .. }));
+    }
+
+    #[tokio::test]
+    async fn cancel_is_rejected_once_sent() {
+        let adapter = fresh_adapter().await;
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        adapter
+            .publish(&LifecycleEvent::Sent {
+                id,
+                sender_name: SenderName::new("test"),
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let result = adapter.cancel(id).await.unwrap();
+        assert!(matches!(result, CancelResult::NotCancellable));
+    }
+
     #[tokio::test]
     async fn list_emails_template_filter_matches_only_named_mjml() {
         let adapter = fresh_adapter().await;
@@ -878,6 +1295,56 @@ mod tests {
         assert_eq!(all.len(), 2);
     }
 
+    #[tokio::test]
+    async fn list_emails_reports_the_pinned_template_version() {
+        let adapter = fresh_adapter().await;
+        for reference in ["welcome@3", "welcome", "welcome_back@1"] {
+            let mut envelope = sample_envelope();
+            envelope.body = BodySource::Mjml(MjmlSource::Named(reference.to_owned()));
+            adapter.save(EmailId::default(), &envelope).await.unwrap();
+        }
+
+        let filtered = adapter
+            .list_emails(ListEmailsParams {
+                template: Some("welcome".to_owned()),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+
+        let mut versions: Vec<_> = filtered
+            .iter()
+            .map(|r| (r.template.as_deref(), r.template_version))
+            .collect();
+        versions.sort_unstable();
+        assert_eq!(
+            versions,
+            [(Some("welcome"), None), (Some("welcome"), Some(3))]
+        );
+    }
+
+    #[tokio::test]
+    async fn list_emails_reports_localized_pins_under_their_template() {
+        let adapter = fresh_adapter().await;
+        let mut envelope = sample_envelope();
+        envelope.body = BodySource::Mjml(MjmlSource::Named("welcome.fr@2".to_owned()));
+        envelope.locale = Some("fr-CA".to_owned());
+        adapter.save(EmailId::default(), &envelope).await.unwrap();
+
+        let filtered = adapter
+            .list_emails(ListEmailsParams {
+                template: Some("welcome".to_owned()),
+                ..default_list_params()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].template.as_deref(), Some("welcome"));
+        assert_eq!(filtered[0].template_version, Some(2));
+        assert_eq!(filtered[0].locale.as_deref(), Some("fr"));
+    }
+
     #[tokio::test]
     async fn list_all_attachment_blobs_excludes_terminal_emails() {
         let adapter = fresh_adapter().await;
@@ -905,6 +1372,7 @@ mod tests {
                             backend: "fs".into(),
                             key: "key-a".into(),
                         },
+                        content_id: None,
                     },
                     AttachmentRef {
                         filename: "b.pdf".into(),
@@ -914,6 +1382,7 @@ mod tests {
                             backend: "fs".into(),
                             key: "key-b".into(),
                         },
+                        content_id: None,
                     },
                 ],
             )
@@ -934,6 +1403,7 @@ mod tests {
                         backend: "fs".into(),
                         key: "key-sent".into(),
                     },
+                    content_id: None,
                 }],
             )
             .await
@@ -961,6 +1431,7 @@ mod tests {
                         backend: "fs".into(),
                         key: "key-failed".into(),
                     },
+                    content_id: None,
                 }],
             )
             .await
@@ -2,16 +2,27 @@ use anyhow::Context;
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
 use catapulte_domain::entity::sender::SenderName;
 use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
+use catapulte_domain::port::webhook_outbox::DeliveryTarget;
 
 use crate::SqliteAdapter;
+use crate::webhook_subscription_store::all_subscriptions;
 
-impl EventPublisher for SqliteAdapter {
+impl SqliteAdapter {
+    /// Stores `event` under `event_id`, the id it is pushed with.
+    ///
     /// # Errors
     ///
     /// Returns `EventPublisherError::Publish` when the database insert fails.
-    async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
+    /// The event is written together with its outbox deliveries, or not at
+    /// all: one for the configured webhook and one for NATS when their outbox
+    /// is enabled, and one per subscription matching the event.
+    pub async fn publish_event(
+        &self,
+        event_id: uuid::Uuid,
+        event: &LifecycleEvent,
+    ) -> Result<(), EventPublisherError> {
         let email_id_bytes = event.email_id().as_uuid().as_bytes().to_vec();
-        let event_id_bytes = uuid::Uuid::now_v7().as_bytes().to_vec();
+        let event_id_bytes = event_id.as_bytes().to_vec();
         // payload is always written as a JSON object so new rows are consistent
         // with the pushed (webhook/NATS) payload. The column remains nullable so
         // rows written before this change keep their NULL value; do not add NOT
@@ -22,30 +33,75 @@ impl EventPublisher for SqliteAdapter {
             .error_class()
             .map(catapulte_domain::entity::error_class::ErrorClass::as_str);
 
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .context("starting lifecycle event transaction")
+            .map_err(|source| EventPublisherError::Publish { source })?;
         sqlx::query(
             "INSERT INTO lifecycle_events (id, email_id, event_type, payload, sender_name, error_class) VALUES (?, ?, ?, ?, ?, ?)",
         )
-        .bind(event_id_bytes)
+        .bind(event_id_bytes.clone())
         .bind(email_id_bytes)
         .bind(event.event_type())
         .bind(Some(payload))
         .bind(sender_name)
         .bind(error_class)
-        .execute(self.pool())
+        .execute(&mut *tx)
         .await
         .context("inserting lifecycle event")
         .map_err(|source| EventPublisherError::Publish { source })?;
 
+        let subscriptions = all_subscriptions(&mut tx)
+            .await
+            .map_err(|source| EventPublisherError::Publish { source })?;
+        let targets = self
+            .webhook_outbox
+            .then_some((DeliveryTarget::Webhook, None))
+            .into_iter()
+            .chain(self.nats_outbox.then_some((DeliveryTarget::Nats, None)))
+            .chain(
+                subscriptions
+                    .iter()
+                    .filter(|s| s.filter.matches(event))
+                    .map(|s| (DeliveryTarget::Webhook, Some(s.id.as_bytes().to_vec()))),
+            );
+        for (target, subscription_id) in targets {
+            sqlx::query(
+                "INSERT INTO webhook_outbox (id, event_id, target, subscription_id) VALUES (?, ?, ?, ?)",
+            )
+            .bind(uuid::Uuid::now_v7().as_bytes().to_vec())
+            .bind(event_id_bytes.clone())
+            .bind(target.as_str())
+            .bind(subscription_id)
+            .execute(&mut *tx)
+            .await
+            .context("queueing webhook delivery")
+            .map_err(|source| EventPublisherError::Publish { source })?;
+        }
+        tx.commit()
+            .await
+            .context("committing lifecycle event")
+            .map_err(|source| EventPublisherError::Publish { source })?;
+
         Ok(())
     }
 }
 
+impl EventPublisher for SqliteAdapter {
+    async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
+        self.publish_event(uuid::Uuid::now_v7(), event).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::email_repository::EmailRepository;
     use catapulte_domain::port::event_publisher::EventPublisher;
@@ -68,6 +124,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -2,7 +2,8 @@ use anyhow::Context;
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::sender::SenderName;
 use catapulte_domain::port::event_repository::{
-    EventRecord, EventRepository, EventRepositoryError, ListEventsParams,
+    EventFilter, EventRecord, EventRepository, EventRepositoryError, ListEventsAfterParams,
+    ListEventsParams,
 };
 use sqlx::QueryBuilder;
 use sqlx::Row;
@@ -10,30 +11,43 @@ use sqlx::Sqlite;
 
 use crate::SqliteAdapter;
 
+const SELECT_EVENTS: &str = "SELECT id, email_id, event_type, payload, sender_name, error_class, \
+     created_at FROM lifecycle_events WHERE 1=1";
+
+fn push_filter(qb: &mut QueryBuilder<Sqlite>, filter: &EventFilter) {
+    if let Some(email_id) = filter.email_id {
+        qb.push(" AND email_id = ");
+        qb.push_bind(email_id.as_uuid().as_bytes().to_vec());
+    }
+    if let Some(event_type) = filter.event_type.as_deref() {
+        qb.push(" AND event_type = ");
+        qb.push_bind(event_type.to_owned());
+    }
+    if let Some(sender_name) = filter.sender_name.as_deref() {
+        qb.push(" AND sender_name = ");
+        qb.push_bind(sender_name.to_owned());
+    }
+    if let Some(error_class) = filter.error_class.as_ref() {
+        qb.push(" AND error_class = ");
+        qb.push_bind(error_class.as_str().to_owned());
+    }
+}
+
 impl EventRepository for SqliteAdapter {
     async fn list_events(
         &self,
         params: ListEventsParams,
     ) -> Result<Vec<EventRecord>, EventRepositoryError> {
-        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(
-            "SELECT id, email_id, event_type, payload, sender_name, error_class, created_at FROM lifecycle_events WHERE 1=1",
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_EVENTS);
+        push_filter(
+            &mut qb,
+            &EventFilter {
+                email_id: params.email_id,
+                event_type: params.event_type,
+                sender_name: params.sender_name,
+                error_class: params.error_class,
+            },
         );
-        if let Some(email_id) = params.email_id {
-            qb.push(" AND email_id = ");
-            qb.push_bind(email_id.as_uuid().as_bytes().to_vec());
-        }
-        if let Some(event_type) = params.event_type.as_deref() {
-            qb.push(" AND event_type = ");
-            qb.push_bind(event_type.to_owned());
-        }
-        if let Some(sender_name) = params.sender_name.as_deref() {
-            qb.push(" AND sender_name = ");
-            qb.push_bind(sender_name.to_owned());
-        }
-        if let Some(error_class) = params.error_class.as_ref() {
-            qb.push(" AND error_class = ");
-            qb.push_bind(error_class.as_str().to_owned());
-        }
         if let Some(after) = params.after_ms {
             qb.push(" AND created_at > ");
             qb.push_bind(after);
@@ -59,6 +73,51 @@ impl EventRepository for SqliteAdapter {
             .collect::<anyhow::Result<Vec<_>>>()
             .map_err(|source| EventRepositoryError::Storage { source })
     }
+
+    async fn get_event(&self, id: uuid::Uuid) -> Result<Option<EventRecord>, EventRepositoryError> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_EVENTS);
+        qb.push(" AND id = ");
+        qb.push_bind(id.as_bytes().to_vec());
+        let row = qb
+            .build()
+            .fetch_optional(self.pool())
+            .await
+            .context("reading lifecycle event")
+            .map_err(|source| EventRepositoryError::Storage { source })?;
+        row.as_ref()
+            .map(SqliteAdapter::row_to_event_record)
+            .transpose()
+            .map_err(|source| EventRepositoryError::Storage { source })
+    }
+
+    async fn list_events_after(
+        &self,
+        params: ListEventsAfterParams,
+    ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_EVENTS);
+        push_filter(&mut qb, &params.filter);
+        if let Some(after) = params.after {
+            qb.push(" AND (created_at, id) > (");
+            qb.push_bind(after.created_at_ms);
+            qb.push(", ");
+            qb.push_bind(after.id.as_bytes().to_vec());
+            qb.push(")");
+        }
+        qb.push(" ORDER BY created_at, id LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing lifecycle events after a cursor")
+            .map_err(|source| EventRepositoryError::Storage { source })?;
+
+        rows.iter()
+            .map(SqliteAdapter::row_to_event_record)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|source| EventRepositoryError::Storage { source })
+    }
 }
 
 impl SqliteAdapter {
@@ -93,10 +152,13 @@ mod tests {
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::envelope::Envelope;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::entity::sender::SenderName;
     use catapulte_domain::port::email_repository::EmailRepository;
     use catapulte_domain::port::event_publisher::EventPublisher;
-    use catapulte_domain::port::event_repository::{EventRepository, ListEventsParams};
+    use catapulte_domain::port::event_repository::{
+        EventFilter, EventRepository, ListEventsAfterParams, ListEventsParams,
+    };
 
     use crate::SqliteAdapter;
 
@@ -116,6 +178,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -428,4 +496,62 @@ mod tests {
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].error_class.as_deref(), Some("template_resolve"));
     }
+
+    #[tokio::test]
+    async fn list_events_after_returns_later_events_oldest_first() {
+        let id = EmailId::default();
+        let adapter = adapter_with_email(id).await;
+        for _ in 0..3 {
+            adapter
+                .publish(&LifecycleEvent::Queued {
+                    id,
+                    correlation_id: None,
+                })
+                .await
+                .unwrap();
+        }
+        adapter
+            .publish(&LifecycleEvent::Cancelled {
+                id,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+        let all = adapter
+            .list_events_after(ListEventsAfterParams {
+                filter: EventFilter::default(),
+                after: None,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all.windows(2).all(|w| w[0].cursor() < w[1].cursor()));
+
+        let later = adapter
+            .list_events_after(ListEventsAfterParams {
+                filter: EventFilter {
+                    event_type: Some("queued".to_owned()),
+                    ..EventFilter::default()
+                },
+                after: Some(all[0].cursor()),
+                limit: 10,
+            })
+            .await
+            .unwrap();
+        let resumed = adapter.get_event(all[1].id).await.unwrap().unwrap();
+
+        assert_eq!(
+            later.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![all[1].id, all[2].id]
+        );
+        assert_eq!(resumed.cursor(), all[1].cursor());
+        assert!(
+            adapter
+                .get_event(uuid::Uuid::now_v7())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,247 @@
+use anyhow::Context;
+use catapulte_domain::entity::unsubscribe::MailingList;
+use catapulte_domain::port::suppression_list::{
+    ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+};
+use sqlx::{QueryBuilder, Row, Sqlite};
+
+use crate::SqliteAdapter;
+
+impl SuppressionList for SqliteAdapter {
+    async fn add(&self, address: &str, reason: &str) -> Result<(), SuppressionListError> {
+        sqlx::query("INSERT OR IGNORE INTO suppressions (address, reason) VALUES (?, ?)")
+            .bind(address)
+            .bind(reason)
+            .execute(self.pool())
+            .await
+            .context("inserting suppression")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(())
+    }
+
+    async fn remove(&self, address: &str) -> Result<bool, SuppressionListError> {
+        let result = sqlx::query("DELETE FROM suppressions WHERE address = ?")
+            .bind(address)
+            .execute(self.pool())
+            .await
+            .context("deleting suppression")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, SuppressionListError> {
+        let rows = sqlx::query(
+            "SELECT address, reason, created_at_ms FROM suppressions \
+             ORDER BY created_at_ms DESC, address LIMIT ? OFFSET ?",
+        )
+        .bind(i64::from(params.limit))
+        .bind(i64::from(params.offset))
+        .fetch_all(self.pool())
+        .await
+        .context("listing suppressions")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| -> anyhow::Result<Suppression> {
+                Ok(Suppression {
+                    address: row.try_get("address").context("reading address")?,
+                    reason: row.try_get("reason").context("reading reason")?,
+                    created_at_ms: row
+                        .try_get("created_at_ms")
+                        .context("reading created_at_ms")?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+
+    async fn find_suppressed(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("SELECT address FROM suppressions WHERE address IN (");
+        let mut sep = qb.separated(", ");
+        for address in addresses {
+            sep.push_bind(address.as_str());
+        }
+        qb.push(")");
+
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("querying suppressions")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| row.try_get("address").context("reading address"))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+
+    async fn opt_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        address: &str,
+    ) -> Result<bool, SuppressionListError> {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO list_opt_outs (sender, list_name, address) VALUES (?, ?, ?)",
+        )
+        .bind(sender)
+        .bind(list.as_str())
+        .bind(address)
+        .execute(self.pool())
+        .await
+        .context("inserting list opt-out")
+        .map_err(|source| SuppressionListError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_opted_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        if addresses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut qb: QueryBuilder<Sqlite> =
+            QueryBuilder::new("SELECT address FROM list_opt_outs WHERE sender = ");
+        qb.push_bind(sender);
+        qb.push(" AND list_name = ");
+        qb.push_bind(list.as_str());
+        qb.push(" AND address IN (");
+        let mut sep = qb.separated(", ");
+        for address in addresses {
+            sep.push_bind(address.as_str());
+        }
+        qb.push(")");
+
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("querying list opt-outs")
+            .map_err(|source| SuppressionListError::Storage { source })?;
+
+        rows.into_iter()
+            .map(|row| row.try_get("address").context("reading address"))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|source| SuppressionListError::Storage { source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::entity::unsubscribe::MailingList;
+    use catapulte_domain::port::suppression_list::{ListSuppressionsParams, SuppressionList};
+
+    use crate::SqliteAdapter;
+
+    async fn fresh_adapter() -> SqliteAdapter {
+        let adapter = SqliteAdapter::connect(":memory:").await.unwrap();
+        adapter.migrate().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn add_keeps_the_original_entry() {
+        let adapter = fresh_adapter().await;
+        adapter.add("bob@example.com", "bounce").await.unwrap();
+        adapter.add("bob@example.com", "manual").await.unwrap();
+
+        let entries = adapter
+            .list(ListSuppressionsParams {
+                limit: 10,
+                offset: 0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].address, "bob@example.com");
+        assert_eq!(entries[0].reason, "bounce");
+        assert!(entries[0].created_at_ms > 0);
+    }
+
+    #[tokio::test]
+    async fn find_suppressed_returns_only_listed_addresses() {
+        let adapter = fresh_adapter().await;
+        adapter.add("bob@example.com", "manual").await.unwrap();
+
+        let found = adapter
+            .find_suppressed(&["alice@example.com".into(), "bob@example.com".into()])
+            .await
+            .unwrap();
+        assert_eq!(found, vec!["bob@example.com".to_owned()]);
+        assert!(adapter.find_suppressed(&[]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn remove_reports_whether_the_address_was_suppressed() {
+        let adapter = fresh_adapter().await;
+        adapter.add("bob@example.com", "manual").await.unwrap();
+
+        assert!(adapter.remove("bob@example.com").await.unwrap());
+        assert!(!adapter.remove("bob@example.com").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn opt_outs_are_scoped_to_the_sender_and_the_list() {
+        let adapter = fresh_adapter().await;
+        let newsletter = MailingList::parse("newsletter").unwrap();
+        let updates = MailingList::parse("updates").unwrap();
+        assert!(
+            adapter
+                .opt_out("news@acme.com", &newsletter, "bob@example.com")
+                .await
+                .unwrap()
+        );
+        assert!(
+            !adapter
+                .opt_out("news@acme.com", &newsletter, "bob@example.com")
+                .await
+                .unwrap()
+        );
+
+        let addresses = ["alice@example.com".to_owned(), "bob@example.com".to_owned()];
+        assert_eq!(
+            adapter
+                .find_opted_out("news@acme.com", &newsletter, &addresses)
+                .await
+                .unwrap(),
+            vec!["bob@example.com".to_owned()]
+        );
+        assert!(
+            adapter
+                .find_opted_out("news@acme.com", &updates, &addresses)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            adapter
+                .find_opted_out("other@acme.com", &newsletter, &addresses)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            adapter
+                .find_suppressed(&addresses)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::Context;
+use catapulte_domain::entity::template::Template;
+use catapulte_domain::port::template_store::{TemplateStore, TemplateStoreError};
+use sqlx::Row;
+use sqlx::sqlite::SqliteRow;
+
+use crate::SqliteAdapter;
+
+fn row_to_template(name: &str, row: &SqliteRow) -> anyhow::Result<Template> {
+    let version: i64 = row.try_get("version").context("reading version")?;
+    Ok(Template {
+        name: name.to_owned(),
+        version: u32::try_from(version).context("template version out of range")?,
+        content: row.try_get("content").context("reading content")?,
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
+
+impl TemplateStore for SqliteAdapter {
+    async fn publish(&self, name: &str, content: &str) -> Result<Template, TemplateStoreError> {
+        // Deleted versions still count, so a version number is never reused.
+        let row = sqlx::query(
+            "INSERT INTO templates (name, version, content) \
+             SELECT ?, COALESCE(MAX(version), 0) + 1, ? FROM templates WHERE name = ? \
+             RETURNING version, content, created_at_ms",
+        )
+        .bind(name)
+        .bind(content)
+        .bind(name)
+        .fetch_one(self.pool())
+        .await
+        .context("inserting template version")
+        .map_err(|source| TemplateStoreError::Storage { source })?;
+        row_to_template(name, &row).map_err(|source| TemplateStoreError::Storage { source })
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<Template>, TemplateStoreError> {
+        let query = match version {
+            Some(version) => sqlx::query(
+                "SELECT version, content, created_at_ms FROM templates \
+                 WHERE name = ? AND version = ?",
+            )
+            .bind(name)
+            .bind(i64::from(version)),
+            None => sqlx::query(
+                "SELECT version, content, created_at_ms FROM templates \
+                 WHERE name = ? AND deleted_at_ms IS NULL ORDER BY version DESC LIMIT 1",
+            )
+            .bind(name),
+        };
+        let row = query
+            .fetch_optional(self.pool())
+            .await
+            .context("fetching template")
+            .map_err(|source| TemplateStoreError::Storage { source })?;
+        row.map(|row| row_to_template(name, &row))
+            .transpose()
+            .map_err(|source| TemplateStoreError::Storage { source })
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool, TemplateStoreError> {
+        let result = sqlx::query(
+            "UPDATE templates SET deleted_at_ms = unixepoch('now', 'subsec') * 1000 \
+             WHERE name = ? AND deleted_at_ms IS NULL",
+        )
+        .bind(name)
+        .execute(self.pool())
+        .await
+        .context("deleting template")
+        .map_err(|source| TemplateStoreError::Storage { source })?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::port::template_store::TemplateStore;
+
+    use crate::SqliteAdapter;
+
+    async fn fresh_adapter() -> SqliteAdapter {
+        let adapter = SqliteAdapter::connect(":memory:").await.unwrap();
+        adapter.migrate().await.unwrap();
+        adapter
+    }
+
+    #[tokio::test]
+    async fn publish_numbers_versions_per_name() {
+        let adapter = fresh_adapter().await;
+        let first = adapter.publish("welcome", "<mjml>1</mjml>").await.unwrap();
+        let second = adapter.publish("welcome", "<mjml>2</mjml>").await.unwrap();
+        let other = adapter.publish("receipt", "<mjml>r</mjml>").await.unwrap();
+
+        assert_eq!((first.version, second.version, other.version), (1, 2, 1));
+        assert!(second.created_at_ms > 0);
+        let latest = adapter.get("welcome", None).await.unwrap().unwrap();
+        assert_eq!(latest.content, "<mjml>2</mjml>");
+        let pinned = adapter.get("welcome", Some(1)).await.unwrap().unwrap();
+        assert_eq!(pinned.content, "<mjml>1</mjml>");
+        assert!(adapter.get("welcome", Some(3)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_retires_the_name_but_keeps_its_versions() {
+        let adapter = fresh_adapter().await;
+        adapter.publish("welcome", "<mjml>1</mjml>").await.unwrap();
+
+        assert!(adapter.delete("welcome").await.unwrap());
+        assert!(!adapter.delete("welcome").await.unwrap());
+        assert!(adapter.get("welcome", None).await.unwrap().is_none());
+        assert!(adapter.get("welcome", Some(1)).await.unwrap().is_some());
+
+        let republished = adapter.publish("welcome", "<mjml>2</mjml>").await.unwrap();
+        assert_eq!(republished.version, 2);
+        let latest = adapter.get("welcome", None).await.unwrap().unwrap();
+        assert_eq!(latest.version, 2);
+    }
+}
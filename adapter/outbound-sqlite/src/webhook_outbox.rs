@@ -0,0 +1,369 @@
+use anyhow::Context;
+use catapulte_domain::entity::email::EmailId;
+use catapulte_domain::port::webhook_outbox::{
+    DeliveryTarget, ListWebhookDeliveriesParams, WebhookDelivery, WebhookDeliveryStatus,
+    WebhookOutbox, WebhookOutboxError,
+};
+use sqlx::{QueryBuilder, Row, Sqlite};
+
+use crate::SqliteAdapter;
+
+const SELECT_DELIVERIES: &str = "SELECT o.id, o.event_id, o.target, o.subscription_id, \
+     e.email_id, e.event_type, e.sender_name, e.payload, o.status, o.attempts, \
+     o.next_attempt_at_ms, o.last_error, o.created_at_ms \
+     FROM webhook_outbox o JOIN lifecycle_events e ON e.id = o.event_id";
+
+fn storage_error(source: anyhow::Error) -> WebhookOutboxError {
+    WebhookOutboxError::Storage { source }
+}
+
+impl WebhookOutbox for SqliteAdapter {
+    async fn claim_due(
+        &self,
+        now_ms: i64,
+        lease_until_ms: i64,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        // The pool holds a single connection, so no other dispatcher can
+        // claim between the select and the update.
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .context("starting webhook claim transaction")
+            .map_err(storage_error)?;
+        let mut select: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_DELIVERIES);
+        select.push(" WHERE o.status = 'pending' AND o.next_attempt_at_ms <= ");
+        select.push_bind(now_ms);
+        select.push(" ORDER BY o.next_attempt_at_ms, o.id LIMIT ");
+        select.push_bind(i64::from(limit));
+        let rows = select
+            .build()
+            .fetch_all(&mut *tx)
+            .await
+            .context("selecting due webhook deliveries")
+            .map_err(storage_error)?;
+        let deliveries = rows
+            .iter()
+            .map(row_to_delivery)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(storage_error)?;
+        if !deliveries.is_empty() {
+            let mut qb: QueryBuilder<Sqlite> =
+                QueryBuilder::new("UPDATE webhook_outbox SET next_attempt_at_ms = ");
+            qb.push_bind(lease_until_ms);
+            qb.push(" WHERE id IN (");
+            let mut sep = qb.separated(", ");
+            for delivery in &deliveries {
+                sep.push_bind(delivery.id.as_bytes().to_vec());
+            }
+            qb.push(")");
+            qb.build()
+                .execute(&mut *tx)
+                .await
+                .context("leasing webhook deliveries")
+                .map_err(storage_error)?;
+        }
+        tx.commit()
+            .await
+            .context("committing webhook claim")
+            .map_err(storage_error)?;
+        Ok(deliveries)
+    }
+
+    async fn complete(&self, id: uuid::Uuid) -> Result<(), WebhookOutboxError> {
+        sqlx::query("DELETE FROM webhook_outbox WHERE id = ?")
+            .bind(id.as_bytes().to_vec())
+            .execute(self.pool())
+            .await
+            .context("deleting delivered webhook")
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: uuid::Uuid,
+        error: &str,
+        next_attempt_at_ms: Option<i64>,
+    ) -> Result<(), WebhookOutboxError> {
+        let status = match next_attempt_at_ms {
+            Some(_) => WebhookDeliveryStatus::Pending,
+            None => WebhookDeliveryStatus::Failed,
+        };
+        sqlx::query(
+            "UPDATE webhook_outbox SET status = ?, attempts = attempts + 1, last_error = ?, \
+             next_attempt_at_ms = COALESCE(?, next_attempt_at_ms) WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(error)
+        .bind(next_attempt_at_ms)
+        .bind(id.as_bytes().to_vec())
+        .execute(self.pool())
+        .await
+        .context("recording webhook failure")
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_DELIVERIES);
+        qb.push(" WHERE 1 = 1");
+        if let Some(status) = params.status {
+            qb.push(" AND o.status = ");
+            qb.push_bind(status.as_str());
+        }
+        if let Some(subscription_id) = params.subscription_id {
+            qb.push(" AND o.subscription_id = ");
+            qb.push_bind(subscription_id.as_bytes().to_vec());
+        }
+        qb.push(" ORDER BY o.created_at_ms DESC, o.id DESC LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+        qb.push(" OFFSET ");
+        qb.push_bind(i64::from(params.offset));
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing webhook deliveries")
+            .map_err(storage_error)?;
+        rows.iter()
+            .map(row_to_delivery)
+            .collect::<anyhow::Result<_>>()
+            .map_err(storage_error)
+    }
+
+    async fn redeliver(&self, id: uuid::Uuid, now_ms: i64) -> Result<bool, WebhookOutboxError> {
+        let result = sqlx::query(
+            "UPDATE webhook_outbox SET status = 'pending', attempts = 0, next_attempt_at_ms = ? \
+             WHERE id = ?",
+        )
+        .bind(now_ms)
+        .bind(id.as_bytes().to_vec())
+        .execute(self.pool())
+        .await
+        .context("rescheduling webhook delivery")
+        .map_err(storage_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn row_to_delivery(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<WebhookDelivery> {
+    let id: Vec<u8> = row.try_get("id").context("reading id")?;
+    let event_id: Vec<u8> = row.try_get("event_id").context("reading event_id")?;
+    let subscription_id: Option<Vec<u8>> = row
+        .try_get("subscription_id")
+        .context("reading subscription_id")?;
+    let email_id: Vec<u8> = row.try_get("email_id").context("reading email_id")?;
+    let payload: Option<sqlx::types::Json<serde_json::Value>> =
+        row.try_get("payload").context("reading payload")?;
+    let status: String = row.try_get("status").context("reading status")?;
+    let attempts: i64 = row.try_get("attempts").context("reading attempts")?;
+    Ok(WebhookDelivery {
+        id: uuid::Uuid::from_slice(&id).context("parsing id")?,
+        event_id: uuid::Uuid::from_slice(&event_id).context("parsing event_id")?,
+        subscription_id: subscription_id
+            .map(|bytes| uuid::Uuid::from_slice(&bytes))
+            .transpose()
+            .context("parsing subscription_id")?,
+        target: parse_target(
+            &row.try_get::<String, _>("target")
+                .context("reading target")?,
+        )?,
+        email_id: EmailId::from(uuid::Uuid::from_slice(&email_id).context("parsing email_id")?),
+        event_type: row.try_get("event_type").context("reading event_type")?,
+        sender_name: row.try_get("sender_name").context("reading sender_name")?,
+        payload: payload.map_or(serde_json::Value::Null, |j| j.0),
+        status: match status.as_str() {
+            "failed" => WebhookDeliveryStatus::Failed,
+            _ => WebhookDeliveryStatus::Pending,
+        },
+        attempts: u32::try_from(attempts).context("reading attempts")?,
+        next_attempt_at_ms: row
+            .try_get("next_attempt_at_ms")
+            .context("reading next_attempt_at_ms")?,
+        last_error: row.try_get("last_error").context("reading last_error")?,
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
+
+fn parse_target(target: &str) -> anyhow::Result<DeliveryTarget> {
+    match target {
+        "webhook" => Ok(DeliveryTarget::Webhook),
+        "nats" => Ok(DeliveryTarget::Nats),
+        other => anyhow::bail!("unknown delivery target {other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::entity::body::{BodySource, Plain};
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+    use catapulte_domain::port::email_repository::EmailRepository;
+    use catapulte_domain::port::event_publisher::EventPublisher;
+    use catapulte_domain::port::webhook_outbox::{
+        DeliveryTarget, ListWebhookDeliveriesParams, WebhookDeliveryStatus, WebhookOutbox,
+    };
+
+    use crate::SqliteAdapter;
+
+    fn sample_envelope() -> Envelope {
+        Envelope {
+            idempotency_key: None,
+            correlation_id: None,
+            subject: None,
+            sender: "sender@example.com".to_owned(),
+            recipients: vec![],
+            body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
+            variables: serde_json::Map::new(),
+            attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
+        }
+    }
+
+    async fn adapter_with_event(outbox: bool) -> (SqliteAdapter, EmailId) {
+        let mut adapter = SqliteAdapter::connect(":memory:").await.unwrap();
+        if outbox {
+            adapter = adapter.with_webhook_outbox();
+        }
+        adapter.migrate().await.unwrap();
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        adapter
+            .publish(&LifecycleEvent::Queued {
+                id,
+                correlation_id: Some("corr-1".into()),
+            })
+            .await
+            .unwrap();
+        (adapter, id)
+    }
+
+    fn all() -> ListWebhookDeliveriesParams {
+        ListWebhookDeliveriesParams {
+            status: None,
+            subscription_id: None,
+            limit: 10,
+            offset: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_queued_only_when_the_outbox_is_enabled() {
+        let (without, _) = adapter_with_event(false).await;
+        assert!(without.list(all()).await.unwrap().is_empty());
+
+        let (with, id) = adapter_with_event(true).await;
+        let deliveries = with.list(all()).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].email_id, id);
+        assert_eq!(deliveries[0].event_type, "queued");
+        assert_eq!(deliveries[0].payload["correlation_id"], "corr-1");
+        assert_eq!(deliveries[0].status, WebhookDeliveryStatus::Pending);
+        assert_eq!(deliveries[0].target, DeliveryTarget::Webhook);
+    }
+
+    #[tokio::test]
+    async fn nats_deliveries_share_the_event_id_with_the_webhook() {
+        let adapter = SqliteAdapter::connect(":memory:")
+            .await
+            .unwrap()
+            .with_webhook_outbox()
+            .with_nats_outbox();
+        adapter.migrate().await.unwrap();
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+        let event_id = uuid::Uuid::now_v7();
+        adapter
+            .publish_event(
+                event_id,
+                &LifecycleEvent::Cancelled {
+                    id,
+                    correlation_id: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut targets: Vec<_> = adapter
+            .list(all())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|d| {
+                assert_eq!(d.event_id, event_id);
+                d.target.as_str()
+            })
+            .collect();
+        targets.sort_unstable();
+        assert_eq!(targets, ["nats", "webhook"]);
+    }
+
+    #[tokio::test]
+    async fn claimed_deliveries_are_leased_until_retried_or_completed() {
+        let (adapter, _) = adapter_with_event(true).await;
+        let now = i64::MAX / 2;
+
+        let claimed = adapter.claim_due(now, now + 60_000, 10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        let id = claimed[0].id;
+        assert!(
+            adapter
+                .claim_due(now, now + 60_000, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        adapter
+            .record_failure(id, "503 Service Unavailable", Some(now))
+            .await
+            .unwrap();
+        let retried = adapter.claim_due(now, now + 60_000, 10).await.unwrap();
+        assert_eq!(retried[0].attempts, 1);
+        assert_eq!(
+            retried[0].last_error.as_deref(),
+            Some("503 Service Unavailable")
+        );
+
+        adapter.complete(id).await.unwrap();
+        assert!(adapter.list(all()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_deliveries_wait_for_a_manual_redelivery() {
+        let (adapter, _) = adapter_with_event(true).await;
+        let now = i64::MAX / 2;
+        let id = adapter.claim_due(now, now, 10).await.unwrap()[0].id;
+
+        adapter.record_failure(id, "timeout", None).await.unwrap();
+        assert!(adapter.claim_due(now, now, 10).await.unwrap().is_empty());
+        let failed = adapter
+            .list(ListWebhookDeliveriesParams {
+                status: Some(WebhookDeliveryStatus::Failed),
+                ..all()
+            })
+            .await
+            .unwrap();
+        assert_eq!(failed.len(), 1);
+
+        assert!(adapter.redeliver(id, now).await.unwrap());
+        assert!(!adapter.redeliver(uuid::Uuid::now_v7(), now).await.unwrap());
+        let claimed = adapter.claim_due(now, now, 10).await.unwrap();
+        assert_eq!(claimed[0].attempts, 0);
+        assert_eq!(claimed[0].status, WebhookDeliveryStatus::Pending);
+    }
+}
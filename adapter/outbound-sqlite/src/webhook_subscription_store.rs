@@ -0,0 +1,235 @@
+use anyhow::Context;
+use catapulte_domain::entity::webhook_subscription::{SubscriptionFilter, WebhookSubscription};
+use catapulte_domain::port::webhook_subscription_store::{
+    ListWebhookSubscriptionsParams, WebhookSubscriptionStore, WebhookSubscriptionStoreError,
+};
+use sqlx::{QueryBuilder, Row, Sqlite, SqliteConnection};
+
+use crate::SqliteAdapter;
+
+const SELECT_SUBSCRIPTIONS: &str = "SELECT id, url, secret, event_types, correlation_id_prefix, \
+     created_at_ms FROM webhook_subscriptions";
+
+fn storage_error(source: anyhow::Error) -> WebhookSubscriptionStoreError {
+    WebhookSubscriptionStoreError::Storage { source }
+}
+
+impl WebhookSubscriptionStore for SqliteAdapter {
+    async fn insert(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), WebhookSubscriptionStoreError> {
+        sqlx::query(
+            "INSERT INTO webhook_subscriptions \
+             (id, url, secret, event_types, correlation_id_prefix, created_at_ms) \
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(subscription.id.as_bytes().to_vec())
+        .bind(subscription.url.as_str())
+        .bind(subscription.secret.as_str())
+        .bind(sqlx::types::Json(&subscription.filter.event_types))
+        .bind(subscription.filter.correlation_id_prefix.as_deref())
+        .bind(subscription.created_at_ms)
+        .execute(self.pool())
+        .await
+        .context("inserting webhook subscription")
+        .map_err(storage_error)?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_SUBSCRIPTIONS);
+        qb.push(" WHERE id = ");
+        qb.push_bind(id.as_bytes().to_vec());
+        let row = qb
+            .build()
+            .fetch_optional(self.pool())
+            .await
+            .context("reading webhook subscription")
+            .map_err(storage_error)?;
+        row.as_ref()
+            .map(row_to_subscription)
+            .transpose()
+            .map_err(storage_error)
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> Result<Vec<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_SUBSCRIPTIONS);
+        qb.push(" ORDER BY created_at_ms, id LIMIT ");
+        qb.push_bind(i64::from(params.limit));
+        qb.push(" OFFSET ");
+        qb.push_bind(i64::from(params.offset));
+        let rows = qb
+            .build()
+            .fetch_all(self.pool())
+            .await
+            .context("listing webhook subscriptions")
+            .map_err(storage_error)?;
+        rows.iter()
+            .map(row_to_subscription)
+            .collect::<anyhow::Result<_>>()
+            .map_err(storage_error)
+    }
+
+    async fn delete(&self, id: uuid::Uuid) -> Result<bool, WebhookSubscriptionStoreError> {
+        // Pending deliveries go along through the ON DELETE CASCADE.
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = ?")
+            .bind(id.as_bytes().to_vec())
+            .execute(self.pool())
+            .await
+            .context("deleting webhook subscription")
+            .map_err(storage_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+/// Every subscription, read within the transaction inserting an event.
+pub(crate) async fn all_subscriptions(
+    conn: &mut SqliteConnection,
+) -> anyhow::Result<Vec<WebhookSubscription>> {
+    let rows = sqlx::query(SELECT_SUBSCRIPTIONS)
+        .fetch_all(conn)
+        .await
+        .context("reading webhook subscriptions")?;
+    rows.iter().map(row_to_subscription).collect()
+}
+
+fn row_to_subscription(row: &sqlx::sqlite::SqliteRow) -> anyhow::Result<WebhookSubscription> {
+    let id: Vec<u8> = row.try_get("id").context("reading id")?;
+    let url: String = row.try_get("url").context("reading url")?;
+    let event_types: sqlx::types::Json<Vec<String>> =
+        row.try_get("event_types").context("reading event_types")?;
+    Ok(WebhookSubscription {
+        id: uuid::Uuid::from_slice(&id).context("parsing id")?,
+        url: url::Url::parse(&url).context("parsing url")?,
+        secret: row.try_get("secret").context("reading secret")?,
+        filter: SubscriptionFilter {
+            event_types: event_types.0,
+            correlation_id_prefix: row
+                .try_get("correlation_id_prefix")
+                .context("reading correlation_id_prefix")?,
+        },
+        created_at_ms: row
+            .try_get("created_at_ms")
+            .context("reading created_at_ms")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use catapulte_domain::entity::body::{BodySource, Plain};
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+    use catapulte_domain::entity::webhook_subscription::{SubscriptionFilter, WebhookSubscription};
+    use catapulte_domain::port::email_repository::EmailRepository;
+    use catapulte_domain::port::event_publisher::EventPublisher;
+    use catapulte_domain::port::webhook_outbox::{ListWebhookDeliveriesParams, WebhookOutbox};
+    use catapulte_domain::port::webhook_subscription_store::WebhookSubscriptionStore;
+
+    use crate::SqliteAdapter;
+
+    fn sample_envelope() -> Envelope {
+        Envelope {
+            idempotency_key: None,
+            correlation_id: None,
+            subject: None,
+            sender: "sender@example.com".to_owned(),
+            recipients: vec![],
+            body: BodySource::Plain(Plain::try_new(Some("hello".to_owned()), None).unwrap()),
+            variables: serde_json::Map::new(),
+            attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
+        }
+    }
+
+    fn subscription(filter: SubscriptionFilter) -> WebhookSubscription {
+        WebhookSubscription {
+            id: uuid::Uuid::now_v7(),
+            url: "https://crm.acme.com/hooks".parse().unwrap(),
+            secret: "s".repeat(32),
+            filter,
+            created_at_ms: 0,
+        }
+    }
+
+    fn deliveries_of(subscription_id: uuid::Uuid) -> ListWebhookDeliveriesParams {
+        ListWebhookDeliveriesParams {
+            status: None,
+            subscription_id: Some(subscription_id),
+            limit: 10,
+            offset: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn events_are_queued_for_matching_subscriptions_until_deleted() {
+        let adapter = SqliteAdapter::connect(":memory:").await.unwrap();
+        adapter.migrate().await.unwrap();
+        let marketing = subscription(SubscriptionFilter {
+            event_types: vec![],
+            correlation_id_prefix: Some("marketing:".into()),
+        });
+        let billing = subscription(SubscriptionFilter {
+            event_types: vec!["cancelled".into()],
+            correlation_id_prefix: None,
+        });
+        adapter.insert(&marketing).await.unwrap();
+        adapter.insert(&billing).await.unwrap();
+        let id = EmailId::default();
+        adapter.save(id, &sample_envelope()).await.unwrap();
+
+        adapter
+            .publish(&LifecycleEvent::Queued {
+                id,
+                correlation_id: Some("marketing:spring-sale".into()),
+            })
+            .await
+            .unwrap();
+
+        let queued = WebhookOutbox::list(&adapter, deliveries_of(marketing.id))
+            .await
+            .unwrap();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].subscription_id, Some(marketing.id));
+        assert!(
+            WebhookOutbox::list(&adapter, deliveries_of(billing.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            adapter.get(marketing.id).await.unwrap().as_ref(),
+            Some(&marketing)
+        );
+
+        assert!(
+            WebhookSubscriptionStore::delete(&adapter, marketing.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !WebhookSubscriptionStore::delete(&adapter, marketing.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            WebhookOutbox::list(&adapter, deliveries_of(marketing.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -1,31 +1,193 @@
+pub mod signature;
+
 use anyhow::Context;
+use catapulte_domain::entity::cloud_event::{
+    CloudEvent, CloudEventsFormat, CloudEventsMode, DATA_CONTENT_TYPE, STRUCTURED_CONTENT_TYPE,
+};
+use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+use catapulte_domain::entity::webhook_subscription::WebhookSubscription;
+use catapulte_domain::port::clock::{Clock, SystemClock};
 use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
+use catapulte_domain::port::webhook_outbox::WebhookDelivery;
+
+use crate::signature::{DELIVERY_ID_HEADER, EVENT_ID_HEADER, SIGNATURE_HEADER, WebhookSigner};
 
 #[derive(Clone)]
 pub struct WebhookPublisher {
     client: reqwest::Client,
     url: url::Url,
+    signer: Option<WebhookSigner>,
+    cloud_events: Option<CloudEventsFormat>,
+}
+
+/// A request body along with the headers describing it.
+struct Encoded {
+    body: Vec<u8>,
+    content_type: &'static str,
+    headers: Vec<(String, String)>,
 }
 
 impl WebhookPublisher {
     #[must_use]
     pub fn new(client: reqwest::Client, url: url::Url) -> Self {
-        Self { client, url }
+        Self {
+            client,
+            url,
+            signer: None,
+            cloud_events: None,
+        }
+    }
+
+    /// Delivers to an API-managed subscription, signed with its secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the subscription secret is too short.
+    pub fn for_subscription(
+        client: reqwest::Client,
+        subscription: &WebhookSubscription,
+    ) -> anyhow::Result<Self> {
+        let signer = WebhookSigner::new([subscription.secret.as_str()])
+            .context("loading subscription secret")?;
+        Ok(Self::new(client, subscription.url.clone()).with_signer(signer))
+    }
+
+    /// Signs every delivery with `signer`.
+    #[must_use]
+    pub fn with_signer(mut self, signer: WebhookSigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Wraps every event in a `CloudEvents` envelope.
+    #[must_use]
+    pub fn with_cloud_events(mut self, format: Option<CloudEventsFormat>) -> Self {
+        self.cloud_events = format;
+        self
+    }
+
+    /// Serialized once per event: the signature covers these exact bytes.
+    fn encode(
+        &self,
+        event_id: uuid::Uuid,
+        event_type: &str,
+        email_id: EmailId,
+        time_ms: i64,
+        payload: &serde_json::Value,
+    ) -> anyhow::Result<Encoded> {
+        let Some(format) = &self.cloud_events else {
+            return Ok(Encoded {
+                body: serde_json::to_vec(&body_json(event_type, email_id, payload))
+                    .context("serializing webhook body")?,
+                content_type: DATA_CONTENT_TYPE,
+                headers: Vec::new(),
+            });
+        };
+        let event = CloudEvent {
+            id: event_id,
+            source: format.source.clone(),
+            event_type: event_type.to_owned(),
+            email_id,
+            time_ms,
+            data: payload.clone(),
+        };
+        Ok(match format.mode {
+            CloudEventsMode::Structured => Encoded {
+                body: serde_json::to_vec(&event.to_structured_json())
+                    .context("serializing webhook body")?,
+                content_type: STRUCTURED_CONTENT_TYPE,
+                headers: Vec::new(),
+            },
+            CloudEventsMode::Binary => Encoded {
+                body: serde_json::to_vec(payload).context("serializing webhook body")?,
+                content_type: DATA_CONTENT_TYPE,
+                headers: event
+                    .attributes()
+                    .into_iter()
+                    .map(|(name, value)| (format!("ce-{name}"), value))
+                    .collect(),
+            },
+        })
+    }
+
+    async fn attempt(&self, encoded: &Encoded, event_id: &str) -> anyhow::Result<()> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, encoded.content_type)
+            .header(EVENT_ID_HEADER, event_id)
+            .header(
+                DELIVERY_ID_HEADER,
+                uuid::Uuid::now_v7().hyphenated().to_string(),
+            );
+        for (name, value) in &encoded.headers {
+            request = request.header(name, value);
+        }
+        if let Some(signer) = &self.signer {
+            // Signed per attempt, so a retry carries a fresh timestamp.
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            request = request.header(SIGNATURE_HEADER, signer.sign(timestamp, &encoded.body));
+        }
+        request
+            .body(encoded.body.clone())
+            .send()
+            .await
+            .context("sending webhook request")?
+            .error_for_status()
+            .context("webhook returned error status")?;
+        Ok(())
+    }
+
+    /// Makes a single attempt at an outbox delivery; retries are left to the
+    /// outbox dispatcher. The body matches what [`EventPublisher::publish`]
+    /// sends for the same event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the request fails or the webhook answers with a
+    /// non-2xx status.
+    pub async fn deliver(&self, delivery: &WebhookDelivery) -> anyhow::Result<()> {
+        let encoded = self.encode(
+            delivery.event_id,
+            &delivery.event_type,
+            delivery.email_id,
+            delivery.created_at_ms,
+            &delivery.payload,
+        )?;
+        self.attempt(&encoded, &delivery.event_id.hyphenated().to_string())
+            .await
     }
 }
 
-fn event_to_json(event: &LifecycleEvent) -> serde_json::Value {
+fn body_json(
+    event_type: &str,
+    email_id: EmailId,
+    payload: &serde_json::Value,
+) -> serde_json::Value {
     serde_json::json!({
-        "event_type": event.event_type(),
-        "email_id": event.email_id().as_uuid().to_string(),
-        "payload": event.payload(),
+        "event_type": event_type,
+        "email_id": email_id.as_uuid().to_string(),
+        "payload": payload,
     })
 }
 
 impl EventPublisher for WebhookPublisher {
     async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
-        let body = event_to_json(event);
+        let id = uuid::Uuid::now_v7();
+        let encoded = self
+            .encode(
+                id,
+                event.event_type(),
+                *event.email_id(),
+                SystemClock.now_ms(),
+                &event.payload(),
+            )
+            .map_err(|source| EventPublisherError::Publish { source })?;
+        let event_id = id.hyphenated().to_string();
         let mut delay = std::time::Duration::from_millis(100);
         let mut last_err: Option<anyhow::Error> = None;
         for attempt in 0..3u32 {
@@ -33,18 +195,8 @@ impl EventPublisher for WebhookPublisher {
                 tokio::time::sleep(delay).await;
                 delay = std::time::Duration::from_millis(500);
             }
-            match self
-                .client
-                .post(self.url.clone())
-                .json(&body)
-                .send()
-                .await
-                .context("sending webhook request")
-                .and_then(|r| {
-                    r.error_for_status()
-                        .context("webhook returned error status")
-                }) {
-                Ok(_) => return Ok(()),
+            match self.attempt(&encoded, &event_id).await {
+                Ok(()) => return Ok(()),
                 Err(e) => {
                     tracing::warn!(error = %e, attempt, "webhook delivery failed");
                     last_err = Some(e);
@@ -60,12 +212,18 @@ impl EventPublisher for WebhookPublisher {
 pub struct WebhookConfig {
     pub url: Option<url::Url>,
     pub timeout_ms: u64,
+    /// Active signing secrets; deliveries are unsigned when empty.
+    pub secrets: Vec<String>,
+    /// Envelope of every webhook body, subscriptions included; the plain
+    /// `{event_type, email_id, payload}` body when `None`.
+    pub cloud_events: Option<CloudEventsFormat>,
 }
 
 impl WebhookConfig {
     /// # Errors
     ///
-    /// Returns an error if the URL env var is set but unparseable.
+    /// Returns an error if the URL env var is set but unparseable, or the
+    /// `CloudEvents` mode is unknown.
     pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
         let url = std::env::var(format!("{prefix}_URL"))
             .ok()
@@ -75,26 +233,60 @@ impl WebhookConfig {
             .ok()
             .and_then(|v| v.parse().ok())
             .unwrap_or(5_000u64);
-        Ok(Self { url, timeout_ms })
+        let secrets = std::env::var(format!("{prefix}_SECRETS"))
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let cloud_events = CloudEventsFormat::parse(
+            std::env::var(format!("{prefix}_CLOUDEVENTS"))
+                .ok()
+                .as_deref(),
+            std::env::var(format!("{prefix}_CLOUDEVENTS_SOURCE"))
+                .ok()
+                .as_deref(),
+        )
+        .context("parsing webhook CloudEvents mode")?;
+        Ok(Self {
+            url,
+            timeout_ms,
+            secrets,
+            cloud_events,
+        })
     }
 
+    /// Returns the HTTP client every webhook is delivered through, and the
+    /// publisher of the configured webhook if any.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the reqwest client cannot be built.
-    pub fn build(self) -> anyhow::Result<Option<WebhookPublisher>> {
-        let Some(url) = self.url else {
-            return Ok(None);
-        };
+    /// Returns an error if the reqwest client cannot be built, or a secret
+    /// is too short.
+    pub fn build(self) -> anyhow::Result<(reqwest::Client, Option<WebhookPublisher>)> {
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_millis(self.timeout_ms))
             .build()
             .context("building reqwest client")?;
-        Ok(Some(WebhookPublisher::new(client, url)))
+        let Some(url) = self.url else {
+            return Ok((client, None));
+        };
+        let publisher =
+            WebhookPublisher::new(client.clone(), url).with_cloud_events(self.cloud_events);
+        if self.secrets.is_empty() {
+            return Ok((client, Some(publisher)));
+        }
+        let signer = WebhookSigner::new(&self.secrets).context("loading webhook secrets")?;
+        Ok((client, Some(publisher.with_signer(signer))))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use catapulte_domain::entity::cloud_event::{CloudEventsFormat, CloudEventsMode};
     use catapulte_domain::entity::email::EmailId;
     use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
     use catapulte_domain::port::event_publisher::EventPublisher;
@@ -102,6 +294,7 @@ mod tests {
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use super::WebhookPublisher;
+    use crate::signature::{DELIVERY_ID_HEADER, EVENT_ID_HEADER, SIGNATURE_HEADER, WebhookSigner};
 
     fn publisher_for(server: &MockServer) -> WebhookPublisher {
         let url = url::Url::parse(&server.uri()).unwrap();
@@ -292,4 +485,124 @@ mod tests {
         });
         assert_eq!(body, expected);
     }
+
+    #[tokio::test]
+    async fn signed_delivery_covers_the_exact_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let signer = WebhookSigner::new(["0123456789abcdef0123456789abcdef"]).unwrap();
+        let publisher = publisher_for(&server).with_signer(signer.clone());
+        publisher
+            .publish(&LifecycleEvent::Queued {
+                id: EmailId::default(),
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let header = requests[0].headers[SIGNATURE_HEADER].to_str().unwrap();
+        let timestamp: u64 = header
+            .strip_prefix("t=")
+            .and_then(|rest| rest.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(header, signer.sign(timestamp, &requests[0].body));
+        assert_eq!(requests[0].headers["content-type"], "application/json");
+    }
+
+    #[tokio::test]
+    async fn retries_keep_the_event_id_and_change_the_delivery_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let publisher = publisher_for(&server);
+        publisher
+            .publish(&LifecycleEvent::Queued {
+                id: EmailId::default(),
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[0].headers[EVENT_ID_HEADER],
+            requests[1].headers[EVENT_ID_HEADER]
+        );
+        assert_ne!(
+            requests[0].headers[DELIVERY_ID_HEADER],
+            requests[1].headers[DELIVERY_ID_HEADER]
+        );
+        assert!(!requests[0].headers.contains_key(SIGNATURE_HEADER));
+    }
+
+    #[tokio::test]
+    async fn cloud_events_share_the_event_id_in_both_modes() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let email_id = EmailId::default();
+        let event = LifecycleEvent::Cancelled {
+            id: email_id,
+            correlation_id: None,
+        };
+
+        for mode in [CloudEventsMode::Structured, CloudEventsMode::Binary] {
+            publisher_for(&server)
+                .with_cloud_events(Some(CloudEventsFormat {
+                    mode,
+                    source: "/catapulte/test".into(),
+                }))
+                .publish(&event)
+                .await
+                .unwrap();
+        }
+
+        let requests = server.received_requests().await.unwrap();
+        let structured = &requests[0];
+        assert_eq!(
+            structured.headers["content-type"],
+            "application/cloudevents+json"
+        );
+        let body: serde_json::Value = serde_json::from_slice(&structured.body).unwrap();
+        assert_eq!(body["specversion"], "1.0");
+        assert_eq!(body["type"], "io.catapulte.cancelled");
+        assert_eq!(body["source"], "/catapulte/test");
+        assert_eq!(body["subject"], email_id.as_uuid().to_string());
+        assert_eq!(body["dataschema"], "urn:catapulte:lifecycle-event:v1");
+        assert_eq!(
+            body["id"],
+            structured.headers[EVENT_ID_HEADER].to_str().unwrap()
+        );
+
+        let binary = &requests[1];
+        assert_eq!(binary.headers["content-type"], "application/json");
+        assert_eq!(binary.headers["ce-type"], "io.catapulte.cancelled");
+        assert_eq!(binary.headers["ce-id"], binary.headers[EVENT_ID_HEADER]);
+        assert!(binary.headers.contains_key("ce-time"));
+        let data: serde_json::Value = serde_json::from_slice(&binary.body).unwrap();
+        assert_eq!(data, event.payload());
+    }
 }
@@ -0,0 +1,105 @@
+use std::fmt::Write as _;
+
+use anyhow::Context;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Header carrying `t=<unix seconds>,v1=<hex hmac>[,v1=<hex hmac>...]`.
+pub const SIGNATURE_HEADER: &str = "X-Catapulte-Signature";
+/// Header identifying the event; the same on every retry of that event.
+pub const EVENT_ID_HEADER: &str = "X-Catapulte-Event-Id";
+/// Header identifying a single delivery attempt.
+pub const DELIVERY_ID_HEADER: &str = "X-Catapulte-Delivery-Id";
+
+/// Shortest accepted secret, in bytes.
+pub const MIN_SECRET_BYTES: usize = 32;
+
+/// Signs webhook bodies with every active secret, so receivers can rotate:
+/// add the new secret next to the old one, move the receiver over, then drop
+/// the old one.
+#[derive(Clone)]
+pub struct WebhookSigner {
+    keys: Vec<Hmac<Sha256>>,
+}
+
+impl WebhookSigner {
+    /// # Errors
+    ///
+    /// Returns an error when no secret is given or one is shorter than
+    /// [`MIN_SECRET_BYTES`].
+    pub fn new<S: AsRef<[u8]>>(secrets: impl IntoIterator<Item = S>) -> anyhow::Result<Self> {
+        let keys = secrets
+            .into_iter()
+            .map(|secret| {
+                let secret = secret.as_ref();
+                anyhow::ensure!(
+                    secret.len() >= MIN_SECRET_BYTES,
+                    "webhook secrets must be at least {MIN_SECRET_BYTES} bytes"
+                );
+                Hmac::<Sha256>::new_from_slice(secret).context("creating hmac key")
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        anyhow::ensure!(!keys.is_empty(), "at least one webhook secret is required");
+        Ok(Self { keys })
+    }
+
+    /// Returns the signature header value for `body` sent at `timestamp`
+    /// (Unix seconds). Each `v1` is the HMAC-SHA256 of `<timestamp>.<body>`.
+    #[must_use]
+    pub fn sign(&self, timestamp: u64, body: &[u8]) -> String {
+        let mut header = format!("t={timestamp}");
+        for key in &self.keys {
+            let mut mac = key.clone();
+            mac.update(timestamp.to_string().as_bytes());
+            mac.update(b".");
+            mac.update(body);
+            header.push_str(",v1=");
+            for byte in mac.finalize().into_bytes() {
+                let _ = write!(header, "{byte:02x}");
+            }
+        }
+        header
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebhookSigner;
+
+    const OLD: &str = "old-secret-old-secret-old-secret";
+    const NEW: &str = "new-secret-new-secret-new-secret";
+
+    #[test]
+    fn one_signature_per_active_secret() {
+        let both = WebhookSigner::new([OLD, NEW])
+            .unwrap()
+            .sign(1_700_000_000, b"{}");
+        let old = WebhookSigner::new([OLD])
+            .unwrap()
+            .sign(1_700_000_000, b"{}");
+        let new = WebhookSigner::new([NEW])
+            .unwrap()
+            .sign(1_700_000_000, b"{}");
+
+        let old_v1 = old.strip_prefix("t=1700000000,").unwrap();
+        let new_v1 = new.strip_prefix("t=1700000000,").unwrap();
+        assert_eq!(both, format!("t=1700000000,{old_v1},{new_v1}"));
+        assert_eq!(old_v1.len(), "v1=".len() + 64);
+    }
+
+    #[test]
+    fn timestamp_and_body_are_both_signed() {
+        let signer = WebhookSigner::new([OLD]).unwrap();
+        let reference = signer.sign(1, b"{}");
+        let v1 = |header: &str| header.split_once(',').unwrap().1.to_owned();
+
+        assert_ne!(v1(&signer.sign(2, b"{}")), v1(&reference));
+        assert_ne!(v1(&signer.sign(1, b"{ }")), v1(&reference));
+    }
+
+    #[test]
+    fn short_or_missing_secrets_are_rejected() {
+        assert!(WebhookSigner::new(["short"]).is_err());
+        assert!(WebhookSigner::new(Vec::<String>::new()).is_err());
+    }
+}
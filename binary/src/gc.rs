@@ -98,6 +98,7 @@ mod tests {
     use catapulte_domain::entity::body::{BodySource, Plain};
     use catapulte_domain::entity::email::{EmailId, RecipientKind};
     use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
     use catapulte_domain::port::attachment_store::AttachmentStore;
     use catapulte_domain::port::email_repository::EmailRepository;
     use catapulte_outbound_attachment_fs::store::FsAttachmentStore;
@@ -127,6 +128,12 @@ mod tests {
             body: BodySource::Plain(Plain::try_new(Some("hi".to_owned()), None).unwrap()),
             variables: serde_json::Map::new(),
             attachments,
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
         }
     }
 
@@ -168,12 +175,14 @@ mod tests {
                         content_type: "text/plain".into(),
                         size_bytes: 8,
                         blob: blob1.blob.clone(),
+                        content_id: None,
                     },
                     AttachmentRef {
                         filename: "b.txt".into(),
                         content_type: "text/plain".into(),
                         size_bytes: 8,
                         blob: blob2.blob.clone(),
+                        content_id: None,
                     },
                 ],
             )
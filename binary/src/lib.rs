@@ -3,14 +3,19 @@ use std::time::Duration;
 
 use anyhow::Context;
 use catapulte_domain::use_case::process_queued_email::ProcessQueuedEmailService;
-use catapulte_domain::use_case::submit_email::SubmitEmailService;
+use catapulte_domain::use_case::submit_email::{RenderContentValidator, SubmitEmailService};
+use catapulte_inbound_http::tracking::TrackingLinksConfig;
+use catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig;
 use catapulte_inbound_http::{InboundHttpConfig, InboundHttpServer};
 use catapulte_inbound_nats::server::{InboundNatsConfig, InboundNatsServer};
 use catapulte_inbound_worker::worker::{Worker, WorkerConfig};
 use catapulte_outbound_attachment_fetcher::fetcher::HttpAttachmentFetcher;
+use catapulte_outbound_http_cache::{RemoteCache, RemoteCacheConfig};
 use catapulte_outbound_interpolator::interpolator::MiniJinjaInterpolator;
 use catapulte_outbound_mjml::renderer::MjmlRenderer;
+use catapulte_outbound_mjml::text::TextAlternative;
 use catapulte_outbound_resolver::resolver::TemplateResolverConfig;
+use catapulte_outbound_resolver::watcher::TemplateDirWatcher;
 use catapulte_outbound_smtp::multi_sender::MultiSenderConfig;
 
 pub mod attachment_fetcher;
@@ -22,6 +27,7 @@ pub mod publisher;
 pub mod queue;
 mod state;
 pub mod storage;
+pub mod webhook_dispatcher;
 
 use publisher::PublisherAdapterConfig;
 use state::AppState;
@@ -40,8 +46,22 @@ pub struct AppConfig {
     pub attachment_fetcher:
         catapulte_outbound_attachment_fetcher::fetcher::HttpAttachmentFetcherConfig,
     pub include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig,
+    /// Shared settings of the remote template and HTTP include caches.
+    pub template_cache: RemoteCacheConfig,
     pub gc_sweep_interval: Duration,
     pub gc_grace_period: Duration,
+    /// Add recipients rejected with a permanent mailbox error to the
+    /// suppression list.
+    pub suppression_auto_add: bool,
+    /// Render every submitted email, failing on undefined variables, before
+    /// accepting it.
+    pub submit_strict_validation: bool,
+    /// Where the text part of emails without an explicit one comes from.
+    pub text_alternative: TextAlternative,
+    /// Signing of the one-click unsubscribe links added to list emails.
+    pub unsubscribe: UnsubscribeLinksConfig,
+    /// Signing of the open pixels and click redirects of tracked emails.
+    pub tracking: TrackingLinksConfig,
 }
 
 impl AppConfig {
@@ -72,6 +92,8 @@ impl AppConfig {
                 "CATAPULTE_INCLUDE_LOADER",
             )
             .context("loading include loader config")?;
+        let template_cache = RemoteCacheConfig::from_env("CATAPULTE_TEMPLATE_CACHE")
+            .context("loading template cache config")?;
         let gc_sweep_secs: u64 = std::env::var("CATAPULTE_GC_SWEEP_INTERVAL_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
@@ -82,6 +104,22 @@ impl AppConfig {
             .and_then(|v| v.parse().ok())
             .unwrap_or(3600);
         let gc_grace_period = Duration::from_secs(gc_grace_secs);
+        let suppression_auto_add = std::env::var("CATAPULTE_SUPPRESSION_AUTO_ADD")
+            .ok()
+            .is_none_or(|v| !v.trim().eq_ignore_ascii_case("false"));
+        let submit_strict_validation = std::env::var("CATAPULTE_SUBMIT_STRICT_VALIDATION")
+            .ok()
+            .is_some_and(|v| v.trim().eq_ignore_ascii_case("true"));
+        let text_alternative = std::env::var("CATAPULTE_TEXT_ALTERNATIVE")
+            .ok()
+            .map(|v| v.parse::<TextAlternative>())
+            .transpose()
+            .context("loading text alternative config")?
+            .unwrap_or_default();
+        let unsubscribe = UnsubscribeLinksConfig::from_env("CATAPULTE_UNSUBSCRIBE")
+            .context("loading unsubscribe config")?;
+        let tracking = TrackingLinksConfig::from_env("CATAPULTE_TRACKING")
+            .context("loading tracking config")?;
         Ok(Self {
             storage,
             http,
@@ -94,8 +132,14 @@ impl AppConfig {
             attachment_store,
             attachment_fetcher,
             include_loader,
+            template_cache,
             gc_sweep_interval,
             gc_grace_period,
+            suppression_auto_add,
+            submit_strict_validation,
+            text_alternative,
+            unsubscribe,
+            tracking,
         })
     }
 
@@ -109,16 +153,24 @@ impl AppConfig {
             .build()
             .await
             .context("building storage adapter")?;
+        let storage = if self.publisher.webhook_enabled() {
+            storage.with_webhook_outbox()
+        } else {
+            storage
+        };
+        let storage = if self.publisher.nats_outbox_enabled() {
+            storage.with_nats_outbox()
+        } else {
+            storage
+        };
 
         let queue = self
             .queue
-            .build(&storage)
+            .build(&storage, self.worker.retry_policy())
             .await
             .context("building queue adapter")?;
 
-        let publisher = self
-            .publisher
-            .build(storage.clone())
+        let (publisher, webhook_dispatcher) = Box::pin(self.publisher.build(storage.clone()))
             .await
             .context("building publisher adapter")?;
 
@@ -166,10 +218,20 @@ impl AppConfig {
         let list_events = Arc::new(
             catapulte_domain::use_case::list_events::ListEventsService::new(storage.clone()),
         );
+        let stream_events = Arc::new(
+            catapulte_domain::use_case::stream_events::StreamEventsService::new(storage.clone()),
+        );
+        let templates_dir = self.resolver.templates_dir.clone();
+        let templates_reload_interval = self.resolver.templates_reload_interval;
+        let include_root = self.include_loader.fs_root.clone();
+        let remote_cache = Arc::new(self.template_cache.build());
+        let include_cache = Arc::new(self.template_cache.build());
         let resolver = self
             .resolver
             .build()
-            .context("building template resolver")?;
+            .context("building template resolver")?
+            .with_template_store(storage.clone())
+            .with_remote_cache(remote_cache.clone());
 
         let attachment_store = self
             .attachment_store
@@ -182,21 +244,110 @@ impl AppConfig {
             .build()
             .context("building attachment fetcher adapter")?;
 
-        let submit_email = Arc::new(SubmitEmailService::new(
-            storage.clone(),
-            queue.clone(),
-            publisher.clone(),
-            attachment_store.clone(),
-            attachment_fetcher,
-        ));
-        let mjml_renderer = MjmlRenderer::new(self.include_loader.build());
-        let process_queued_email = Arc::new(ProcessQueuedEmailService::new(
-            resolver,
-            MiniJinjaInterpolator::new(),
-            mjml_renderer,
-            smtp,
-            attachment_store.clone(),
-        ));
+        // Shared with the submit-time content check, which renders the same
+        // templates the worker will.
+        let resolver = Arc::new(resolver);
+        let mjml_renderer = Arc::new(
+            MjmlRenderer::new(
+                self.include_loader
+                    .with_cache(include_cache.clone())
+                    .build(),
+            )
+            .with_text_alternative(self.text_alternative),
+        );
+        let template_watcher =
+            templates_dir
+                .zip(templates_reload_interval)
+                .map(|(dir, interval)| {
+                    TemplateDirWatcher::new(
+                        dir,
+                        resolver.templates(),
+                        MiniJinjaInterpolator::new(),
+                        mjml_renderer.clone(),
+                        interval,
+                    )
+                    .with_include_root(include_root)
+                });
+        let content_validator = self.submit_strict_validation.then(|| {
+            RenderContentValidator::new(
+                resolver.clone(),
+                MiniJinjaInterpolator::strict(),
+                mjml_renderer.clone(),
+            )
+        });
+        let unsubscribe_links = self
+            .unsubscribe
+            .build()
+            .context("building unsubscribe links")?;
+        let tracking_links = self.tracking.build().context("building tracking links")?;
+        let submit_email = Arc::new(
+            SubmitEmailService::new(
+                storage.clone(),
+                queue.clone(),
+                publisher.clone(),
+                attachment_store.clone(),
+                attachment_fetcher,
+            )
+            .with_template_store(storage.clone())
+            .with_content_validator(content_validator)
+            .with_unsubscribe_links(unsubscribe_links.clone()),
+        );
+        let cancel_email = Arc::new(
+            catapulte_domain::use_case::cancel_email::CancelEmailService::new(
+                storage.clone(),
+                publisher.clone(),
+                attachment_store.clone(),
+            ),
+        );
+        let process_queued_email = Arc::new(
+            ProcessQueuedEmailService::new(
+                resolver,
+                MiniJinjaInterpolator::new(),
+                mjml_renderer,
+                smtp,
+                attachment_store.clone(),
+            )
+            .with_suppression_list(storage.clone(), self.suppression_auto_add)
+            .with_tracking_links(tracking_links.clone()),
+        );
+        let suppressions = Arc::new(
+            catapulte_domain::use_case::manage_suppressions::ManageSuppressionsService::new(
+                storage.clone(),
+            ),
+        );
+        let templates = Arc::new(
+            catapulte_domain::use_case::manage_templates::ManageTemplatesService::new(
+                storage.clone(),
+            ),
+        );
+        let unsubscribe = Arc::new(
+            catapulte_domain::use_case::unsubscribe::UnsubscribeService::new(
+                unsubscribe_links,
+                storage.clone(),
+                publisher.clone(),
+            ),
+        );
+        let track_engagement = Arc::new(
+            catapulte_domain::use_case::track_engagement::TrackEngagementService::new(
+                tracking_links,
+                publisher.clone(),
+                catapulte_domain::port::clock::SystemClock,
+            ),
+        );
+
+        let webhook_deliveries = Arc::new(
+            catapulte_domain::use_case::manage_webhook_deliveries::ManageWebhookDeliveriesService::new(
+                storage.clone(),
+                catapulte_domain::port::clock::SystemClock,
+            ),
+        );
+
+        let webhook_subscriptions = Arc::new(
+            catapulte_domain::use_case::manage_webhook_subscriptions::ManageWebhookSubscriptionsService::new(
+                storage.clone(),
+                catapulte_domain::port::clock::SystemClock,
+            ),
+        );
 
         let check_readiness = Arc::new(
             catapulte_domain::use_case::check_readiness::CheckReadinessService::new(
@@ -210,6 +361,14 @@ impl AppConfig {
             list_senders,
             list_emails,
             list_events,
+            stream_events,
+            cancel_email,
+            suppressions,
+            templates,
+            unsubscribe,
+            track_engagement,
+            webhook_deliveries,
+            webhook_subscriptions,
             check_readiness,
             queue,
             publisher,
@@ -237,6 +396,9 @@ impl AppConfig {
             inbound_nats_server,
             worker,
             gc,
+            webhook_dispatcher,
+            template_watcher,
+            template_caches: vec![("remote", remote_cache), ("include", include_cache)],
             metrics_enabled: false,
             metric_export_interval: Duration::from_mins(1),
         })
@@ -249,6 +411,9 @@ pub struct Application {
     inbound_nats_server: Option<InboundNatsServer>,
     worker: Worker,
     gc: gc::AttachmentGc,
+    webhook_dispatcher: webhook_dispatcher::WebhookDispatcher,
+    template_watcher: Option<TemplateDirWatcher<MiniJinjaInterpolator, Arc<MjmlRenderer>>>,
+    template_caches: Vec<(&'static str, Arc<RemoteCache>)>,
     metrics_enabled: bool,
     metric_export_interval: Duration,
 }
@@ -307,6 +472,22 @@ impl Application {
             Ok(())
         });
 
+        // Webhook outbox dispatcher
+        let dispatcher_cancel = cancel.clone();
+        tasks.spawn(async move {
+            self.webhook_dispatcher.run(dispatcher_cancel).await;
+            Ok(())
+        });
+
+        // Templates directory reload (optional)
+        if let Some(watcher) = self.template_watcher {
+            let watcher_cancel = cancel.clone();
+            tasks.spawn(async move {
+                watcher.run(watcher_cancel).await;
+                Ok(())
+            });
+        }
+
         // Inbound NATS (optional)
         if let Some(inbound) = self.inbound_nats_server {
             let inb_cancel = cancel.clone();
@@ -322,6 +503,7 @@ impl Application {
             let sampler_queue = state.queue.clone();
             let sampler_senders = std::sync::Arc::clone(&state.list_senders);
             let sampler_backend = state.queue.backend_name();
+            let sampler_caches = self.template_caches;
             let sampler_interval = self.metric_export_interval;
             let sampler_cancel = cancel.clone();
             tasks.spawn(async move {
@@ -329,6 +511,7 @@ impl Application {
                     sampler_queue,
                     sampler_senders,
                     sampler_backend,
+                    sampler_caches,
                     sampler_interval,
                     sampler_cancel,
                 )
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use opentelemetry::KeyValue;
 
 use catapulte_domain::use_case::list_senders::ListSendersUseCase as _;
+use catapulte_outbound_http_cache::RemoteCache;
 
 use crate::queue::QueueAdapter;
 use crate::state::ListSendersServiceImpl;
@@ -12,6 +14,7 @@ pub(crate) async fn run_sampler(
     queue: QueueAdapter,
     list_senders: Arc<ListSendersServiceImpl>,
     backend: &'static str,
+    template_caches: Vec<(&'static str, Arc<RemoteCache>)>,
     interval: Duration,
     cancel: tokio_util::sync::CancellationToken,
 ) {
@@ -19,6 +22,11 @@ pub(crate) async fn run_sampler(
     let queue_pending = meter.u64_gauge("catapulte.queue.pending").build();
     let sender_sent = meter.u64_gauge("catapulte.sender.sent_in_range").build();
     let sender_quota_limit = meter.u64_gauge("catapulte.sender.quota_limit").build();
+    let cache_lookups = meter
+        .u64_counter("catapulte.template_cache.lookups")
+        .build();
+    let cache_bytes = meter.u64_gauge("catapulte.template_cache.bytes").build();
+    let mut cache_lookups_seen = HashMap::new();
 
     // Sample once immediately, then on the configured interval.
     sample_once(
@@ -30,6 +38,12 @@ pub(crate) async fn run_sampler(
         &sender_quota_limit,
     )
     .await;
+    sample_caches(
+        &template_caches,
+        &cache_lookups,
+        &cache_bytes,
+        &mut cache_lookups_seen,
+    );
 
     loop {
         tokio::select! {
@@ -45,6 +59,12 @@ pub(crate) async fn run_sampler(
             &sender_quota_limit,
         )
         .await;
+        sample_caches(
+            &template_caches,
+            &cache_lookups,
+            &cache_bytes,
+            &mut cache_lookups_seen,
+        );
     }
 }
 
@@ -75,3 +95,32 @@ async fn sample_once(
         }
     }
 }
+
+/// Adds the lookups of each template cache since the previous sample, split
+/// by outcome. `seen` holds the totals read at that sample.
+fn sample_caches(
+    caches: &[(&'static str, Arc<RemoteCache>)],
+    lookups: &opentelemetry::metrics::Counter<u64>,
+    bytes: &opentelemetry::metrics::Gauge<u64>,
+    seen: &mut HashMap<(&'static str, &'static str), u64>,
+) {
+    for (name, cache) in caches {
+        let stats = cache.stats();
+        for (result, count) in [
+            ("hit", stats.hits),
+            ("miss", stats.misses),
+            ("revalidated", stats.revalidated),
+            ("stale", stats.stale),
+        ] {
+            let previous = seen.insert((*name, result), count).unwrap_or(0);
+            lookups.add(
+                count.saturating_sub(previous),
+                &[
+                    KeyValue::new("cache", *name),
+                    KeyValue::new("result", result),
+                ],
+            );
+        }
+        bytes.record(stats.bytes, &[KeyValue::new("cache", *name)]);
+    }
+}
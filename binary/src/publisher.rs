@@ -1,17 +1,22 @@
+use std::time::Duration;
+
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
 use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
 use catapulte_outbound_nats::event_publisher::{NatsEventConfig, NatsEventPublisher};
-use catapulte_outbound_webhook::{WebhookConfig, WebhookPublisher};
+use catapulte_outbound_webhook::WebhookConfig;
 use tracing::Instrument as _;
 
 use crate::storage::StorageAdapter;
+use crate::webhook_dispatcher::WebhookDispatcher;
 
+/// Lifecycle events are always stored. Webhook and `JetStream` deliveries are
+/// not pushed from here: the storage queues one per target in its outbox, in
+/// the same transaction as the event, for the [`WebhookDispatcher`]. Core NATS
+/// is best effort and published directly, under the id the event is stored with.
 #[derive(Clone)]
 pub(crate) enum PublisherAdapter {
     Storage(StorageAdapter),
-    StorageWebhook(StorageAdapter, WebhookPublisher),
-    StorageNats(StorageAdapter, NatsEventPublisher),
-    StorageBoth(StorageAdapter, WebhookPublisher, NatsEventPublisher),
+    StorageNats(StorageAdapter, Box<NatsEventPublisher>),
 }
 
 impl EventPublisher for PublisherAdapter {
@@ -24,31 +29,15 @@ impl EventPublisher for PublisherAdapter {
                 span.record("outcome", if result.is_ok() { "ok" } else { "error" });
                 result
             }
-            Self::StorageWebhook(s, w) => {
-                let storage_span =
-                    tracing::info_span!("publisher.storage", outcome = tracing::field::Empty);
-                let webhook_span =
-                    tracing::info_span!("publisher.webhook", outcome = tracing::field::Empty);
-                let (sr, wr) = tokio::join!(
-                    s.publish(event).instrument(storage_span.clone()),
-                    w.publish(event).instrument(webhook_span.clone()),
-                );
-                storage_span.record("outcome", if sr.is_ok() { "ok" } else { "error" });
-                webhook_span.record("outcome", if wr.is_ok() { "ok" } else { "error" });
-                sr?;
-                if let Err(e) = wr {
-                    tracing::warn!(error = %e, "webhook event delivery failed");
-                }
-                Ok(())
-            }
             Self::StorageNats(s, n) => {
                 let storage_span =
                     tracing::info_span!("publisher.storage", outcome = tracing::field::Empty);
                 let nats_span =
                     tracing::info_span!("publisher.nats", outcome = tracing::field::Empty);
+                let id = uuid::Uuid::now_v7();
                 let (sr, nr) = tokio::join!(
-                    s.publish(event).instrument(storage_span.clone()),
-                    n.publish(event).instrument(nats_span.clone()),
+                    s.publish_event(id, event).instrument(storage_span.clone()),
+                    n.publish_event(id, event).instrument(nats_span.clone()),
                 );
                 storage_span.record("outcome", if sr.is_ok() { "ok" } else { "error" });
                 nats_span.record("outcome", if nr.is_ok() { "ok" } else { "error" });
@@ -58,30 +47,6 @@ impl EventPublisher for PublisherAdapter {
                 }
                 Ok(())
             }
-            Self::StorageBoth(s, w, n) => {
-                let storage_span =
-                    tracing::info_span!("publisher.storage", outcome = tracing::field::Empty);
-                let webhook_span =
-                    tracing::info_span!("publisher.webhook", outcome = tracing::field::Empty);
-                let nats_span =
-                    tracing::info_span!("publisher.nats", outcome = tracing::field::Empty);
-                let (sr, wr, nr) = tokio::join!(
-                    s.publish(event).instrument(storage_span.clone()),
-                    w.publish(event).instrument(webhook_span.clone()),
-                    n.publish(event).instrument(nats_span.clone()),
-                );
-                storage_span.record("outcome", if sr.is_ok() { "ok" } else { "error" });
-                webhook_span.record("outcome", if wr.is_ok() { "ok" } else { "error" });
-                nats_span.record("outcome", if nr.is_ok() { "ok" } else { "error" });
-                sr?;
-                if let Err(e) = wr {
-                    tracing::warn!(error = %e, "webhook event delivery failed");
-                }
-                if let Err(e) = nr {
-                    tracing::warn!(error = %e, "NATS event delivery failed");
-                }
-                Ok(())
-            }
         }
     }
 }
@@ -98,10 +63,14 @@ impl PublisherAdapterConfig {
             webhook: WebhookConfig {
                 url: None,
                 timeout_ms: 5_000,
+                secrets: Vec::new(),
+                cloud_events: None,
             },
             nats_events: NatsEventConfig {
                 url: None,
                 subject: "catapulte.lifecycle".to_owned(),
+                stream: None,
+                cloud_events: None,
             },
         }
     }
@@ -112,10 +81,14 @@ impl PublisherAdapterConfig {
             webhook: WebhookConfig {
                 url: None,
                 timeout_ms: 5_000,
+                secrets: Vec::new(),
+                cloud_events: None,
             },
             nats_events: NatsEventConfig {
                 url: Some(url),
                 subject,
+                stream: None,
+                cloud_events: None,
             },
         }
     }
@@ -132,18 +105,44 @@ impl PublisherAdapterConfig {
         })
     }
 
+    /// Whether events are pushed to the configured webhook, in which case the
+    /// storage must queue them in its outbox. Deliveries to API-managed
+    /// subscriptions are queued regardless.
+    pub(crate) fn webhook_enabled(&self) -> bool {
+        self.webhook.url.is_some()
+    }
+
+    /// Whether events are pushed to a NATS `JetStream` stream, in which case
+    /// the storage must queue them in its outbox.
+    pub(crate) fn nats_outbox_enabled(&self) -> bool {
+        self.nats_events.url.is_some() && self.nats_events.stream.is_some()
+    }
+
+    /// Returns the publisher and the dispatcher draining the outbox.
+    ///
     /// # Errors
     ///
     /// Returns an error if an outbound connection fails.
-    pub(crate) async fn build(self, storage: StorageAdapter) -> anyhow::Result<PublisherAdapter> {
-        let webhook = self.webhook.build()?;
-        let nats = self.nats_events.build().await?;
-        let adapter = match (webhook, nats) {
-            (None, None) => PublisherAdapter::Storage(storage),
-            (Some(w), None) => PublisherAdapter::StorageWebhook(storage, w),
-            (None, Some(n)) => PublisherAdapter::StorageNats(storage, n),
-            (Some(w), Some(n)) => PublisherAdapter::StorageBoth(storage, w, n),
+    pub(crate) async fn build(
+        self,
+        storage: StorageAdapter,
+    ) -> anyhow::Result<(PublisherAdapter, WebhookDispatcher)> {
+        let cloud_events = self.webhook.cloud_events.clone();
+        let timeout = Duration::from_millis(self.webhook.timeout_ms);
+        let (client, webhook) = self.webhook.build()?;
+        let dispatcher = WebhookDispatcher::new(storage.clone(), client, webhook)
+            .with_cloud_events(cloud_events)
+            .with_timeout(timeout);
+        let (adapter, dispatcher) = match self.nats_events.build().await? {
+            None => (PublisherAdapter::Storage(storage), dispatcher),
+            Some(n) if n.uses_jetstream() => {
+                (PublisherAdapter::Storage(storage), dispatcher.with_nats(n))
+            }
+            Some(n) => (
+                PublisherAdapter::StorageNats(storage, Box::new(n)),
+                dispatcher,
+            ),
         };
-        Ok(adapter)
+        Ok((adapter, dispatcher))
     }
 }
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::envelope::Envelope;
+use catapulte_domain::entity::retry_policy::RetryPolicy;
 use catapulte_domain::port::email_queue::{AckToken, DequeuedEmail, EmailQueue, EmailQueueError};
 use catapulte_outbound_nats::{NatsAdapter, NatsConfig};
 use catapulte_outbound_postgres::PostgresAdapter;
@@ -105,6 +106,26 @@ impl catapulte_domain::port::health::HealthCheck for QueueAdapter {
     }
 }
 
+/// Former `JetStream` consumer settings, now derived from the worker's retry
+/// policy, with the variables replacing them.
+const DEPRECATED_VARS: [(&str, &str); 2] = [
+    ("MAX_DELIVER", "CATAPULTE_WORKER_RETRY_MAX_ATTEMPTS"),
+    ("BACKOFF", "CATAPULTE_WORKER_RETRY_BACKOFF"),
+];
+
+fn warn_deprecated(prefix: &str) {
+    for (suffix, replacement) in DEPRECATED_VARS {
+        let key = format!("{prefix}_{suffix}");
+        if std::env::var_os(&key).is_some() {
+            tracing::warn!(
+                key = %key,
+                replacement,
+                "deprecated env var is ignored, set the worker retry policy instead"
+            );
+        }
+    }
+}
+
 pub enum QueueBackendConfig {
     Storage,
     Memory,
@@ -117,6 +138,7 @@ impl QueueBackendConfig {
     /// Returns an error if `<prefix>_BACKEND` is set to an unknown value or if the
     /// NATS config cannot be loaded from environment variables.
     pub fn from_env(prefix: &str) -> anyhow::Result<Self> {
+        warn_deprecated(prefix);
         let key = format!("{prefix}_BACKEND");
         match std::env::var(&key).as_deref() {
             Ok("memory") => Ok(Self::Memory),
@@ -130,10 +152,17 @@ impl QueueBackendConfig {
         }
     }
 
+    /// Builds the queue adapter. `retry_policy` is the worker's, so backends
+    /// with their own redelivery limits stay in line with it.
+    ///
     /// # Errors
     ///
     /// Returns an error if building the NATS adapter fails.
-    pub(crate) async fn build(self, storage: &StorageAdapter) -> anyhow::Result<QueueAdapter> {
+    pub(crate) async fn build(
+        self,
+        storage: &StorageAdapter,
+        retry_policy: &RetryPolicy,
+    ) -> anyhow::Result<QueueAdapter> {
         match self {
             Self::Storage => Ok(match storage {
                 StorageAdapter::Sqlite(a) => QueueAdapter::Sqlite(a.clone()),
@@ -144,7 +173,7 @@ impl QueueBackendConfig {
                 Ok(QueueAdapter::Memory(MemoryQueue::new()))
             }
             Self::Nats(config) => {
-                let adapter = config.build().await?;
+                let adapter = config.with_retry_policy(retry_policy).build().await?;
                 Ok(QueueAdapter::Nats(adapter))
             }
         }
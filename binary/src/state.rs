@@ -6,14 +6,36 @@ use catapulte_domain::port::email_queue::EmailQueue;
 use catapulte_domain::port::email_repository::EmailRepository;
 use catapulte_domain::port::event_publisher::EventPublisher;
 use catapulte_domain::service::routed_email_sender::RoutedEmailSender;
+use catapulte_domain::use_case::cancel_email::{CancelEmailService, CancelEmailUseCase};
 use catapulte_domain::use_case::list_emails::{ListEmailsService, ListEmailsUseCase};
 use catapulte_domain::use_case::list_events::{ListEventsService, ListEventsUseCase};
 use catapulte_domain::use_case::list_senders::{ListSendersService, ListSendersUseCase};
+use catapulte_domain::use_case::manage_suppressions::{
+    ManageSuppressionsService, ManageSuppressionsUseCase,
+};
+use catapulte_domain::use_case::manage_templates::{
+    ManageTemplatesService, ManageTemplatesUseCase,
+};
+use catapulte_domain::use_case::manage_webhook_deliveries::{
+    ManageWebhookDeliveriesService, ManageWebhookDeliveriesUseCase,
+};
+use catapulte_domain::use_case::manage_webhook_subscriptions::{
+    ManageWebhookSubscriptionsService, ManageWebhookSubscriptionsUseCase,
+};
 use catapulte_domain::use_case::process_queued_email::{
-    ProcessQueuedEmailService, ProcessQueuedEmailUseCase,
+    PreviewEmailUseCase, ProcessQueuedEmailService, ProcessQueuedEmailUseCase,
 };
-use catapulte_domain::use_case::submit_email::{SubmitEmailService, SubmitEmailUseCase};
+use catapulte_domain::use_case::stream_events::{StreamEventsService, StreamEventsUseCase};
+use catapulte_domain::use_case::submit_email::{
+    RenderContentValidator, SubmitEmailService, SubmitEmailUseCase,
+};
+use catapulte_domain::use_case::track_engagement::{
+    TrackEngagementService, TrackEngagementUseCase,
+};
+use catapulte_domain::use_case::unsubscribe::{UnsubscribeService, UnsubscribeUseCase};
 use catapulte_inbound_http::HttpServerState;
+use catapulte_inbound_http::tracking::HmacTrackingLinks;
+use catapulte_inbound_http::unsubscribe::HmacUnsubscribeLinks;
 use catapulte_inbound_nats::server::InboundNatsState;
 use catapulte_inbound_worker::worker::WorkerState;
 use catapulte_outbound_interpolator::interpolator::MiniJinjaInterpolator;
@@ -29,16 +51,47 @@ use crate::queue::QueueAdapter;
 use crate::storage::StorageAdapter;
 
 pub(crate) type ProcessService = ProcessQueuedEmailService<
-    TemplateResolverAdapter,
+    Arc<TemplateResolverAdapter<StorageAdapter>>,
     MiniJinjaInterpolator,
-    MjmlRenderer,
+    Arc<MjmlRenderer>,
     RoutedEmailSender<SmtpTransport, StorageAdapter>,
     AttachmentStoreAdapter,
+    StorageAdapter,
+    Option<HmacTrackingLinks>,
 >;
 
+pub(crate) type SubmitEmailServiceImpl = SubmitEmailService<
+    StorageAdapter,
+    QueueAdapter,
+    PublisherAdapter,
+    AttachmentStoreAdapter,
+    HttpAttachmentFetcher,
+    Option<
+        RenderContentValidator<
+            Arc<TemplateResolverAdapter<StorageAdapter>>,
+            MiniJinjaInterpolator,
+            Arc<MjmlRenderer>,
+        >,
+    >,
+    StorageAdapter,
+    Option<HmacUnsubscribeLinks>,
+>;
 pub(crate) type ListSendersServiceImpl = ListSendersService<StorageAdapter, SystemClock>;
 pub(crate) type ListEmailsServiceImpl = ListEmailsService<StorageAdapter>;
 pub(crate) type ListEventsServiceImpl = ListEventsService<StorageAdapter>;
+pub(crate) type StreamEventsServiceImpl = StreamEventsService<StorageAdapter>;
+pub(crate) type CancelEmailServiceImpl =
+    CancelEmailService<StorageAdapter, PublisherAdapter, AttachmentStoreAdapter>;
+pub(crate) type ManageSuppressionsServiceImpl = ManageSuppressionsService<StorageAdapter>;
+pub(crate) type ManageTemplatesServiceImpl = ManageTemplatesService<StorageAdapter>;
+pub(crate) type UnsubscribeServiceImpl =
+    UnsubscribeService<Option<HmacUnsubscribeLinks>, StorageAdapter, PublisherAdapter>;
+pub(crate) type TrackEngagementServiceImpl =
+    TrackEngagementService<Option<HmacTrackingLinks>, PublisherAdapter, SystemClock>;
+pub(crate) type ManageWebhookDeliveriesServiceImpl =
+    ManageWebhookDeliveriesService<StorageAdapter, SystemClock>;
+pub(crate) type ManageWebhookSubscriptionsServiceImpl =
+    ManageWebhookSubscriptionsService<StorageAdapter, SystemClock>;
 pub(crate) type CheckReadinessServiceImpl =
     catapulte_domain::use_case::check_readiness::CheckReadinessService<
         crate::health::ReadinessProbe,
@@ -46,19 +99,19 @@ pub(crate) type CheckReadinessServiceImpl =
 
 #[derive(Clone)]
 pub(crate) struct AppState {
-    pub(crate) submit_email: Arc<
-        SubmitEmailService<
-            StorageAdapter,
-            QueueAdapter,
-            PublisherAdapter,
-            AttachmentStoreAdapter,
-            HttpAttachmentFetcher,
-        >,
-    >,
+    pub(crate) submit_email: Arc<SubmitEmailServiceImpl>,
     pub(crate) process_queued_email: Arc<ProcessService>,
     pub(crate) list_senders: Arc<ListSendersServiceImpl>,
     pub(crate) list_emails: Arc<ListEmailsServiceImpl>,
     pub(crate) list_events: Arc<ListEventsServiceImpl>,
+    pub(crate) stream_events: Arc<StreamEventsServiceImpl>,
+    pub(crate) cancel_email: Arc<CancelEmailServiceImpl>,
+    pub(crate) suppressions: Arc<ManageSuppressionsServiceImpl>,
+    pub(crate) templates: Arc<ManageTemplatesServiceImpl>,
+    pub(crate) unsubscribe: Arc<UnsubscribeServiceImpl>,
+    pub(crate) track_engagement: Arc<TrackEngagementServiceImpl>,
+    pub(crate) webhook_deliveries: Arc<ManageWebhookDeliveriesServiceImpl>,
+    pub(crate) webhook_subscriptions: Arc<ManageWebhookSubscriptionsServiceImpl>,
     pub(crate) check_readiness: Arc<CheckReadinessServiceImpl>,
     pub(crate) queue: QueueAdapter,
     pub(crate) publisher: PublisherAdapter,
@@ -87,9 +140,45 @@ impl HttpServerState for AppState {
         self.list_events.as_ref()
     }
 
+    fn stream_events(&self) -> &impl StreamEventsUseCase {
+        self.stream_events.as_ref()
+    }
+
     fn list_senders(&self) -> &impl ListSendersUseCase {
         self.list_senders.as_ref()
     }
+
+    fn cancel_email(&self) -> &impl CancelEmailUseCase {
+        self.cancel_email.as_ref()
+    }
+
+    fn suppressions(&self) -> &impl ManageSuppressionsUseCase {
+        self.suppressions.as_ref()
+    }
+
+    fn preview_email(&self) -> &impl PreviewEmailUseCase {
+        self.process_queued_email.as_ref()
+    }
+
+    fn templates(&self) -> &impl ManageTemplatesUseCase {
+        self.templates.as_ref()
+    }
+
+    fn unsubscribe(&self) -> &impl UnsubscribeUseCase {
+        self.unsubscribe.as_ref()
+    }
+
+    fn track_engagement(&self) -> &impl TrackEngagementUseCase {
+        self.track_engagement.as_ref()
+    }
+
+    fn webhook_deliveries(&self) -> &impl ManageWebhookDeliveriesUseCase {
+        self.webhook_deliveries.as_ref()
+    }
+
+    fn webhook_subscriptions(&self) -> &impl ManageWebhookSubscriptionsUseCase {
+        self.webhook_subscriptions.as_ref()
+    }
 }
 
 impl InboundNatsState for AppState {
@@ -3,12 +3,25 @@ use catapulte_domain::entity::attachment::{AttachmentRef, BlobRef};
 use catapulte_domain::entity::email::EmailId;
 use catapulte_domain::entity::envelope::Envelope;
 use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+use catapulte_domain::entity::template::Template;
+use catapulte_domain::entity::unsubscribe::MailingList;
+use catapulte_domain::entity::webhook_subscription::WebhookSubscription;
 use catapulte_domain::port::email_repository::{
-    EmailRecord, EmailRepository, EmailRepositoryError, ListEmailsParams, SaveResult,
+    CancelResult, EmailRecord, EmailRepository, EmailRepositoryError, ListEmailsParams, SaveResult,
 };
 use catapulte_domain::port::event_publisher::{EventPublisher, EventPublisherError};
 use catapulte_domain::port::event_repository::{
-    EventRecord, EventRepository, EventRepositoryError, ListEventsParams,
+    EventRecord, EventRepository, EventRepositoryError, ListEventsAfterParams, ListEventsParams,
+};
+use catapulte_domain::port::suppression_list::{
+    ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+};
+use catapulte_domain::port::template_store::{TemplateStore, TemplateStoreError};
+use catapulte_domain::port::webhook_outbox::{
+    ListWebhookDeliveriesParams, WebhookDelivery, WebhookOutbox, WebhookOutboxError,
+};
+use catapulte_domain::port::webhook_subscription_store::{
+    ListWebhookSubscriptionsParams, WebhookSubscriptionStore, WebhookSubscriptionStoreError,
 };
 use catapulte_outbound_postgres::{PostgresAdapter, PostgresConfig};
 use catapulte_outbound_sqlite::{SqliteAdapter, SqliteConfig};
@@ -55,8 +68,29 @@ impl EmailRepository for StorageAdapter {
 
     async fn delete(&self, id: EmailId) -> Result<(), EmailRepositoryError> {
         match self {
-            Self::Sqlite(a) => a.delete(id).await,
-            Self::Postgres(a) => a.delete(id).await,
+            Self::Sqlite(a) => EmailRepository::delete(a, id).await,
+            Self::Postgres(a) => EmailRepository::delete(a, id).await,
+        }
+    }
+
+    async fn cancel(&self, id: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+        match self {
+            Self::Sqlite(a) => a.cancel(id).await,
+            Self::Postgres(a) => a.cancel(id).await,
+        }
+    }
+
+    async fn begin_delivery(&self, id: EmailId) -> Result<bool, EmailRepositoryError> {
+        match self {
+            Self::Sqlite(a) => a.begin_delivery(id).await,
+            Self::Postgres(a) => a.begin_delivery(id).await,
+        }
+    }
+
+    async fn end_delivery(&self, id: EmailId) -> Result<(), EmailRepositoryError> {
+        match self {
+            Self::Sqlite(a) => a.end_delivery(id).await,
+            Self::Postgres(a) => a.end_delivery(id).await,
         }
     }
 
@@ -71,8 +105,8 @@ impl EmailRepository for StorageAdapter {
 impl EventPublisher for StorageAdapter {
     async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
         match self {
-            Self::Sqlite(a) => a.publish(event).await,
-            Self::Postgres(a) => a.publish(event).await,
+            Self::Sqlite(a) => EventPublisher::publish(a, event).await,
+            Self::Postgres(a) => EventPublisher::publish(a, event).await,
         }
     }
 }
@@ -87,6 +121,23 @@ impl EventRepository for StorageAdapter {
             Self::Postgres(a) => a.list_events(params).await,
         }
     }
+
+    async fn get_event(&self, id: uuid::Uuid) -> Result<Option<EventRecord>, EventRepositoryError> {
+        match self {
+            Self::Sqlite(a) => a.get_event(id).await,
+            Self::Postgres(a) => a.get_event(id).await,
+        }
+    }
+
+    async fn list_events_after(
+        &self,
+        params: ListEventsAfterParams,
+    ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+        match self {
+            Self::Sqlite(a) => a.list_events_after(params).await,
+            Self::Postgres(a) => a.list_events_after(params).await,
+        }
+    }
 }
 
 impl catapulte_domain::port::health::HealthCheck for StorageAdapter {
@@ -120,6 +171,182 @@ impl catapulte_domain::port::sender_usage::SenderUsage for StorageAdapter {
     }
 }
 
+impl SuppressionList for StorageAdapter {
+    async fn add(&self, address: &str, reason: &str) -> Result<(), SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => SuppressionList::add(a, address, reason).await,
+            Self::Postgres(a) => SuppressionList::add(a, address, reason).await,
+        }
+    }
+
+    async fn remove(&self, address: &str) -> Result<bool, SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => SuppressionList::remove(a, address).await,
+            Self::Postgres(a) => SuppressionList::remove(a, address).await,
+        }
+    }
+
+    async fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => SuppressionList::list(a, params).await,
+            Self::Postgres(a) => SuppressionList::list(a, params).await,
+        }
+    }
+
+    async fn find_suppressed(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => a.find_suppressed(addresses).await,
+            Self::Postgres(a) => a.find_suppressed(addresses).await,
+        }
+    }
+
+    async fn opt_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        address: &str,
+    ) -> Result<bool, SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => a.opt_out(sender, list, address).await,
+            Self::Postgres(a) => a.opt_out(sender, list, address).await,
+        }
+    }
+
+    async fn find_opted_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        match self {
+            Self::Sqlite(a) => a.find_opted_out(sender, list, addresses).await,
+            Self::Postgres(a) => a.find_opted_out(sender, list, addresses).await,
+        }
+    }
+}
+
+impl TemplateStore for StorageAdapter {
+    async fn publish(&self, name: &str, content: &str) -> Result<Template, TemplateStoreError> {
+        match self {
+            Self::Sqlite(a) => TemplateStore::publish(a, name, content).await,
+            Self::Postgres(a) => TemplateStore::publish(a, name, content).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> Result<Option<Template>, TemplateStoreError> {
+        match self {
+            Self::Sqlite(a) => TemplateStore::get(a, name, version).await,
+            Self::Postgres(a) => TemplateStore::get(a, name, version).await,
+        }
+    }
+
+    async fn delete(&self, name: &str) -> Result<bool, TemplateStoreError> {
+        match self {
+            Self::Sqlite(a) => TemplateStore::delete(a, name).await,
+            Self::Postgres(a) => TemplateStore::delete(a, name).await,
+        }
+    }
+}
+
+impl WebhookOutbox for StorageAdapter {
+    async fn claim_due(
+        &self,
+        now_ms: i64,
+        lease_until_ms: i64,
+        limit: u32,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        match self {
+            Self::Sqlite(a) => a.claim_due(now_ms, lease_until_ms, limit).await,
+            Self::Postgres(a) => a.claim_due(now_ms, lease_until_ms, limit).await,
+        }
+    }
+
+    async fn complete(&self, id: uuid::Uuid) -> Result<(), WebhookOutboxError> {
+        match self {
+            Self::Sqlite(a) => a.complete(id).await,
+            Self::Postgres(a) => a.complete(id).await,
+        }
+    }
+
+    async fn record_failure(
+        &self,
+        id: uuid::Uuid,
+        error: &str,
+        next_attempt_at_ms: Option<i64>,
+    ) -> Result<(), WebhookOutboxError> {
+        match self {
+            Self::Sqlite(a) => a.record_failure(id, error, next_attempt_at_ms).await,
+            Self::Postgres(a) => a.record_failure(id, error, next_attempt_at_ms).await,
+        }
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+        match self {
+            Self::Sqlite(a) => WebhookOutbox::list(a, params).await,
+            Self::Postgres(a) => WebhookOutbox::list(a, params).await,
+        }
+    }
+
+    async fn redeliver(&self, id: uuid::Uuid, now_ms: i64) -> Result<bool, WebhookOutboxError> {
+        match self {
+            Self::Sqlite(a) => a.redeliver(id, now_ms).await,
+            Self::Postgres(a) => a.redeliver(id, now_ms).await,
+        }
+    }
+}
+
+impl WebhookSubscriptionStore for StorageAdapter {
+    async fn insert(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<(), WebhookSubscriptionStoreError> {
+        match self {
+            Self::Sqlite(a) => a.insert(subscription).await,
+            Self::Postgres(a) => a.insert(subscription).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        match self {
+            Self::Sqlite(a) => WebhookSubscriptionStore::get(a, id).await,
+            Self::Postgres(a) => WebhookSubscriptionStore::get(a, id).await,
+        }
+    }
+
+    async fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> Result<Vec<WebhookSubscription>, WebhookSubscriptionStoreError> {
+        match self {
+            Self::Sqlite(a) => WebhookSubscriptionStore::list(a, params).await,
+            Self::Postgres(a) => WebhookSubscriptionStore::list(a, params).await,
+        }
+    }
+
+    async fn delete(&self, id: uuid::Uuid) -> Result<bool, WebhookSubscriptionStoreError> {
+        match self {
+            Self::Sqlite(a) => WebhookSubscriptionStore::delete(a, id).await,
+            Self::Postgres(a) => WebhookSubscriptionStore::delete(a, id).await,
+        }
+    }
+}
+
 impl StorageAdapter {
     fn backend_name(&self) -> &'static str {
         match self {
@@ -127,6 +354,36 @@ impl StorageAdapter {
             Self::Postgres(_) => "postgres",
         }
     }
+
+    /// See `SqliteAdapter::with_webhook_outbox`.
+    #[must_use]
+    pub(crate) fn with_webhook_outbox(self) -> Self {
+        match self {
+            Self::Sqlite(a) => Self::Sqlite(a.with_webhook_outbox()),
+            Self::Postgres(a) => Self::Postgres(a.with_webhook_outbox()),
+        }
+    }
+
+    /// See `SqliteAdapter::with_nats_outbox`.
+    #[must_use]
+    pub(crate) fn with_nats_outbox(self) -> Self {
+        match self {
+            Self::Sqlite(a) => Self::Sqlite(a.with_nats_outbox()),
+            Self::Postgres(a) => Self::Postgres(a.with_nats_outbox()),
+        }
+    }
+
+    /// See `SqliteAdapter::publish_event`.
+    pub(crate) async fn publish_event(
+        &self,
+        event_id: uuid::Uuid,
+        event: &LifecycleEvent,
+    ) -> Result<(), EventPublisherError> {
+        match self {
+            Self::Sqlite(a) => a.publish_event(event_id, event).await,
+            Self::Postgres(a) => a.publish_event(event_id, event).await,
+        }
+    }
 }
 
 pub enum StorageBackendConfig {
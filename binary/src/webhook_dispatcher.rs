@@ -0,0 +1,485 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context as _;
+use catapulte_domain::entity::cloud_event::CloudEventsFormat;
+use catapulte_domain::port::clock::{Clock, SystemClock};
+use catapulte_domain::port::webhook_outbox::{DeliveryTarget, WebhookDelivery, WebhookOutbox};
+use catapulte_domain::port::webhook_subscription_store::WebhookSubscriptionStore;
+use catapulte_outbound_nats::event_publisher::NatsEventPublisher;
+use catapulte_outbound_webhook::WebhookPublisher;
+use tokio_util::sync::CancellationToken;
+
+use crate::storage::StorageAdapter;
+
+/// Attempts before a delivery is marked failed. With the backoff below the
+/// last one happens about five hours after the event.
+pub const MAX_ATTEMPTS: u32 = 12;
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_hours(1);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const BATCH_SIZE: u32 = 50;
+/// Deliveries of a batch attempted at the same time.
+const CONCURRENCY: u32 = 10;
+/// Timeout of one attempt when none is configured, the webhook's default.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Slack on top of the attempts themselves, for the outbox queries around
+/// them.
+const LEASE_MARGIN: Duration = Duration::from_mins(1);
+
+/// Drains the webhook outbox, towards the configured webhook, the API-managed
+/// subscription each delivery belongs to, or the NATS `JetStream` stream. Each
+/// delivery is
+/// attempted once per claim; failures are rescheduled with exponential
+/// backoff, persisted in the outbox so they survive restarts.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    outbox: StorageAdapter,
+    client: reqwest::Client,
+    webhook: Option<WebhookPublisher>,
+    /// Envelope of subscription deliveries, the same as the webhook's.
+    cloud_events: Option<CloudEventsFormat>,
+    nats: Option<NatsEventPublisher>,
+    /// How long a claimed delivery stays hidden from other dispatchers.
+    lease: Duration,
+}
+
+impl WebhookDispatcher {
+    #[must_use]
+    pub fn new(
+        outbox: StorageAdapter,
+        client: reqwest::Client,
+        webhook: Option<WebhookPublisher>,
+    ) -> Self {
+        Self {
+            outbox,
+            client,
+            webhook,
+            cloud_events: None,
+            nats: None,
+            lease: lease_for(DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Sizes the lease of a claimed batch for attempts taking up to
+    /// `timeout`, so that it outlasts the whole batch and no delivery is sent
+    /// twice concurrently by another dispatcher.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.lease = lease_for(timeout);
+        self
+    }
+
+    /// Delivers the outbox rows targeting NATS through `nats`.
+    #[must_use]
+    pub fn with_nats(mut self, nats: NatsEventPublisher) -> Self {
+        self.nats = Some(nats);
+        self
+    }
+
+    #[must_use]
+    pub fn with_cloud_events(mut self, format: Option<CloudEventsFormat>) -> Self {
+        self.cloud_events = format;
+        self
+    }
+
+    pub async fn run(self, cancel: CancellationToken) {
+        loop {
+            tokio::select! {
+                biased;
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(POLL_INTERVAL) => {
+                    if let Err(e) = self.dispatch_once().await {
+                        tracing::warn!(error = %e, "webhook outbox dispatch failed");
+                    }
+                }
+            }
+        }
+        tracing::info!("webhook dispatcher stopped");
+    }
+
+    /// Attempts every due delivery once, a few at a time. Returns how many
+    /// were delivered. A delivery that cannot be attempted or recorded is
+    /// logged and left to come back once its lease expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the due deliveries cannot be claimed.
+    pub async fn dispatch_once(&self) -> anyhow::Result<usize> {
+        let now_ms = SystemClock.now_ms();
+        let lease_until_ms = now_ms.saturating_add(duration_ms(self.lease));
+        let due = self
+            .outbox
+            .claim_due(now_ms, lease_until_ms, BATCH_SIZE)
+            .await?;
+        let sem = Arc::new(tokio::sync::Semaphore::new(CONCURRENCY as usize));
+        let mut tasks: tokio::task::JoinSet<anyhow::Result<bool>> = tokio::task::JoinSet::new();
+        for delivery in due {
+            let permit = Arc::clone(&sem).acquire_owned().await?;
+            let this = self.clone();
+            tasks.spawn(async move {
+                let _permit = permit;
+                let id = delivery.id;
+                this.attempt(delivery)
+                    .await
+                    .with_context(|| format!("delivery {id}"))
+            });
+        }
+        let mut delivered = 0;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(Ok(true)) => delivered += 1,
+                Ok(Ok(false)) => {}
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %format!("{e:#}"), "outbox delivery could not be attempted");
+                }
+                Err(e) => tracing::warn!(error = %e, "outbox delivery task failed"),
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Attempts `delivery` once and records the outcome. Returns whether it
+    /// was delivered.
+    async fn attempt(&self, delivery: WebhookDelivery) -> anyhow::Result<bool> {
+        let result = match delivery.target {
+            DeliveryTarget::Nats => {
+                let Some(nats) = &self.nats else {
+                    self.outbox
+                        .record_failure(delivery.id, "NATS events no longer configured", None)
+                        .await?;
+                    return Ok(false);
+                };
+                nats.deliver(&delivery).await
+            }
+            DeliveryTarget::Webhook => {
+                let webhook = match delivery.subscription_id {
+                    None => {
+                        let Some(webhook) = &self.webhook else {
+                            self.outbox
+                                .record_failure(delivery.id, "webhook no longer configured", None)
+                                .await?;
+                            return Ok(false);
+                        };
+                        webhook.clone()
+                    }
+                    Some(subscription_id) => {
+                        match WebhookSubscriptionStore::get(&self.outbox, subscription_id).await? {
+                            Some(subscription) => WebhookPublisher::for_subscription(
+                                self.client.clone(),
+                                &subscription,
+                            )?
+                            .with_cloud_events(self.cloud_events.clone()),
+                            // Deleted since the claim; its deliveries went along.
+                            None => return Ok(false),
+                        }
+                    }
+                };
+                webhook.deliver(&delivery).await
+            }
+        };
+        match result {
+            Ok(()) => {
+                self.outbox.complete(delivery.id).await?;
+                Ok(true)
+            }
+            Err(e) => {
+                let attempts = delivery.attempts + 1;
+                let error = format!("{e:#}");
+                let next_attempt_at_ms = (attempts < MAX_ATTEMPTS).then(|| {
+                    SystemClock
+                        .now_ms()
+                        .saturating_add(duration_ms(retry_delay(attempts)))
+                });
+                if next_attempt_at_ms.is_none() {
+                    tracing::warn!(
+                        error = %error,
+                        delivery_id = %delivery.id,
+                        attempts,
+                        target = delivery.target.as_str(),
+                        "outbox delivery failed for good"
+                    );
+                }
+                self.outbox
+                    .record_failure(delivery.id, &error, next_attempt_at_ms)
+                    .await?;
+                Ok(false)
+            }
+        }
+    }
+}
+
+/// Lease of a batch whose attempts take up to `timeout`: as many rounds of
+/// `timeout` as the batch needs at `CONCURRENCY`, plus a margin.
+fn lease_for(timeout: Duration) -> Duration {
+    timeout
+        .saturating_mul(BATCH_SIZE.div_ceil(CONCURRENCY))
+        .saturating_add(LEASE_MARGIN)
+}
+
+/// Delay before the attempt following the `attempts`-th failure: 30s,
+/// doubling up to an hour.
+fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    FIRST_RETRY_DELAY
+        .saturating_mul(1 << exponent)
+        .min(MAX_RETRY_DELAY)
+}
+
+fn duration_ms(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use catapulte_domain::entity::body::{BodySource, Plain};
+    use catapulte_domain::entity::email::EmailId;
+    use catapulte_domain::entity::envelope::Envelope;
+    use catapulte_domain::entity::lifecycle_event::LifecycleEvent;
+    use catapulte_domain::entity::message_headers::MessageHeaders;
+    use catapulte_domain::entity::webhook_subscription::{SubscriptionFilter, WebhookSubscription};
+    use catapulte_domain::port::email_repository::EmailRepository;
+    use catapulte_domain::port::event_publisher::EventPublisher;
+    use catapulte_domain::port::webhook_outbox::{
+        DeliveryTarget, ListWebhookDeliveriesParams, WebhookDeliveryStatus, WebhookOutbox,
+    };
+    use catapulte_domain::port::webhook_subscription_store::WebhookSubscriptionStore;
+    use catapulte_outbound_webhook::WebhookPublisher;
+    use catapulte_outbound_webhook::signature::SIGNATURE_HEADER;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use super::{WebhookDispatcher, lease_for, retry_delay};
+    use crate::storage::StorageAdapter;
+
+    async fn sqlite_storage() -> (StorageAdapter, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = catapulte_outbound_sqlite::SqliteConfig {
+            url: format!("sqlite:{}", dir.path().join("test.db").display()),
+        };
+        let adapter = config.build().await.expect("sqlite build");
+        adapter.migrate().await.expect("sqlite migrate");
+        (StorageAdapter::Sqlite(adapter), dir)
+    }
+
+    async fn save_email(storage: &StorageAdapter) -> EmailId {
+        let id = EmailId::default();
+        let envelope = Envelope {
+            idempotency_key: None,
+            correlation_id: None,
+            subject: None,
+            sender: "sender@example.com".to_owned(),
+            recipients: vec![],
+            body: BodySource::Plain(Plain::try_new(Some("hi".to_owned()), None).unwrap()),
+            variables: serde_json::Map::new(),
+            attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: catapulte_domain::entity::tracking::Tracking::default(),
+        };
+        storage.save(id, &envelope).await.unwrap();
+        id
+    }
+
+    async fn storage_with_event() -> (StorageAdapter, tempfile::TempDir) {
+        let (storage, dir) = sqlite_storage().await;
+        let storage = storage.with_webhook_outbox();
+        let id = save_email(&storage).await;
+        storage
+            .publish(&LifecycleEvent::Queued {
+                id,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+        (storage, dir)
+    }
+
+    fn all() -> ListWebhookDeliveriesParams {
+        ListWebhookDeliveriesParams {
+            status: None,
+            subscription_id: None,
+            limit: 10,
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(2), Duration::from_mins(1));
+        assert_eq!(retry_delay(4), Duration::from_mins(4));
+        assert_eq!(retry_delay(8), Duration::from_hours(1));
+        assert_eq!(retry_delay(40), Duration::from_hours(1));
+    }
+
+    #[test]
+    fn leases_outlast_a_batch_of_timed_out_attempts() {
+        assert_eq!(lease_for(Duration::from_secs(5)), Duration::from_secs(85));
+        assert_eq!(lease_for(Duration::from_mins(1)), Duration::from_mins(6));
+    }
+
+    #[tokio::test]
+    async fn delivered_events_leave_the_outbox() {
+        let (storage, _dir) = storage_with_event().await;
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let webhook = WebhookPublisher::new(reqwest::Client::new(), server.uri().parse().unwrap());
+        let event_id = WebhookOutbox::list(&storage, all()).await.unwrap()[0].event_id;
+
+        let dispatcher =
+            WebhookDispatcher::new(storage.clone(), reqwest::Client::new(), Some(webhook));
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 1);
+
+        assert!(
+            WebhookOutbox::list(&storage, all())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers["x-catapulte-event-id"],
+            event_id.hyphenated().to_string().as_str()
+        );
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["event_type"], "queued");
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_are_rescheduled_with_the_error() {
+        let (storage, _dir) = storage_with_event().await;
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .mount(&server)
+            .await;
+        let webhook = WebhookPublisher::new(reqwest::Client::new(), server.uri().parse().unwrap());
+
+        let dispatcher =
+            WebhookDispatcher::new(storage.clone(), reqwest::Client::new(), Some(webhook));
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+        // Not due again before the backoff elapses.
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+
+        let pending = WebhookOutbox::list(&storage, all()).await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), 1);
+        assert_eq!(pending[0].attempts, 1);
+        assert!(pending[0].last_error.as_deref().unwrap().contains("503"));
+    }
+
+    #[tokio::test]
+    async fn subscription_deliveries_go_to_the_subscription_url() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/billing"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let (storage, _dir) = sqlite_storage().await;
+        storage
+            .insert(&WebhookSubscription {
+                id: uuid::Uuid::now_v7(),
+                url: format!("{}/billing", server.uri()).parse().unwrap(),
+                secret: "s".repeat(32),
+                filter: SubscriptionFilter {
+                    event_types: vec!["cancelled".into()],
+                    correlation_id_prefix: None,
+                },
+                created_at_ms: 0,
+            })
+            .await
+            .unwrap();
+        let id = save_email(&storage).await;
+        for event in [
+            LifecycleEvent::Queued {
+                id,
+                correlation_id: None,
+            },
+            LifecycleEvent::Cancelled {
+                id,
+                correlation_id: None,
+            },
+        ] {
+            storage.publish(&event).await.unwrap();
+        }
+
+        // No webhook configured: only the subscription gets a delivery.
+        let dispatcher = WebhookDispatcher::new(storage.clone(), reqwest::Client::new(), None);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 1);
+
+        server.verify().await;
+        let requests = server.received_requests().await.unwrap();
+        assert!(requests[0].headers.contains_key(SIGNATURE_HEADER));
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["event_type"], "cancelled");
+    }
+
+    #[tokio::test]
+    async fn nats_deliveries_fail_without_a_nats_publisher() {
+        let (storage, _dir) = sqlite_storage().await;
+        let storage = storage.with_nats_outbox();
+        let id = save_email(&storage).await;
+        storage
+            .publish(&LifecycleEvent::Queued {
+                id,
+                correlation_id: None,
+            })
+            .await
+            .unwrap();
+
+        let dispatcher = WebhookDispatcher::new(storage.clone(), reqwest::Client::new(), None);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 0);
+
+        let failed = WebhookOutbox::list(&storage, all()).await.unwrap();
+        assert_eq!(failed[0].target, DeliveryTarget::Nats);
+        assert_eq!(failed[0].status, WebhookDeliveryStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn slow_endpoints_are_called_concurrently() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .expect(8)
+            .mount(&server)
+            .await;
+        let (storage, _dir) = sqlite_storage().await;
+        let storage = storage.with_webhook_outbox();
+        for _ in 0..8 {
+            let id = save_email(&storage).await;
+            storage
+                .publish(&LifecycleEvent::Queued {
+                    id,
+                    correlation_id: None,
+                })
+                .await
+                .unwrap();
+        }
+        let webhook = WebhookPublisher::new(reqwest::Client::new(), server.uri().parse().unwrap());
+
+        let dispatcher =
+            WebhookDispatcher::new(storage.clone(), reqwest::Client::new(), Some(webhook));
+        let started = std::time::Instant::now();
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), 8);
+
+        // One after the other, they would take four seconds.
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(
+            WebhookOutbox::list(&storage, all())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
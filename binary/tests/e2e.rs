@@ -99,6 +99,7 @@ fn base_resolver() -> TemplateResolverConfig {
     TemplateResolverConfig {
         allowed_domains: HashSet::new(),
         templates_dir: None,
+        templates_reload_interval: None,
         auth_entries: Vec::new(),
     }
 }
@@ -167,8 +168,20 @@ async fn multi_sender_primary_delivers_email_before_backup() {
         attachment_store: base_attachment_store(),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -326,8 +339,20 @@ async fn multi_sender_falls_back_to_backup_when_primary_fails() {
         attachment_store: base_attachment_store(),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -464,8 +489,20 @@ async fn sent_email_blob_is_deleted_after_delivery() {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -603,8 +640,20 @@ async fn submit_email_with_remote_url_attachment_is_delivered() {
                 fetch_timeout: Duration::from_secs(30),
             },
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -748,8 +797,20 @@ async fn submit_via_inbound_nats_delivers_and_emits_lifecycle_event_with_correla
         attachment_store: base_attachment_store(),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -868,8 +929,20 @@ async fn submit_email_with_disallowed_remote_attachment_returns_400() {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -975,8 +1048,20 @@ async fn multi_sender_routes_by_sender_domain() {
         attachment_store: base_attachment_store(),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: Duration::from_hours(1),
         gc_grace_period: Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
 
     let app = config.build().await.expect("failed to build app");
@@ -60,6 +60,7 @@ fn base_resolver() -> TemplateResolverConfig {
     TemplateResolverConfig {
         allowed_domains: HashSet::new(),
         templates_dir: None,
+        templates_reload_interval: None,
         auth_entries: Vec::new(),
     }
 }
@@ -130,8 +131,20 @@ fn sqlite_config(smtp_port: u16, http_port: u16, db_tag: &str) -> (AppConfig, te
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     (config, db_dir)
 }
@@ -169,8 +182,20 @@ pub async fn sqlite_memory(smtp_port: u16, http_port: u16) -> BackendBundle {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     BackendBundle {
         config,
@@ -210,8 +235,20 @@ pub async fn sqlite_nats(smtp_port: u16, http_port: u16) -> BackendBundle {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     BackendBundle {
         config,
@@ -247,8 +284,20 @@ pub async fn postgres_storage(smtp_port: u16, http_port: u16) -> BackendBundle {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     BackendBundle {
         config,
@@ -284,8 +333,20 @@ pub async fn postgres_memory(smtp_port: u16, http_port: u16) -> BackendBundle {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     BackendBundle {
         config,
@@ -327,8 +388,20 @@ pub async fn postgres_nats(smtp_port: u16, http_port: u16) -> BackendBundle {
         }),
         attachment_fetcher: base_attachment_fetcher(),
         include_loader: catapulte_outbound_mjml::include_loader::IncludeLoaderConfig::default(),
+        template_cache: catapulte_outbound_http_cache::RemoteCacheConfig::default(),
         gc_sweep_interval: std::time::Duration::from_hours(1),
         gc_grace_period: std::time::Duration::from_hours(1),
+        suppression_auto_add: true,
+        submit_strict_validation: false,
+        text_alternative: catapulte_outbound_mjml::text::TextAlternative::default(),
+        unsubscribe: catapulte_inbound_http::unsubscribe::UnsubscribeLinksConfig {
+            base_url: None,
+            secret: None,
+        },
+        tracking: catapulte_inbound_http::tracking::TrackingLinksConfig {
+            base_url: None,
+            secret: None,
+        },
     };
     BackendBundle {
         config,
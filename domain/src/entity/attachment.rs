@@ -1,3 +1,5 @@
+use thiserror::Error;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BlobRef {
     pub backend: String,
@@ -10,6 +12,8 @@ pub struct AttachmentRef {
     pub content_type: String,
     pub size_bytes: u64,
     pub blob: BlobRef,
+    /// Set for inline parts the HTML body references as `cid:<content_id>`.
+    pub content_id: Option<String>,
 }
 
 #[derive(Debug)]
@@ -17,4 +21,58 @@ pub struct ResolvedAttachment {
     pub filename: String,
     pub content_type: String,
     pub bytes: bytes::Bytes,
+    pub content_id: Option<String>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error(
+    "content id {0:?} must be 1 to 250 ASCII letters, digits or one of \".-_@+\", such as \"logo\" or \"logo@acme.com\""
+)]
+pub struct InvalidContentId(pub String);
+
+/// Longest accepted content id, in bytes.
+pub const MAX_CONTENT_ID_BYTES: usize = 250;
+
+/// # Errors
+///
+/// Returns `InvalidContentId` unless `content_id` is made of 1 to
+/// [`MAX_CONTENT_ID_BYTES`] characters safe both in a `Content-ID` header and
+/// in a `cid:` URL.
+pub fn validate_content_id(content_id: &str) -> Result<(), InvalidContentId> {
+    let valid = (1..=MAX_CONTENT_ID_BYTES).contains(&content_id.len())
+        && content_id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b".-_@+".contains(&b));
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidContentId(content_id.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_content_id;
+
+    #[test]
+    fn content_ids_accept_tokens_and_addresses() {
+        assert!(validate_content_id("logo").is_ok());
+        assert!(validate_content_id("logo.png@acme.com").is_ok());
+        assert!(validate_content_id("hero_image-2+x").is_ok());
+    }
+
+    #[test]
+    fn content_ids_reject_header_and_url_breaking_characters() {
+        for id in [
+            "",
+            "<logo>",
+            "my logo",
+            "logo\r\nX-Evil: 1",
+            "logo\"",
+            "lógo",
+        ] {
+            assert!(validate_content_id(id).is_err(), "{id:?}");
+        }
+        assert!(validate_content_id(&"a".repeat(251)).is_err());
+    }
 }
@@ -58,6 +58,20 @@ pub enum ResolvedBody {
     Mjml(String),
 }
 
+/// A resolved body with the locale of the template variant it came from.
+#[derive(Debug, Clone)]
+pub struct ResolvedTemplate {
+    pub body: ResolvedBody,
+    /// `None` when no localized variant was used.
+    pub locale: Option<String>,
+}
+
+impl From<ResolvedBody> for ResolvedTemplate {
+    fn from(body: ResolvedBody) -> Self {
+        Self { body, locale: None }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum InterpolatedBody {
     Plain(Plain),
@@ -65,27 +79,68 @@ pub enum InterpolatedBody {
 }
 
 #[derive(Debug)]
-pub struct RenderedBody(Plain);
+pub struct RenderedBody {
+    plain: Plain,
+    title: Option<String>,
+    warnings: Vec<String>,
+}
 
 impl RenderedBody {
     #[must_use]
     pub const fn new(plain: Plain) -> Self {
-        Self(plain)
+        Self {
+            plain,
+            title: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Sets the title the template declares (`<mj-title>` for MJML), used as
+    /// the subject when the request has none.
+    #[must_use]
+    pub fn with_title(mut self, title: Option<String>) -> Self {
+        self.title = title.filter(|t| !t.trim().is_empty());
+        self
+    }
+
+    /// Sets the non-fatal issues the renderer reported, such as unknown MJML
+    /// attributes.
+    #[must_use]
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Applies `f` to the HTML part, if there is one.
+    #[must_use]
+    pub fn map_html(mut self, f: impl FnOnce(String) -> String) -> Self {
+        self.plain.html = self.plain.html.map(f);
+        self
     }
 
     #[must_use]
     pub fn into_plain(self) -> Plain {
-        self.0
+        self.plain
     }
 
     #[must_use]
     pub fn text(&self) -> Option<&str> {
-        self.0.text()
+        self.plain.text()
     }
 
     #[must_use]
     pub fn html(&self) -> Option<&str> {
-        self.0.html()
+        self.plain.html()
+    }
+
+    #[must_use]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    #[must_use]
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
     }
 }
 
@@ -0,0 +1,316 @@
+use thiserror::Error;
+
+use crate::entity::utc::UtcDateTime;
+
+/// Largest accepted iCalendar object, in bytes.
+pub const MAX_CALENDAR_BYTES: usize = 256 * 1024;
+
+/// Longest content line before folding, in octets (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidCalendar {
+    #[error("calendar must be an iCalendar object, from BEGIN:VCALENDAR to END:VCALENDAR")]
+    NotICalendar,
+    #[error("calendar must declare a METHOD such as REQUEST or CANCEL")]
+    MissingMethod,
+    #[error("calendar must not exceed {MAX_CALENDAR_BYTES} bytes")]
+    TooLarge,
+    #[error("calendar event must have a uid")]
+    EmptyUid,
+    #[error("calendar event must end after it starts")]
+    EndBeforeStart,
+}
+
+/// An iCalendar object sent as the `text/calendar` alternative of an email,
+/// so that clients show it as an invitation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calendar {
+    method: String,
+    content: String,
+}
+
+impl Calendar {
+    /// Reads a raw iCalendar object, normalizing its line endings to CRLF.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidCalendar` when `content` is too large, is not a
+    /// `VCALENDAR` object or has no `METHOD`.
+    pub fn parse(content: &str) -> Result<Self, InvalidCalendar> {
+        let mut content = content.trim().replace("\r\n", "\n").replace('\n', "\r\n");
+        content.push_str("\r\n");
+        if content.len() > MAX_CALENDAR_BYTES {
+            return Err(InvalidCalendar::TooLarge);
+        }
+        let lines = unfold(&content);
+        let first = lines.first().map(String::as_str).unwrap_or_default();
+        let last = lines.last().map(String::as_str).unwrap_or_default();
+        if !first.eq_ignore_ascii_case("BEGIN:VCALENDAR")
+            || !last.eq_ignore_ascii_case("END:VCALENDAR")
+        {
+            return Err(InvalidCalendar::NotICalendar);
+        }
+        let method = lines
+            .iter()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("METHOD").then(|| value.trim())
+            })
+            .filter(|m| !m.is_empty() && m.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'))
+            .ok_or(InvalidCalendar::MissingMethod)?
+            .to_ascii_uppercase();
+        Ok(Self { method, content })
+    }
+
+    /// The iTIP method (`REQUEST`, `CANCEL`, …), upper case.
+    #[must_use]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    #[must_use]
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// Joins folded content lines back together.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.trim_end().split("\r\n") {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(previous)) => previous.push_str(rest),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+    lines
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarParticipant {
+    pub address: String,
+    pub name: Option<String>,
+}
+
+/// A meeting described field by field, serialized as a `METHOD:REQUEST`
+/// invitation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalendarEvent {
+    /// Identifies the meeting across updates; resend with the same uid and a
+    /// higher `sequence` to move it.
+    pub uid: String,
+    pub sequence: u32,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    /// Unix epoch ms.
+    pub start_ms: i64,
+    /// Unix epoch ms.
+    pub end_ms: i64,
+    pub organizer: CalendarParticipant,
+    pub attendees: Vec<CalendarParticipant>,
+}
+
+impl CalendarEvent {
+    /// Serializes the event, stamped at `stamp_ms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidCalendar` when the uid is empty, the event does not
+    /// end after it starts or the result is too large.
+    pub fn to_calendar(&self, stamp_ms: i64) -> Result<Calendar, InvalidCalendar> {
+        if self.uid.trim().is_empty() {
+            return Err(InvalidCalendar::EmptyUid);
+        }
+        if self.end_ms <= self.start_ms {
+            return Err(InvalidCalendar::EndBeforeStart);
+        }
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_owned(),
+            "VERSION:2.0".to_owned(),
+            "PRODID:-//catapulte//EN".to_owned(),
+            "CALSCALE:GREGORIAN".to_owned(),
+            "METHOD:REQUEST".to_owned(),
+            "BEGIN:VEVENT".to_owned(),
+            format!("UID:{}", escape_text(&self.uid)),
+            format!("SEQUENCE:{}", self.sequence),
+            format!("DTSTAMP:{}", UtcDateTime::from_ms(stamp_ms).to_basic()),
+            format!("DTSTART:{}", UtcDateTime::from_ms(self.start_ms).to_basic()),
+            format!("DTEND:{}", UtcDateTime::from_ms(self.end_ms).to_basic()),
+            format!("SUMMARY:{}", escape_text(&self.summary)),
+        ];
+        if let Some(description) = &self.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        if let Some(location) = &self.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        lines.push(format!(
+            "ORGANIZER{}:mailto:{}",
+            common_name(self.organizer.name.as_deref()),
+            self.organizer.address
+        ));
+        for attendee in &self.attendees {
+            lines.push(format!(
+                "ATTENDEE{};ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:{}",
+                common_name(attendee.name.as_deref()),
+                attendee.address
+            ));
+        }
+        lines.extend(["STATUS:CONFIRMED", "END:VEVENT", "END:VCALENDAR"].map(str::to_owned));
+
+        let mut content = String::new();
+        for line in &lines {
+            fold_into(&mut content, line);
+        }
+        if content.len() > MAX_CALENDAR_BYTES {
+            return Err(InvalidCalendar::TooLarge);
+        }
+        Ok(Calendar {
+            method: "REQUEST".to_owned(),
+            content,
+        })
+    }
+}
+
+/// Escapes a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// `;CN="…"` parameter for a display name, which may not hold quotes.
+fn common_name(name: Option<&str>) -> String {
+    name.map(|n| n.chars().filter(|c| *c != '"' && !c.is_control()))
+        .map(|n| format!(";CN=\"{}\"", n.collect::<String>()))
+        .unwrap_or_default()
+}
+
+/// Appends `line` and its CRLF, folded at 75 octets on character boundaries.
+fn fold_into(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Calendar, CalendarEvent, CalendarParticipant, InvalidCalendar, MAX_CALENDAR_BYTES, unfold,
+    };
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            uid: "kickoff-42@acme.com".to_owned(),
+            sequence: 1,
+            summary: "Kickoff; part 1, intro".to_owned(),
+            description: Some("Agenda:\nintro".to_owned()),
+            location: None,
+            start_ms: 1_772_375_400_000,
+            end_ms: 1_772_379_000_000,
+            organizer: CalendarParticipant {
+                address: "alice@acme.com".to_owned(),
+                name: Some("Alice \"Al\" Doe".to_owned()),
+            },
+            attendees: vec![CalendarParticipant {
+                address: "bob@example.com".to_owned(),
+                name: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn events_serialize_as_requests() {
+        let calendar = event().to_calendar(1_772_000_000_000).unwrap();
+        assert_eq!(calendar.method(), "REQUEST");
+        let content = calendar.content();
+        assert!(
+            content.split("\r\n").all(|line| line.len() <= 75),
+            "{content}"
+        );
+        let lines = unfold(content);
+        for line in [
+            "METHOD:REQUEST",
+            "UID:kickoff-42@acme.com",
+            "SEQUENCE:1",
+            "DTSTART:20260301T143000Z",
+            "DTEND:20260301T153000Z",
+            "SUMMARY:Kickoff\\; part 1\\, intro",
+            "DESCRIPTION:Agenda:\\nintro",
+            "ORGANIZER;CN=\"Alice Al Doe\":mailto:alice@acme.com",
+            "ATTENDEE;ROLE=REQ-PARTICIPANT;PARTSTAT=NEEDS-ACTION;RSVP=TRUE:mailto:bob@example.com",
+        ] {
+            assert!(
+                lines.iter().any(|l| l == line),
+                "{line:?} missing from {content}"
+            );
+        }
+        assert!(content.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(Calendar::parse(content).unwrap(), calendar);
+    }
+
+    #[test]
+    fn invalid_events_are_rejected() {
+        let mut ended_early = event();
+        ended_early.end_ms = ended_early.start_ms;
+        assert_eq!(
+            ended_early.to_calendar(0),
+            Err(InvalidCalendar::EndBeforeStart)
+        );
+        let mut no_uid = event();
+        no_uid.uid = " ".to_owned();
+        assert_eq!(no_uid.to_calendar(0), Err(InvalidCalendar::EmptyUid));
+    }
+
+    #[test]
+    fn raw_calendars_are_normalized_and_need_a_method() {
+        let calendar = Calendar::parse(
+            "BEGIN:VCALENDAR\nVERSION:2.0\nMETH\n OD:cancel\nBEGIN:VEVENT\nUID:1\nEND:VEVENT\nEND:VCALENDAR\n",
+        )
+        .unwrap();
+        assert_eq!(calendar.method(), "CANCEL");
+        assert!(
+            calendar
+                .content()
+                .starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n")
+        );
+        assert!(calendar.content().ends_with("END:VCALENDAR\r\n"));
+
+        assert_eq!(
+            Calendar::parse("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nEND:VCALENDAR"),
+            Err(InvalidCalendar::MissingMethod)
+        );
+        assert_eq!(
+            Calendar::parse("BEGIN:VEVENT\r\nEND:VEVENT"),
+            Err(InvalidCalendar::NotICalendar)
+        );
+    }
+
+    #[test]
+    fn raw_calendars_are_measured_after_normalization() {
+        let head = "BEGIN:VCALENDAR\nMETHOD:REQUEST\n";
+        let tail = "END:VCALENDAR";
+        let padding = "X:\n".repeat((MAX_CALENDAR_BYTES - head.len() - tail.len()) / 3);
+        let content = format!("{head}{padding}{tail}");
+        assert!(content.len() <= MAX_CALENDAR_BYTES);
+        assert_eq!(Calendar::parse(&content), Err(InvalidCalendar::TooLarge));
+    }
+}
@@ -0,0 +1,171 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::entity::email::EmailId;
+use crate::entity::utc::UtcDateTime;
+
+pub const SPEC_VERSION: &str = "1.0";
+/// Prefixes the lifecycle event type, e.g. `io.catapulte.delivery.failed`.
+pub const TYPE_PREFIX: &str = "io.catapulte.";
+/// Identifies the shape of `data`; the trailing version is bumped on an
+/// incompatible change to an event payload.
+pub const DATA_SCHEMA: &str = "urn:catapulte:lifecycle-event:v1";
+/// Content type of a structured-mode message.
+pub const STRUCTURED_CONTENT_TYPE: &str = "application/cloudevents+json";
+pub const DATA_CONTENT_TYPE: &str = "application/json";
+/// Source used when the operator does not set one.
+pub const DEFAULT_SOURCE: &str = "/catapulte";
+
+/// How a cloud event travels: as one JSON document, or as `ce-*` headers with
+/// the data alone in the body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloudEventsMode {
+    Structured,
+    Binary,
+}
+
+#[derive(Debug, Error)]
+#[error("unknown CloudEvents mode {value:?}, expected structured or binary")]
+pub struct UnknownCloudEventsMode {
+    pub value: String,
+}
+
+impl FromStr for CloudEventsMode {
+    type Err = UnknownCloudEventsMode;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "structured" => Ok(Self::Structured),
+            "binary" => Ok(Self::Binary),
+            _ => Err(UnknownCloudEventsMode {
+                value: s.to_owned(),
+            }),
+        }
+    }
+}
+
+/// Opt-in `CloudEvents` 1.0 envelope for pushed lifecycle events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CloudEventsFormat {
+    pub mode: CloudEventsMode,
+    /// The `source` attribute, a URI reference.
+    pub source: String,
+}
+
+impl CloudEventsFormat {
+    /// Reads the operator settings: no envelope when `mode` is unset or
+    /// empty, [`DEFAULT_SOURCE`] when `source` is.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnknownCloudEventsMode` for a mode other than `structured` or
+    /// `binary`.
+    pub fn parse(
+        mode: Option<&str>,
+        source: Option<&str>,
+    ) -> Result<Option<Self>, UnknownCloudEventsMode> {
+        let Some(mode) = mode.map(str::trim).filter(|m| !m.is_empty()) else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            mode: mode.parse()?,
+            source: source
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .unwrap_or(DEFAULT_SOURCE)
+                .to_owned(),
+        }))
+    }
+}
+
+/// A lifecycle event as a cloud event. The email is the `subject`, and the
+/// event payload the `data`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CloudEvent {
+    pub id: uuid::Uuid,
+    pub source: String,
+    /// Lifecycle event type, e.g. `delivery.failed`.
+    pub event_type: String,
+    pub email_id: EmailId,
+    pub time_ms: i64,
+    pub data: serde_json::Value,
+}
+
+impl CloudEvent {
+    /// Context attributes, each sent as a `ce-<name>` header in binary mode.
+    #[must_use]
+    pub fn attributes(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("specversion", SPEC_VERSION.to_owned()),
+            ("id", self.id.hyphenated().to_string()),
+            ("source", self.source.clone()),
+            ("type", format!("{TYPE_PREFIX}{}", self.event_type)),
+            ("subject", self.email_id.as_uuid().hyphenated().to_string()),
+            ("time", UtcDateTime::from_ms(self.time_ms).to_rfc3339()),
+            ("dataschema", DATA_SCHEMA.to_owned()),
+        ]
+    }
+
+    /// The whole event, sent as [`STRUCTURED_CONTENT_TYPE`] in structured mode.
+    #[must_use]
+    pub fn to_structured_json(&self) -> serde_json::Value {
+        let mut event: serde_json::Map<String, serde_json::Value> = self
+            .attributes()
+            .into_iter()
+            .map(|(name, value)| (name.to_owned(), value.into()))
+            .collect();
+        event.insert("datacontenttype".into(), DATA_CONTENT_TYPE.into());
+        event.insert("data".into(), self.data.clone());
+        event.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::email::EmailId;
+
+    use super::{CloudEvent, CloudEventsFormat, CloudEventsMode};
+
+    #[test]
+    fn format_is_opt_in() {
+        assert_eq!(CloudEventsFormat::parse(None, Some("/acme")).unwrap(), None);
+        assert_eq!(
+            CloudEventsFormat::parse(Some("binary"), None).unwrap(),
+            Some(CloudEventsFormat {
+                mode: CloudEventsMode::Binary,
+                source: "/catapulte".into(),
+            })
+        );
+        assert!(CloudEventsFormat::parse(Some("json"), None).is_err());
+    }
+
+    #[test]
+    fn structured_event_carries_every_attribute_and_the_data() {
+        let id = uuid::Uuid::now_v7();
+        let email_id = EmailId::default();
+        let event = CloudEvent {
+            id,
+            source: "/catapulte/eu-west-1".into(),
+            event_type: "delivery.failed".into(),
+            email_id,
+            time_ms: 1_700_000_000_050,
+            data: serde_json::json!({ "attempt": 3 }),
+        };
+
+        assert_eq!(
+            event.to_structured_json(),
+            serde_json::json!({
+                "specversion": "1.0",
+                "id": id.to_string(),
+                "source": "/catapulte/eu-west-1",
+                "type": "io.catapulte.delivery.failed",
+                "subject": email_id.as_uuid().to_string(),
+                "time": "2023-11-14T22:13:20.050Z",
+                "dataschema": "urn:catapulte:lifecycle-event:v1",
+                "datacontenttype": "application/json",
+                "data": { "attempt": 3 },
+            })
+        );
+    }
+}
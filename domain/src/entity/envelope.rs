@@ -1,6 +1,11 @@
 use crate::entity::attachment::AttachmentRef;
-use crate::entity::body::BodySource;
+use crate::entity::body::{BodySource, MjmlSource};
+use crate::entity::calendar::Calendar;
 use crate::entity::email::RecipientKind;
+use crate::entity::message_headers::MessageHeaders;
+use crate::entity::template::{parse_template_ref, split_variant};
+use crate::entity::tracking::Tracking;
+use crate::entity::unsubscribe::MailingList;
 
 #[derive(Clone)]
 pub struct Envelope {
@@ -12,4 +17,31 @@ pub struct Envelope {
     pub body: BodySource,
     pub variables: serde_json::Map<String, serde_json::Value>,
     pub attachments: Vec<AttachmentRef>,
+    /// Earliest delivery time (Unix epoch ms). `None` means "as soon as possible".
+    pub send_at_ms: Option<i64>,
+    /// Reply-To, display names and custom header fields.
+    pub headers: MessageHeaders,
+    /// Preferred locale (`fr-CA`), used to pick a localized template variant.
+    pub locale: Option<String>,
+    /// Invitation sent as the `text/calendar` alternative of the body.
+    pub calendar: Option<Calendar>,
+    /// Mailing list the email belongs to. Recipients who unsubscribed from
+    /// it are skipped.
+    pub list: Option<MailingList>,
+    /// Opens and clicks to report as lifecycle events.
+    pub tracking: Tracking,
+}
+
+impl Envelope {
+    /// Template of a named MJML body, without its pinned version, and the
+    /// locale of the variant it was pinned to: `welcome.fr@2` submitted with
+    /// `fr-CA` is `welcome` in `fr`.
+    #[must_use]
+    pub fn template(&self) -> Option<(&str, Option<&str>)> {
+        let BodySource::Mjml(MjmlSource::Named(reference)) = &self.body else {
+            return None;
+        };
+        let (name, _) = parse_template_ref(reference);
+        Some(split_variant(name, self.locale.as_deref()))
+    }
 }
@@ -2,17 +2,32 @@ use std::str::FromStr;
 
 use thiserror::Error;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum ErrorClass {
     TemplateResolve,
     TemplateInterpolate,
     TemplateRender,
     Attachment,
     Delivery,
+    /// The upstream server refused the message for good.
+    Rejected,
     Routing,
+    /// The message could not be DKIM-signed.
+    Signing,
 }
 
 impl ErrorClass {
+    pub const ALL: [Self; 8] = [
+        Self::TemplateResolve,
+        Self::TemplateInterpolate,
+        Self::TemplateRender,
+        Self::Attachment,
+        Self::Delivery,
+        Self::Rejected,
+        Self::Routing,
+        Self::Signing,
+    ];
+
     #[must_use]
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -21,7 +36,9 @@ impl ErrorClass {
             Self::TemplateRender => "template_render",
             Self::Attachment => "attachment",
             Self::Delivery => "delivery",
+            Self::Rejected => "rejected",
             Self::Routing => "routing",
+            Self::Signing => "signing",
         }
     }
 }
@@ -42,7 +59,9 @@ impl FromStr for ErrorClass {
             "template_render" => Ok(Self::TemplateRender),
             "attachment" => Ok(Self::Attachment),
             "delivery" => Ok(Self::Delivery),
+            "rejected" => Ok(Self::Rejected),
             "routing" => Ok(Self::Routing),
+            "signing" => Ok(Self::Signing),
             _ => Err(UnknownErrorClass {
                 value: s.to_owned(),
             }),
@@ -86,12 +105,24 @@ mod tests {
         assert_eq!(ErrorClass::from_str(ec.as_str()).unwrap(), ec);
     }
 
+    #[test]
+    fn round_trip_rejected() {
+        let ec = ErrorClass::Rejected;
+        assert_eq!(ErrorClass::from_str(ec.as_str()).unwrap(), ec);
+    }
+
     #[test]
     fn round_trip_routing() {
         let ec = ErrorClass::Routing;
         assert_eq!(ErrorClass::from_str(ec.as_str()).unwrap(), ec);
     }
 
+    #[test]
+    fn round_trip_signing() {
+        let ec = ErrorClass::Signing;
+        assert_eq!(ErrorClass::from_str(ec.as_str()).unwrap(), ec);
+    }
+
     #[test]
     fn unknown_string_returns_error() {
         let result = ErrorClass::from_str("bogus");
@@ -1,6 +1,21 @@
 use crate::entity::email::EmailId;
 use crate::entity::error_class::ErrorClass;
 use crate::entity::sender::SenderName;
+use crate::entity::unsubscribe::MailingList;
+
+/// Every value [`LifecycleEvent::event_type`] can return.
+pub const EVENT_TYPES: [&str; 10] = [
+    "queued",
+    "sending",
+    "delivery.succeeded",
+    "retrying",
+    "delivery.failed",
+    "cancelled",
+    "suppressed",
+    "unsubscribed",
+    "opened",
+    "clicked",
+];
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LifecycleEvent {
@@ -34,6 +49,39 @@ pub enum LifecycleEvent {
         sender_name: Option<SenderName>,
         correlation_id: Option<String>,
     },
+    Cancelled {
+        id: EmailId,
+        correlation_id: Option<String>,
+    },
+    /// Recipients skipped because they are on the suppression list.
+    Suppressed {
+        id: EmailId,
+        recipients: Vec<String>,
+        correlation_id: Option<String>,
+    },
+    /// The recipient of this email left `list` through its unsubscribe link.
+    Unsubscribed {
+        id: EmailId,
+        address: String,
+        list: MailingList,
+        correlation_id: Option<String>,
+    },
+    /// The open pixel of this email was loaded at `at_ms` (Unix epoch ms).
+    Opened {
+        id: EmailId,
+        user_agent: Option<String>,
+        at_ms: i64,
+        correlation_id: Option<String>,
+    },
+    /// A tracked link of this email, pointing to `url`, was followed at
+    /// `at_ms` (Unix epoch ms).
+    Clicked {
+        id: EmailId,
+        url: String,
+        user_agent: Option<String>,
+        at_ms: i64,
+        correlation_id: Option<String>,
+    },
 }
 
 impl LifecycleEvent {
@@ -46,6 +94,11 @@ impl LifecycleEvent {
             Self::Sent { .. } => "delivery.succeeded",
             Self::Retrying { .. } => "retrying",
             Self::Failed { .. } => "delivery.failed",
+            Self::Cancelled { .. } => "cancelled",
+            Self::Suppressed { .. } => "suppressed",
+            Self::Unsubscribed { .. } => "unsubscribed",
+            Self::Opened { .. } => "opened",
+            Self::Clicked { .. } => "clicked",
         }
     }
 
@@ -57,18 +110,47 @@ impl LifecycleEvent {
             | Self::Sending { id, .. }
             | Self::Sent { id, .. }
             | Self::Retrying { id, .. }
-            | Self::Failed { id, .. } => id,
+            | Self::Failed { id, .. }
+            | Self::Cancelled { id, .. }
+            | Self::Suppressed { id, .. }
+            | Self::Unsubscribed { id, .. }
+            | Self::Opened { id, .. }
+            | Self::Clicked { id, .. } => id,
+        }
+    }
+
+    /// The correlation id of the email this event belongs to.
+    #[must_use]
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            Self::Queued { correlation_id, .. }
+            | Self::Sending { correlation_id, .. }
+            | Self::Sent { correlation_id, .. }
+            | Self::Retrying { correlation_id, .. }
+            | Self::Failed { correlation_id, .. }
+            | Self::Cancelled { correlation_id, .. }
+            | Self::Suppressed { correlation_id, .. }
+            | Self::Unsubscribed { correlation_id, .. }
+            | Self::Opened { correlation_id, .. }
+            | Self::Clicked { correlation_id, .. } => correlation_id.as_deref(),
         }
     }
 
     /// The sender name, if this event carries one.
     ///
-    /// `Queued` and `Sending` never have a sender; `Sent` always has one;
-    /// `Retrying` and `Failed` carry an optional sender.
+    /// `Queued`, `Sending`, `Cancelled`, `Suppressed`, `Unsubscribed`,
+    /// `Opened` and `Clicked` never have a sender;
+    /// `Sent` always has one; `Retrying` and `Failed` carry an optional sender.
     #[must_use]
     pub fn sender_name(&self) -> Option<&SenderName> {
         match self {
-            Self::Queued { .. } | Self::Sending { .. } => None,
+            Self::Queued { .. }
+            | Self::Sending { .. }
+            | Self::Cancelled { .. }
+            | Self::Suppressed { .. }
+            | Self::Unsubscribed { .. }
+            | Self::Opened { .. }
+            | Self::Clicked { .. } => None,
             Self::Sent { sender_name, .. } => Some(sender_name),
             Self::Retrying { sender_name, .. } | Self::Failed { sender_name, .. } => {
                 sender_name.as_ref()
@@ -94,7 +176,7 @@ impl LifecycleEvent {
     #[must_use]
     pub fn payload(&self) -> serde_json::Value {
         match self {
-            Self::Queued { correlation_id, .. } => {
+            Self::Queued { correlation_id, .. } | Self::Cancelled { correlation_id, .. } => {
                 serde_json::json!({ "correlation_id": correlation_id })
             }
             Self::Sending {
@@ -102,6 +184,46 @@ impl LifecycleEvent {
                 correlation_id,
                 ..
             } => serde_json::json!({ "attempt": attempt, "correlation_id": correlation_id }),
+            Self::Suppressed {
+                recipients,
+                correlation_id,
+                ..
+            } => serde_json::json!({
+                "recipients": recipients,
+                "correlation_id": correlation_id,
+            }),
+            Self::Unsubscribed {
+                address,
+                list,
+                correlation_id,
+                ..
+            } => serde_json::json!({
+                "address": address,
+                "list": list.as_str(),
+                "correlation_id": correlation_id,
+            }),
+            Self::Opened {
+                user_agent,
+                at_ms,
+                correlation_id,
+                ..
+            } => serde_json::json!({
+                "user_agent": user_agent,
+                "at_ms": at_ms,
+                "correlation_id": correlation_id,
+            }),
+            Self::Clicked {
+                url,
+                user_agent,
+                at_ms,
+                correlation_id,
+                ..
+            } => serde_json::json!({
+                "url": url,
+                "user_agent": user_agent,
+                "at_ms": at_ms,
+                "correlation_id": correlation_id,
+            }),
             Self::Sent {
                 sender_name,
                 correlation_id,
@@ -152,6 +274,15 @@ mod tests {
         assert_eq!(e.event_type(), "queued");
     }
 
+    #[test]
+    fn event_type_cancelled() {
+        let e = LifecycleEvent::Cancelled {
+            id: EmailId::default(),
+            correlation_id: None,
+        };
+        assert_eq!(e.event_type(), "cancelled");
+    }
+
     #[test]
     fn event_type_sending() {
         let e = LifecycleEvent::Sending {
@@ -232,6 +363,15 @@ mod tests {
                 sender_name: None,
                 correlation_id: None,
             },
+            LifecycleEvent::Cancelled {
+                id,
+                correlation_id: None,
+            },
+            LifecycleEvent::Suppressed {
+                id,
+                recipients: vec!["a@example.com".to_owned()],
+                correlation_id: None,
+            },
         ];
         for e in &variants {
             assert_eq!(e.email_id(), &id);
@@ -352,6 +492,96 @@ mod tests {
         assert_eq!(e.payload(), expected);
     }
 
+    #[test]
+    fn payload_cancelled() {
+        let e = LifecycleEvent::Cancelled {
+            id: EmailId::default(),
+            correlation_id: Some("corr-123".to_owned()),
+        };
+        assert_eq!(
+            e.payload(),
+            serde_json::json!({ "correlation_id": "corr-123" })
+        );
+        assert!(e.sender_name().is_none());
+        assert!(e.error_class().is_none());
+    }
+
+    #[test]
+    fn payload_suppressed() {
+        let e = LifecycleEvent::Suppressed {
+            id: EmailId::default(),
+            recipients: vec!["gone@example.com".to_owned()],
+            correlation_id: None,
+        };
+        assert_eq!(e.event_type(), "suppressed");
+        assert_eq!(
+            e.payload(),
+            serde_json::json!({ "recipients": ["gone@example.com"], "correlation_id": null })
+        );
+        assert!(e.sender_name().is_none());
+        assert!(e.error_class().is_none());
+    }
+
+    #[test]
+    fn payload_unsubscribed() {
+        let e = LifecycleEvent::Unsubscribed {
+            id: EmailId::default(),
+            address: "bob@example.com".to_owned(),
+            list: crate::entity::unsubscribe::MailingList::parse("newsletter").unwrap(),
+            correlation_id: Some("corr-123".to_owned()),
+        };
+        assert_eq!(e.event_type(), "unsubscribed");
+        assert_eq!(
+            e.payload(),
+            serde_json::json!({
+                "address": "bob@example.com",
+                "list": "newsletter",
+                "correlation_id": "corr-123",
+            })
+        );
+        assert!(e.sender_name().is_none());
+        assert!(e.error_class().is_none());
+    }
+
+    #[test]
+    fn payload_opened_and_clicked() {
+        let id = EmailId::default();
+        let opened = LifecycleEvent::Opened {
+            id,
+            user_agent: Some("Mozilla/5.0".to_owned()),
+            at_ms: 1_700_000_000_000,
+            correlation_id: None,
+        };
+        assert_eq!(opened.event_type(), "opened");
+        assert_eq!(
+            opened.payload(),
+            serde_json::json!({
+                "user_agent": "Mozilla/5.0",
+                "at_ms": 1_700_000_000_000_i64,
+                "correlation_id": null,
+            })
+        );
+        let clicked = LifecycleEvent::Clicked {
+            id,
+            url: "https://acme.com/pricing".to_owned(),
+            user_agent: None,
+            at_ms: 1_700_000_000_001,
+            correlation_id: Some("corr-123".to_owned()),
+        };
+        assert_eq!(clicked.event_type(), "clicked");
+        assert_eq!(
+            clicked.payload(),
+            serde_json::json!({
+                "url": "https://acme.com/pricing",
+                "user_agent": null,
+                "at_ms": 1_700_000_000_001_i64,
+                "correlation_id": "corr-123",
+            })
+        );
+        assert_eq!(clicked.email_id(), &id);
+        assert!(clicked.sender_name().is_none());
+    }
+
     #[test]
     fn payload_queued_with_correlation_id() {
         let id = EmailId::default();
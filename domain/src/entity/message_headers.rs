@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+/// Header fields generated by catapulte or the transport, which callers may
+/// not set through [`MessageHeaders::custom`]. Compared case-insensitively.
+pub const RESERVED_HEADERS: &[&str] = &[
+    "bcc",
+    "cc",
+    "content-disposition",
+    "content-id",
+    "content-transfer-encoding",
+    "content-type",
+    "date",
+    "dkim-signature",
+    "from",
+    "message-id",
+    "mime-version",
+    "received",
+    "reply-to",
+    "return-path",
+    "sender",
+    "subject",
+    "to",
+];
+
+/// Longest accepted custom header value, in bytes.
+pub const MAX_HEADER_VALUE_BYTES: usize = 998;
+
+/// Header fields set by the caller on top of sender, recipients and subject.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MessageHeaders {
+    /// Address replies should go to instead of the sender.
+    pub reply_to: Option<String>,
+    /// Display names by address, for the sender, the recipients and
+    /// `reply_to`, e.g. `billing@acme.com` → `Acme Billing`.
+    pub display_names: BTreeMap<String, String>,
+    /// Extra header fields such as `X-Campaign-Id` or `Importance`.
+    pub custom: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidHeader {
+    #[error("header name {0:?} is not a valid field name")]
+    InvalidName(String),
+    #[error("header {0:?} is reserved")]
+    Reserved(String),
+    #[error("value of header {0:?} contains a line break or is too long")]
+    InvalidValue(String),
+    #[error("display name for {0:?} contains a line break")]
+    InvalidDisplayName(String),
+}
+
+impl MessageHeaders {
+    /// Display name to show next to `address`, if any.
+    #[must_use]
+    pub fn display_name(&self, address: &str) -> Option<&str> {
+        self.display_names.get(address).map(String::as_str)
+    }
+
+    /// # Errors
+    ///
+    /// Returns `InvalidHeader` when a custom header is reserved or malformed,
+    /// or when a display name spans several lines.
+    pub fn validate(&self) -> Result<(), InvalidHeader> {
+        for (name, value) in &self.custom {
+            validate_custom_header(name, value)?;
+        }
+        for (address, name) in &self.display_names {
+            if name.contains(['\r', '\n']) {
+                return Err(InvalidHeader::InvalidDisplayName(address.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// # Errors
+///
+/// Returns `InvalidHeader` when `name` is not an RFC 5322 field name or is
+/// reserved, or when `value` contains a line break or is too long.
+pub fn validate_custom_header(name: &str, value: &str) -> Result<(), InvalidHeader> {
+    let valid_name = !name.is_empty() && name.bytes().all(|b| (33..=126).contains(&b) && b != b':');
+    if !valid_name {
+        return Err(InvalidHeader::InvalidName(name.to_owned()));
+    }
+    if RESERVED_HEADERS
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    {
+        return Err(InvalidHeader::Reserved(name.to_owned()));
+    }
+    if value.contains(['\r', '\n']) || value.len() > MAX_HEADER_VALUE_BYTES {
+        return Err(InvalidHeader::InvalidValue(name.to_owned()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidHeader, MessageHeaders, validate_custom_header};
+
+    #[test]
+    fn custom_headers_accept_extension_fields() {
+        assert_eq!(
+            validate_custom_header("X-Campaign-Id", "spring-sale"),
+            Ok(())
+        );
+        assert_eq!(validate_custom_header("Importance", "high"), Ok(()));
+    }
+
+    #[test]
+    fn reserved_headers_are_rejected_whatever_the_case() {
+        assert_eq!(
+            validate_custom_header("reply-TO", "a@b.c"),
+            Err(InvalidHeader::Reserved("reply-TO".into()))
+        );
+        assert_eq!(
+            validate_custom_header("Bcc", "spy@b.c"),
+            Err(InvalidHeader::Reserved("Bcc".into()))
+        );
+    }
+
+    #[test]
+    fn malformed_names_and_values_are_rejected() {
+        assert!(matches!(
+            validate_custom_header("X Campaign", "v"),
+            Err(InvalidHeader::InvalidName(_))
+        ));
+        assert!(matches!(
+            validate_custom_header("X-Id:", "v"),
+            Err(InvalidHeader::InvalidName(_))
+        ));
+        assert!(matches!(
+            validate_custom_header("X-Id", "a\r\nBcc: spy@b.c"),
+            Err(InvalidHeader::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn display_names_must_fit_on_one_line() {
+        let mut headers = MessageHeaders::default();
+        headers
+            .display_names
+            .insert("a@b.c".into(), "Acme\nBcc: spy@b.c".into());
+        assert_eq!(
+            headers.validate(),
+            Err(InvalidHeader::InvalidDisplayName("a@b.c".into()))
+        );
+    }
+}
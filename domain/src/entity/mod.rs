@@ -1,7 +1,16 @@
 pub mod attachment;
 pub mod body;
+pub mod calendar;
+pub mod cloud_event;
 pub mod email;
 pub mod envelope;
 pub mod error_class;
 pub mod lifecycle_event;
+pub mod message_headers;
+pub mod retry_policy;
 pub mod sender;
+pub mod template;
+pub mod tracking;
+pub mod unsubscribe;
+pub(crate) mod utc;
+pub mod webhook_subscription;
@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher as _, Hasher as _};
+use std::time::Duration;
+
+use crate::entity::error_class::ErrorClass;
+
+/// How failures of one [`ErrorClass`] are retried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryRule {
+    /// Attempts in total, the first one included. `1` never retries.
+    pub max_attempts: u32,
+    /// Delay before each retry. The last step repeats when there are more
+    /// retries than steps.
+    pub backoff: Vec<Duration>,
+    /// Each delay is randomly shortened or lengthened by up to this
+    /// percentage, so emails failing together do not retry together.
+    pub jitter_percent: u8,
+    /// Stop retrying once the email has been due for this long, whatever the
+    /// attempt count.
+    pub max_age: Option<Duration>,
+}
+
+impl RetryRule {
+    /// A rule that fails on the first error.
+    #[must_use]
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the retry that follows the failed `attempt` (1-based),
+    /// before jitter.
+    #[must_use]
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let index = usize::try_from(attempt.saturating_sub(1)).unwrap_or(usize::MAX);
+        self.backoff
+            .get(index)
+            .or_else(|| self.backoff.last())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Delay before the retry that follows the failed `attempt`, jitter applied.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        jittered(self.base_delay(attempt), self.jitter_percent, random_u64())
+    }
+
+    /// Whether another attempt is allowed after `attempt` failed, `age` after
+    /// the email became due.
+    #[must_use]
+    pub fn allows_retry(&self, attempt: u32, age: Duration) -> bool {
+        attempt < self.max_attempts && self.max_age.is_none_or(|max_age| age < max_age)
+    }
+}
+
+impl Default for RetryRule {
+    /// Three attempts, 30 seconds apart and doubling up to an hour.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff: [30, 60, 120, 240, 480, 960, 1920, 3600]
+                .into_iter()
+                .map(Duration::from_secs)
+                .collect(),
+            jitter_percent: 0,
+            max_age: None,
+        }
+    }
+}
+
+/// Retry rules for failed delivery attempts, per [`ErrorClass`].
+///
+/// Shared by the worker, which decides whether and when to retry, and by the
+/// queue backends that keep their own redelivery limits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    default_rule: RetryRule,
+    rules: HashMap<ErrorClass, RetryRule>,
+}
+
+impl RetryPolicy {
+    #[must_use]
+    pub fn new(default_rule: RetryRule) -> Self {
+        Self {
+            default_rule,
+            rules: HashMap::new(),
+        }
+    }
+
+    /// Overrides the default rule for `class`.
+    #[must_use]
+    pub fn with_rule(mut self, class: ErrorClass, rule: RetryRule) -> Self {
+        self.rules.insert(class, rule);
+        self
+    }
+
+    /// Rule for failures that have no [`ErrorClass`], and for every class
+    /// without an override.
+    #[must_use]
+    pub fn default_rule(&self) -> &RetryRule {
+        &self.default_rule
+    }
+
+    #[must_use]
+    pub fn rule(&self, class: &ErrorClass) -> &RetryRule {
+        self.rules.get(class).unwrap_or(&self.default_rule)
+    }
+
+    /// Returns the delay before the next attempt, or `None` when the email
+    /// must fail for good.
+    #[must_use]
+    pub fn retry_delay(&self, class: &ErrorClass, attempt: u32, age: Duration) -> Option<Duration> {
+        let rule = self.rule(class);
+        rule.allows_retry(attempt, age).then(|| rule.delay(attempt))
+    }
+
+    /// Highest attempt count any rule allows. Backends that cap redeliveries
+    /// on their own must allow at least this many.
+    #[must_use]
+    pub fn max_attempts(&self) -> u32 {
+        self.rules
+            .values()
+            .map(|rule| rule.max_attempts)
+            .fold(self.default_rule.max_attempts, u32::max)
+    }
+}
+
+fn jittered(delay: Duration, jitter_percent: u8, random: u64) -> Duration {
+    let delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX);
+    let spread = delay_ms.saturating_mul(u64::from(jitter_percent.min(100))) / 100;
+    if spread == 0 {
+        return delay;
+    }
+    let offset = random % (spread.saturating_mul(2).saturating_add(1));
+    Duration::from_millis(delay_ms.saturating_sub(spread).saturating_add(offset))
+}
+
+fn random_u64() -> u64 {
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{RetryPolicy, RetryRule, jittered};
+    use crate::entity::error_class::ErrorClass;
+
+    #[test]
+    fn default_rule_matches_three_doubling_attempts() {
+        let policy = RetryPolicy::default();
+        let age = Duration::ZERO;
+        assert_eq!(
+            policy.retry_delay(&ErrorClass::Delivery, 1, age),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            policy.retry_delay(&ErrorClass::Delivery, 2, age),
+            Some(Duration::from_mins(1))
+        );
+        assert_eq!(policy.retry_delay(&ErrorClass::Delivery, 3, age), None);
+    }
+
+    #[test]
+    fn last_backoff_step_repeats() {
+        let rule = RetryRule {
+            max_attempts: 10,
+            backoff: vec![Duration::from_secs(5), Duration::from_secs(10)],
+            ..RetryRule::default()
+        };
+        assert_eq!(rule.base_delay(1), Duration::from_secs(5));
+        assert_eq!(rule.base_delay(2), Duration::from_secs(10));
+        assert_eq!(rule.base_delay(7), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn per_class_rule_overrides_the_default() {
+        let policy = RetryPolicy::default()
+            .with_rule(ErrorClass::TemplateRender, RetryRule::never())
+            .with_rule(
+                ErrorClass::Delivery,
+                RetryRule {
+                    max_attempts: 50,
+                    ..RetryRule::default()
+                },
+            );
+        let age = Duration::ZERO;
+        assert_eq!(
+            policy.retry_delay(&ErrorClass::TemplateRender, 1, age),
+            None
+        );
+        assert!(policy.retry_delay(&ErrorClass::Delivery, 10, age).is_some());
+        assert!(
+            policy
+                .retry_delay(&ErrorClass::Attachment, 1, age)
+                .is_some()
+        );
+        assert_eq!(policy.max_attempts(), 50);
+    }
+
+    #[test]
+    fn max_age_stops_retries_before_max_attempts() {
+        let rule = RetryRule {
+            max_attempts: 100,
+            max_age: Some(Duration::from_hours(24)),
+            ..RetryRule::default()
+        };
+        assert!(rule.allows_retry(5, Duration::from_hours(1)));
+        assert!(!rule.allows_retry(5, Duration::from_hours(24)));
+    }
+
+    #[test]
+    fn jitter_stays_within_the_configured_spread() {
+        let delay = Duration::from_secs(100);
+        assert_eq!(jittered(delay, 0, 12345), delay);
+        assert_eq!(jittered(delay, 10, 0), Duration::from_secs(90));
+        assert_eq!(jittered(delay, 10, 20_000), Duration::from_secs(110));
+        for random in [1, 7_777, u64::MAX] {
+            let d = jittered(delay, 10, random);
+            assert!(d >= Duration::from_secs(90) && d <= Duration::from_secs(110));
+        }
+    }
+}
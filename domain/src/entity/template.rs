@@ -0,0 +1,191 @@
+use thiserror::Error;
+
+/// One immutable version of a named MJML template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Template {
+    pub name: String,
+    /// Starts at 1 and grows by one on every publish; never reused.
+    pub version: u32,
+    pub content: String,
+    pub created_at_ms: i64,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("template name {0:?} must be 1 to 128 letters, digits, '-', '_' or '.'")]
+pub struct InvalidTemplateName(pub String);
+
+/// Longest accepted template name, in bytes.
+pub const MAX_TEMPLATE_NAME_BYTES: usize = 128;
+
+/// # Errors
+///
+/// Returns `InvalidTemplateName` when `name` is empty, too long, or contains
+/// anything but ASCII letters, digits, `-`, `_` and `.`.
+pub fn validate_template_name(name: &str) -> Result<(), InvalidTemplateName> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_TEMPLATE_NAME_BYTES
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidTemplateName(name.to_owned()))
+    }
+}
+
+/// Splits a template reference into its name and pinned version:
+/// `welcome@3` → (`welcome`, `Some(3)`), `welcome` → (`welcome`, `None`).
+#[must_use]
+pub fn parse_template_ref(reference: &str) -> (&str, Option<u32>) {
+    match reference.rsplit_once('@') {
+        Some((name, version)) => match version.parse() {
+            Ok(version) => (name, Some(version)),
+            Err(_) => (reference, None),
+        },
+        None => (reference, None),
+    }
+}
+
+/// Reference to `version` of `name`, as read by [`parse_template_ref`].
+#[must_use]
+pub fn pinned_template_ref(name: &str, version: u32) -> String {
+    format!("{name}@{version}")
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("locale {0:?} must be a language tag such as \"fr\" or \"fr-CA\"")]
+pub struct InvalidLocale(pub String);
+
+/// Longest accepted locale, in bytes.
+pub const MAX_LOCALE_BYTES: usize = 35;
+
+/// # Errors
+///
+/// Returns `InvalidLocale` unless `locale` is a `-`-separated list of 1 to 8
+/// ASCII letters or digits, starting with a letter-only language subtag.
+pub fn validate_locale(locale: &str) -> Result<(), InvalidLocale> {
+    let mut subtags = locale.split('-');
+    let language_ok = subtags
+        .next()
+        .is_some_and(|l| (1..=8).contains(&l.len()) && l.bytes().all(|b| b.is_ascii_alphabetic()));
+    let valid = language_ok
+        && locale.len() <= MAX_LOCALE_BYTES
+        && subtags
+            .all(|s| (1..=8).contains(&s.len()) && s.bytes().all(|b| b.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(InvalidLocale(locale.to_owned()))
+    }
+}
+
+/// Locales tried for `locale`, most specific first: `fr-CA` → `fr-CA`, `fr`.
+pub fn locale_fallbacks(locale: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(locale), |l| {
+        l.rsplit_once('-').map(|(parent, _)| parent)
+    })
+}
+
+/// Name of the `locale` variant of template `name`: `welcome` → `welcome.fr`.
+#[must_use]
+pub fn localized_template_name(name: &str, locale: &str) -> String {
+    format!("{name}.{locale}")
+}
+
+/// Locale of the variant `name` was picked as for `locale`, if any:
+/// `welcome.fr` with `fr-CA` → `fr`.
+#[must_use]
+pub fn variant_locale<'a>(name: &str, locale: &'a str) -> Option<&'a str> {
+    locale_fallbacks(locale).find(|candidate| {
+        name.strip_suffix(candidate)
+            .is_some_and(|base| base.len() > 1 && base.ends_with('.'))
+    })
+}
+
+/// Splits a template name into the template it is a variant of and the
+/// locale of that variant, picked for `locale`: `welcome.fr` with `fr-CA` →
+/// (`welcome`, `fr`). Names that are no variant for `locale` are returned as
+/// they are.
+#[must_use]
+pub fn split_variant<'a, 'b>(name: &'a str, locale: Option<&'b str>) -> (&'a str, Option<&'b str>) {
+    match locale.and_then(|locale| variant_locale(name, locale)) {
+        Some(variant) => (&name[..name.len() - variant.len() - 1], Some(variant)),
+        None => (name, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        locale_fallbacks, localized_template_name, parse_template_ref, pinned_template_ref,
+        split_variant, validate_locale, validate_template_name, variant_locale,
+    };
+
+    #[test]
+    fn template_refs_round_trip() {
+        assert_eq!(parse_template_ref("welcome"), ("welcome", None));
+        assert_eq!(parse_template_ref("welcome@3"), ("welcome", Some(3)));
+        assert_eq!(
+            parse_template_ref(&pinned_template_ref("order.shipped", 12)),
+            ("order.shipped", Some(12))
+        );
+        assert_eq!(
+            parse_template_ref("welcome@latest"),
+            ("welcome@latest", None)
+        );
+    }
+
+    #[test]
+    fn template_names_are_restricted() {
+        assert!(validate_template_name("order.shipped_v-2").is_ok());
+        assert!(validate_template_name("").is_err());
+        assert!(validate_template_name("welcome@3").is_err());
+        assert!(validate_template_name("../etc/passwd").is_err());
+        assert!(validate_template_name(&"a".repeat(129)).is_err());
+    }
+
+    #[test]
+    fn locales_are_language_tags() {
+        assert!(validate_locale("fr").is_ok());
+        assert!(validate_locale("fr-CA").is_ok());
+        assert!(validate_locale("zh-Hant-TW").is_ok());
+        assert!(validate_locale("es-419").is_ok());
+        assert!(validate_locale("").is_err());
+        assert!(validate_locale("fr_CA").is_err());
+        assert!(validate_locale("fr-").is_err());
+        assert!(validate_locale("419").is_err());
+        assert!(validate_locale("../fr").is_err());
+    }
+
+    #[test]
+    fn locale_fallbacks_drop_subtags_one_by_one() {
+        assert_eq!(
+            locale_fallbacks("zh-Hant-TW").collect::<Vec<_>>(),
+            ["zh-Hant-TW", "zh-Hant", "zh"]
+        );
+        assert_eq!(locale_fallbacks("fr").collect::<Vec<_>>(), ["fr"]);
+    }
+
+    #[test]
+    fn variant_locale_matches_localized_names() {
+        assert_eq!(localized_template_name("welcome", "fr-CA"), "welcome.fr-CA");
+        assert_eq!(variant_locale("welcome.fr", "fr-CA"), Some("fr"));
+        assert_eq!(variant_locale("welcome.fr-CA", "fr-CA"), Some("fr-CA"));
+        assert_eq!(variant_locale("welcome", "fr-CA"), None);
+        assert_eq!(variant_locale("welcome.de", "fr-CA"), None);
+    }
+
+    #[test]
+    fn variants_are_split_from_their_template() {
+        assert_eq!(
+            split_variant("welcome.fr", Some("fr-CA")),
+            ("welcome", Some("fr"))
+        );
+        assert_eq!(split_variant("welcome", Some("fr")), ("welcome", None));
+        assert_eq!(
+            split_variant("order.shipped", None),
+            ("order.shipped", None)
+        );
+    }
+}
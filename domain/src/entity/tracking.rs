@@ -0,0 +1,36 @@
+use crate::entity::email::EmailId;
+
+/// Engagement an email is tracked for. Nothing is tracked by default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tracking {
+    /// Add a pixel to the HTML part, reported as `opened`.
+    pub opens: bool,
+    /// Route the `http(s)` links of the HTML part through a redirect,
+    /// reported as `clicked`.
+    pub clicks: bool,
+}
+
+impl Tracking {
+    #[must_use]
+    pub const fn is_enabled(self) -> bool {
+        self.opens || self.clicks
+    }
+}
+
+/// The email a tracking link was issued for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedEmail {
+    pub email_id: EmailId,
+    pub correlation_id: Option<String>,
+}
+
+/// What a verified tracking link stands for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrackingHit {
+    Open(TrackedEmail),
+    /// A click on a link pointing to `url`.
+    Click {
+        email: TrackedEmail,
+        url: String,
+    },
+}
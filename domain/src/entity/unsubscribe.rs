@@ -0,0 +1,90 @@
+use thiserror::Error;
+
+use crate::entity::email::EmailId;
+
+/// Longest accepted list name, in bytes.
+pub const MAX_LIST_NAME_BYTES: usize = 64;
+
+/// Header carrying the unsubscribe link (RFC 2369).
+pub const LIST_UNSUBSCRIBE_HEADER: &str = "List-Unsubscribe";
+/// Header announcing one-click unsubscribe support (RFC 8058).
+pub const LIST_UNSUBSCRIBE_POST_HEADER: &str = "List-Unsubscribe-Post";
+/// The only value RFC 8058 allows for [`LIST_UNSUBSCRIBE_POST_HEADER`].
+pub const ONE_CLICK_VALUE: &str = "List-Unsubscribe=One-Click";
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidListName {
+    #[error("list name is empty")]
+    Empty,
+    #[error("list name is longer than {MAX_LIST_NAME_BYTES} bytes")]
+    TooLong,
+    #[error("list name contains {0:?}; only ASCII letters, digits, '.', '-' and '_' are allowed")]
+    InvalidCharacter(char),
+}
+
+/// Mailing list a recipient can unsubscribe from, such as `newsletter`.
+/// Opt-outs are scoped to the sender address and the list.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MailingList(String);
+
+impl MailingList {
+    /// # Errors
+    ///
+    /// Returns `InvalidListName` when `name` is empty, too long or contains
+    /// anything but ASCII letters, digits, `.`, `-` and `_`.
+    pub fn parse(name: &str) -> Result<Self, InvalidListName> {
+        if name.is_empty() {
+            return Err(InvalidListName::Empty);
+        }
+        if name.len() > MAX_LIST_NAME_BYTES {
+            return Err(InvalidListName::TooLong);
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+        {
+            return Err(InvalidListName::InvalidCharacter(c));
+        }
+        Ok(Self(name.to_owned()))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// What an unsubscribe link stands for: `address` receiving `list` from
+/// `sender`, through the email `email_id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subscription {
+    pub email_id: EmailId,
+    pub sender: String,
+    pub list: MailingList,
+    pub address: String,
+    pub correlation_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InvalidListName, MAX_LIST_NAME_BYTES, MailingList};
+
+    #[test]
+    fn list_names_accept_simple_identifiers() {
+        let list = MailingList::parse("product-updates_v2.fr").unwrap();
+        assert_eq!(list.as_str(), "product-updates_v2.fr");
+    }
+
+    #[test]
+    fn list_names_reject_empty_long_and_odd_values() {
+        assert_eq!(MailingList::parse(""), Err(InvalidListName::Empty));
+        assert_eq!(
+            MailingList::parse(&"a".repeat(MAX_LIST_NAME_BYTES + 1)),
+            Err(InvalidListName::TooLong)
+        );
+        assert_eq!(
+            MailingList::parse("news letter"),
+            Err(InvalidListName::InvalidCharacter(' '))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+/// A Unix epoch ms timestamp broken down into its UTC calendar fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct UtcDateTime {
+    year: i64,
+    month: i64,
+    day: i64,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    millisecond: i64,
+}
+
+impl UtcDateTime {
+    pub(crate) fn from_ms(ms: i64) -> Self {
+        let secs = ms.div_euclid(1000);
+        let (days, secs_of_day) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day % 3600 / 60,
+            second: secs_of_day % 60,
+            millisecond: ms.rem_euclid(1000),
+        }
+    }
+
+    /// `20260301T143000Z`, the iCalendar UTC form (RFC 5545 §3.3.5).
+    pub(crate) fn to_basic(self) -> String {
+        format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// `2026-03-01T14:30:00.000Z` (RFC 3339).
+    pub(crate) fn to_rfc3339(self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second, self.millisecond
+        )
+    }
+}
+
+/// Gregorian date of a day count since 1970-01-01 (Howard Hinnant's
+/// `civil_from_days`).
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UtcDateTime;
+
+    #[test]
+    fn basic_timestamps_are_formatted() {
+        assert_eq!(UtcDateTime::from_ms(0).to_basic(), "19700101T000000Z");
+        assert_eq!(
+            UtcDateTime::from_ms(1_772_375_400_000).to_basic(),
+            "20260301T143000Z"
+        );
+        assert_eq!(
+            UtcDateTime::from_ms(951_782_400_000).to_basic(),
+            "20000229T000000Z"
+        );
+    }
+
+    #[test]
+    fn rfc3339_timestamps_keep_milliseconds() {
+        assert_eq!(
+            UtcDateTime::from_ms(0).to_rfc3339(),
+            "1970-01-01T00:00:00.000Z"
+        );
+        assert_eq!(
+            UtcDateTime::from_ms(1_700_000_000_050).to_rfc3339(),
+            "2023-11-14T22:13:20.050Z"
+        );
+    }
+
+    #[test]
+    fn timestamps_before_the_epoch_are_formatted() {
+        assert_eq!(
+            UtcDateTime::from_ms(-1).to_rfc3339(),
+            "1969-12-31T23:59:59.999Z"
+        );
+    }
+}
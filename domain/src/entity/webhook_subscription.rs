@@ -0,0 +1,152 @@
+use thiserror::Error;
+
+use crate::entity::lifecycle_event::{EVENT_TYPES, LifecycleEvent};
+
+/// Shortest accepted signing secret, in bytes.
+pub const MIN_SECRET_BYTES: usize = 32;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum InvalidSubscription {
+    #[error("url must be an absolute http or https URL")]
+    InvalidUrl,
+    #[error("secret must be at least {MIN_SECRET_BYTES} bytes")]
+    SecretTooShort,
+    #[error("unknown event type {0:?}")]
+    UnknownEventType(String),
+}
+
+/// Which events a subscription receives. An empty `event_types` stands for
+/// every type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubscriptionFilter {
+    pub event_types: Vec<String>,
+    /// Only events of emails whose correlation id starts with this prefix.
+    pub correlation_id_prefix: Option<String>,
+}
+
+impl SubscriptionFilter {
+    /// # Errors
+    ///
+    /// Returns `InvalidSubscription::UnknownEventType` for a type that is not
+    /// in [`EVENT_TYPES`].
+    pub fn validate(&self) -> Result<(), InvalidSubscription> {
+        match self
+            .event_types
+            .iter()
+            .find(|t| !EVENT_TYPES.contains(&t.as_str()))
+        {
+            Some(unknown) => Err(InvalidSubscription::UnknownEventType(unknown.clone())),
+            None => Ok(()),
+        }
+    }
+
+    #[must_use]
+    pub fn matches(&self, event: &LifecycleEvent) -> bool {
+        let type_matches =
+            self.event_types.is_empty() || self.event_types.iter().any(|t| t == event.event_type());
+        let correlation_matches = match &self.correlation_id_prefix {
+            None => true,
+            Some(prefix) => event
+                .correlation_id()
+                .is_some_and(|id| id.starts_with(prefix.as_str())),
+        };
+        type_matches && correlation_matches
+    }
+}
+
+/// Endpoint registered through the API to receive lifecycle events, on top
+/// of the webhook configured by the operator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookSubscription {
+    pub id: uuid::Uuid,
+    pub url: url::Url,
+    /// Signs every delivery to `url`.
+    pub secret: String,
+    pub filter: SubscriptionFilter,
+    pub created_at_ms: i64,
+}
+
+/// Parses and checks the fields of a subscription to create.
+///
+/// # Errors
+///
+/// Returns `InvalidSubscription` when the URL is not http(s), the secret is
+/// too short or the filter names an unknown event type.
+pub fn validate_subscription(
+    url: &str,
+    secret: &str,
+    filter: &SubscriptionFilter,
+) -> Result<url::Url, InvalidSubscription> {
+    let url = url::Url::parse(url).map_err(|_| InvalidSubscription::InvalidUrl)?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(InvalidSubscription::InvalidUrl);
+    }
+    if secret.len() < MIN_SECRET_BYTES {
+        return Err(InvalidSubscription::SecretTooShort);
+    }
+    filter.validate()?;
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::email::EmailId;
+    use crate::entity::lifecycle_event::LifecycleEvent;
+
+    use super::{InvalidSubscription, SubscriptionFilter, validate_subscription};
+
+    fn queued(correlation_id: Option<&str>) -> LifecycleEvent {
+        LifecycleEvent::Queued {
+            id: EmailId::default(),
+            correlation_id: correlation_id.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_every_event() {
+        assert!(SubscriptionFilter::default().matches(&queued(None)));
+    }
+
+    #[test]
+    fn filter_requires_both_type_and_correlation_prefix() {
+        let filter = SubscriptionFilter {
+            event_types: vec!["queued".into(), "delivery.failed".into()],
+            correlation_id_prefix: Some("marketing:".into()),
+        };
+
+        assert!(filter.matches(&queued(Some("marketing:spring-sale"))));
+        assert!(!filter.matches(&queued(Some("billing:inv-42"))));
+        assert!(!filter.matches(&queued(None)));
+        assert!(!filter.matches(&LifecycleEvent::Cancelled {
+            id: EmailId::default(),
+            correlation_id: Some("marketing:spring-sale".into()),
+        }));
+    }
+
+    #[test]
+    fn validation_rejects_bad_urls_short_secrets_and_unknown_types() {
+        let secret = "s".repeat(32);
+        let filter = SubscriptionFilter::default();
+
+        assert!(validate_subscription("https://crm.acme.com/hooks", &secret, &filter).is_ok());
+        assert_eq!(
+            validate_subscription("ftp://crm.acme.com", &secret, &filter),
+            Err(InvalidSubscription::InvalidUrl)
+        );
+        assert_eq!(
+            validate_subscription("https://crm.acme.com", "short", &filter),
+            Err(InvalidSubscription::SecretTooShort)
+        );
+        assert_eq!(
+            validate_subscription(
+                "https://crm.acme.com",
+                &secret,
+                &SubscriptionFilter {
+                    event_types: vec!["delivered".into()],
+                    correlation_id_prefix: None,
+                }
+            ),
+            Err(InvalidSubscription::UnknownEventType("delivered".into()))
+        );
+    }
+}
@@ -57,6 +57,9 @@ pub struct DequeuedEmail {
     pub envelope: Envelope,
     /// 1-based delivery attempt count.
     pub attempt: u32,
+    /// When the email first entered the queue, in milliseconds since the Unix
+    /// epoch. Unchanged across retries.
+    pub enqueued_at_ms: i64,
     /// Must be passed back to [`EmailQueue::ack`] or [`EmailQueue::nack`].
     pub token: AckToken,
     /// W3C trace-context headers captured at enqueue time. Empty when the
@@ -9,6 +9,18 @@ pub enum SaveResult {
     Duplicate(EmailId),
 }
 
+pub enum CancelResult {
+    /// The email was marked cancelled. Carries what the caller needs to clean
+    /// up after it.
+    Cancelled {
+        correlation_id: Option<String>,
+        attachments: Vec<AttachmentRef>,
+    },
+    NotFound,
+    /// The email is being delivered, or already reached a final state.
+    NotCancellable,
+}
+
 #[derive(Debug, Error)]
 pub enum EmailRepositoryError {
     #[error("email storage failed")]
@@ -64,13 +76,56 @@ pub trait EmailRepository: Send + Sync + 'static {
         &self,
         id: EmailId,
     ) -> impl std::future::Future<Output = Result<(), EmailRepositoryError>> + Send;
+
+    /// Marks the email cancelled unless a worker has already started
+    /// delivering it or it reached a final state. Backends sharing storage
+    /// with the queue also drop its pending queue entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EmailRepositoryError::Storage` when the update fails.
+    fn cancel(
+        &self,
+        id: EmailId,
+    ) -> impl std::future::Future<Output = Result<CancelResult, EmailRepositoryError>> + Send;
+
+    /// Records that a worker is about to deliver the email, so that a
+    /// concurrent [`cancel`](Self::cancel) conflicts instead of racing the
+    /// send. Returns `false` when the email was cancelled (or no longer
+    /// exists) and must be skipped. A mark older than the queue's processing
+    /// timeout is stale, left by a worker that never reached
+    /// [`end_delivery`](Self::end_delivery), and no longer blocks a cancel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EmailRepositoryError::Storage` when the update fails.
+    fn begin_delivery(
+        &self,
+        id: EmailId,
+    ) -> impl std::future::Future<Output = Result<bool, EmailRepositoryError>> + Send;
+
+    /// Clears the mark set by [`begin_delivery`](Self::begin_delivery) once an
+    /// attempt failed and the email went back to the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns `EmailRepositoryError::Storage` when the update fails.
+    fn end_delivery(
+        &self,
+        id: EmailId,
+    ) -> impl std::future::Future<Output = Result<(), EmailRepositoryError>> + Send;
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum EmailStatus {
+    /// Accepted but held back until its `send_at_ms`.
+    Scheduled,
     Queued,
     Sent,
     Failed,
+    Cancelled,
+    /// Not sent because every recipient is on the suppression list.
+    Suppressed,
 }
 
 #[derive(Clone, Debug)]
@@ -93,5 +148,16 @@ pub struct EmailRecord {
     pub sender: String,
     pub recipients: Vec<(RecipientKind, String)>,
     pub created_at_ms: i64,
+    pub send_at_ms: Option<i64>,
     pub status: EmailStatus,
+    /// Name of the stored or directory template a named MJML body uses,
+    /// without its locale variant.
+    pub template: Option<String>,
+    /// Template version the email was pinned to at submit time; `None` for
+    /// directory templates.
+    pub template_version: Option<u32>,
+    /// Locale of the template variant the email was pinned to (`fr` for
+    /// `welcome.fr` picked for `fr-CA`, `None` for the base template), or the
+    /// requested locale when the variant is picked at send time.
+    pub locale: Option<String>,
 }
@@ -3,6 +3,7 @@ use thiserror::Error;
 use crate::entity::body::RenderedBody;
 use crate::entity::email::RecipientKind;
 use crate::entity::sender::SenderName;
+use crate::port::email_transport::SmtpReply;
 
 #[derive(Debug, Error)]
 pub enum SendError {
@@ -12,6 +13,21 @@ pub enum SendError {
         #[source]
         source: anyhow::Error,
     },
+    /// The upstream server refused the message for good.
+    #[error("rejected by upstream server: {reply}")]
+    Rejected {
+        sender_name: SenderName,
+        reply: SmtpReply,
+        /// Recipients the rejection is pinned on; may be empty.
+        recipients: Vec<String>,
+    },
+    /// The sender could not DKIM-sign the message.
+    #[error("dkim signing failed")]
+    Signing {
+        sender_name: SenderName,
+        #[source]
+        source: anyhow::Error,
+    },
     #[error("no route matches sender domain {sender_domain:?}")]
     NoMatchingRoute { sender_domain: String },
 }
@@ -20,7 +36,9 @@ impl SendError {
     #[must_use]
     pub fn sender_name(&self) -> Option<&SenderName> {
         match self {
-            Self::Send { sender_name, .. } => Some(sender_name),
+            Self::Send { sender_name, .. }
+            | Self::Rejected { sender_name, .. }
+            | Self::Signing { sender_name, .. } => Some(sender_name),
             Self::NoMatchingRoute { .. } => None,
         }
     }
@@ -28,8 +46,8 @@ impl SendError {
     #[must_use]
     pub fn is_transient(&self) -> bool {
         match self {
-            Self::Send { .. } => true,
-            Self::NoMatchingRoute { .. } => false,
+            Self::Send { .. } | Self::Signing { .. } => true,
+            Self::Rejected { .. } | Self::NoMatchingRoute { .. } => false,
         }
     }
 }
@@ -40,14 +58,18 @@ pub struct OutboundEmail {
     pub recipients: Vec<(RecipientKind, String)>,
     pub body: RenderedBody,
     pub attachments: Vec<crate::entity::attachment::ResolvedAttachment>,
+    pub headers: crate::entity::message_headers::MessageHeaders,
+    pub calendar: Option<crate::entity::calendar::Calendar>,
 }
 
 pub trait EmailSender: Send + Sync + 'static {
     /// # Errors
     ///
     /// Returns a `SendError` when delivery cannot complete: `SendError::NoMatchingRoute`
-    /// when no configured route matches the sender domain, or `SendError::Send` when
-    /// all matched transports fail to deliver.
+    /// when no configured route matches the sender domain, `SendError::Rejected` when
+    /// a server refused the message with a permanent reply, `SendError::Signing` when
+    /// the message could not be DKIM-signed, or `SendError::Send` when all matched
+    /// transports fail to deliver.
     fn send(
         &self,
         email: OutboundEmail,
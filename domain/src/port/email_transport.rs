@@ -1,11 +1,128 @@
+use thiserror::Error;
+
 use crate::port::email_sender::OutboundEmail;
 
+/// RFC 3463 status details (`X.1.Y`) that blame the recipient mailbox itself:
+/// bad mailbox, bad system, bad syntax, moved, no mail accepted.
+const MAILBOX_STATUS_DETAILS: [&str; 5] = ["1.1", "1.2", "1.3", "1.6", "1.10"];
+
+/// Negative reply sent by the upstream SMTP server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmtpReply {
+    /// Basic reply code, e.g. `550`.
+    pub code: u16,
+    /// RFC 3463 enhanced status code, e.g. `5.1.1`, when the server sent one.
+    pub enhanced_status: Option<String>,
+    /// Reply text as sent by the server, enhanced status included.
+    pub text: String,
+}
+
+impl SmtpReply {
+    /// Builds a reply, picking the enhanced status code out of the leading
+    /// token of `text` when there is one.
+    #[must_use]
+    pub fn new(code: u16, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let enhanced_status = text
+            .split_whitespace()
+            .next()
+            .filter(|token| is_enhanced_status(token))
+            .map(str::to_owned);
+        Self {
+            code,
+            enhanced_status,
+            text,
+        }
+    }
+
+    /// Whether the reply refuses a recipient mailbox for good, as opposed to
+    /// the sender, the content or the server's own policy.
+    #[must_use]
+    pub fn is_recipient_rejection(&self) -> bool {
+        self.enhanced_status
+            .as_deref()
+            .and_then(|status| status.strip_prefix("5."))
+            .is_some_and(|detail| MAILBOX_STATUS_DETAILS.contains(&detail))
+    }
+}
+
+impl std::fmt::Display for SmtpReply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code, self.text)
+    }
+}
+
+fn is_enhanced_status(token: &str) -> bool {
+    let mut parts = token.split('.');
+    let class = parts.next();
+    let subject = parts.next();
+    let detail = parts.next();
+    parts.next().is_none()
+        && matches!(class, Some("2" | "4" | "5"))
+        && [subject, detail].iter().all(|part| {
+            part.is_some_and(|p| {
+                (1..=3).contains(&p.len()) && p.bytes().all(|b| b.is_ascii_digit())
+            })
+        })
+}
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    /// The server refused the message with a permanent (5xx) reply.
+    #[error("rejected by upstream server: {reply}")]
+    Rejected {
+        reply: SmtpReply,
+        /// Recipients the reply is pinned on. A mailbox rejection that cannot
+        /// be pinned, the server not saying which `RCPT TO` failed, leaves
+        /// this empty and does not stop the search for another sender.
+        recipients: Vec<String>,
+    },
+    /// The message could not be DKIM-signed, e.g. no key covers its sender.
+    #[error("dkim signing failed")]
+    Signing(#[source] anyhow::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 pub trait EmailTransport: Send + Sync + 'static {
     /// # Errors
     ///
-    /// Returns an error when the underlying transport fails to deliver the email.
+    /// Returns `TransportError::Rejected` when the server refuses the message
+    /// for good, `TransportError::Signing` when it cannot be signed, or
+    /// `TransportError::Other` on any other delivery failure.
     fn deliver<'a>(
         &'a self,
         email: &'a OutboundEmail,
-    ) -> impl std::future::Future<Output = Result<(), anyhow::Error>> + Send + 'a;
+    ) -> impl std::future::Future<Output = Result<(), TransportError>> + Send + 'a;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SmtpReply;
+
+    #[test]
+    fn new_extracts_the_enhanced_status() {
+        let reply = SmtpReply::new(550, "5.1.1 <bob@example.com>: user unknown");
+        assert_eq!(reply.enhanced_status.as_deref(), Some("5.1.1"));
+        assert_eq!(
+            reply.to_string(),
+            "550 5.1.1 <bob@example.com>: user unknown"
+        );
+
+        let bare = SmtpReply::new(550, "mailbox unavailable");
+        assert_eq!(bare.enhanced_status, None);
+    }
+
+    #[test]
+    fn recipient_rejection_requires_a_permanent_mailbox_status() {
+        assert!(
+            SmtpReply::new(550, "5.1.1 <bob@example.com>: user unknown").is_recipient_rejection()
+        );
+        assert!(SmtpReply::new(556, "5.1.10 null MX").is_recipient_rejection());
+
+        assert!(!SmtpReply::new(450, "4.1.1 try again later").is_recipient_rejection());
+        assert!(!SmtpReply::new(553, "5.1.8 sender domain unknown").is_recipient_rejection());
+        assert!(!SmtpReply::new(554, "5.7.1 message refused as spam").is_recipient_rejection());
+        assert!(!SmtpReply::new(550, "mailbox unavailable").is_recipient_rejection());
+    }
 }
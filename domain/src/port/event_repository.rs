@@ -16,6 +16,31 @@ pub struct ListEventsParams {
     pub offset: u32,
 }
 
+/// Filters shared by the event listing and the event stream.
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    pub email_id: Option<EmailId>,
+    pub event_type: Option<String>,
+    pub sender_name: Option<String>,
+    pub error_class: Option<ErrorClass>,
+}
+
+/// Position of an event in the `(created_at_ms, id)` order events are
+/// streamed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventCursor {
+    pub created_at_ms: i64,
+    pub id: uuid::Uuid,
+}
+
+#[derive(Clone, Debug)]
+pub struct ListEventsAfterParams {
+    pub filter: EventFilter,
+    /// Exclusive; from the oldest event when `None`.
+    pub after: Option<EventCursor>,
+    pub limit: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct EventRecord {
     pub id: uuid::Uuid,
@@ -27,6 +52,16 @@ pub struct EventRecord {
     pub created_at_ms: i64,
 }
 
+impl EventRecord {
+    #[must_use]
+    pub fn cursor(&self) -> EventCursor {
+        EventCursor {
+            created_at_ms: self.created_at_ms,
+            id: self.id,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum EventRepositoryError {
     #[error("event repository error")]
@@ -44,4 +79,22 @@ pub trait EventRepository: Send + Sync + 'static {
         &self,
         params: ListEventsParams,
     ) -> impl std::future::Future<Output = Result<Vec<EventRecord>, EventRepositoryError>> + Send;
+
+    /// # Errors
+    ///
+    /// Returns an `EventRepositoryError` when the underlying query fails.
+    fn get_event(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<Option<EventRecord>, EventRepositoryError>> + Send;
+
+    /// Oldest first, in `(created_at_ms, id)` order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `EventRepositoryError` when the underlying query fails.
+    fn list_events_after(
+        &self,
+        params: ListEventsAfterParams,
+    ) -> impl std::future::Future<Output = Result<Vec<EventRecord>, EventRepositoryError>> + Send;
 }
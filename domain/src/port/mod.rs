@@ -9,6 +9,12 @@ pub mod event_publisher;
 pub mod event_repository;
 pub mod health;
 pub mod sender_usage;
+pub mod suppression_list;
 pub mod template_interpolator;
 pub mod template_renderer;
 pub mod template_resolver;
+pub mod template_store;
+pub mod tracking_links;
+pub mod unsubscribe_links;
+pub mod webhook_outbox;
+pub mod webhook_subscription_store;
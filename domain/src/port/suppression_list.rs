@@ -0,0 +1,161 @@
+use thiserror::Error;
+
+use crate::entity::unsubscribe::MailingList;
+
+/// Recipient address that must not be mailed again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suppression {
+    /// Normalized address, see [`normalize_address`].
+    pub address: String,
+    pub reason: String,
+    pub created_at_ms: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ListSuppressionsParams {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum SuppressionListError {
+    #[error("suppression list error")]
+    Storage {
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// Normalizes an address for storage and lookup: surrounding whitespace is
+/// dropped and the whole address is lowercased.
+#[must_use]
+pub fn normalize_address(address: &str) -> String {
+    address.trim().to_lowercase()
+}
+
+pub trait SuppressionList: Send + Sync + 'static {
+    /// Adds `address`. An address that is already suppressed keeps its
+    /// original entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the insert fails.
+    fn add(
+        &self,
+        address: &str,
+        reason: &str,
+    ) -> impl std::future::Future<Output = Result<(), SuppressionListError>> + Send;
+
+    /// Returns `false` when `address` was not suppressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the delete fails.
+    fn remove(
+        &self,
+        address: &str,
+    ) -> impl std::future::Future<Output = Result<bool, SuppressionListError>> + Send;
+
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the query fails.
+    fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> impl std::future::Future<Output = Result<Vec<Suppression>, SuppressionListError>> + Send;
+
+    /// Returns the subset of `addresses` that is suppressed. Inputs are
+    /// expected to be normalized already.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the query fails.
+    fn find_suppressed(
+        &self,
+        addresses: &[String],
+    ) -> impl std::future::Future<Output = Result<Vec<String>, SuppressionListError>> + Send;
+
+    /// Records that `address` no longer wants `list` from `sender`. Returns
+    /// `false` when the opt-out was already recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the insert fails.
+    fn opt_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        address: &str,
+    ) -> impl std::future::Future<Output = Result<bool, SuppressionListError>> + Send;
+
+    /// Returns the subset of `addresses` that opted out of `list` from
+    /// `sender`. Inputs are expected to be normalized already.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SuppressionListError::Storage` when the query fails.
+    fn find_opted_out(
+        &self,
+        sender: &str,
+        list: &MailingList,
+        addresses: &[String],
+    ) -> impl std::future::Future<Output = Result<Vec<String>, SuppressionListError>> + Send;
+}
+
+/// Suppression list that never suppresses anything.
+pub struct NoopSuppressionList;
+
+impl SuppressionList for NoopSuppressionList {
+    async fn add(&self, _address: &str, _reason: &str) -> Result<(), SuppressionListError> {
+        Ok(())
+    }
+
+    async fn remove(&self, _address: &str) -> Result<bool, SuppressionListError> {
+        Ok(false)
+    }
+
+    async fn list(
+        &self,
+        _params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, SuppressionListError> {
+        Ok(vec![])
+    }
+
+    async fn find_suppressed(
+        &self,
+        _addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        Ok(vec![])
+    }
+
+    async fn opt_out(
+        &self,
+        _sender: &str,
+        _list: &MailingList,
+        _address: &str,
+    ) -> Result<bool, SuppressionListError> {
+        Ok(false)
+    }
+
+    async fn find_opted_out(
+        &self,
+        _sender: &str,
+        _list: &MailingList,
+        _addresses: &[String],
+    ) -> Result<Vec<String>, SuppressionListError> {
+        Ok(vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_address;
+
+    #[test]
+    fn normalize_address_trims_and_lowercases() {
+        assert_eq!(
+            normalize_address("  Alice@Example.COM "),
+            "alice@example.com"
+        );
+    }
+}
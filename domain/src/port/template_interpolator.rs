@@ -20,4 +20,15 @@ pub trait TemplateInterpolator: Send + Sync + 'static {
         body: ResolvedBody,
         variables: &serde_json::Map<String, serde_json::Value>,
     ) -> Result<InterpolatedBody, InterpolateError>;
+
+    /// Renders the subject line with the same variables as the body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InterpolateError` when the templating engine fails to process the subject.
+    fn interpolate_subject(
+        &self,
+        subject: &str,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<String, InterpolateError>;
 }
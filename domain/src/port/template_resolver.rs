@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::entity::body::{BodySource, ResolvedBody};
+use crate::entity::body::{BodySource, ResolvedTemplate};
 
 #[derive(Debug, Error)]
 pub enum ResolveError {
@@ -8,6 +8,12 @@ pub enum ResolveError {
     NotFound { name: String },
     #[error("domain not allowed for remote template: {url:?}")]
     DomainNotAllowed { url: String },
+    #[error("failed to load template {name:?} from the template store")]
+    Store {
+        name: String,
+        #[source]
+        source: anyhow::Error,
+    },
     #[error("failed to fetch template from {url:?}")]
     Fetch {
         url: String,
@@ -17,11 +23,25 @@ pub enum ResolveError {
 }
 
 pub trait TemplateResolver: Send + Sync + 'static {
+    /// Resolves `body`, preferring the variant of a named or remote template
+    /// for `locale` or one of its [fallbacks](crate::entity::template::locale_fallbacks).
+    ///
     /// # Errors
     ///
     /// Returns a `ResolveError` when the template cannot be found or fetched.
     fn resolve(
         &self,
         body: BodySource,
-    ) -> impl std::future::Future<Output = Result<ResolvedBody, ResolveError>> + Send;
+        locale: Option<String>,
+    ) -> impl std::future::Future<Output = Result<ResolvedTemplate, ResolveError>> + Send;
+}
+
+impl<T: TemplateResolver> TemplateResolver for std::sync::Arc<T> {
+    fn resolve(
+        &self,
+        body: BodySource,
+        locale: Option<String>,
+    ) -> impl std::future::Future<Output = Result<ResolvedTemplate, ResolveError>> + Send {
+        (**self).resolve(body, locale)
+    }
 }
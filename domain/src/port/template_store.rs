@@ -0,0 +1,71 @@
+use thiserror::Error;
+
+use crate::entity::template::Template;
+
+#[derive(Debug, Error)]
+pub enum TemplateStoreError {
+    #[error("template store error")]
+    Storage {
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+pub trait TemplateStore: Send + Sync + 'static {
+    /// Stores `content` as the next version of `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateStoreError::Storage` when the insert fails.
+    fn publish(
+        &self,
+        name: &str,
+        content: &str,
+    ) -> impl std::future::Future<Output = Result<Template, TemplateStoreError>> + Send;
+
+    /// Returns `version` of `name`, or its latest version when `None`. A
+    /// deleted template has no latest version, but its versions can still
+    /// be fetched by number so emails pinned to them go out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateStoreError::Storage` when the query fails.
+    fn get(
+        &self,
+        name: &str,
+        version: Option<u32>,
+    ) -> impl std::future::Future<Output = Result<Option<Template>, TemplateStoreError>> + Send;
+
+    /// Retires `name`. Returns `false` when it had no live version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TemplateStoreError::Storage` when the update fails.
+    fn delete(
+        &self,
+        name: &str,
+    ) -> impl std::future::Future<Output = Result<bool, TemplateStoreError>> + Send;
+}
+
+/// Template store that holds nothing; named templates come from elsewhere.
+pub struct NoopTemplateStore;
+
+impl TemplateStore for NoopTemplateStore {
+    async fn publish(&self, _name: &str, _content: &str) -> Result<Template, TemplateStoreError> {
+        Err(TemplateStoreError::Storage {
+            source: anyhow::anyhow!("no template store configured"),
+        })
+    }
+
+    async fn get(
+        &self,
+        _name: &str,
+        _version: Option<u32>,
+    ) -> Result<Option<Template>, TemplateStoreError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, _name: &str) -> Result<bool, TemplateStoreError> {
+        Ok(false)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::entity::tracking::{TrackedEmail, TrackingHit};
+
+/// Issues and checks the open pixel and click redirect links put in the HTML
+/// of tracked emails.
+pub trait TrackingLinks: Send + Sync + 'static {
+    /// The pixel URL for `email`, or `None` when no link can be issued
+    /// (e.g. tracking is not configured).
+    fn open_link(&self, email: &TrackedEmail) -> Option<String>;
+
+    /// The URL redirecting to `url` for `email`, or `None` when no link can
+    /// be issued.
+    fn click_link(&self, email: &TrackedEmail, url: &str) -> Option<String>;
+
+    /// What `token` was issued for, or `None` when the token is malformed or
+    /// its signature does not match.
+    fn verify(&self, token: &str) -> Option<TrackingHit>;
+}
+
+/// Issues no links and accepts no token.
+pub struct NoTrackingLinks;
+
+impl TrackingLinks for NoTrackingLinks {
+    fn open_link(&self, _email: &TrackedEmail) -> Option<String> {
+        None
+    }
+
+    fn click_link(&self, _email: &TrackedEmail, _url: &str) -> Option<String> {
+        None
+    }
+
+    fn verify(&self, _token: &str) -> Option<TrackingHit> {
+        None
+    }
+}
+
+impl<T: TrackingLinks> TrackingLinks for Option<T> {
+    fn open_link(&self, email: &TrackedEmail) -> Option<String> {
+        self.as_ref().and_then(|links| links.open_link(email))
+    }
+
+    fn click_link(&self, email: &TrackedEmail, url: &str) -> Option<String> {
+        self.as_ref().and_then(|links| links.click_link(email, url))
+    }
+
+    fn verify(&self, token: &str) -> Option<TrackingHit> {
+        self.as_ref().and_then(|links| links.verify(token))
+    }
+}
+
+impl<T: TrackingLinks> TrackingLinks for std::sync::Arc<T> {
+    fn open_link(&self, email: &TrackedEmail) -> Option<String> {
+        self.as_ref().open_link(email)
+    }
+
+    fn click_link(&self, email: &TrackedEmail, url: &str) -> Option<String> {
+        self.as_ref().click_link(email, url)
+    }
+
+    fn verify(&self, token: &str) -> Option<TrackingHit> {
+        self.as_ref().verify(token)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::entity::unsubscribe::Subscription;
+
+/// Issues and checks the one-click unsubscribe links put in
+/// `List-Unsubscribe` headers.
+pub trait UnsubscribeLinks: Send + Sync + 'static {
+    /// The unsubscribe URL for `subscription`, or `None` when no link can be
+    /// issued (e.g. unsubscribe links are not configured).
+    fn link(&self, subscription: &Subscription) -> Option<String>;
+
+    /// The subscription `token` was issued for, or `None` when the token is
+    /// malformed or its signature does not match.
+    fn verify(&self, token: &str) -> Option<Subscription>;
+}
+
+/// Issues no links and accepts no token.
+pub struct NoUnsubscribeLinks;
+
+impl UnsubscribeLinks for NoUnsubscribeLinks {
+    fn link(&self, _subscription: &Subscription) -> Option<String> {
+        None
+    }
+
+    fn verify(&self, _token: &str) -> Option<Subscription> {
+        None
+    }
+}
+
+impl<L: UnsubscribeLinks> UnsubscribeLinks for Option<L> {
+    fn link(&self, subscription: &Subscription) -> Option<String> {
+        self.as_ref().and_then(|links| links.link(subscription))
+    }
+
+    fn verify(&self, token: &str) -> Option<Subscription> {
+        self.as_ref().and_then(|links| links.verify(token))
+    }
+}
+
+impl<L: UnsubscribeLinks> UnsubscribeLinks for std::sync::Arc<L> {
+    fn link(&self, subscription: &Subscription) -> Option<String> {
+        self.as_ref().link(subscription)
+    }
+
+    fn verify(&self, token: &str) -> Option<Subscription> {
+        self.as_ref().verify(token)
+    }
+}
@@ -0,0 +1,144 @@
+use thiserror::Error;
+
+use crate::entity::email::EmailId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its next attempt.
+    Pending,
+    /// Attempts are exhausted; only a manual redelivery sends it again.
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Transport a delivery is pushed through.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeliveryTarget {
+    /// The configured webhook, or the subscription of the delivery.
+    Webhook,
+    /// The NATS `JetStream` stream configured for events.
+    Nats,
+}
+
+impl DeliveryTarget {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Webhook => "webhook",
+            Self::Nats => "nats",
+        }
+    }
+}
+
+/// Event waiting in the outbox to be pushed to one endpoint. Delivered
+/// events leave the outbox.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebhookDelivery {
+    pub id: uuid::Uuid,
+    /// Id of the `lifecycle_events` row, sent as the event id header. Every
+    /// endpoint receiving the event gets the same one.
+    pub event_id: uuid::Uuid,
+    pub target: DeliveryTarget,
+    /// `None` for the webhook configured by the operator, and for NATS.
+    pub subscription_id: Option<uuid::Uuid>,
+    pub email_id: EmailId,
+    pub event_type: String,
+    pub sender_name: Option<String>,
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    pub next_attempt_at_ms: i64,
+    pub last_error: Option<String>,
+    pub created_at_ms: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct ListWebhookDeliveriesParams {
+    pub status: Option<WebhookDeliveryStatus>,
+    pub subscription_id: Option<uuid::Uuid>,
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookOutboxError {
+    #[error("webhook outbox error")]
+    Storage {
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// Outbox rows are written by the storage `EventPublisher`, in the same
+/// transaction as the event itself: one for the configured webhook, one for
+/// the `JetStream` stream, and one per matching subscription.
+pub trait WebhookOutbox: Send + Sync + 'static {
+    /// Returns up to `limit` pending deliveries due at `now_ms`, oldest first,
+    /// and pushes their next attempt to `lease_until_ms` so that concurrent
+    /// dispatchers skip them. A dispatcher that dies mid-attempt leaves the
+    /// delivery due again once the lease expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookOutboxError::Storage` when the query fails.
+    fn claim_due(
+        &self,
+        now_ms: i64,
+        lease_until_ms: i64,
+        limit: u32,
+    ) -> impl std::future::Future<Output = Result<Vec<WebhookDelivery>, WebhookOutboxError>> + Send;
+
+    /// Removes a delivery once it succeeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookOutboxError::Storage` when the delete fails.
+    fn complete(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<(), WebhookOutboxError>> + Send;
+
+    /// Records a failed attempt and schedules the next one at
+    /// `next_attempt_at_ms`, or marks the delivery failed when it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookOutboxError::Storage` when the update fails.
+    fn record_failure(
+        &self,
+        id: uuid::Uuid,
+        error: &str,
+        next_attempt_at_ms: Option<i64>,
+    ) -> impl std::future::Future<Output = Result<(), WebhookOutboxError>> + Send;
+
+    /// Most recent first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookOutboxError::Storage` when the query fails.
+    fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> impl std::future::Future<Output = Result<Vec<WebhookDelivery>, WebhookOutboxError>> + Send;
+
+    /// Makes a delivery pending and due at `now_ms`, with a fresh attempt
+    /// count. Returns `false` when the delivery is not in the outbox.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookOutboxError::Storage` when the update fails.
+    fn redeliver(
+        &self,
+        id: uuid::Uuid,
+        now_ms: i64,
+    ) -> impl std::future::Future<Output = Result<bool, WebhookOutboxError>> + Send;
+}
@@ -0,0 +1,63 @@
+use thiserror::Error;
+
+use crate::entity::webhook_subscription::WebhookSubscription;
+
+#[derive(Clone, Debug)]
+pub struct ListWebhookSubscriptionsParams {
+    pub limit: u32,
+    pub offset: u32,
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookSubscriptionStoreError {
+    #[error("webhook subscription store error")]
+    Storage {
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+/// The storage `EventPublisher` reads the subscriptions to queue an outbox
+/// delivery for each one matching an event.
+pub trait WebhookSubscriptionStore: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns `WebhookSubscriptionStoreError::Storage` when the insert fails.
+    fn insert(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl std::future::Future<Output = Result<(), WebhookSubscriptionStoreError>> + Send;
+
+    /// # Errors
+    ///
+    /// Returns `WebhookSubscriptionStoreError::Storage` when the query fails.
+    fn get(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<
+        Output = Result<Option<WebhookSubscription>, WebhookSubscriptionStoreError>,
+    > + Send;
+
+    /// Oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookSubscriptionStoreError::Storage` when the query fails.
+    fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> impl std::future::Future<
+        Output = Result<Vec<WebhookSubscription>, WebhookSubscriptionStoreError>,
+    > + Send;
+
+    /// Removes the subscription along with its pending deliveries. Returns
+    /// `false` when there is no such subscription.
+    ///
+    /// # Errors
+    ///
+    /// Returns `WebhookSubscriptionStoreError::Storage` when the delete fails.
+    fn delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<bool, WebhookSubscriptionStoreError>> + Send;
+}
@@ -0,0 +1,246 @@
+//! Instruments the rendered HTML of tracked emails: `http(s)` links go
+//! through click redirects and an open pixel is added before `</body>`.
+
+use std::ops::Range;
+
+use crate::entity::tracking::{TrackedEmail, Tracking};
+use crate::port::tracking_links::TrackingLinks;
+
+/// Returns `html` with the links and pixel `tracking` asks for. Links the
+/// tracker cannot issue are left as they are.
+#[must_use]
+pub fn track_html<T: TrackingLinks>(
+    html: &str,
+    links: &T,
+    email: &TrackedEmail,
+    tracking: Tracking,
+) -> String {
+    let mut html = if tracking.clicks {
+        rewrite_links(html, |url| links.click_link(email, url))
+    } else {
+        html.to_owned()
+    };
+    if tracking.opens
+        && let Some(pixel) = links.open_link(email)
+    {
+        insert_pixel(&mut html, &pixel);
+    }
+    html
+}
+
+/// Replaces the `href` of every `<a>` pointing to an `http(s)` URL with
+/// `link(url)`. Other schemes (`mailto:`, `tel:`, `cid:`) and fragments are
+/// kept, since there is nothing to redirect to.
+fn rewrite_links(html: &str, mut link: impl FnMut(&str) -> Option<String>) -> String {
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower`
+    // index `html` too.
+    let lower = html.to_ascii_lowercase();
+    let mut out = String::with_capacity(html.len());
+    let mut pos = 0;
+    while let Some(found) = lower[pos..].find("<a") {
+        let attrs_start = pos + found + 2;
+        if !lower[attrs_start..].starts_with(|c: char| c.is_ascii_whitespace()) {
+            out.push_str(&html[pos..attrs_start]);
+            pos = attrs_start;
+            continue;
+        }
+        let (href, tag_len) = find_href(&html[attrs_start..]);
+        let tag_end = attrs_start + tag_len;
+        let replacement = href.and_then(|range| {
+            let url = decode_amp(html[attrs_start..][range.clone()].trim());
+            let lower_url = url.to_ascii_lowercase();
+            if !(lower_url.starts_with("http://") || lower_url.starts_with("https://")) {
+                return None;
+            }
+            link(&url).map(|tracked| (range, tracked))
+        });
+        match replacement {
+            Some((range, tracked)) => {
+                out.push_str(&html[pos..attrs_start + range.start]);
+                out.push_str(&escape_attr(&tracked));
+                out.push_str(&html[attrs_start + range.end..tag_end]);
+            }
+            None => out.push_str(&html[pos..tag_end]),
+        }
+        pos = tag_end;
+    }
+    out.push_str(&html[pos..]);
+    out
+}
+
+/// Scans the attributes of a tag, `tag` starting right after its name.
+/// Returns the range of the `href` value, if any, and the length up to and
+/// including the closing `>` (or the whole input for an unclosed tag).
+fn find_href(tag: &str) -> (Option<Range<usize>>, usize) {
+    let bytes = tag.as_bytes();
+    let mut href = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'>' => return (href, i + 1),
+            b if b.is_ascii_whitespace() || b == b'/' => i += 1,
+            _ => {
+                let name_start = i;
+                while i < bytes.len()
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'=' | b'>' | b'/')
+                {
+                    i += 1;
+                }
+                let name = &tag[name_start..i];
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                if i >= bytes.len() || bytes[i] != b'=' {
+                    continue;
+                }
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+                    i += 1;
+                }
+                let value = if let Some(&quote @ (b'"' | b'\'')) = bytes.get(i) {
+                    let start = i + 1;
+                    let end = tag[start..]
+                        .find(char::from(quote))
+                        .map_or(bytes.len(), |n| start + n);
+                    i = (end + 1).min(bytes.len());
+                    start..end
+                } else {
+                    let start = i;
+                    while i < bytes.len() && !bytes[i].is_ascii_whitespace() && bytes[i] != b'>' {
+                        i += 1;
+                    }
+                    start..i
+                };
+                if name.eq_ignore_ascii_case("href") && href.is_none() {
+                    href = Some(value);
+                }
+            }
+        }
+    }
+    (href, bytes.len())
+}
+
+fn insert_pixel(html: &mut String, url: &str) {
+    let pixel = format!(
+        r#"<img src="{}" width="1" height="1" alt="" style="display:block;border:0;width:1px;height:1px" />"#,
+        escape_attr(url)
+    );
+    match html.to_ascii_lowercase().rfind("</body") {
+        Some(at) => html.insert_str(at, &pixel),
+        None => html.push_str(&pixel),
+    }
+}
+
+/// Attribute values commonly spell `&` as `&amp;` in query strings.
+fn decode_amp(value: &str) -> String {
+    value.replace("&amp;", "&")
+}
+
+fn escape_attr(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::email::EmailId;
+    use crate::entity::tracking::{TrackedEmail, Tracking, TrackingHit};
+    use crate::port::tracking_links::TrackingLinks;
+
+    use super::track_html;
+
+    /// Encodes the target in the link so tests can read it back.
+    struct FakeLinks;
+
+    impl TrackingLinks for FakeLinks {
+        fn open_link(&self, _email: &TrackedEmail) -> Option<String> {
+            Some("https://t.example/open".into())
+        }
+
+        fn click_link(&self, _email: &TrackedEmail, url: &str) -> Option<String> {
+            Some(format!("https://t.example/click?to={url}"))
+        }
+
+        fn verify(&self, _token: &str) -> Option<TrackingHit> {
+            None
+        }
+    }
+
+    fn email() -> TrackedEmail {
+        TrackedEmail {
+            email_id: EmailId::default(),
+            correlation_id: None,
+        }
+    }
+
+    const BOTH: Tracking = Tracking {
+        opens: true,
+        clicks: true,
+    };
+
+    #[test]
+    fn http_links_are_redirected_and_others_kept() {
+        let html = concat!(
+            r#"<p><a class="btn" href="https://acme.com/a?x=1&amp;y=2">A</a> "#,
+            r"<A HREF='http://acme.com/b'>B</A> ",
+            r##"<a href="mailto:help@acme.com">mail</a> <a href="#top">top</a> "##,
+            r#"<abbr title="x">abbr</abbr> <a name="anchor">no href</a></p>"#,
+        );
+        let tracked = track_html(
+            html,
+            &FakeLinks,
+            &email(),
+            Tracking {
+                opens: false,
+                clicks: true,
+            },
+        );
+        assert_eq!(
+            tracked,
+            concat!(
+                r#"<p><a class="btn" href="https://t.example/click?to=https://acme.com/a?x=1&amp;y=2">A</a> "#,
+                r"<A HREF='https://t.example/click?to=http://acme.com/b'>B</A> ",
+                r##"<a href="mailto:help@acme.com">mail</a> <a href="#top">top</a> "##,
+                r#"<abbr title="x">abbr</abbr> <a name="anchor">no href</a></p>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn pixel_goes_right_before_the_closing_body_tag() {
+        let tracked = track_html(
+            r#"<html><body><a href="https://acme.com">hi</a></BODY></html>"#,
+            &FakeLinks,
+            &email(),
+            BOTH,
+        );
+        assert!(
+            tracked.starts_with(
+                r#"<html><body><a href="https://t.example/click?to=https://acme.com">hi</a><img src="https://t.example/open""#
+            ),
+            "{tracked}"
+        );
+        assert!(tracked.ends_with("/></BODY></html>"), "{tracked}");
+
+        let fragment = track_html("<p>hi</p>", &FakeLinks, &email(), BOTH);
+        assert!(fragment.starts_with(r"<p>hi</p><img src="), "{fragment}");
+    }
+
+    #[test]
+    fn untracked_or_unconfigured_html_is_unchanged() {
+        let html = r#"<body><a href="https://acme.com">hi</a></body>"#;
+        assert_eq!(
+            track_html(html, &FakeLinks, &email(), Tracking::default()),
+            html
+        );
+        assert_eq!(
+            track_html(
+                html,
+                &crate::port::tracking_links::NoTrackingLinks,
+                &email(),
+                BOTH
+            ),
+            html
+        );
+    }
+}
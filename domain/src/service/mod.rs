@@ -1 +1,2 @@
+pub mod html_tracking;
 pub mod routed_email_sender;
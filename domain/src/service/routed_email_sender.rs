@@ -5,7 +5,7 @@ use thiserror::Error;
 use crate::entity::sender::{SenderName, SenderQuota};
 use crate::port::clock::{Clock, SystemClock};
 use crate::port::email_sender::{EmailSender, OutboundEmail, SendError};
-use crate::port::email_transport::EmailTransport;
+use crate::port::email_transport::{EmailTransport, TransportError};
 use crate::port::sender_usage::{SenderStats, SenderUsage};
 
 #[derive(Debug, Error)]
@@ -37,6 +37,34 @@ fn normalize_domain(s: &str) -> String {
     s.trim_end_matches('.').to_ascii_lowercase()
 }
 
+fn send_error(sender_name: SenderName, err: TransportError) -> SendError {
+    match err {
+        TransportError::Rejected { reply, recipients } => SendError::Rejected {
+            sender_name,
+            reply,
+            recipients,
+        },
+        TransportError::Signing(source) => SendError::Signing {
+            sender_name,
+            source,
+        },
+        TransportError::Other(source) => SendError::Send {
+            sender_name,
+            source,
+        },
+    }
+}
+
+/// A mailbox that does not exist won't exist behind another route either, as
+/// long as the rejection is pinned on it rather than on any of the recipients.
+fn is_recipient_rejection(err: &SendError) -> bool {
+    matches!(
+        err,
+        SendError::Rejected { reply, recipients, .. }
+            if !recipients.is_empty() && reply.is_recipient_rejection()
+    )
+}
+
 pub struct SenderRoute<T> {
     pub name: SenderName,
     pub priority: u8,
@@ -90,13 +118,16 @@ where
     /// exhausted are skipped. If the usage port is unavailable the error is
     /// logged and every sender is treated as eligible (fail-open). Second pass:
     /// if every sender was over-quota in the first pass, the quota check is
-    /// bypassed so delivery still succeeds.
+    /// bypassed so delivery still succeeds. A permanent rejection of a recipient
+    /// mailbox stops the search: no other route is tried.
     ///
     /// # Errors
     ///
     /// Returns `SendError::NoMatchingRoute` when no route matches the sender
-    /// domain and there are no catch-all routes. Returns `SendError::Send` when
-    /// all attempted senders fail to deliver.
+    /// domain and there are no catch-all routes, and `SendError::Rejected` as
+    /// soon as a recipient mailbox is refused. Otherwise returns the error of
+    /// the last attempted sender when all of them fail: `SendError::Rejected`
+    /// for a permanent reply, `SendError::Send` for anything else.
     #[allow(clippy::too_many_lines)]
     async fn send(&self, email: OutboundEmail) -> Result<SenderName, SendError> {
         let sender_domain = email
@@ -187,10 +218,11 @@ where
                 }
                 Err(err) => {
                     deliver_span.record("outcome", "error");
-                    last_err = Some(SendError::Send {
-                        sender_name: route.name.clone(),
-                        source: err,
-                    });
+                    let err = send_error(route.name.clone(), err);
+                    if is_recipient_rejection(&err) {
+                        return Err(err);
+                    }
+                    last_err = Some(err);
                 }
             }
         }
@@ -220,10 +252,11 @@ where
                 }
                 Err(err) => {
                     deliver_span.record("outcome", "error");
-                    last_err = Some(SendError::Send {
-                        sender_name: route.name.clone(),
-                        source: err,
-                    });
+                    let err = send_error(route.name.clone(), err);
+                    if is_recipient_rejection(&err) {
+                        return Err(err);
+                    }
+                    last_err = Some(err);
                 }
             }
         }
@@ -238,26 +271,50 @@ mod tests {
 
     use super::{NoopSenderUsage, RoutedEmailSender, SenderRoute};
     use crate::entity::body::{Plain, RenderedBody};
+    use crate::entity::message_headers::MessageHeaders;
     use crate::entity::sender::{QuotaRange, SenderName, SenderQuota};
     use crate::port::clock::SystemClock;
-    use crate::port::email_sender::{EmailSender, OutboundEmail};
-    use crate::port::email_transport::EmailTransport;
+    use crate::port::email_sender::{EmailSender, OutboundEmail, SendError};
+    use crate::port::email_transport::{EmailTransport, SmtpReply, TransportError};
     use crate::port::sender_usage::{SenderStats, SenderUsage, SenderUsageError};
 
     enum FakeTransport {
         Ok,
         Fail,
+        Reject(SmtpReply, Vec<String>),
     }
 
     impl EmailTransport for FakeTransport {
-        async fn deliver<'a>(&'a self, _email: &'a OutboundEmail) -> Result<(), anyhow::Error> {
+        async fn deliver<'a>(&'a self, _email: &'a OutboundEmail) -> Result<(), TransportError> {
             match self {
                 Self::Ok => Ok(()),
-                Self::Fail => Err(anyhow::anyhow!("simulated failure")),
+                Self::Fail => Err(anyhow::anyhow!("simulated failure").into()),
+                Self::Reject(reply, recipients) => Err(TransportError::Rejected {
+                    reply: reply.clone(),
+                    recipients: recipients.clone(),
+                }),
             }
         }
     }
 
+    fn reject_route(
+        name: &str,
+        priority: u8,
+        reply: SmtpReply,
+        recipients: &[&str],
+    ) -> SenderRoute<FakeTransport> {
+        SenderRoute {
+            name: SenderName::new(name),
+            priority,
+            quota: None,
+            match_sender_domain: None,
+            transport: FakeTransport::Reject(
+                reply,
+                recipients.iter().map(|&r| r.to_owned()).collect(),
+            ),
+        }
+    }
+
     fn ok_route(
         name: &str,
         priority: u8,
@@ -319,6 +376,8 @@ mod tests {
                 Plain::try_new(None, Some("<p>hi</p>".into())).expect("valid body"),
             ),
             attachments: vec![],
+            headers: MessageHeaders::default(),
+            calendar: None,
         }
     }
 
@@ -395,6 +454,64 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn recipient_rejection_stops_before_other_routes() {
+        let sender = RoutedEmailSender::new(
+            vec![
+                reject_route(
+                    "first",
+                    0,
+                    SmtpReply::new(550, "5.1.1 user unknown"),
+                    &["bob@example.com"],
+                ),
+                ok_route("second", 1, None),
+            ],
+            NoopSenderUsage,
+            SystemClock,
+        )
+        .unwrap();
+        let err = sender.send(make_email()).await.unwrap_err();
+        assert!(!err.is_transient());
+        let SendError::Rejected {
+            sender_name, reply, ..
+        } = err
+        else {
+            panic!("expected a rejection, got {err:?}");
+        };
+        assert_eq!(sender_name.as_str(), "first");
+        assert_eq!(reply.code, 550);
+    }
+
+    #[tokio::test]
+    async fn unpinned_mailbox_rejection_falls_back_to_next_route() {
+        let sender = RoutedEmailSender::new(
+            vec![
+                reject_route("first", 0, SmtpReply::new(550, "5.1.1 user unknown"), &[]),
+                ok_route("second", 1, None),
+            ],
+            NoopSenderUsage,
+            SystemClock,
+        )
+        .unwrap();
+        let result = sender.send(make_email()).await;
+        assert_eq!(result.unwrap().as_str(), "second");
+    }
+
+    #[tokio::test]
+    async fn policy_rejection_falls_back_to_next_route() {
+        let sender = RoutedEmailSender::new(
+            vec![
+                reject_route("first", 0, SmtpReply::new(554, "5.7.1 relay denied"), &[]),
+                ok_route("second", 1, None),
+            ],
+            NoopSenderUsage,
+            SystemClock,
+        )
+        .unwrap();
+        let result = sender.send(make_email()).await;
+        assert_eq!(result.unwrap().as_str(), "second");
+    }
+
     #[tokio::test]
     async fn first_sender_succeeds_returns_immediately() {
         let sender = RoutedEmailSender::new(
@@ -510,6 +627,8 @@ mod tests {
                 Plain::try_new(None, Some("<p>hi</p>".into())).expect("valid body"),
             ),
             attachments: vec![],
+            headers: MessageHeaders::default(),
+            calendar: None,
         }
     }
 
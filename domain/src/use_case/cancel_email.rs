@@ -0,0 +1,298 @@
+use thiserror::Error;
+
+use crate::entity::email::EmailId;
+use crate::entity::lifecycle_event::LifecycleEvent;
+use crate::port::attachment_store::AttachmentStore;
+use crate::port::email_repository::{CancelResult, EmailRepository, EmailRepositoryError};
+use crate::port::event_publisher::EventPublisher;
+
+#[derive(Debug, Error)]
+pub enum CancelEmailError {
+    #[error("email not found")]
+    NotFound,
+    #[error("email is already being delivered or finished")]
+    Conflict,
+    #[error(transparent)]
+    Persist(#[from] EmailRepositoryError),
+}
+
+pub trait CancelEmailUseCase: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns `CancelEmailError::NotFound` when no email has this id.
+    /// Returns `CancelEmailError::Conflict` when the email is sending or done.
+    /// Returns `CancelEmailError::Persist` when the repository fails.
+    fn execute(
+        &self,
+        id: EmailId,
+    ) -> impl std::future::Future<Output = Result<(), CancelEmailError>> + Send;
+}
+
+pub struct CancelEmailService<R, P, A> {
+    repository: R,
+    event_publisher: P,
+    attachment_store: A,
+}
+
+impl<R, P, A> CancelEmailService<R, P, A>
+where
+    R: EmailRepository,
+    P: EventPublisher,
+    A: AttachmentStore,
+{
+    #[must_use]
+    pub fn new(repository: R, event_publisher: P, attachment_store: A) -> Self {
+        Self {
+            repository,
+            event_publisher,
+            attachment_store,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// See [`CancelEmailUseCase::execute`].
+    #[tracing::instrument(skip_all, name = "cancel_email", fields(email_id = %id.as_uuid()))]
+    pub async fn execute(&self, id: EmailId) -> Result<(), CancelEmailError> {
+        let (correlation_id, attachments) = match self.repository.cancel(id).await? {
+            CancelResult::Cancelled {
+                correlation_id,
+                attachments,
+            } => (correlation_id, attachments),
+            CancelResult::NotFound => return Err(CancelEmailError::NotFound),
+            CancelResult::NotCancellable => return Err(CancelEmailError::Conflict),
+        };
+
+        // The email is cancelled from here on; cleanup failures only leave
+        // orphans for the GC sweep.
+        for att in &attachments {
+            if let Err(e) = self.attachment_store.delete(&att.blob).await {
+                tracing::warn!(
+                    error = %e,
+                    blob_key = %att.blob.key,
+                    "failed to delete attachment blob after cancel"
+                );
+            }
+        }
+        if !attachments.is_empty()
+            && let Err(e) = self.repository.set_attachments(id, &[]).await
+        {
+            tracing::warn!(error = %e, "failed to clear attachment refs after cancel");
+        }
+        if let Err(e) = self
+            .event_publisher
+            .publish(&LifecycleEvent::Cancelled { id, correlation_id })
+            .await
+        {
+            tracing::warn!(error = %e, "failed to publish cancelled event");
+        }
+        Ok(())
+    }
+}
+
+impl<R, P, A> CancelEmailUseCase for CancelEmailService<R, P, A>
+where
+    R: EmailRepository + Send + Sync + 'static,
+    P: EventPublisher + Send + Sync + 'static,
+    A: AttachmentStore + Send + Sync + 'static,
+{
+    fn execute(
+        &self,
+        id: EmailId,
+    ) -> impl std::future::Future<Output = Result<(), CancelEmailError>> + Send {
+        Self::execute(self, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::attachment::{AttachmentRef, BlobRef};
+    use crate::entity::email::EmailId;
+    use crate::entity::envelope::Envelope;
+    use crate::entity::lifecycle_event::LifecycleEvent;
+    use crate::port::attachment_store::{
+        AttachmentReader, AttachmentStore, AttachmentStoreError, PutResult,
+    };
+    use crate::port::email_repository::{
+        CancelResult, EmailRecord, EmailRepository, EmailRepositoryError, ListEmailsParams,
+        SaveResult,
+    };
+    use crate::port::event_publisher::{EventPublisher, EventPublisherError};
+
+    use super::{CancelEmailError, CancelEmailService};
+
+    #[derive(Clone, Copy)]
+    enum Outcome {
+        Cancelled,
+        NotFound,
+        NotCancellable,
+    }
+
+    #[derive(Clone)]
+    struct FakeRepository {
+        outcome: Outcome,
+        attachments_cleared: Arc<Mutex<bool>>,
+    }
+
+    impl FakeRepository {
+        fn new(outcome: Outcome) -> Self {
+            Self {
+                outcome,
+                attachments_cleared: Arc::new(Mutex::new(false)),
+            }
+        }
+    }
+
+    fn blob(key: &str) -> AttachmentRef {
+        AttachmentRef {
+            filename: format!("{key}.pdf"),
+            content_type: "application/pdf".into(),
+            size_bytes: 3,
+            blob: BlobRef {
+                backend: "fs".into(),
+                key: key.into(),
+            },
+            content_id: None,
+        }
+    }
+
+    impl EmailRepository for FakeRepository {
+        async fn save(
+            &self,
+            id: EmailId,
+            _envelope: &Envelope,
+        ) -> Result<SaveResult, EmailRepositoryError> {
+            Ok(SaveResult::Created(id))
+        }
+
+        async fn list_all_attachment_blobs(&self) -> Result<Vec<BlobRef>, EmailRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn list_emails(
+            &self,
+            _params: ListEmailsParams,
+        ) -> Result<Vec<EmailRecord>, EmailRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn set_attachments(
+            &self,
+            _id: EmailId,
+            attachments: &[AttachmentRef],
+        ) -> Result<(), EmailRepositoryError> {
+            *self.attachments_cleared.lock().unwrap() = attachments.is_empty();
+            Ok(())
+        }
+
+        async fn delete(&self, _id: EmailId) -> Result<(), EmailRepositoryError> {
+            Ok(())
+        }
+
+        async fn cancel(&self, _id: EmailId) -> Result<CancelResult, EmailRepositoryError> {
+            Ok(match self.outcome {
+                Outcome::Cancelled => CancelResult::Cancelled {
+                    correlation_id: Some("corr-1".into()),
+                    attachments: vec![blob("a"), blob("b")],
+                },
+                Outcome::NotFound => CancelResult::NotFound,
+                Outcome::NotCancellable => CancelResult::NotCancellable,
+            })
+        }
+
+        async fn begin_delivery(&self, _id: EmailId) -> Result<bool, EmailRepositoryError> {
+            Ok(true)
+        }
+
+        async fn end_delivery(&self, _id: EmailId) -> Result<(), EmailRepositoryError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingPublisher {
+        events: Arc<Mutex<Vec<LifecycleEvent>>>,
+    }
+
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingStore {
+        deleted: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl AttachmentStore for RecordingStore {
+        async fn put(&self, _reader: AttachmentReader) -> Result<PutResult, AttachmentStoreError> {
+            unreachable!("cancel never writes blobs")
+        }
+
+        async fn get(&self, _blob: &BlobRef) -> Result<AttachmentReader, AttachmentStoreError> {
+            Err(AttachmentStoreError::NotFound)
+        }
+
+        async fn delete(&self, blob: &BlobRef) -> Result<(), AttachmentStoreError> {
+            self.deleted.lock().unwrap().push(blob.key.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn cancel_cleans_up_blobs_and_publishes_cancelled() {
+        let repo = FakeRepository::new(Outcome::Cancelled);
+        let publisher = RecordingPublisher::default();
+        let store = RecordingStore::default();
+        let svc = CancelEmailService::new(repo.clone(), publisher.clone(), store.clone());
+        let id = EmailId::default();
+
+        svc.execute(id).await.unwrap();
+
+        assert_eq!(*store.deleted.lock().unwrap(), vec!["a", "b"]);
+        assert!(*repo.attachments_cleared.lock().unwrap());
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec![LifecycleEvent::Cancelled {
+                id,
+                correlation_id: Some("corr-1".into()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_unknown_email_returns_not_found() {
+        let publisher = RecordingPublisher::default();
+        let svc = CancelEmailService::new(
+            FakeRepository::new(Outcome::NotFound),
+            publisher.clone(),
+            RecordingStore::default(),
+        );
+
+        let err = svc.execute(EmailId::default()).await.unwrap_err();
+
+        assert!(matches!(err, CancelEmailError::NotFound));
+        assert!(publisher.events.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancel_in_flight_email_returns_conflict_without_side_effects() {
+        let publisher = RecordingPublisher::default();
+        let store = RecordingStore::default();
+        let svc = CancelEmailService::new(
+            FakeRepository::new(Outcome::NotCancellable),
+            publisher.clone(),
+            store.clone(),
+        );
+
+        let err = svc.execute(EmailId::default()).await.unwrap_err();
+
+        assert!(matches!(err, CancelEmailError::Conflict));
+        assert!(publisher.events.lock().unwrap().is_empty());
+        assert!(store.deleted.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use thiserror::Error;
+
+use crate::port::suppression_list::{
+    ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError, normalize_address,
+};
+
+/// Reason recorded for entries added through the API without one.
+pub const MANUAL_REASON: &str = "manual";
+
+#[derive(Debug, Error)]
+pub enum ManageSuppressionsError {
+    #[error("address is not suppressed")]
+    NotFound,
+    #[error(transparent)]
+    Storage(#[from] SuppressionListError),
+}
+
+pub trait ManageSuppressionsUseCase: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns `ManageSuppressionsError::Storage` when the underlying query fails.
+    fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> impl std::future::Future<Output = Result<Vec<Suppression>, ManageSuppressionsError>> + Send;
+
+    /// Suppresses `address`; `reason` defaults to [`MANUAL_REASON`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageSuppressionsError::Storage` when the insert fails.
+    fn add(
+        &self,
+        address: String,
+        reason: Option<String>,
+    ) -> impl std::future::Future<Output = Result<(), ManageSuppressionsError>> + Send;
+
+    /// # Errors
+    ///
+    /// Returns `ManageSuppressionsError::NotFound` when `address` is not
+    /// suppressed, or `ManageSuppressionsError::Storage` when the delete fails.
+    fn remove(
+        &self,
+        address: String,
+    ) -> impl std::future::Future<Output = Result<(), ManageSuppressionsError>> + Send;
+}
+
+pub struct ManageSuppressionsService<L> {
+    list: L,
+}
+
+impl<L> ManageSuppressionsService<L> {
+    pub fn new(list: L) -> Self {
+        Self { list }
+    }
+}
+
+impl<L: SuppressionList> ManageSuppressionsService<L> {
+    async fn list_inner(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> Result<Vec<Suppression>, ManageSuppressionsError> {
+        Ok(self.list.list(params).await?)
+    }
+
+    async fn add_inner(
+        &self,
+        address: String,
+        reason: Option<String>,
+    ) -> Result<(), ManageSuppressionsError> {
+        let reason = reason.unwrap_or_else(|| MANUAL_REASON.to_owned());
+        self.list.add(&normalize_address(&address), &reason).await?;
+        Ok(())
+    }
+
+    async fn remove_inner(&self, address: String) -> Result<(), ManageSuppressionsError> {
+        if self.list.remove(&normalize_address(&address)).await? {
+            Ok(())
+        } else {
+            Err(ManageSuppressionsError::NotFound)
+        }
+    }
+}
+
+impl<L: SuppressionList> ManageSuppressionsUseCase for ManageSuppressionsService<L> {
+    fn list(
+        &self,
+        params: ListSuppressionsParams,
+    ) -> impl std::future::Future<Output = Result<Vec<Suppression>, ManageSuppressionsError>> + Send
+    {
+        self.list_inner(params)
+    }
+
+    fn add(
+        &self,
+        address: String,
+        reason: Option<String>,
+    ) -> impl std::future::Future<Output = Result<(), ManageSuppressionsError>> + Send {
+        self.add_inner(address, reason)
+    }
+
+    fn remove(
+        &self,
+        address: String,
+    ) -> impl std::future::Future<Output = Result<(), ManageSuppressionsError>> + Send {
+        self.remove_inner(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::unsubscribe::MailingList;
+    use crate::port::suppression_list::{
+        ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+    };
+
+    use super::{ManageSuppressionsError, ManageSuppressionsService, ManageSuppressionsUseCase};
+
+    #[derive(Clone, Default)]
+    struct MemoryList {
+        entries: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl SuppressionList for MemoryList {
+        async fn add(&self, address: &str, reason: &str) -> Result<(), SuppressionListError> {
+            self.entries
+                .lock()
+                .unwrap()
+                .push((address.to_owned(), reason.to_owned()));
+            Ok(())
+        }
+
+        async fn remove(&self, address: &str) -> Result<bool, SuppressionListError> {
+            let mut entries = self.entries.lock().unwrap();
+            let before = entries.len();
+            entries.retain(|(a, _)| a != address);
+            Ok(entries.len() != before)
+        }
+
+        async fn list(
+            &self,
+            _params: ListSuppressionsParams,
+        ) -> Result<Vec<Suppression>, SuppressionListError> {
+            Ok(vec![])
+        }
+
+        async fn find_suppressed(
+            &self,
+            _addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(vec![])
+        }
+
+        async fn opt_out(
+            &self,
+            _sender: &str,
+            _list: &MailingList,
+            _address: &str,
+        ) -> Result<bool, SuppressionListError> {
+            Ok(true)
+        }
+
+        async fn find_opted_out(
+            &self,
+            _sender: &str,
+            _list: &MailingList,
+            _addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn add_normalizes_the_address_and_defaults_the_reason() {
+        let list = MemoryList::default();
+        let svc = ManageSuppressionsService::new(list.clone());
+
+        svc.add(" Bob@Example.com".into(), None).await.unwrap();
+
+        assert_eq!(
+            *list.entries.lock().unwrap(),
+            vec![("bob@example.com".to_owned(), "manual".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_unknown_address_returns_not_found() {
+        let svc = ManageSuppressionsService::new(MemoryList::default());
+
+        let err = svc.remove("nobody@example.com".into()).await.unwrap_err();
+
+        assert!(matches!(err, ManageSuppressionsError::NotFound));
+    }
+
+    #[tokio::test]
+    async fn remove_matches_case_insensitively() {
+        let list = MemoryList::default();
+        let svc = ManageSuppressionsService::new(list.clone());
+        svc.add("bob@example.com".into(), Some("bounce".into()))
+            .await
+            .unwrap();
+
+        svc.remove("BOB@example.com".into()).await.unwrap();
+
+        assert!(list.entries.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use thiserror::Error;
+
+use crate::entity::template::{InvalidTemplateName, Template, validate_template_name};
+use crate::port::template_store::{TemplateStore, TemplateStoreError};
+
+#[derive(Debug, Error)]
+pub enum ManageTemplatesError {
+    #[error("template not found")]
+    NotFound,
+    #[error(transparent)]
+    InvalidName(#[from] InvalidTemplateName),
+    #[error("template content must not be empty")]
+    EmptyContent,
+    #[error(transparent)]
+    Storage(#[from] TemplateStoreError),
+}
+
+pub trait ManageTemplatesUseCase: Send + Sync + 'static {
+    /// Returns `version` of `name`, or its latest version.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageTemplatesError::NotFound` when there is no such
+    /// version, or `ManageTemplatesError::Storage` when the query fails.
+    fn get(
+        &self,
+        name: String,
+        version: Option<u32>,
+    ) -> impl std::future::Future<Output = Result<Template, ManageTemplatesError>> + Send;
+
+    /// Publishes `content` as a new version of `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageTemplatesError::InvalidName` or
+    /// `ManageTemplatesError::EmptyContent` for bad input, or
+    /// `ManageTemplatesError::Storage` when the insert fails.
+    fn put(
+        &self,
+        name: String,
+        content: String,
+    ) -> impl std::future::Future<Output = Result<Template, ManageTemplatesError>> + Send;
+
+    /// Retires `name`; emails already pinned to one of its versions still
+    /// render.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageTemplatesError::NotFound` when `name` has no live
+    /// version, or `ManageTemplatesError::Storage` when the update fails.
+    fn delete(
+        &self,
+        name: String,
+    ) -> impl std::future::Future<Output = Result<(), ManageTemplatesError>> + Send;
+}
+
+pub struct ManageTemplatesService<S> {
+    store: S,
+}
+
+impl<S> ManageTemplatesService<S> {
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+}
+
+impl<S: TemplateStore> ManageTemplatesService<S> {
+    async fn get_inner(
+        &self,
+        name: String,
+        version: Option<u32>,
+    ) -> Result<Template, ManageTemplatesError> {
+        self.store
+            .get(&name, version)
+            .await?
+            .ok_or(ManageTemplatesError::NotFound)
+    }
+
+    async fn put_inner(
+        &self,
+        name: String,
+        content: String,
+    ) -> Result<Template, ManageTemplatesError> {
+        validate_template_name(&name)?;
+        if content.trim().is_empty() {
+            return Err(ManageTemplatesError::EmptyContent);
+        }
+        Ok(self.store.publish(&name, &content).await?)
+    }
+
+    async fn delete_inner(&self, name: String) -> Result<(), ManageTemplatesError> {
+        if self.store.delete(&name).await? {
+            Ok(())
+        } else {
+            Err(ManageTemplatesError::NotFound)
+        }
+    }
+}
+
+impl<S: TemplateStore> ManageTemplatesUseCase for ManageTemplatesService<S> {
+    fn get(
+        &self,
+        name: String,
+        version: Option<u32>,
+    ) -> impl std::future::Future<Output = Result<Template, ManageTemplatesError>> + Send {
+        self.get_inner(name, version)
+    }
+
+    fn put(
+        &self,
+        name: String,
+        content: String,
+    ) -> impl std::future::Future<Output = Result<Template, ManageTemplatesError>> + Send {
+        self.put_inner(name, content)
+    }
+
+    fn delete(
+        &self,
+        name: String,
+    ) -> impl std::future::Future<Output = Result<(), ManageTemplatesError>> + Send {
+        self.delete_inner(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::template::Template;
+    use crate::port::template_store::{TemplateStore, TemplateStoreError};
+
+    use super::{ManageTemplatesError, ManageTemplatesService, ManageTemplatesUseCase};
+
+    #[derive(Clone, Default)]
+    struct MemoryStore {
+        versions: Arc<Mutex<Vec<Template>>>,
+    }
+
+    impl TemplateStore for MemoryStore {
+        async fn publish(&self, name: &str, content: &str) -> Result<Template, TemplateStoreError> {
+            let mut versions = self.versions.lock().unwrap();
+            let version = versions.iter().filter(|t| t.name == name).count() + 1;
+            let template = Template {
+                name: name.to_owned(),
+                version: u32::try_from(version).unwrap(),
+                content: content.to_owned(),
+                created_at_ms: 0,
+            };
+            versions.push(template.clone());
+            Ok(template)
+        }
+
+        async fn get(
+            &self,
+            name: &str,
+            version: Option<u32>,
+        ) -> Result<Option<Template>, TemplateStoreError> {
+            let versions = self.versions.lock().unwrap();
+            let mut matching = versions.iter().filter(|t| t.name == name);
+            Ok(match version {
+                Some(v) => matching.find(|t| t.version == v),
+                None => matching.next_back(),
+            }
+            .cloned())
+        }
+
+        async fn delete(&self, _name: &str) -> Result<bool, TemplateStoreError> {
+            Ok(false)
+        }
+    }
+
+    #[tokio::test]
+    async fn put_publishes_a_new_version_each_time() {
+        let svc = ManageTemplatesService::new(MemoryStore::default());
+
+        let first = svc
+            .put("welcome".into(), "<mjml>1</mjml>".into())
+            .await
+            .unwrap();
+        let second = svc
+            .put("welcome".into(), "<mjml>2</mjml>".into())
+            .await
+            .unwrap();
+
+        assert_eq!((first.version, second.version), (1, 2));
+        let latest = svc.get("welcome".into(), None).await.unwrap();
+        assert_eq!(latest.content, "<mjml>2</mjml>");
+        let pinned = svc.get("welcome".into(), Some(1)).await.unwrap();
+        assert_eq!(pinned.content, "<mjml>1</mjml>");
+    }
+
+    #[tokio::test]
+    async fn put_rejects_bad_names_and_empty_content() {
+        let svc = ManageTemplatesService::new(MemoryStore::default());
+
+        let err = svc.put("a/b".into(), "<mjml/>".into()).await.unwrap_err();
+        assert!(matches!(err, ManageTemplatesError::InvalidName(_)));
+        let err = svc.put("welcome".into(), "  ".into()).await.unwrap_err();
+        assert!(matches!(err, ManageTemplatesError::EmptyContent));
+    }
+
+    #[tokio::test]
+    async fn unknown_templates_are_not_found() {
+        let svc = ManageTemplatesService::new(MemoryStore::default());
+
+        let err = svc.get("nope".into(), None).await.unwrap_err();
+        assert!(matches!(err, ManageTemplatesError::NotFound));
+        let err = svc.delete("nope".into()).await.unwrap_err();
+        assert!(matches!(err, ManageTemplatesError::NotFound));
+    }
+}
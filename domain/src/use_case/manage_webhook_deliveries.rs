@@ -0,0 +1,172 @@
+use thiserror::Error;
+
+use crate::port::clock::Clock;
+use crate::port::webhook_outbox::{
+    ListWebhookDeliveriesParams, WebhookDelivery, WebhookOutbox, WebhookOutboxError,
+};
+
+#[derive(Debug, Error)]
+pub enum ManageWebhookDeliveriesError {
+    #[error("webhook delivery not found")]
+    NotFound,
+    #[error(transparent)]
+    Storage(#[from] WebhookOutboxError),
+}
+
+pub trait ManageWebhookDeliveriesUseCase: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns `ManageWebhookDeliveriesError::Storage` when the query fails.
+    fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> impl std::future::Future<
+        Output = Result<Vec<WebhookDelivery>, ManageWebhookDeliveriesError>,
+    > + Send;
+
+    /// Schedules the delivery to be attempted now, restarting its backoff.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageWebhookDeliveriesError::NotFound` when the delivery is
+    /// not waiting in the outbox (unknown, or already delivered), or
+    /// `ManageWebhookDeliveriesError::Storage` when the update fails.
+    fn redeliver(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<(), ManageWebhookDeliveriesError>> + Send;
+}
+
+pub struct ManageWebhookDeliveriesService<O, C> {
+    outbox: O,
+    clock: C,
+}
+
+impl<O, C> ManageWebhookDeliveriesService<O, C> {
+    pub fn new(outbox: O, clock: C) -> Self {
+        Self { outbox, clock }
+    }
+}
+
+impl<O: WebhookOutbox, C: Clock> ManageWebhookDeliveriesService<O, C> {
+    async fn list_inner(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> Result<Vec<WebhookDelivery>, ManageWebhookDeliveriesError> {
+        Ok(self.outbox.list(params).await?)
+    }
+
+    async fn redeliver_inner(&self, id: uuid::Uuid) -> Result<(), ManageWebhookDeliveriesError> {
+        if self.outbox.redeliver(id, self.clock.now_ms()).await? {
+            Ok(())
+        } else {
+            Err(ManageWebhookDeliveriesError::NotFound)
+        }
+    }
+}
+
+impl<O: WebhookOutbox, C: Clock> ManageWebhookDeliveriesUseCase
+    for ManageWebhookDeliveriesService<O, C>
+{
+    fn list(
+        &self,
+        params: ListWebhookDeliveriesParams,
+    ) -> impl std::future::Future<
+        Output = Result<Vec<WebhookDelivery>, ManageWebhookDeliveriesError>,
+    > + Send {
+        self.list_inner(params)
+    }
+
+    fn redeliver(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<(), ManageWebhookDeliveriesError>> + Send {
+        self.redeliver_inner(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::port::clock::Clock;
+    use crate::port::webhook_outbox::{
+        ListWebhookDeliveriesParams, WebhookDelivery, WebhookOutbox, WebhookOutboxError,
+    };
+
+    use super::{
+        ManageWebhookDeliveriesError, ManageWebhookDeliveriesService,
+        ManageWebhookDeliveriesUseCase,
+    };
+
+    /// Knows a single event and records redeliveries of it.
+    struct FakeOutbox {
+        known: uuid::Uuid,
+        redelivered_at: Mutex<Vec<i64>>,
+    }
+
+    impl WebhookOutbox for FakeOutbox {
+        async fn claim_due(
+            &self,
+            _now_ms: i64,
+            _lease_until_ms: i64,
+            _limit: u32,
+        ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+            Ok(vec![])
+        }
+
+        async fn complete(&self, _id: uuid::Uuid) -> Result<(), WebhookOutboxError> {
+            Ok(())
+        }
+
+        async fn record_failure(
+            &self,
+            _id: uuid::Uuid,
+            _error: &str,
+            _next_attempt_at_ms: Option<i64>,
+        ) -> Result<(), WebhookOutboxError> {
+            Ok(())
+        }
+
+        async fn list(
+            &self,
+            _params: ListWebhookDeliveriesParams,
+        ) -> Result<Vec<WebhookDelivery>, WebhookOutboxError> {
+            Ok(vec![])
+        }
+
+        async fn redeliver(&self, id: uuid::Uuid, now_ms: i64) -> Result<bool, WebhookOutboxError> {
+            if id != self.known {
+                return Ok(false);
+            }
+            self.redelivered_at.lock().unwrap().push(now_ms);
+            Ok(true)
+        }
+    }
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now_ms(&self) -> i64 {
+            42
+        }
+    }
+
+    #[tokio::test]
+    async fn redeliver_schedules_known_events_now_and_rejects_others() {
+        let known = uuid::Uuid::now_v7();
+        let svc = ManageWebhookDeliveriesService::new(
+            FakeOutbox {
+                known,
+                redelivered_at: Mutex::new(vec![]),
+            },
+            FixedClock,
+        );
+
+        svc.redeliver(known).await.unwrap();
+        let err = svc.redeliver(uuid::Uuid::now_v7()).await.unwrap_err();
+
+        assert!(matches!(err, ManageWebhookDeliveriesError::NotFound));
+        assert_eq!(*svc.outbox.redelivered_at.lock().unwrap(), vec![42]);
+    }
+}
@@ -0,0 +1,247 @@
+use thiserror::Error;
+
+use crate::entity::webhook_subscription::{
+    InvalidSubscription, SubscriptionFilter, WebhookSubscription, validate_subscription,
+};
+use crate::port::clock::Clock;
+use crate::port::webhook_subscription_store::{
+    ListWebhookSubscriptionsParams, WebhookSubscriptionStore, WebhookSubscriptionStoreError,
+};
+
+#[derive(Debug, Error)]
+pub enum ManageWebhookSubscriptionsError {
+    #[error(transparent)]
+    Invalid(#[from] InvalidSubscription),
+    #[error("webhook subscription not found")]
+    NotFound,
+    #[error(transparent)]
+    Storage(#[from] WebhookSubscriptionStoreError),
+}
+
+#[derive(Clone, Debug)]
+pub struct CreateWebhookSubscriptionInput {
+    pub url: String,
+    pub secret: String,
+    pub filter: SubscriptionFilter,
+}
+
+pub trait ManageWebhookSubscriptionsUseCase: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns `ManageWebhookSubscriptionsError::Storage` when the query fails.
+    fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> impl std::future::Future<
+        Output = Result<Vec<WebhookSubscription>, ManageWebhookSubscriptionsError>,
+    > + Send;
+
+    /// The subscription receives the events published from now on.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageWebhookSubscriptionsError::Invalid` when the input fails
+    /// validation, or `ManageWebhookSubscriptionsError::Storage` when the
+    /// insert fails.
+    fn create(
+        &self,
+        input: CreateWebhookSubscriptionInput,
+    ) -> impl std::future::Future<
+        Output = Result<WebhookSubscription, ManageWebhookSubscriptionsError>,
+    > + Send;
+
+    /// Deliveries still waiting for this subscription are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ManageWebhookSubscriptionsError::NotFound` when there is no
+    /// such subscription, or `ManageWebhookSubscriptionsError::Storage` when
+    /// the delete fails.
+    fn delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<(), ManageWebhookSubscriptionsError>> + Send;
+}
+
+pub struct ManageWebhookSubscriptionsService<S, C> {
+    store: S,
+    clock: C,
+}
+
+impl<S, C> ManageWebhookSubscriptionsService<S, C> {
+    pub fn new(store: S, clock: C) -> Self {
+        Self { store, clock }
+    }
+}
+
+impl<S: WebhookSubscriptionStore, C: Clock> ManageWebhookSubscriptionsService<S, C> {
+    async fn list_inner(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> Result<Vec<WebhookSubscription>, ManageWebhookSubscriptionsError> {
+        Ok(self.store.list(params).await?)
+    }
+
+    async fn create_inner(
+        &self,
+        input: CreateWebhookSubscriptionInput,
+    ) -> Result<WebhookSubscription, ManageWebhookSubscriptionsError> {
+        let url = validate_subscription(&input.url, &input.secret, &input.filter)?;
+        let subscription = WebhookSubscription {
+            id: uuid::Uuid::now_v7(),
+            url,
+            secret: input.secret,
+            filter: input.filter,
+            created_at_ms: self.clock.now_ms(),
+        };
+        self.store.insert(&subscription).await?;
+        Ok(subscription)
+    }
+
+    async fn delete_inner(&self, id: uuid::Uuid) -> Result<(), ManageWebhookSubscriptionsError> {
+        if self.store.delete(id).await? {
+            Ok(())
+        } else {
+            Err(ManageWebhookSubscriptionsError::NotFound)
+        }
+    }
+}
+
+impl<S: WebhookSubscriptionStore, C: Clock> ManageWebhookSubscriptionsUseCase
+    for ManageWebhookSubscriptionsService<S, C>
+{
+    fn list(
+        &self,
+        params: ListWebhookSubscriptionsParams,
+    ) -> impl std::future::Future<
+        Output = Result<Vec<WebhookSubscription>, ManageWebhookSubscriptionsError>,
+    > + Send {
+        self.list_inner(params)
+    }
+
+    fn create(
+        &self,
+        input: CreateWebhookSubscriptionInput,
+    ) -> impl std::future::Future<
+        Output = Result<WebhookSubscription, ManageWebhookSubscriptionsError>,
+    > + Send {
+        self.create_inner(input)
+    }
+
+    fn delete(
+        &self,
+        id: uuid::Uuid,
+    ) -> impl std::future::Future<Output = Result<(), ManageWebhookSubscriptionsError>> + Send {
+        self.delete_inner(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::webhook_subscription::{
+        InvalidSubscription, SubscriptionFilter, WebhookSubscription,
+    };
+    use crate::port::clock::Clock;
+    use crate::port::webhook_subscription_store::{
+        ListWebhookSubscriptionsParams, WebhookSubscriptionStore, WebhookSubscriptionStoreError,
+    };
+
+    use super::{
+        CreateWebhookSubscriptionInput, ManageWebhookSubscriptionsError,
+        ManageWebhookSubscriptionsService, ManageWebhookSubscriptionsUseCase,
+    };
+
+    #[derive(Clone, Default)]
+    struct MemoryStore {
+        subscriptions: Arc<Mutex<Vec<WebhookSubscription>>>,
+    }
+
+    impl WebhookSubscriptionStore for MemoryStore {
+        async fn insert(
+            &self,
+            subscription: &WebhookSubscription,
+        ) -> Result<(), WebhookSubscriptionStoreError> {
+            self.subscriptions
+                .lock()
+                .unwrap()
+                .push(subscription.clone());
+            Ok(())
+        }
+
+        async fn get(
+            &self,
+            id: uuid::Uuid,
+        ) -> Result<Option<WebhookSubscription>, WebhookSubscriptionStoreError> {
+            Ok(self
+                .subscriptions
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|s| s.id == id)
+                .cloned())
+        }
+
+        async fn list(
+            &self,
+            _params: ListWebhookSubscriptionsParams,
+        ) -> Result<Vec<WebhookSubscription>, WebhookSubscriptionStoreError> {
+            Ok(self.subscriptions.lock().unwrap().clone())
+        }
+
+        async fn delete(&self, id: uuid::Uuid) -> Result<bool, WebhookSubscriptionStoreError> {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            let before = subscriptions.len();
+            subscriptions.retain(|s| s.id != id);
+            Ok(subscriptions.len() != before)
+        }
+    }
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now_ms(&self) -> i64 {
+            42
+        }
+    }
+
+    fn input(url: &str) -> CreateWebhookSubscriptionInput {
+        CreateWebhookSubscriptionInput {
+            url: url.into(),
+            secret: "s".repeat(32),
+            filter: SubscriptionFilter {
+                event_types: vec!["delivery.failed".into()],
+                correlation_id_prefix: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn create_stores_valid_subscriptions_only() {
+        let store = MemoryStore::default();
+        let svc = ManageWebhookSubscriptionsService::new(store.clone(), FixedClock);
+
+        let created = svc
+            .create(input("https://billing.acme.com/hooks"))
+            .await
+            .unwrap();
+        let err = svc.create(input("not a url")).await.unwrap_err();
+
+        assert_eq!(created.created_at_ms, 42);
+        assert_eq!(*store.subscriptions.lock().unwrap(), vec![created]);
+        assert!(matches!(
+            err,
+            ManageWebhookSubscriptionsError::Invalid(InvalidSubscription::InvalidUrl)
+        ));
+    }
+
+    #[tokio::test]
+    async fn delete_unknown_subscription_returns_not_found() {
+        let svc = ManageWebhookSubscriptionsService::new(MemoryStore::default(), FixedClock);
+
+        let err = svc.delete(uuid::Uuid::now_v7()).await.unwrap_err();
+
+        assert!(matches!(err, ManageWebhookSubscriptionsError::NotFound));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod cancel_email;
 pub mod check_readiness;
 pub mod list_emails;
 pub mod list_events;
 pub mod list_senders;
+pub mod manage_suppressions;
+pub mod manage_templates;
+pub mod manage_webhook_deliveries;
+pub mod manage_webhook_subscriptions;
 pub mod process_queued_email;
+pub mod stream_events;
 pub mod submit_email;
+pub mod track_engagement;
+pub mod unsubscribe;
@@ -2,20 +2,64 @@ use thiserror::Error;
 use tokio::io::AsyncReadExt;
 
 use crate::entity::attachment::{AttachmentRef, ResolvedAttachment};
+use crate::entity::body::{BodySource, RenderedBody, ResolvedTemplate};
+use crate::entity::email::{EmailId, RecipientKind};
 use crate::entity::envelope::Envelope;
 use crate::entity::error_class::ErrorClass;
 use crate::entity::sender::SenderName;
+use crate::entity::tracking::TrackedEmail;
+use crate::entity::unsubscribe::MailingList;
 use crate::port::attachment_store::AttachmentStore;
 use crate::port::email_sender::{EmailSender, OutboundEmail, SendError};
+use crate::port::email_transport::SmtpReply;
+use crate::port::suppression_list::{NoopSuppressionList, SuppressionList, normalize_address};
 use crate::port::template_interpolator::{InterpolateError, TemplateInterpolator};
 use crate::port::template_renderer::{RenderError, TemplateRenderer};
 use crate::port::template_resolver::{ResolveError, TemplateResolver};
+use crate::port::tracking_links::{NoTrackingLinks, TrackingLinks};
+use crate::service::html_tracking::track_html;
 
 pub trait ProcessQueuedEmailUseCase: Send + Sync + 'static {
     fn execute(
         &self,
+        id: EmailId,
         envelope: Envelope,
-    ) -> impl std::future::Future<Output = Result<SenderName, ProcessQueuedEmailError>> + Send;
+    ) -> impl std::future::Future<Output = Result<ProcessOutcome, ProcessQueuedEmailError>> + Send;
+}
+
+/// Renders an email the way the worker would, without sending it.
+pub trait PreviewEmailUseCase: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns the `ProcessQueuedEmailError` the worker would have failed
+    /// with, so its [`ErrorClass`] matches.
+    fn preview(
+        &self,
+        body: BodySource,
+        subject: Option<String>,
+        locale: Option<String>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> impl std::future::Future<Output = Result<EmailPreview, ProcessQueuedEmailError>> + Send;
+}
+
+#[derive(Debug)]
+pub struct EmailPreview {
+    /// The interpolated subject, or the template's title when none was given.
+    pub subject: Option<String>,
+    pub body: RenderedBody,
+    /// Locale of the template variant used, if a localized one was found.
+    pub locale: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ProcessOutcome {
+    /// Delivered. `suppressed` lists the recipients dropped beforehand.
+    Sent {
+        sender_name: SenderName,
+        suppressed: Vec<String>,
+    },
+    /// Every recipient is suppressed: nothing was sent.
+    Suppressed { recipients: Vec<String> },
 }
 
 #[derive(Debug, Error)]
@@ -52,17 +96,23 @@ impl ProcessQueuedEmailError {
             Self::Render(_) => ErrorClass::TemplateRender,
             Self::AttachmentResolve { .. } => ErrorClass::Attachment,
             Self::Send(SendError::Send { .. }) => ErrorClass::Delivery,
+            Self::Send(SendError::Rejected { .. }) => ErrorClass::Rejected,
             Self::Send(SendError::NoMatchingRoute { .. }) => ErrorClass::Routing,
+            Self::Send(SendError::Signing { .. }) => ErrorClass::Signing,
         }
     }
 }
 
-pub struct ProcessQueuedEmailService<R, I, Rdr, S, A> {
+pub struct ProcessQueuedEmailService<R, I, Rdr, S, A, L = NoopSuppressionList, T = NoTrackingLinks>
+{
     resolver: R,
     interpolator: I,
     renderer: Rdr,
     sender: S,
     attachment_store: A,
+    suppression_list: L,
+    auto_suppress: bool,
+    tracking_links: T,
 }
 
 impl<R, I, Rdr, S, A> ProcessQueuedEmailService<R, I, Rdr, S, A>
@@ -86,32 +136,208 @@ where
             renderer,
             sender,
             attachment_store,
+            suppression_list: NoopSuppressionList,
+            auto_suppress: false,
+            tracking_links: NoTrackingLinks,
         }
     }
+}
+
+impl<R, I, Rdr, S, A, L, T> ProcessQueuedEmailService<R, I, Rdr, S, A, L, T>
+where
+    R: TemplateResolver,
+    I: TemplateInterpolator,
+    Rdr: TemplateRenderer,
+    S: EmailSender,
+    A: AttachmentStore,
+    L: SuppressionList,
+    T: TrackingLinks,
+{
+    /// Skips recipients found on `list`. With `auto_suppress`, recipients the
+    /// server rejects for good are added to it.
+    #[must_use]
+    pub fn with_suppression_list<L2: SuppressionList>(
+        self,
+        list: L2,
+        auto_suppress: bool,
+    ) -> ProcessQueuedEmailService<R, I, Rdr, S, A, L2, T> {
+        ProcessQueuedEmailService {
+            resolver: self.resolver,
+            interpolator: self.interpolator,
+            renderer: self.renderer,
+            sender: self.sender,
+            attachment_store: self.attachment_store,
+            suppression_list: list,
+            auto_suppress,
+            tracking_links: self.tracking_links,
+        }
+    }
+
+    /// Issues the open pixel and click links of emails asking for tracking.
+    /// Without links, tracked emails are sent as rendered.
+    #[must_use]
+    pub fn with_tracking_links<T2: TrackingLinks>(
+        self,
+        links: T2,
+    ) -> ProcessQueuedEmailService<R, I, Rdr, S, A, L, T2> {
+        ProcessQueuedEmailService {
+            resolver: self.resolver,
+            interpolator: self.interpolator,
+            renderer: self.renderer,
+            sender: self.sender,
+            attachment_store: self.attachment_store,
+            suppression_list: self.suppression_list,
+            auto_suppress: self.auto_suppress,
+            tracking_links: links,
+        }
+    }
+
+    /// Splits `recipients` into the ones to deliver to and the suppressed
+    /// addresses, including the ones that opted out of `list` from `sender`.
+    /// A failing lookup is logged and nothing is suppressed (fail-open), like
+    /// the sender quota check.
+    async fn split_suppressed(
+        &self,
+        sender: &str,
+        list: Option<&MailingList>,
+        recipients: Vec<(RecipientKind, String)>,
+    ) -> (Vec<(RecipientKind, String)>, Vec<String>) {
+        let normalized: Vec<String> = recipients
+            .iter()
+            .map(|(_, address)| normalize_address(address))
+            .collect();
+        let mut suppressed = match self.suppression_list.find_suppressed(&normalized).await {
+            Ok(found) => found,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "suppression list unavailable; no recipient suppressed"
+                );
+                return (recipients, Vec::new());
+            }
+        };
+        if let Some(list) = list {
+            match self
+                .suppression_list
+                .find_opted_out(&normalize_address(sender), list, &normalized)
+                .await
+            {
+                Ok(found) => suppressed.extend(found),
+                Err(err) => {
+                    tracing::warn!(
+                        error = %err,
+                        "list opt-outs unavailable; no recipient suppressed"
+                    );
+                    return (recipients, Vec::new());
+                }
+            }
+        }
+        if suppressed.is_empty() {
+            return (recipients, Vec::new());
+        }
+        let (dropped, kept): (Vec<_>, Vec<_>) = recipients
+            .into_iter()
+            .zip(normalized)
+            .partition(|(_, n)| suppressed.contains(n));
+        (
+            kept.into_iter().map(|(r, _)| r).collect(),
+            dropped
+                .into_iter()
+                .map(|((_, address), _)| address)
+                .collect(),
+        )
+    }
+
+    async fn suppress_rejected(&self, recipients: &[String], reply: &SmtpReply) {
+        let reply = reply.to_string();
+        for address in recipients {
+            if let Err(err) = self
+                .suppression_list
+                .add(&normalize_address(address), &reply)
+                .await
+            {
+                tracing::warn!(error = %err, "failed to suppress rejected recipient");
+            }
+        }
+    }
+
+    /// Resolves, interpolates and renders the body and subject.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ProcessQueuedEmailError` if the body fails to resolve,
+    /// interpolate or render.
+    pub async fn render(
+        &self,
+        body: BodySource,
+        subject: Option<String>,
+        locale: Option<String>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<EmailPreview, ProcessQueuedEmailError> {
+        render_email(
+            &self.resolver,
+            &self.interpolator,
+            &self.renderer,
+            body,
+            subject,
+            locale,
+            variables,
+        )
+        .await
+    }
 
     /// # Errors
     ///
     /// Returns a `ProcessQueuedEmailError` if the body fails to resolve, interpolate, render, or send.
     #[tracing::instrument(skip_all, name = "process_queued_email", fields(correlation_id = tracing::field::Empty))]
-    pub async fn execute(&self, envelope: Envelope) -> Result<SenderName, ProcessQueuedEmailError> {
+    pub async fn execute(
+        &self,
+        id: EmailId,
+        envelope: Envelope,
+    ) -> Result<ProcessOutcome, ProcessQueuedEmailError> {
         if let Some(ref cid) = envelope.correlation_id {
             tracing::Span::current().record("correlation_id", cid.as_str());
         }
         let Envelope {
+            correlation_id,
             sender,
             subject,
             recipients,
             body,
             variables,
             attachments,
+            headers,
+            locale,
+            calendar,
+            list,
+            tracking,
             ..
         } = envelope;
-        let resolved = self.resolver.resolve(body).await?;
-        let interpolated = self.interpolator.interpolate(resolved, &variables)?;
-        let rendered = self.renderer.render(interpolated).await?;
+        let (recipients, suppressed) = self
+            .split_suppressed(&sender, list.as_ref(), recipients)
+            .await;
+        if recipients.is_empty() {
+            return Ok(ProcessOutcome::Suppressed {
+                recipients: suppressed,
+            });
+        }
+        let EmailPreview {
+            subject,
+            body: rendered,
+            ..
+        } = self.render(body, subject, locale, &variables).await?;
+        let rendered = if tracking.is_enabled() {
+            let email = TrackedEmail {
+                email_id: id,
+                correlation_id,
+            };
+            rendered.map_html(|html| track_html(&html, &self.tracking_links, &email, tracking))
+        } else {
+            rendered
+        };
         let resolved_attachments =
             resolve_attachments(&self.attachment_store, &attachments).await?;
-        let sender_name = self
+        let result = self
             .sender
             .send(OutboundEmail {
                 sender,
@@ -119,9 +345,26 @@ where
                 recipients,
                 body: rendered,
                 attachments: resolved_attachments,
+                headers,
+                calendar,
             })
-            .await?;
-        Ok(sender_name)
+            .await;
+        match result {
+            Ok(sender_name) => Ok(ProcessOutcome::Sent {
+                sender_name,
+                suppressed,
+            }),
+            Err(err) => {
+                if let SendError::Rejected {
+                    recipients, reply, ..
+                } = &err
+                    && self.auto_suppress
+                {
+                    self.suppress_rejected(recipients, reply).await;
+                }
+                Err(err.into())
+            }
+        }
     }
 }
 
@@ -148,24 +391,100 @@ async fn resolve_attachments<A: AttachmentStore>(
             filename: att.filename.clone(),
             content_type: att.content_type.clone(),
             bytes: bytes::Bytes::from(buf),
+            content_id: att.content_id.clone(),
         });
     }
     Ok(resolved)
 }
 
-impl<R, I, Rdr, S, A> ProcessQueuedEmailUseCase for ProcessQueuedEmailService<R, I, Rdr, S, A>
+/// Resolves, interpolates and renders `body` and `subject`, shared by the
+/// worker and the submit-time content check. The locale of the template
+/// variant used is available to the template as `locale`.
+///
+/// # Errors
+///
+/// Returns a `ProcessQueuedEmailError` if the body fails to resolve,
+/// interpolate or render.
+pub(crate) async fn render_email<R, I, Rdr>(
+    resolver: &R,
+    interpolator: &I,
+    renderer: &Rdr,
+    body: BodySource,
+    subject: Option<String>,
+    locale: Option<String>,
+    variables: &serde_json::Map<String, serde_json::Value>,
+) -> Result<EmailPreview, ProcessQueuedEmailError>
+where
+    R: TemplateResolver,
+    I: TemplateInterpolator,
+    Rdr: TemplateRenderer,
+{
+    let ResolvedTemplate {
+        body: resolved_body,
+        locale,
+    } = resolver.resolve(body, locale).await?;
+    let variables = match &locale {
+        Some(locale) => {
+            let mut variables = variables.clone();
+            variables.insert("locale".into(), locale.clone().into());
+            std::borrow::Cow::Owned(variables)
+        }
+        None => std::borrow::Cow::Borrowed(variables),
+    };
+    let subject = subject
+        .map(|s| interpolator.interpolate_subject(&s, &variables))
+        .transpose()?;
+    let interpolated = interpolator.interpolate(resolved_body, &variables)?;
+    let body = renderer.render(interpolated).await?;
+    // The template's own title is interpolated along with the body.
+    let subject = subject.or_else(|| body.title().map(str::to_owned));
+    Ok(EmailPreview {
+        subject,
+        body,
+        locale,
+    })
+}
+
+impl<R, I, Rdr, S, A, L, T> PreviewEmailUseCase for ProcessQueuedEmailService<R, I, Rdr, S, A, L, T>
+where
+    R: TemplateResolver,
+    I: TemplateInterpolator,
+    Rdr: TemplateRenderer,
+    S: EmailSender,
+    A: AttachmentStore,
+    L: SuppressionList,
+    T: TrackingLinks,
+{
+    fn preview(
+        &self,
+        body: BodySource,
+        subject: Option<String>,
+        locale: Option<String>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> impl std::future::Future<Output = Result<EmailPreview, ProcessQueuedEmailError>> + Send
+    {
+        self.render(body, subject, locale, variables)
+    }
+}
+
+impl<R, I, Rdr, S, A, L, T> ProcessQueuedEmailUseCase
+    for ProcessQueuedEmailService<R, I, Rdr, S, A, L, T>
 where
     R: TemplateResolver,
     I: TemplateInterpolator,
     Rdr: TemplateRenderer,
     S: EmailSender,
     A: AttachmentStore,
+    L: SuppressionList,
+    T: TrackingLinks,
 {
     fn execute(
         &self,
+        id: EmailId,
         envelope: Envelope,
-    ) -> impl std::future::Future<Output = Result<SenderName, ProcessQueuedEmailError>> + Send {
-        Self::execute(self, envelope)
+    ) -> impl std::future::Future<Output = Result<ProcessOutcome, ProcessQueuedEmailError>> + Send
+    {
+        Self::execute(self, id, envelope)
     }
 }
 
@@ -178,19 +497,30 @@ mod tests {
     use crate::entity::attachment::{AttachmentRef, BlobRef};
     use crate::entity::body::{
         BodySource, InterpolatedBody, MjmlSource, Plain, RenderedBody, ResolvedBody,
+        ResolvedTemplate,
     };
-    use crate::entity::email::RecipientKind;
+    use crate::entity::email::{EmailId, RecipientKind};
     use crate::entity::envelope::Envelope;
+    use crate::entity::message_headers::MessageHeaders;
     use crate::entity::sender::SenderName;
+    use crate::entity::tracking::{TrackedEmail, Tracking, TrackingHit};
+    use crate::entity::unsubscribe::MailingList;
     use crate::port::attachment_store::{
         AttachmentReader, AttachmentStore, AttachmentStoreError, PutResult,
     };
     use crate::port::email_sender::{EmailSender, OutboundEmail, SendError};
+    use crate::port::email_transport::SmtpReply;
+    use crate::port::suppression_list::{
+        ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+    };
     use crate::port::template_interpolator::{InterpolateError, TemplateInterpolator};
     use crate::port::template_renderer::{RenderError, TemplateRenderer};
     use crate::port::template_resolver::{ResolveError, TemplateResolver};
+    use crate::port::tracking_links::TrackingLinks;
 
-    use super::{ProcessQueuedEmailError, ProcessQueuedEmailService};
+    use super::{
+        PreviewEmailUseCase, ProcessOutcome, ProcessQueuedEmailError, ProcessQueuedEmailService,
+    };
 
     type CapturingService = (
         ProcessQueuedEmailService<
@@ -230,13 +560,21 @@ mod tests {
         inline_mjml: String,
     }
 
+    /// Has a variant of every named or remote template for any locale.
     impl TemplateResolver for FakeResolver {
-        async fn resolve(&self, body: BodySource) -> Result<ResolvedBody, ResolveError> {
+        async fn resolve(
+            &self,
+            body: BodySource,
+            locale: Option<String>,
+        ) -> Result<ResolvedTemplate, ResolveError> {
             match body {
-                BodySource::Plain(p) => Ok(ResolvedBody::Plain(p)),
-                BodySource::Mjml(MjmlSource::Inline(s)) => Ok(ResolvedBody::Mjml(s)),
+                BodySource::Plain(p) => Ok(ResolvedBody::Plain(p).into()),
+                BodySource::Mjml(MjmlSource::Inline(s)) => Ok(ResolvedBody::Mjml(s).into()),
                 BodySource::Mjml(MjmlSource::Named(_) | MjmlSource::Remote(_)) => {
-                    Ok(ResolvedBody::Mjml(self.inline_mjml.clone()))
+                    Ok(ResolvedTemplate {
+                        body: ResolvedBody::Mjml(self.inline_mjml.clone()),
+                        locale,
+                    })
                 }
             }
         }
@@ -245,7 +583,11 @@ mod tests {
     struct FailingResolver;
 
     impl TemplateResolver for FailingResolver {
-        async fn resolve(&self, _body: BodySource) -> Result<ResolvedBody, ResolveError> {
+        async fn resolve(
+            &self,
+            _body: BodySource,
+            _locale: Option<String>,
+        ) -> Result<ResolvedTemplate, ResolveError> {
             Err(ResolveError::NotFound {
                 name: "missing".into(),
             })
@@ -284,6 +626,14 @@ mod tests {
                 ResolvedBody::Mjml(s) => Ok(InterpolatedBody::Mjml(apply_vars(&s, variables))),
             }
         }
+
+        fn interpolate_subject(
+            &self,
+            subject: &str,
+            variables: &Map<String, Value>,
+        ) -> Result<String, InterpolateError> {
+            Ok(apply_vars(subject, variables))
+        }
     }
 
     struct FakeRenderer;
@@ -301,9 +651,13 @@ mod tests {
                     } else {
                         None
                     };
+                    let title = s.find("<mj-title>").and_then(|start| {
+                        let after = &s[start + "<mj-title>".len()..];
+                        after.find("</mj-title>").map(|end| after[..end].to_owned())
+                    });
                     let html = format!("<html>{s}</html>");
                     let plain = Plain::try_new(text, Some(html)).expect("html always present");
-                    Ok(RenderedBody::new(plain))
+                    Ok(RenderedBody::new(plain).with_title(title))
                 }
             }
         }
@@ -364,6 +718,16 @@ mod tests {
                 source: anyhow::anyhow!("interpolation failed"),
             })
         }
+
+        fn interpolate_subject(
+            &self,
+            _subject: &str,
+            _variables: &Map<String, Value>,
+        ) -> Result<String, InterpolateError> {
+            Err(InterpolateError::Engine {
+                source: anyhow::anyhow!("interpolation failed"),
+            })
+        }
     }
 
     struct FailingRenderer;
@@ -386,6 +750,12 @@ mod tests {
             body,
             variables: Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: Tracking::default(),
         }
     }
 
@@ -399,6 +769,12 @@ mod tests {
             body,
             variables,
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: Tracking::default(),
         }
     }
 
@@ -427,7 +803,7 @@ mod tests {
         vars.insert("name".into(), Value::String("Jeremie".into()));
         let body = BodySource::Plain(Plain::try_new(Some("hi {{ name }}".into()), None).unwrap());
         let envelope = default_envelope_with_vars(body, vars);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
     }
 
     #[tokio::test]
@@ -443,7 +819,7 @@ mod tests {
             .unwrap(),
         );
         let envelope = default_envelope_with_vars(body, vars);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
     }
 
     #[tokio::test]
@@ -452,7 +828,7 @@ mod tests {
         let service = default_service();
         let body = BodySource::Mjml(MjmlSource::Inline(mjml));
         let envelope = default_envelope(body);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
     }
 
     #[tokio::test]
@@ -461,7 +837,7 @@ mod tests {
         let service = default_service();
         let body = BodySource::Mjml(MjmlSource::Inline(mjml));
         let envelope = default_envelope(body);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
     }
 
     #[tokio::test]
@@ -477,7 +853,7 @@ mod tests {
         );
         let body = BodySource::Mjml(MjmlSource::Named("welcome".into()));
         let envelope = default_envelope(body);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
     }
 
     #[tokio::test]
@@ -491,7 +867,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert!(matches!(err, ProcessQueuedEmailError::Resolve(_)));
     }
 
@@ -508,7 +887,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert!(matches!(err, ProcessQueuedEmailError::Send(_)));
     }
 
@@ -525,7 +907,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert!(matches!(err, ProcessQueuedEmailError::Interpolate(_)));
     }
 
@@ -542,7 +927,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert!(matches!(err, ProcessQueuedEmailError::Render(_)));
     }
 
@@ -565,7 +953,7 @@ mod tests {
         let (service, spy) = capturing_service();
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
         let captured = spy.lock().unwrap();
         let email = captured.as_ref().unwrap();
         assert_eq!(email.sender, "sender@example.com");
@@ -578,7 +966,7 @@ mod tests {
         vars.insert("name".into(), Value::String("World".into()));
         let body = BodySource::Plain(Plain::try_new(Some("hi {{ name }}".into()), None).unwrap());
         let envelope = default_envelope_with_vars(body, vars);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
         let captured = spy.lock().unwrap();
         let email = captured.as_ref().unwrap();
         let plain = email.body.text();
@@ -595,12 +983,204 @@ mod tests {
             Plain::try_new(Some("text".into()), Some("<p>{{ greeting }}</p>".into())).unwrap(),
         );
         let envelope = default_envelope_with_vars(body, vars);
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
         let captured = spy.lock().unwrap();
         let email = captured.as_ref().unwrap();
         assert_eq!(email.body.html(), Some("<p>hello</p>"));
     }
 
+    /// Puts the email id in every link.
+    struct FakeTrackingLinks;
+
+    impl TrackingLinks for FakeTrackingLinks {
+        fn open_link(&self, email: &TrackedEmail) -> Option<String> {
+            Some(format!(
+                "https://t.example/open/{}",
+                email.email_id.as_uuid()
+            ))
+        }
+
+        fn click_link(&self, email: &TrackedEmail, url: &str) -> Option<String> {
+            Some(format!(
+                "https://t.example/click/{}?to={url}",
+                email.email_id.as_uuid()
+            ))
+        }
+
+        fn verify(&self, _token: &str) -> Option<TrackingHit> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn tracked_html_gets_a_pixel_and_redirected_links() {
+        let (sender, spy) = CapturingSender::new();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            sender,
+            FakeAttachmentStore,
+        )
+        .with_tracking_links(FakeTrackingLinks);
+        let id = EmailId::default();
+        let body = BodySource::Plain(
+            Plain::try_new(
+                Some("see https://acme.com".into()),
+                Some(r#"<body><a href="https://acme.com">see</a></body>"#.into()),
+            )
+            .unwrap(),
+        );
+        let mut envelope = default_envelope(body);
+        envelope.tracking = Tracking {
+            opens: true,
+            clicks: true,
+        };
+        service.execute(id, envelope).await.unwrap();
+
+        let captured = spy.lock().unwrap();
+        let email = captured.as_ref().unwrap();
+        let uuid = id.as_uuid();
+        assert_eq!(
+            email.body.html(),
+            Some(
+                format!(
+                    r#"<body><a href="https://t.example/click/{uuid}?to=https://acme.com">see</a><img src="https://t.example/open/{uuid}" width="1" height="1" alt="" style="display:block;border:0;width:1px;height:1px" /></body>"#
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(email.body.text(), Some("see https://acme.com"));
+    }
+
+    #[tokio::test]
+    async fn subject_is_interpolated_with_body_variables() {
+        let (service, spy) = capturing_service();
+        let mut vars = Map::new();
+        vars.insert("order_id".into(), Value::String("A-42".into()));
+        let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
+        let mut envelope = default_envelope_with_vars(body, vars);
+        envelope.subject = Some("Your order {{ order_id }} shipped".into());
+        service.execute(EmailId::default(), envelope).await.unwrap();
+        let captured = spy.lock().unwrap();
+        let email = captured.as_ref().unwrap();
+        assert_eq!(email.subject.as_deref(), Some("Your order A-42 shipped"));
+    }
+
+    #[tokio::test]
+    async fn template_title_is_the_default_subject() {
+        let (sender, spy) = CapturingSender::new();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml:
+                    "<mjml><mj-head><mj-title>Welcome {{ name }}</mj-title></mj-head></mjml>".into(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            sender,
+            FakeAttachmentStore,
+        );
+        let mut vars = Map::new();
+        vars.insert("name".into(), Value::String("Jane".into()));
+        let body = BodySource::Mjml(MjmlSource::Named("welcome".into()));
+        service
+            .execute(
+                EmailId::default(),
+                default_envelope_with_vars(body.clone(), vars.clone()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            spy.lock().unwrap().as_ref().unwrap().subject.as_deref(),
+            Some("Welcome Jane")
+        );
+
+        let mut envelope = default_envelope_with_vars(body, vars);
+        envelope.subject = Some("Explicit".into());
+        service.execute(EmailId::default(), envelope).await.unwrap();
+        assert_eq!(
+            spy.lock().unwrap().as_ref().unwrap().subject.as_deref(),
+            Some("Explicit")
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_renders_without_sending() {
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: "<mjml><mj-head><mj-title>Hi {{ name }}</mj-title></mj-head></mjml>"
+                    .into(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            FailingSender,
+            FakeAttachmentStore,
+        );
+        let mut vars = Map::new();
+        vars.insert("name".into(), Value::String("Jane".into()));
+        let preview = PreviewEmailUseCase::preview(
+            &service,
+            BodySource::Mjml(MjmlSource::Named("welcome".into())),
+            None,
+            None,
+            &vars,
+        )
+        .await
+        .unwrap();
+        assert_eq!(preview.subject.as_deref(), Some("Hi Jane"));
+        assert!(preview.body.html().unwrap().contains("Hi Jane"));
+    }
+
+    #[tokio::test]
+    async fn chosen_locale_is_available_to_the_template() {
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: "<mjml><mj-head><mj-title>{{ locale }}</mj-title></mj-head></mjml>"
+                    .into(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            FailingSender,
+            FakeAttachmentStore,
+        );
+        let mut vars = Map::new();
+        vars.insert("locale".into(), Value::String("caller".into()));
+        let preview = PreviewEmailUseCase::preview(
+            &service,
+            BodySource::Mjml(MjmlSource::Named("welcome".into())),
+            None,
+            Some("fr".into()),
+            &vars,
+        )
+        .await
+        .unwrap();
+        assert_eq!(preview.subject.as_deref(), Some("fr"));
+        assert_eq!(preview.locale.as_deref(), Some("fr"));
+    }
+
+    #[tokio::test]
+    async fn preview_failure_has_the_worker_error_class() {
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FailingRenderer,
+            FakeSender,
+            FakeAttachmentStore,
+        );
+        let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
+        let err = PreviewEmailUseCase::preview(&service, body, None, None, &Map::new())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.error_class(),
+            crate::entity::error_class::ErrorClass::TemplateRender
+        );
+    }
+
     #[tokio::test]
     async fn attachments_are_resolved_from_store() {
         let (service, spy) = capturing_service();
@@ -614,8 +1194,9 @@ mod tests {
                 backend: "fake".into(),
                 key: "fake-key".into(),
             },
+            content_id: None,
         });
-        service.execute(envelope).await.unwrap();
+        service.execute(EmailId::default(), envelope).await.unwrap();
         let captured = spy.lock().unwrap();
         let email = captured.as_ref().unwrap();
         assert_eq!(email.attachments.len(), 1);
@@ -636,7 +1217,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert_eq!(err.error_class(), ErrorClass::TemplateResolve);
     }
 
@@ -655,7 +1239,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert_eq!(err.error_class(), ErrorClass::TemplateInterpolate);
     }
 
@@ -674,7 +1261,10 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert_eq!(err.error_class(), ErrorClass::TemplateRender);
     }
 
@@ -693,10 +1283,263 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert_eq!(err.error_class(), ErrorClass::Delivery);
     }
 
+    #[derive(Clone, Default)]
+    struct FakeSuppressionList {
+        suppressed: Vec<String>,
+        /// `(sender, list, address)` opt-outs.
+        opted_out: Vec<(String, String, String)>,
+        added: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl SuppressionList for FakeSuppressionList {
+        async fn add(&self, address: &str, reason: &str) -> Result<(), SuppressionListError> {
+            self.added
+                .lock()
+                .unwrap()
+                .push((address.to_owned(), reason.to_owned()));
+            Ok(())
+        }
+
+        async fn remove(&self, _address: &str) -> Result<bool, SuppressionListError> {
+            Ok(false)
+        }
+
+        async fn list(
+            &self,
+            _params: ListSuppressionsParams,
+        ) -> Result<Vec<Suppression>, SuppressionListError> {
+            Ok(vec![])
+        }
+
+        async fn find_suppressed(
+            &self,
+            addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(addresses
+                .iter()
+                .filter(|a| self.suppressed.contains(a))
+                .cloned()
+                .collect())
+        }
+
+        async fn opt_out(
+            &self,
+            _sender: &str,
+            _list: &MailingList,
+            _address: &str,
+        ) -> Result<bool, SuppressionListError> {
+            Ok(true)
+        }
+
+        async fn find_opted_out(
+            &self,
+            sender: &str,
+            list: &MailingList,
+            addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(addresses
+                .iter()
+                .filter(|a| {
+                    self.opted_out
+                        .iter()
+                        .any(|(s, l, o)| s == sender && l == list.as_str() && o == *a)
+                })
+                .cloned()
+                .collect())
+        }
+    }
+
+    struct RejectingSender;
+
+    impl EmailSender for RejectingSender {
+        async fn send(&self, email: OutboundEmail) -> Result<SenderName, SendError> {
+            Err(SendError::Rejected {
+                sender_name: SenderName::new("rejecting"),
+                recipients: email.recipients.into_iter().map(|(_, a)| a).collect(),
+                reply: SmtpReply::new(550, "5.1.1 no such user"),
+            })
+        }
+    }
+
+    fn plain_body() -> BodySource {
+        BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn fully_suppressed_email_is_not_sent() {
+        let (sender, spy) = CapturingSender::new();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            sender,
+            FakeAttachmentStore,
+        )
+        .with_suppression_list(
+            FakeSuppressionList {
+                suppressed: vec!["to@example.com".into()],
+                ..Default::default()
+            },
+            true,
+        );
+        let mut envelope = default_envelope(plain_body());
+        envelope.recipients = vec![(RecipientKind::To, "To@Example.com".into())];
+
+        let outcome = service.execute(EmailId::default(), envelope).await.unwrap();
+
+        assert!(
+            matches!(outcome, ProcessOutcome::Suppressed { ref recipients } if recipients == &["To@Example.com"]),
+            "got {outcome:?}"
+        );
+        assert!(spy.lock().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn suppressed_recipients_are_dropped_from_the_delivery() {
+        let (sender, spy) = CapturingSender::new();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            sender,
+            FakeAttachmentStore,
+        )
+        .with_suppression_list(
+            FakeSuppressionList {
+                suppressed: vec!["gone@example.com".into()],
+                ..Default::default()
+            },
+            true,
+        );
+        let mut envelope = default_envelope(plain_body());
+        envelope
+            .recipients
+            .push((RecipientKind::Cc, "gone@example.com".into()));
+
+        let outcome = service.execute(EmailId::default(), envelope).await.unwrap();
+
+        assert!(
+            matches!(outcome, ProcessOutcome::Sent { ref suppressed, .. } if suppressed == &["gone@example.com"]),
+            "got {outcome:?}"
+        );
+        let captured = spy.lock().unwrap();
+        assert_eq!(
+            captured.as_ref().unwrap().recipients,
+            vec![(RecipientKind::To, "to@example.com".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn recipients_who_left_the_list_are_skipped_for_that_list_only() {
+        let (sender, spy) = CapturingSender::new();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            sender,
+            FakeAttachmentStore,
+        )
+        .with_suppression_list(
+            FakeSuppressionList {
+                opted_out: vec![(
+                    "sender@example.com".into(),
+                    "newsletter".into(),
+                    "to@example.com".into(),
+                )],
+                ..Default::default()
+            },
+            false,
+        );
+
+        let mut envelope = default_envelope(plain_body());
+        envelope.list = Some(MailingList::parse("newsletter").unwrap());
+        let outcome = service.execute(EmailId::default(), envelope).await.unwrap();
+        assert!(
+            matches!(outcome, ProcessOutcome::Suppressed { ref recipients } if recipients == &["to@example.com"]),
+            "got {outcome:?}"
+        );
+        assert!(spy.lock().unwrap().is_none());
+
+        let mut envelope = default_envelope(plain_body());
+        envelope.list = Some(MailingList::parse("product-updates").unwrap());
+        let outcome = service.execute(EmailId::default(), envelope).await.unwrap();
+        assert!(
+            matches!(outcome, ProcessOutcome::Sent { .. }),
+            "got {outcome:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_recipients_are_added_when_auto_suppress_is_on() {
+        let list = FakeSuppressionList::default();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            RejectingSender,
+            FakeAttachmentStore,
+        )
+        .with_suppression_list(list.clone(), true);
+
+        let err = service
+            .execute(EmailId::default(), default_envelope(plain_body()))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            ProcessQueuedEmailError::Send(SendError::Rejected { .. })
+        ));
+        assert_eq!(
+            err.error_class(),
+            crate::entity::error_class::ErrorClass::Rejected
+        );
+        assert_eq!(
+            *list.added.lock().unwrap(),
+            vec![(
+                "to@example.com".to_owned(),
+                "550 5.1.1 no such user".to_owned()
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_recipients_are_not_added_when_auto_suppress_is_off() {
+        let list = FakeSuppressionList::default();
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            RejectingSender,
+            FakeAttachmentStore,
+        )
+        .with_suppression_list(list.clone(), false);
+
+        service
+            .execute(EmailId::default(), default_envelope(plain_body()))
+            .await
+            .unwrap_err();
+
+        assert!(list.added.lock().unwrap().is_empty());
+    }
+
     struct NoMatchingRouteSender;
 
     impl EmailSender for NoMatchingRouteSender {
@@ -722,7 +1565,43 @@ mod tests {
         );
         let body = BodySource::Plain(Plain::try_new(Some("hello".into()), None).unwrap());
         let envelope = default_envelope(body);
-        let err = service.execute(envelope).await.unwrap_err();
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
         assert_eq!(err.error_class(), ErrorClass::Routing);
     }
+
+    struct UnsignableSender;
+
+    impl EmailSender for UnsignableSender {
+        async fn send(&self, _email: OutboundEmail) -> Result<SenderName, SendError> {
+            Err(SendError::Signing {
+                sender_name: SenderName::new("primary"),
+                source: anyhow::anyhow!("no DKIM key for domain \"example.com\""),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn signing_failure_maps_to_signing_error_class() {
+        use crate::entity::error_class::ErrorClass;
+
+        let service = ProcessQueuedEmailService::new(
+            FakeResolver {
+                inline_mjml: String::new(),
+            },
+            FakeInterpolator,
+            FakeRenderer,
+            UnsignableSender,
+            FakeAttachmentStore,
+        );
+        let envelope = default_envelope(plain_body());
+        let err = service
+            .execute(EmailId::default(), envelope)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_class(), ErrorClass::Signing);
+        assert_eq!(err.sender_name().map(SenderName::as_str), Some("primary"));
+    }
 }
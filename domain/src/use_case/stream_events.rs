@@ -0,0 +1,297 @@
+use std::collections::BTreeSet;
+
+use thiserror::Error;
+
+use crate::port::event_repository::{
+    EventCursor, EventFilter, EventRecord, EventRepository, EventRepositoryError,
+    ListEventsAfterParams, ListEventsParams,
+};
+
+/// How far back each poll looks again for events committed after newer
+/// ones. Their `created_at_ms` is taken when the inserting transaction
+/// starts, so this must outlast any such transaction.
+pub const LOOKBACK_MS: i64 = 2_000;
+const BATCH_SIZE: u32 = 100;
+
+#[derive(Debug, Error)]
+pub enum StreamEventsError {
+    #[error(transparent)]
+    Repository(#[from] EventRepositoryError),
+}
+
+/// Where a stream stands: events up to `start` are never sent, later ones
+/// are sent once.
+#[derive(Clone, Debug)]
+pub struct EventTail {
+    filter: EventFilter,
+    start: Option<EventCursor>,
+    /// Newest event sent so far.
+    cursor: Option<EventCursor>,
+    /// Events sent within the lookback window of `cursor`.
+    sent: BTreeSet<EventCursor>,
+}
+
+impl EventTail {
+    fn window_start(&self) -> Option<EventCursor> {
+        let lookback = self.cursor.map(|cursor| EventCursor {
+            created_at_ms: cursor.created_at_ms.saturating_sub(LOOKBACK_MS),
+            id: uuid::Uuid::nil(),
+        });
+        self.start.max(lookback)
+    }
+
+    fn record(&mut self, events: &[EventRecord]) {
+        for event in events {
+            self.sent.insert(event.cursor());
+            self.cursor = self.cursor.max(Some(event.cursor()));
+        }
+        if let Some(window_start) = self.window_start() {
+            self.sent = self.sent.split_off(&window_start);
+        }
+    }
+}
+
+pub trait StreamEventsUseCase: Send + Sync + 'static {
+    /// Starts right after the event `last_event_id` when given and still
+    /// stored, after the newest stored event otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StreamEventsError::Repository` when the underlying query fails.
+    fn start(
+        &self,
+        filter: EventFilter,
+        last_event_id: Option<uuid::Uuid>,
+    ) -> impl std::future::Future<Output = Result<EventTail, StreamEventsError>> + Send;
+
+    /// Events matching the tail filter stored since the previous call, oldest
+    /// first. An event committed by another replica after newer ones is
+    /// still returned, as long as it is no older than [`LOOKBACK_MS`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `StreamEventsError::Repository` when the underlying query fails.
+    fn next_batch(
+        &self,
+        tail: &mut EventTail,
+    ) -> impl std::future::Future<Output = Result<Vec<EventRecord>, StreamEventsError>> + Send;
+}
+
+pub struct StreamEventsService<R> {
+    repo: R,
+}
+
+impl<R> StreamEventsService<R> {
+    pub fn new(repo: R) -> Self {
+        Self { repo }
+    }
+}
+
+impl<R: EventRepository> StreamEventsService<R> {
+    async fn start_inner(
+        &self,
+        filter: EventFilter,
+        last_event_id: Option<uuid::Uuid>,
+    ) -> Result<EventTail, StreamEventsError> {
+        let resumed = match last_event_id {
+            Some(id) => self.repo.get_event(id).await?,
+            None => None,
+        };
+        let start = match resumed {
+            Some(event) => Some(event.cursor()),
+            None => self.newest().await?,
+        };
+        Ok(EventTail {
+            filter,
+            start,
+            cursor: None,
+            sent: BTreeSet::new(),
+        })
+    }
+
+    async fn newest(&self) -> Result<Option<EventCursor>, StreamEventsError> {
+        let newest = self
+            .repo
+            .list_events(ListEventsParams {
+                email_id: None,
+                event_type: None,
+                sender_name: None,
+                error_class: None,
+                after_ms: None,
+                before_ms: None,
+                limit: 1,
+                offset: 0,
+            })
+            .await?;
+        Ok(newest.first().map(EventRecord::cursor))
+    }
+
+    async fn next_batch_inner(
+        &self,
+        tail: &mut EventTail,
+    ) -> Result<Vec<EventRecord>, StreamEventsError> {
+        // Sent events within the window come back too, so they must not eat
+        // into the batch.
+        let limit = BATCH_SIZE.saturating_add(u32::try_from(tail.sent.len()).unwrap_or(u32::MAX));
+        let events: Vec<EventRecord> = self
+            .repo
+            .list_events_after(ListEventsAfterParams {
+                filter: tail.filter.clone(),
+                after: tail.window_start(),
+                limit,
+            })
+            .await?
+            .into_iter()
+            .filter(|event| !tail.sent.contains(&event.cursor()))
+            .collect();
+        tail.record(&events);
+        Ok(events)
+    }
+}
+
+impl<R: EventRepository> StreamEventsUseCase for StreamEventsService<R> {
+    fn start(
+        &self,
+        filter: EventFilter,
+        last_event_id: Option<uuid::Uuid>,
+    ) -> impl std::future::Future<Output = Result<EventTail, StreamEventsError>> + Send {
+        self.start_inner(filter, last_event_id)
+    }
+
+    fn next_batch(
+        &self,
+        tail: &mut EventTail,
+    ) -> impl std::future::Future<Output = Result<Vec<EventRecord>, StreamEventsError>> + Send {
+        self.next_batch_inner(tail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::email::EmailId;
+    use crate::port::event_repository::{
+        EventFilter, EventRecord, EventRepository, EventRepositoryError, ListEventsAfterParams,
+        ListEventsParams,
+    };
+
+    use super::{StreamEventsService, StreamEventsUseCase};
+
+    #[derive(Clone, Default)]
+    struct MemoryEvents {
+        events: Arc<Mutex<Vec<EventRecord>>>,
+    }
+
+    impl MemoryEvents {
+        fn push(&self, event_type: &str, created_at_ms: i64) -> uuid::Uuid {
+            let id = uuid::Uuid::now_v7();
+            self.events.lock().unwrap().push(EventRecord {
+                id,
+                email_id: EmailId::default(),
+                event_type: event_type.into(),
+                payload: None,
+                sender_name: None,
+                error_class: None,
+                created_at_ms,
+            });
+            id
+        }
+    }
+
+    impl EventRepository for MemoryEvents {
+        async fn list_events(
+            &self,
+            params: ListEventsParams,
+        ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+            let mut events = self.events.lock().unwrap().clone();
+            events.sort_by_key(|e| std::cmp::Reverse(e.cursor()));
+            events.truncate(params.limit as usize);
+            Ok(events)
+        }
+
+        async fn get_event(
+            &self,
+            id: uuid::Uuid,
+        ) -> Result<Option<EventRecord>, EventRepositoryError> {
+            Ok(self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|e| e.id == id)
+                .cloned())
+        }
+
+        async fn list_events_after(
+            &self,
+            params: ListEventsAfterParams,
+        ) -> Result<Vec<EventRecord>, EventRepositoryError> {
+            let mut events: Vec<EventRecord> = self
+                .events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| params.after.is_none_or(|after| e.cursor() > after))
+                .filter(|e| {
+                    params
+                        .filter
+                        .event_type
+                        .as_ref()
+                        .is_none_or(|t| *t == e.event_type)
+                })
+                .cloned()
+                .collect();
+            events.sort_by_key(EventRecord::cursor);
+            events.truncate(params.limit as usize);
+            Ok(events)
+        }
+    }
+
+    fn types(events: &[EventRecord]) -> Vec<&str> {
+        events.iter().map(|e| e.event_type.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn stream_starts_after_the_newest_event_and_catches_late_commits() {
+        let repo = MemoryEvents::default();
+        repo.push("queued", 1_000);
+        let svc = StreamEventsService::new(repo.clone());
+        let mut tail = svc.start(EventFilter::default(), None).await.unwrap();
+
+        repo.push("sending", 2_000);
+        assert_eq!(
+            types(&svc.next_batch(&mut tail).await.unwrap()),
+            ["sending"]
+        );
+        // Committed after `sending` by another replica, with an older time.
+        repo.push("cancelled", 1_500);
+        repo.push("delivery.succeeded", 3_000);
+        assert_eq!(
+            types(&svc.next_batch(&mut tail).await.unwrap()),
+            ["cancelled", "delivery.succeeded"]
+        );
+        assert!(svc.next_batch(&mut tail).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stream_resumes_after_the_last_event_id_with_its_filter() {
+        let repo = MemoryEvents::default();
+        let seen = repo.push("queued", 1_000);
+        repo.push("delivery.failed", 1_100);
+        repo.push("sending", 1_200);
+        repo.push("delivery.failed", 1_300);
+        let svc = StreamEventsService::new(repo.clone());
+        let filter = EventFilter {
+            event_type: Some("delivery.failed".into()),
+            ..EventFilter::default()
+        };
+
+        let mut resumed = svc.start(filter.clone(), Some(seen)).await.unwrap();
+        let mut unknown = svc.start(filter, Some(uuid::Uuid::now_v7())).await.unwrap();
+
+        let events = svc.next_batch(&mut resumed).await.unwrap();
+        assert_eq!(types(&events), ["delivery.failed", "delivery.failed"]);
+        assert!(svc.next_batch(&mut unknown).await.unwrap().is_empty());
+    }
+}
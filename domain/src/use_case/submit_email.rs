@@ -1,33 +1,84 @@
 use thiserror::Error;
 
 use crate::entity::attachment::AttachmentRef;
+use crate::entity::body::{BodySource, MjmlSource};
+use crate::entity::calendar::{Calendar, CalendarEvent, InvalidCalendar};
 use crate::entity::email::EmailId;
 use crate::entity::envelope::Envelope;
 use crate::entity::lifecycle_event::LifecycleEvent;
+use crate::entity::template::{
+    locale_fallbacks, localized_template_name, parse_template_ref, pinned_template_ref,
+};
+use crate::entity::unsubscribe::{
+    LIST_UNSUBSCRIBE_HEADER, LIST_UNSUBSCRIBE_POST_HEADER, ONE_CLICK_VALUE, Subscription,
+};
 use crate::port::attachment_fetcher::AttachmentFetcher;
 use crate::port::attachment_store::{AttachmentReader, AttachmentStore};
+use crate::port::clock::{Clock, SystemClock};
 use crate::port::email_queue::{EmailQueue, EmailQueueError};
 use crate::port::email_repository::{EmailRepository, EmailRepositoryError, SaveResult};
 use crate::port::event_publisher::EventPublisher;
-
+use crate::port::template_interpolator::TemplateInterpolator;
+use crate::port::template_renderer::TemplateRenderer;
+use crate::port::template_resolver::TemplateResolver;
+use crate::port::template_store::{NoopTemplateStore, TemplateStore, TemplateStoreError};
+use crate::port::unsubscribe_links::{NoUnsubscribeLinks, UnsubscribeLinks};
+use crate::use_case::process_queued_email::{ProcessQueuedEmailError, render_email};
+
+/// Attachment content of a submission. A `content_id` makes it an inline
+/// part the HTML body can reference as `cid:<content_id>`.
 pub enum AttachmentInput {
     Inline {
         filename: String,
         content_type: String,
         bytes: bytes::Bytes,
+        content_id: Option<String>,
     },
     Stream {
         filename: String,
         content_type: String,
         reader: crate::port::attachment_store::AttachmentReader,
+        content_id: Option<String>,
     },
     Remote {
         filename: String,
         content_type: String,
         url: url::Url,
+        content_id: Option<String>,
     },
 }
 
+impl AttachmentInput {
+    #[must_use]
+    pub fn content_id(&self) -> Option<&str> {
+        match self {
+            Self::Inline { content_id, .. }
+            | Self::Stream { content_id, .. }
+            | Self::Remote { content_id, .. } => content_id.as_deref(),
+        }
+    }
+}
+
+/// Invitation of a submission: a raw iCalendar object, or an event serialized
+/// when the email is accepted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CalendarInput {
+    Raw(Calendar),
+    Event(CalendarEvent),
+}
+
+impl CalendarInput {
+    /// # Errors
+    ///
+    /// Returns `InvalidCalendar` when the event is invalid.
+    pub fn into_calendar(self, stamp_ms: i64) -> Result<Calendar, InvalidCalendar> {
+        match self {
+            Self::Raw(calendar) => Ok(calendar),
+            Self::Event(event) => event.to_calendar(stamp_ms),
+        }
+    }
+}
+
 pub struct SubmitEmailInput {
     pub idempotency_key: Option<String>,
     pub correlation_id: Option<String>,
@@ -37,6 +88,30 @@ pub struct SubmitEmailInput {
     pub body: crate::entity::body::BodySource,
     pub variables: serde_json::Map<String, serde_json::Value>,
     pub attachments: Vec<AttachmentInput>,
+    /// Earliest delivery time (Unix epoch ms). `None` means "as soon as possible".
+    pub send_at_ms: Option<i64>,
+    /// Reply-To, display names and custom header fields.
+    pub headers: crate::entity::message_headers::MessageHeaders,
+    /// Preferred locale (`fr-CA`), used to pick a localized template variant.
+    pub locale: Option<String>,
+    /// Invitation sent as the `text/calendar` alternative of the body.
+    pub calendar: Option<CalendarInput>,
+    /// Mailing list the email belongs to. Adds one-click `List-Unsubscribe`
+    /// headers; the email must have a single recipient.
+    pub list: Option<crate::entity::unsubscribe::MailingList>,
+    /// Opens and clicks to report as lifecycle events.
+    pub tracking: crate::entity::tracking::Tracking,
+}
+
+/// Why the unsubscribe headers of an email sent for a list could not be added.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ListUnsubscribeError {
+    #[error("unsubscribe links are not configured")]
+    NotConfigured,
+    #[error("an email sent for a list needs exactly one recipient, got {0}")]
+    RecipientCount(usize),
+    #[error("header {0:?} is generated for emails sent for a list")]
+    HeaderConflict(String),
 }
 
 #[derive(Debug, Error)]
@@ -45,6 +120,8 @@ pub enum SubmitEmailError {
     Persist(#[from] EmailRepositoryError),
     #[error(transparent)]
     Enqueue(#[from] EmailQueueError),
+    #[error(transparent)]
+    TemplateStore(#[from] TemplateStoreError),
     #[error("attachment store failed")]
     AttachmentStore {
         #[source]
@@ -55,6 +132,17 @@ pub enum SubmitEmailError {
         #[source]
         source: anyhow::Error,
     },
+    /// The strict content check failed; carries the error the worker would
+    /// have failed with.
+    #[error("email content is invalid")]
+    InvalidContent {
+        #[source]
+        source: ProcessQueuedEmailError,
+    },
+    #[error(transparent)]
+    ListUnsubscribe(#[from] ListUnsubscribeError),
+    #[error(transparent)]
+    InvalidCalendar(#[from] InvalidCalendar),
 }
 
 impl SubmitEmailError {
@@ -66,10 +154,17 @@ impl SubmitEmailError {
             // Storage / queue issues are infrastructure-level and likely transient.
             // Attachment store I/O could be either; treat as transient (disk full
             // recovers; remote storage hiccup recovers).
-            Self::Persist(_) | Self::Enqueue(_) | Self::AttachmentStore { .. } => true,
+            Self::Persist(_)
+            | Self::Enqueue(_)
+            | Self::TemplateStore(_)
+            | Self::AttachmentStore { .. } => true,
             // Remote URL fetch errors are almost always permanent for the given
-            // URL (404, 410, blocked domain, oversize). Don't retry.
-            Self::AttachmentFetch { .. } => false,
+            // URL (404, 410, blocked domain, oversize). Don't retry. Invalid
+            // content needs a new template or variables before it can pass.
+            Self::AttachmentFetch { .. }
+            | Self::InvalidContent { .. }
+            | Self::ListUnsubscribe(_)
+            | Self::InvalidCalendar(_) => false,
         }
     }
 }
@@ -80,18 +175,136 @@ pub trait SubmitEmailUseCase: Send + Sync + 'static {
     /// Returns `SubmitEmailError::Persist` when saving the envelope fails.
     /// Returns `SubmitEmailError::Enqueue` when enqueuing fails.
     /// Returns `SubmitEmailError::AttachmentStore` when blob upload fails.
+    /// Returns `SubmitEmailError::TemplateStore` when the template lookup fails.
+    /// Returns `SubmitEmailError::InvalidContent` when the content check
+    /// rejects the email.
+    /// Returns `SubmitEmailError::ListUnsubscribe` when the unsubscribe
+    /// headers of an email sent for a list cannot be added.
+    /// Returns `SubmitEmailError::InvalidCalendar` when the calendar event is
+    /// invalid.
     fn execute(
         &self,
         input: SubmitEmailInput,
     ) -> impl std::future::Future<Output = Result<EmailId, SubmitEmailError>> + Send;
 }
 
-pub struct SubmitEmailService<R, Q, P, A, F> {
+/// Checks an email's content before it is accepted.
+pub trait ContentValidator: Send + Sync + 'static {
+    /// # Errors
+    ///
+    /// Returns the `ProcessQueuedEmailError` the worker would fail with.
+    fn validate(
+        &self,
+        body: &BodySource,
+        subject: Option<&str>,
+        locale: Option<&str>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> impl std::future::Future<Output = Result<(), ProcessQueuedEmailError>> + Send;
+}
+
+/// Accepts any content; templates are only checked by the worker.
+pub struct NoContentValidation;
+
+impl ContentValidator for NoContentValidation {
+    async fn validate(
+        &self,
+        _body: &BodySource,
+        _subject: Option<&str>,
+        _locale: Option<&str>,
+        _variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), ProcessQueuedEmailError> {
+        Ok(())
+    }
+}
+
+impl<V: ContentValidator> ContentValidator for Option<V> {
+    async fn validate(
+        &self,
+        body: &BodySource,
+        subject: Option<&str>,
+        locale: Option<&str>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), ProcessQueuedEmailError> {
+        match self {
+            Some(validator) => validator.validate(body, subject, locale, variables).await,
+            None => Ok(()),
+        }
+    }
+}
+
+/// Resolves, interpolates and renders the body and subject the way the
+/// worker will, so broken templates and missing variables are caught at
+/// submit time. Pair it with a strict interpolator to reject undefined
+/// variables.
+pub struct RenderContentValidator<Res, I, Rdr> {
+    resolver: Res,
+    interpolator: I,
+    renderer: Rdr,
+}
+
+impl<Res, I, Rdr> RenderContentValidator<Res, I, Rdr>
+where
+    Res: TemplateResolver,
+    I: TemplateInterpolator,
+    Rdr: TemplateRenderer,
+{
+    pub fn new(resolver: Res, interpolator: I, renderer: Rdr) -> Self {
+        Self {
+            resolver,
+            interpolator,
+            renderer,
+        }
+    }
+}
+
+impl<Res, I, Rdr> ContentValidator for RenderContentValidator<Res, I, Rdr>
+where
+    Res: TemplateResolver,
+    I: TemplateInterpolator,
+    Rdr: TemplateRenderer,
+{
+    async fn validate(
+        &self,
+        body: &BodySource,
+        subject: Option<&str>,
+        locale: Option<&str>,
+        variables: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<(), ProcessQueuedEmailError> {
+        render_email(
+            &self.resolver,
+            &self.interpolator,
+            &self.renderer,
+            body.clone(),
+            subject.map(str::to_owned),
+            locale.map(str::to_owned),
+            variables,
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+pub struct SubmitEmailService<
+    R,
+    Q,
+    P,
+    A,
+    F,
+    V = NoContentValidation,
+    T = NoopTemplateStore,
+    U = NoUnsubscribeLinks,
+    C = SystemClock,
+> {
     repository: R,
     queue: Q,
     event_publisher: P,
     attachment_store: A,
     attachment_fetcher: F,
+    content_validator: V,
+    template_store: T,
+    unsubscribe_links: U,
+    /// Stamps the calendar events of submissions.
+    clock: C,
 }
 
 impl<R, Q, P, A, F> SubmitEmailService<R, Q, P, A, F>
@@ -116,7 +329,170 @@ where
             event_publisher,
             attachment_store,
             attachment_fetcher,
+            content_validator: NoContentValidation,
+            template_store: NoopTemplateStore,
+            unsubscribe_links: NoUnsubscribeLinks,
+            clock: SystemClock,
+        }
+    }
+}
+
+impl<R, Q, P, A, F, V, T, U, C> SubmitEmailService<R, Q, P, A, F, V, T, U, C>
+where
+    R: EmailRepository,
+    Q: EmailQueue,
+    P: EventPublisher,
+    A: AttachmentStore,
+    F: AttachmentFetcher,
+    V: ContentValidator,
+    T: TemplateStore,
+    U: UnsubscribeLinks,
+    C: Clock,
+{
+    /// Runs `validator` on every submission before anything is persisted.
+    #[must_use]
+    pub fn with_content_validator<V2: ContentValidator>(
+        self,
+        validator: V2,
+    ) -> SubmitEmailService<R, Q, P, A, F, V2, T, U, C> {
+        SubmitEmailService {
+            repository: self.repository,
+            queue: self.queue,
+            event_publisher: self.event_publisher,
+            attachment_store: self.attachment_store,
+            attachment_fetcher: self.attachment_fetcher,
+            content_validator: validator,
+            template_store: self.template_store,
+            unsubscribe_links: self.unsubscribe_links,
+            clock: self.clock,
+        }
+    }
+
+    /// Pins named templates found in `store` to their latest version, so
+    /// retries and scheduled sends use the template the caller saw.
+    #[must_use]
+    pub fn with_template_store<T2: TemplateStore>(
+        self,
+        store: T2,
+    ) -> SubmitEmailService<R, Q, P, A, F, V, T2, U, C> {
+        SubmitEmailService {
+            repository: self.repository,
+            queue: self.queue,
+            event_publisher: self.event_publisher,
+            attachment_store: self.attachment_store,
+            attachment_fetcher: self.attachment_fetcher,
+            content_validator: self.content_validator,
+            template_store: store,
+            unsubscribe_links: self.unsubscribe_links,
+            clock: self.clock,
+        }
+    }
+
+    /// Issues the one-click unsubscribe links of emails sent for a list.
+    #[must_use]
+    pub fn with_unsubscribe_links<U2: UnsubscribeLinks>(
+        self,
+        links: U2,
+    ) -> SubmitEmailService<R, Q, P, A, F, V, T, U2, C> {
+        SubmitEmailService {
+            repository: self.repository,
+            queue: self.queue,
+            event_publisher: self.event_publisher,
+            attachment_store: self.attachment_store,
+            attachment_fetcher: self.attachment_fetcher,
+            content_validator: self.content_validator,
+            template_store: self.template_store,
+            unsubscribe_links: links,
+            clock: self.clock,
+        }
+    }
+
+    #[must_use]
+    pub fn with_clock<C2: Clock>(
+        self,
+        clock: C2,
+    ) -> SubmitEmailService<R, Q, P, A, F, V, T, U, C2> {
+        SubmitEmailService {
+            repository: self.repository,
+            queue: self.queue,
+            event_publisher: self.event_publisher,
+            attachment_store: self.attachment_store,
+            attachment_fetcher: self.attachment_fetcher,
+            content_validator: self.content_validator,
+            template_store: self.template_store,
+            unsubscribe_links: self.unsubscribe_links,
+            clock,
+        }
+    }
+
+    /// Adds the `List-Unsubscribe` and `List-Unsubscribe-Post` headers of an
+    /// email sent for a list, pointing at a link for its single recipient.
+    fn add_unsubscribe_headers(
+        &self,
+        id: EmailId,
+        input: &mut SubmitEmailInput,
+    ) -> Result<(), ListUnsubscribeError> {
+        let Some(list) = &input.list else {
+            return Ok(());
+        };
+        let [(_, address)] = input.recipients.as_slice() else {
+            return Err(ListUnsubscribeError::RecipientCount(input.recipients.len()));
+        };
+        if let Some(name) = input.headers.custom.keys().find(|name| {
+            name.eq_ignore_ascii_case(LIST_UNSUBSCRIBE_HEADER)
+                || name.eq_ignore_ascii_case(LIST_UNSUBSCRIBE_POST_HEADER)
+        }) {
+            return Err(ListUnsubscribeError::HeaderConflict(name.clone()));
+        }
+        let link = self
+            .unsubscribe_links
+            .link(&Subscription {
+                email_id: id,
+                sender: input.sender.clone(),
+                list: list.clone(),
+                address: address.clone(),
+                correlation_id: input.correlation_id.clone(),
+            })
+            .ok_or(ListUnsubscribeError::NotConfigured)?;
+        input
+            .headers
+            .custom
+            .insert(LIST_UNSUBSCRIBE_HEADER.to_owned(), format!("<{link}>"));
+        input.headers.custom.insert(
+            LIST_UNSUBSCRIBE_POST_HEADER.to_owned(),
+            ONE_CLICK_VALUE.to_owned(),
+        );
+        Ok(())
+    }
+
+    /// Rewrites an unpinned `Named` body to `name@version` when the store
+    /// knows the template, preferring its variant for `locale`
+    /// (`welcome.fr@2`). Other bodies are returned as they are.
+    async fn pin_template(
+        &self,
+        body: BodySource,
+        locale: Option<&str>,
+    ) -> Result<BodySource, SubmitEmailError> {
+        let BodySource::Mjml(MjmlSource::Named(reference)) = body else {
+            return Ok(body);
+        };
+        let (name, None) = parse_template_ref(&reference) else {
+            return Ok(BodySource::Mjml(MjmlSource::Named(reference)));
+        };
+        let candidates = locale
+            .into_iter()
+            .flat_map(locale_fallbacks)
+            .map(|locale| localized_template_name(name, locale))
+            .chain(std::iter::once(name.to_owned()));
+        for candidate in candidates {
+            if let Some(template) = self.template_store.get(&candidate, None).await? {
+                return Ok(BodySource::Mjml(MjmlSource::Named(pinned_template_ref(
+                    &candidate,
+                    template.version,
+                ))));
+            }
         }
+        Ok(BodySource::Mjml(MjmlSource::Named(reference)))
     }
 
     /// # Errors
@@ -124,13 +500,38 @@ where
     /// Returns `SubmitEmailError::Persist` when saving the envelope fails.
     /// Returns `SubmitEmailError::Enqueue` when enqueuing fails.
     /// Returns `SubmitEmailError::AttachmentStore` when blob upload fails.
+    /// Returns `SubmitEmailError::TemplateStore` when the template lookup fails.
+    /// Returns `SubmitEmailError::InvalidContent` when the content check
+    /// rejects the email.
+    /// Returns `SubmitEmailError::ListUnsubscribe` when the unsubscribe
+    /// headers of an email sent for a list cannot be added.
+    /// Returns `SubmitEmailError::InvalidCalendar` when the calendar event is
+    /// invalid.
     #[allow(clippy::too_many_lines)]
     #[tracing::instrument(skip_all, name = "submit_email", fields(email_id = tracing::field::Empty, correlation_id = tracing::field::Empty))]
-    pub async fn execute(&self, input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
+    pub async fn execute(&self, mut input: SubmitEmailInput) -> Result<EmailId, SubmitEmailError> {
         let id = EmailId::default();
         if let Some(ref cid) = input.correlation_id {
             tracing::Span::current().record("correlation_id", cid.as_str());
         }
+        self.add_unsubscribe_headers(id, &mut input)?;
+        let calendar = input
+            .calendar
+            .take()
+            .map(|calendar| calendar.into_calendar(self.clock.now_ms()))
+            .transpose()?;
+        input.body = self
+            .pin_template(input.body, input.locale.as_deref())
+            .await?;
+        self.content_validator
+            .validate(
+                &input.body,
+                input.subject.as_deref(),
+                input.locale.as_deref(),
+                &input.variables,
+            )
+            .await
+            .map_err(|source| SubmitEmailError::InvalidContent { source })?;
 
         // Reserve the row with an empty attachment list first; the final list is
         // patched in after blobs are written so the worker never sees stale refs.
@@ -143,6 +544,12 @@ where
             body: input.body.clone(),
             variables: input.variables.clone(),
             attachments: vec![],
+            send_at_ms: input.send_at_ms,
+            headers: input.headers.clone(),
+            locale: input.locale.clone(),
+            calendar: calendar.clone(),
+            list: input.list.clone(),
+            tracking: input.tracking,
         };
 
         let result = self.repository.save(id, &envelope_for_reservation).await?;
@@ -167,31 +574,41 @@ where
             body,
             variables,
             attachments,
+            send_at_ms,
+            headers,
+            locale,
+            calendar: _,
+            list,
+            tracking,
         } = input;
 
         let mut written_refs: Vec<AttachmentRef> = Vec::with_capacity(attachments.len());
         for att in attachments {
-            let (filename, content_type, reader) = match att {
+            let (filename, content_type, content_id, reader) = match att {
                 AttachmentInput::Inline {
                     filename,
                     content_type,
                     bytes,
+                    content_id,
                 } => (
                     filename,
                     content_type,
+                    content_id,
                     Box::pin(std::io::Cursor::new(bytes.to_vec())) as AttachmentReader,
                 ),
                 AttachmentInput::Stream {
                     filename,
                     content_type,
                     reader,
-                } => (filename, content_type, reader),
+                    content_id,
+                } => (filename, content_type, content_id, reader),
                 AttachmentInput::Remote {
                     filename,
                     content_type,
                     url,
+                    content_id,
                 } => match self.attachment_fetcher.fetch(&url).await {
-                    Ok(r) => (filename, content_type, r),
+                    Ok(r) => (filename, content_type, content_id, r),
                     Err(fetch_err) => {
                         for r in &written_refs {
                             let _ = self.attachment_store.delete(&r.blob).await;
@@ -210,6 +627,7 @@ where
                         content_type,
                         size_bytes: put_result.size_bytes,
                         blob: put_result.blob,
+                        content_id,
                     });
                 }
                 Err(store_err) => {
@@ -243,6 +661,12 @@ where
             body,
             variables,
             attachments: written_refs,
+            send_at_ms,
+            headers,
+            locale,
+            calendar,
+            list,
+            tracking,
         };
 
         if let Err(enqueue_err) = self.queue.enqueue(id, &envelope).await {
@@ -266,13 +690,17 @@ where
     }
 }
 
-impl<R, Q, P, A, F> SubmitEmailUseCase for SubmitEmailService<R, Q, P, A, F>
+impl<R, Q, P, A, F, V, T, U, C> SubmitEmailUseCase for SubmitEmailService<R, Q, P, A, F, V, T, U, C>
 where
     R: EmailRepository + Send + Sync + 'static,
     Q: EmailQueue + Send + Sync + 'static,
     P: EventPublisher + Send + Sync + 'static,
     A: AttachmentStore + Send + Sync + 'static,
     F: AttachmentFetcher + Send + Sync + 'static,
+    V: ContentValidator,
+    T: TemplateStore,
+    U: UnsubscribeLinks,
+    C: Clock,
 {
     fn execute(
         &self,
@@ -287,19 +715,32 @@ mod tests {
     use std::sync::{Arc, Mutex};
 
     use crate::entity::attachment::{AttachmentRef, BlobRef};
-    use crate::entity::body::Plain;
+    use crate::entity::body::{BodySource, MjmlSource, Plain};
+    use crate::entity::calendar::{CalendarEvent, CalendarParticipant};
     use crate::entity::email::{EmailId, RecipientKind};
     use crate::entity::envelope::Envelope;
     use crate::entity::lifecycle_event::LifecycleEvent;
+    use crate::entity::message_headers::MessageHeaders;
     use crate::port::attachment_fetcher::{AttachmentFetchError, AttachmentFetcher};
     use crate::port::attachment_store::{
         AttachmentReader, AttachmentStore, AttachmentStoreError, PutResult,
     };
+    use crate::port::clock::Clock;
     use crate::port::email_queue::{EmailQueue, EmailQueueError};
     use crate::port::email_repository::{EmailRepository, EmailRepositoryError, SaveResult};
     use crate::port::event_publisher::{EventPublisher, EventPublisherError};
 
-    use super::{AttachmentInput, SubmitEmailError, SubmitEmailInput, SubmitEmailService};
+    use crate::entity::template::Template;
+    use crate::entity::unsubscribe::{MailingList, Subscription};
+    use crate::port::template_interpolator::InterpolateError;
+    use crate::port::template_store::{TemplateStore, TemplateStoreError};
+    use crate::port::unsubscribe_links::UnsubscribeLinks;
+    use crate::use_case::process_queued_email::ProcessQueuedEmailError;
+
+    use super::{
+        AttachmentInput, CalendarInput, ContentValidator, ListUnsubscribeError, SubmitEmailError,
+        SubmitEmailInput, SubmitEmailService,
+    };
 
     fn make_input(sender: &str) -> SubmitEmailInput {
         SubmitEmailInput {
@@ -313,6 +754,12 @@ mod tests {
             ),
             variables: serde_json::Map::new(),
             attachments: vec![],
+            send_at_ms: None,
+            headers: MessageHeaders::default(),
+            locale: None,
+            calendar: None,
+            list: None,
+            tracking: crate::entity::tracking::Tracking::default(),
         }
     }
 
@@ -371,6 +818,21 @@ mod tests {
             Ok(())
         }
 
+        async fn cancel(
+            &self,
+            _id: EmailId,
+        ) -> Result<crate::port::email_repository::CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _id: EmailId) -> Result<bool, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn end_delivery(&self, _id: EmailId) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+
         async fn list_all_attachment_blobs(
             &self,
         ) -> Result<Vec<crate::entity::attachment::BlobRef>, EmailRepositoryError> {
@@ -415,6 +877,21 @@ mod tests {
             })
         }
 
+        async fn cancel(
+            &self,
+            _id: EmailId,
+        ) -> Result<crate::port::email_repository::CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _id: EmailId) -> Result<bool, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn end_delivery(&self, _id: EmailId) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+
         async fn list_all_attachment_blobs(
             &self,
         ) -> Result<Vec<crate::entity::attachment::BlobRef>, EmailRepositoryError> {
@@ -455,6 +932,21 @@ mod tests {
             Ok(())
         }
 
+        async fn cancel(
+            &self,
+            _id: EmailId,
+        ) -> Result<crate::port::email_repository::CancelResult, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn begin_delivery(&self, _id: EmailId) -> Result<bool, EmailRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn end_delivery(&self, _id: EmailId) -> Result<(), EmailRepositoryError> {
+            unimplemented!()
+        }
+
         async fn list_all_attachment_blobs(
             &self,
         ) -> Result<Vec<crate::entity::attachment::BlobRef>, EmailRepositoryError> {
@@ -465,20 +957,32 @@ mod tests {
     #[derive(Clone)]
     struct FakeQueue {
         enqueued: Arc<Mutex<Vec<EmailId>>>,
+        bodies: Arc<Mutex<Vec<BodySource>>>,
+        attachments: Arc<Mutex<Vec<AttachmentRef>>>,
+        envelopes: Arc<Mutex<Vec<Envelope>>>,
     }
 
     impl FakeQueue {
         fn new() -> Self {
             Self {
                 enqueued: Arc::new(Mutex::new(Vec::new())),
+                bodies: Arc::new(Mutex::new(Vec::new())),
+                attachments: Arc::new(Mutex::new(Vec::new())),
+                envelopes: Arc::new(Mutex::new(Vec::new())),
             }
         }
     }
 
     #[allow(async_fn_in_trait)]
     impl EmailQueue for FakeQueue {
-        async fn enqueue(&self, id: EmailId, _envelope: &Envelope) -> Result<(), EmailQueueError> {
+        async fn enqueue(&self, id: EmailId, envelope: &Envelope) -> Result<(), EmailQueueError> {
             self.enqueued.lock().unwrap().push(id);
+            self.bodies.lock().unwrap().push(envelope.body.clone());
+            self.attachments
+                .lock()
+                .unwrap()
+                .extend(envelope.attachments.iter().cloned());
+            self.envelopes.lock().unwrap().push(envelope.clone());
             Ok(())
         }
 
@@ -746,6 +1250,7 @@ mod tests {
             filename: "attach.txt".into(),
             content_type: "text/plain".into(),
             bytes: bytes::Bytes::from_static(b"data"),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -894,6 +1399,7 @@ mod tests {
             filename: "file.txt".into(),
             content_type: "text/plain".into(),
             bytes: bytes::Bytes::from_static(b"hello"),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -918,6 +1424,7 @@ mod tests {
             filename: "test.txt".into(),
             content_type: "text/plain".into(),
             bytes: bytes::Bytes::from_static(b"attachment content"),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -931,6 +1438,29 @@ mod tests {
         assert_eq!(queue.enqueued.lock().unwrap().len(), 1);
     }
 
+    #[tokio::test]
+    async fn inline_attachments_keep_their_content_id() {
+        let queue = FakeQueue::new();
+        let mut input = make_input("sender@example.com");
+        input.attachments.push(AttachmentInput::Inline {
+            filename: "logo.png".into(),
+            content_type: "image/png".into(),
+            bytes: bytes::Bytes::from_static(b"png"),
+            content_id: Some("logo".into()),
+        });
+        let service = SubmitEmailService::new(
+            FakeRepository::new(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        );
+        service.execute(input).await.unwrap();
+        let attachments = queue.attachments.lock().unwrap();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].content_id.as_deref(), Some("logo"));
+    }
+
     #[tokio::test]
     async fn execute_writes_remote_attachment_via_fetcher() {
         let repo = FakeRepository::new();
@@ -942,6 +1472,7 @@ mod tests {
             filename: "remote.txt".into(),
             content_type: "text/plain".into(),
             url: url::Url::parse("https://example.com/file.txt").unwrap(),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -964,6 +1495,7 @@ mod tests {
             filename: "remote.txt".into(),
             content_type: "text/plain".into(),
             url: url::Url::parse("https://example.com/file.txt").unwrap(),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -1041,6 +1573,7 @@ mod tests {
             filename: "streamed.txt".into(),
             content_type: "text/plain".into(),
             reader: Box::pin(std::io::Cursor::new(b"streamed bytes".to_vec())),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             repo.clone(),
@@ -1087,6 +1620,169 @@ mod tests {
         assert!(!err.is_transient());
     }
 
+    struct RejectingValidator;
+
+    impl ContentValidator for RejectingValidator {
+        async fn validate(
+            &self,
+            _body: &crate::entity::body::BodySource,
+            _subject: Option<&str>,
+            _locale: Option<&str>,
+            _variables: &serde_json::Map<String, serde_json::Value>,
+        ) -> Result<(), ProcessQueuedEmailError> {
+            Err(ProcessQueuedEmailError::Interpolate(
+                InterpolateError::Engine {
+                    source: anyhow::anyhow!("undefined value"),
+                },
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_content_is_rejected_before_anything_is_persisted() {
+        let repo = FakeRepository::new();
+        let queue = FakeQueue::new();
+        let service = SubmitEmailService::new(
+            repo.clone(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_content_validator(RejectingValidator);
+
+        let err = service
+            .execute(make_input("sender@example.com"))
+            .await
+            .unwrap_err();
+
+        assert!(
+            matches!(err, SubmitEmailError::InvalidContent { ref source } if source.error_class() == crate::entity::error_class::ErrorClass::TemplateInterpolate),
+            "got {err:?}"
+        );
+        assert!(!err.is_transient());
+        assert!(repo.saved.lock().unwrap().is_empty());
+        assert!(queue.enqueued.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn disabled_content_check_accepts_the_email() {
+        let repo = FakeRepository::new();
+        let service = SubmitEmailService::new(
+            repo.clone(),
+            FakeQueue::new(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_content_validator(None::<RejectingValidator>);
+
+        service
+            .execute(make_input("sender@example.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(repo.live_count(), 1);
+    }
+
+    /// Knows `welcome` at version 3 and `welcome.fr` at version 2.
+    struct FakeTemplateStore;
+
+    impl TemplateStore for FakeTemplateStore {
+        async fn publish(
+            &self,
+            _name: &str,
+            _content: &str,
+        ) -> Result<Template, TemplateStoreError> {
+            unimplemented!()
+        }
+
+        async fn get(
+            &self,
+            name: &str,
+            _version: Option<u32>,
+        ) -> Result<Option<Template>, TemplateStoreError> {
+            let version = match name {
+                "welcome" => 3,
+                "welcome.fr" => 2,
+                _ => return Ok(None),
+            };
+            Ok(Some(Template {
+                name: name.to_owned(),
+                version,
+                content: "<mjml></mjml>".into(),
+                created_at_ms: 0,
+            }))
+        }
+
+        async fn delete(&self, _name: &str) -> Result<bool, TemplateStoreError> {
+            unimplemented!()
+        }
+    }
+
+    fn named(reference: &str) -> BodySource {
+        BodySource::Mjml(MjmlSource::Named(reference.into()))
+    }
+
+    #[tokio::test]
+    async fn stored_templates_are_pinned_to_their_latest_version() {
+        let queue = FakeQueue::new();
+        let service = SubmitEmailService::new(
+            FakeRepository::new(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_template_store(FakeTemplateStore);
+
+        for reference in ["welcome", "welcome@1", "from-dir"] {
+            let mut input = make_input("sender@example.com");
+            input.body = named(reference);
+            service.execute(input).await.unwrap();
+        }
+
+        let bodies = queue.bodies.lock().unwrap();
+        let references: Vec<&str> = bodies
+            .iter()
+            .map(|body| match body {
+                BodySource::Mjml(MjmlSource::Named(reference)) => reference.as_str(),
+                other => panic!("unexpected body {other:?}"),
+            })
+            .collect();
+        assert_eq!(references, ["welcome@3", "welcome@1", "from-dir"]);
+    }
+
+    #[tokio::test]
+    async fn stored_templates_are_pinned_to_the_closest_locale_variant() {
+        let queue = FakeQueue::new();
+        let service = SubmitEmailService::new(
+            FakeRepository::new(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_template_store(FakeTemplateStore);
+
+        for locale in ["fr-CA", "de"] {
+            let mut input = make_input("sender@example.com");
+            input.body = named("welcome");
+            input.locale = Some(locale.into());
+            service.execute(input).await.unwrap();
+        }
+
+        let bodies = queue.bodies.lock().unwrap();
+        let references: Vec<&str> = bodies
+            .iter()
+            .map(|body| match body {
+                BodySource::Mjml(MjmlSource::Named(reference)) => reference.as_str(),
+                other => panic!("unexpected body {other:?}"),
+            })
+            .collect();
+        assert_eq!(references, ["welcome.fr@2", "welcome@3"]);
+    }
+
     #[tokio::test]
     async fn duplicate_idempotency_key_does_not_call_attachment_store_put() {
         let existing_id = EmailId::default();
@@ -1098,6 +1794,7 @@ mod tests {
             filename: "idem.txt".into(),
             content_type: "text/plain".into(),
             bytes: bytes::Bytes::from_static(b"x"),
+            content_id: None,
         });
         let service = SubmitEmailService::new(
             DuplicatingRepository { existing_id },
@@ -1113,4 +1810,200 @@ mod tests {
             "attachment store put must not be called for a duplicate submission"
         );
     }
+
+    /// Links to `https://mail.example.com/unsubscribe/<address>`.
+    struct FakeLinks;
+
+    impl UnsubscribeLinks for FakeLinks {
+        fn link(&self, subscription: &Subscription) -> Option<String> {
+            Some(format!(
+                "https://mail.example.com/unsubscribe/{}",
+                subscription.address
+            ))
+        }
+
+        fn verify(&self, _token: &str) -> Option<Subscription> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn list_emails_get_one_click_unsubscribe_headers() {
+        let queue = FakeQueue::new();
+        let service = SubmitEmailService::new(
+            FakeRepository::new(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_unsubscribe_links(FakeLinks);
+        let mut input = make_input("news@example.com");
+        input.list = Some(MailingList::parse("newsletter").unwrap());
+
+        service.execute(input).await.unwrap();
+
+        let envelopes = queue.envelopes.lock().unwrap();
+        let envelope = &envelopes[0];
+        assert_eq!(
+            envelope.list.as_ref().map(MailingList::as_str),
+            Some("newsletter")
+        );
+        assert_eq!(
+            envelope
+                .headers
+                .custom
+                .get("List-Unsubscribe")
+                .map(String::as_str),
+            Some("<https://mail.example.com/unsubscribe/to@example.com>")
+        );
+        assert_eq!(
+            envelope
+                .headers
+                .custom
+                .get("List-Unsubscribe-Post")
+                .map(String::as_str),
+            Some("List-Unsubscribe=One-Click")
+        );
+    }
+
+    #[tokio::test]
+    async fn list_emails_are_rejected_when_no_link_can_be_issued() {
+        let repo = FakeRepository::new();
+        let unconfigured = SubmitEmailService::new(
+            repo.clone(),
+            FakeQueue::new(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        );
+        let configured = SubmitEmailService::new(
+            repo.clone(),
+            FakeQueue::new(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_unsubscribe_links(FakeLinks);
+        let list_input = || {
+            let mut input = make_input("news@example.com");
+            input.list = Some(MailingList::parse("newsletter").unwrap());
+            input
+        };
+
+        let err = unconfigured.execute(list_input()).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SubmitEmailError::ListUnsubscribe(ListUnsubscribeError::NotConfigured)
+            ),
+            "got {err:?}"
+        );
+        assert!(!err.is_transient());
+
+        let mut input = list_input();
+        input
+            .recipients
+            .push((RecipientKind::Cc, "cc@example.com".into()));
+        let err = configured.execute(input).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SubmitEmailError::ListUnsubscribe(ListUnsubscribeError::RecipientCount(2))
+            ),
+            "got {err:?}"
+        );
+
+        let mut input = list_input();
+        input.headers.custom.insert(
+            "list-unsubscribe".into(),
+            "<mailto:leave@example.com>".into(),
+        );
+        let err = configured.execute(input).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                SubmitEmailError::ListUnsubscribe(ListUnsubscribeError::HeaderConflict(ref name)) if name == "list-unsubscribe"
+            ),
+            "got {err:?}"
+        );
+        assert!(repo.saved.lock().unwrap().is_empty());
+    }
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now_ms(&self) -> i64 {
+            1_772_000_000_000
+        }
+    }
+
+    fn calendar_event(uid: &str) -> CalendarEvent {
+        CalendarEvent {
+            uid: uid.to_owned(),
+            sequence: 0,
+            summary: "Kickoff".to_owned(),
+            description: None,
+            location: None,
+            start_ms: 1_772_375_400_000,
+            end_ms: 1_772_379_000_000,
+            organizer: CalendarParticipant {
+                address: "sender@example.com".to_owned(),
+                name: None,
+            },
+            attendees: vec![CalendarParticipant {
+                address: "to@example.com".to_owned(),
+                name: None,
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn calendar_events_are_stamped_with_the_service_clock() {
+        let queue = FakeQueue::new();
+        let service = SubmitEmailService::new(
+            FakeRepository::new(),
+            queue.clone(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        )
+        .with_clock(FixedClock);
+        let mut input = make_input("sender@example.com");
+        input.calendar = Some(CalendarInput::Event(calendar_event(
+            "kickoff-42@example.com",
+        )));
+
+        service.execute(input).await.unwrap();
+
+        let envelopes = queue.envelopes.lock().unwrap();
+        let calendar = envelopes[0].calendar.as_ref().unwrap();
+        assert!(
+            calendar.content().contains("DTSTAMP:20260225T061320Z\r\n"),
+            "got {}",
+            calendar.content()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_calendar_events_are_rejected_before_persisting() {
+        let repo = FakeRepository::new();
+        let service = SubmitEmailService::new(
+            repo.clone(),
+            FakeQueue::new(),
+            FakeEventPublisher::new(),
+            FakeAttachmentStore::new(),
+            FakeFetcher,
+        );
+        let mut input = make_input("sender@example.com");
+        input.calendar = Some(CalendarInput::Event(calendar_event(" ")));
+
+        let err = service.execute(input).await.unwrap_err();
+
+        assert!(
+            matches!(err, SubmitEmailError::InvalidCalendar(_)),
+            "got {err:?}"
+        );
+        assert!(repo.saved.lock().unwrap().is_empty());
+    }
 }
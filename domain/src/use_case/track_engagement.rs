@@ -0,0 +1,258 @@
+use thiserror::Error;
+
+use crate::entity::lifecycle_event::LifecycleEvent;
+use crate::entity::tracking::TrackingHit;
+use crate::port::clock::Clock;
+use crate::port::event_publisher::EventPublisher;
+use crate::port::tracking_links::TrackingLinks;
+
+/// Longest user agent kept on `opened` / `clicked` events, in bytes.
+pub const MAX_USER_AGENT_BYTES: usize = 512;
+
+#[derive(Debug, Error)]
+pub enum TrackEngagementError {
+    #[error("tracking token is invalid")]
+    InvalidToken,
+}
+
+/// What the tracking route answers with.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Engagement {
+    Opened,
+    /// Redirect the reader to `url`.
+    Clicked {
+        url: String,
+    },
+}
+
+pub trait TrackEngagementUseCase: Send + Sync + 'static {
+    /// Records the open or click behind `token`. Every hit is recorded: a
+    /// reader opening an email twice yields two `opened` events.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TrackEngagementError::InvalidToken` when the token was not
+    /// issued by this instance.
+    fn execute(
+        &self,
+        token: String,
+        user_agent: Option<String>,
+    ) -> impl std::future::Future<Output = Result<Engagement, TrackEngagementError>> + Send;
+}
+
+pub struct TrackEngagementService<T, P, C> {
+    links: T,
+    event_publisher: P,
+    clock: C,
+}
+
+impl<T, P, C> TrackEngagementService<T, P, C>
+where
+    T: TrackingLinks,
+    P: EventPublisher,
+    C: Clock,
+{
+    #[must_use]
+    pub fn new(links: T, event_publisher: P, clock: C) -> Self {
+        Self {
+            links,
+            event_publisher,
+            clock,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// See [`TrackEngagementUseCase::execute`].
+    #[tracing::instrument(skip_all, name = "track_engagement")]
+    pub async fn execute(
+        &self,
+        token: String,
+        user_agent: Option<String>,
+    ) -> Result<Engagement, TrackEngagementError> {
+        let hit = self
+            .links
+            .verify(&token)
+            .ok_or(TrackEngagementError::InvalidToken)?;
+        let user_agent = user_agent.map(truncate_user_agent);
+        let at_ms = self.clock.now_ms();
+        let (event, engagement) = match hit {
+            TrackingHit::Open(email) => (
+                LifecycleEvent::Opened {
+                    id: email.email_id,
+                    user_agent,
+                    at_ms,
+                    correlation_id: email.correlation_id,
+                },
+                Engagement::Opened,
+            ),
+            TrackingHit::Click { email, url } => (
+                LifecycleEvent::Clicked {
+                    id: email.email_id,
+                    url: url.clone(),
+                    user_agent,
+                    at_ms,
+                    correlation_id: email.correlation_id,
+                },
+                Engagement::Clicked { url },
+            ),
+        };
+        // The reader is redirected or served the pixel either way.
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish engagement event");
+        }
+        Ok(engagement)
+    }
+}
+
+fn truncate_user_agent(mut user_agent: String) -> String {
+    if user_agent.len() > MAX_USER_AGENT_BYTES {
+        let mut end = MAX_USER_AGENT_BYTES;
+        while !user_agent.is_char_boundary(end) {
+            end -= 1;
+        }
+        user_agent.truncate(end);
+    }
+    user_agent
+}
+
+impl<T, P, C> TrackEngagementUseCase for TrackEngagementService<T, P, C>
+where
+    T: TrackingLinks,
+    P: EventPublisher,
+    C: Clock,
+{
+    fn execute(
+        &self,
+        token: String,
+        user_agent: Option<String>,
+    ) -> impl std::future::Future<Output = Result<Engagement, TrackEngagementError>> + Send {
+        Self::execute(self, token, user_agent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::email::EmailId;
+    use crate::entity::lifecycle_event::LifecycleEvent;
+    use crate::entity::tracking::{TrackedEmail, TrackingHit};
+    use crate::port::clock::Clock;
+    use crate::port::event_publisher::{EventPublisher, EventPublisherError};
+    use crate::port::tracking_links::TrackingLinks;
+
+    use super::{Engagement, MAX_USER_AGENT_BYTES, TrackEngagementError, TrackEngagementService};
+
+    /// Accepts `open` and `click` for a fixed email.
+    struct FakeLinks {
+        email: TrackedEmail,
+    }
+
+    impl TrackingLinks for FakeLinks {
+        fn open_link(&self, _email: &TrackedEmail) -> Option<String> {
+            None
+        }
+
+        fn click_link(&self, _email: &TrackedEmail, _url: &str) -> Option<String> {
+            None
+        }
+
+        fn verify(&self, token: &str) -> Option<TrackingHit> {
+            match token {
+                "open" => Some(TrackingHit::Open(self.email.clone())),
+                "click" => Some(TrackingHit::Click {
+                    email: self.email.clone(),
+                    url: "https://acme.com/pricing".into(),
+                }),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingPublisher {
+        events: Arc<Mutex<Vec<LifecycleEvent>>>,
+    }
+
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    struct FixedClock;
+
+    impl Clock for FixedClock {
+        fn now_ms(&self) -> i64 {
+            1_700_000_000_000
+        }
+    }
+
+    fn service(
+        id: EmailId,
+        publisher: &RecordingPublisher,
+    ) -> TrackEngagementService<FakeLinks, RecordingPublisher, FixedClock> {
+        TrackEngagementService::new(
+            FakeLinks {
+                email: TrackedEmail {
+                    email_id: id,
+                    correlation_id: Some("corr-1".into()),
+                },
+            },
+            publisher.clone(),
+            FixedClock,
+        )
+    }
+
+    #[tokio::test]
+    async fn opens_and_clicks_are_published_with_the_user_agent() {
+        let id = EmailId::default();
+        let publisher = RecordingPublisher::default();
+        let svc = service(id, &publisher);
+
+        let opened = svc.execute("open".into(), None).await.unwrap();
+        let clicked = svc
+            .execute("click".into(), Some("x".repeat(MAX_USER_AGENT_BYTES + 10)))
+            .await
+            .unwrap();
+
+        assert_eq!(opened, Engagement::Opened);
+        assert_eq!(
+            clicked,
+            Engagement::Clicked {
+                url: "https://acme.com/pricing".into()
+            }
+        );
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec![
+                LifecycleEvent::Opened {
+                    id,
+                    user_agent: None,
+                    at_ms: 1_700_000_000_000,
+                    correlation_id: Some("corr-1".into()),
+                },
+                LifecycleEvent::Clicked {
+                    id,
+                    url: "https://acme.com/pricing".into(),
+                    user_agent: Some("x".repeat(MAX_USER_AGENT_BYTES)),
+                    at_ms: 1_700_000_000_000,
+                    correlation_id: Some("corr-1".into()),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_token_is_rejected_without_an_event() {
+        let publisher = RecordingPublisher::default();
+        let svc = service(EmailId::default(), &publisher);
+
+        let err = svc.execute("forged".into(), None).await.unwrap_err();
+
+        assert!(matches!(err, TrackEngagementError::InvalidToken));
+        assert!(publisher.events.lock().unwrap().is_empty());
+    }
+}
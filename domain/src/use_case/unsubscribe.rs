@@ -0,0 +1,261 @@
+use thiserror::Error;
+
+use crate::entity::lifecycle_event::LifecycleEvent;
+use crate::port::event_publisher::EventPublisher;
+use crate::port::suppression_list::{SuppressionList, SuppressionListError, normalize_address};
+use crate::port::unsubscribe_links::UnsubscribeLinks;
+
+#[derive(Debug, Error)]
+pub enum UnsubscribeError {
+    #[error("unsubscribe token is invalid")]
+    InvalidToken,
+    #[error(transparent)]
+    Storage(#[from] SuppressionListError),
+}
+
+pub trait UnsubscribeUseCase: Send + Sync + 'static {
+    /// Opts the recipient behind `token` out of its list. Repeating the call
+    /// with the same token succeeds without recording anything new.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsubscribeError::InvalidToken` when the token was not issued
+    /// by this instance, or `UnsubscribeError::Storage` when recording the
+    /// opt-out fails.
+    fn execute(
+        &self,
+        token: String,
+    ) -> impl std::future::Future<Output = Result<(), UnsubscribeError>> + Send;
+}
+
+pub struct UnsubscribeService<U, L, P> {
+    links: U,
+    list: L,
+    event_publisher: P,
+}
+
+impl<U, L, P> UnsubscribeService<U, L, P>
+where
+    U: UnsubscribeLinks,
+    L: SuppressionList,
+    P: EventPublisher,
+{
+    #[must_use]
+    pub fn new(links: U, list: L, event_publisher: P) -> Self {
+        Self {
+            links,
+            list,
+            event_publisher,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// See [`UnsubscribeUseCase::execute`].
+    #[tracing::instrument(skip_all, name = "unsubscribe")]
+    pub async fn execute(&self, token: String) -> Result<(), UnsubscribeError> {
+        let subscription = self
+            .links
+            .verify(&token)
+            .ok_or(UnsubscribeError::InvalidToken)?;
+        let recorded = self
+            .list
+            .opt_out(
+                &normalize_address(&subscription.sender),
+                &subscription.list,
+                &normalize_address(&subscription.address),
+            )
+            .await?;
+        if !recorded {
+            return Ok(());
+        }
+        let event = LifecycleEvent::Unsubscribed {
+            id: subscription.email_id,
+            address: subscription.address,
+            list: subscription.list,
+            correlation_id: subscription.correlation_id,
+        };
+        if let Err(e) = self.event_publisher.publish(&event).await {
+            tracing::warn!(error = %e, "failed to publish unsubscribed event");
+        }
+        Ok(())
+    }
+}
+
+impl<U, L, P> UnsubscribeUseCase for UnsubscribeService<U, L, P>
+where
+    U: UnsubscribeLinks,
+    L: SuppressionList,
+    P: EventPublisher,
+{
+    fn execute(
+        &self,
+        token: String,
+    ) -> impl std::future::Future<Output = Result<(), UnsubscribeError>> + Send {
+        Self::execute(self, token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::entity::email::EmailId;
+    use crate::entity::lifecycle_event::LifecycleEvent;
+    use crate::entity::unsubscribe::{MailingList, Subscription};
+    use crate::port::event_publisher::{EventPublisher, EventPublisherError};
+    use crate::port::suppression_list::{
+        ListSuppressionsParams, Suppression, SuppressionList, SuppressionListError,
+    };
+    use crate::port::unsubscribe_links::UnsubscribeLinks;
+
+    use super::{UnsubscribeError, UnsubscribeService};
+
+    /// Accepts the token `valid` for a fixed subscription.
+    struct FakeLinks {
+        subscription: Subscription,
+    }
+
+    impl UnsubscribeLinks for FakeLinks {
+        fn link(&self, _subscription: &Subscription) -> Option<String> {
+            None
+        }
+
+        fn verify(&self, token: &str) -> Option<Subscription> {
+            (token == "valid").then(|| self.subscription.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MemoryOptOuts {
+        entries: Arc<Mutex<Vec<(String, String, String)>>>,
+    }
+
+    impl SuppressionList for MemoryOptOuts {
+        async fn add(&self, _address: &str, _reason: &str) -> Result<(), SuppressionListError> {
+            Ok(())
+        }
+
+        async fn remove(&self, _address: &str) -> Result<bool, SuppressionListError> {
+            Ok(false)
+        }
+
+        async fn list(
+            &self,
+            _params: ListSuppressionsParams,
+        ) -> Result<Vec<Suppression>, SuppressionListError> {
+            Ok(vec![])
+        }
+
+        async fn find_suppressed(
+            &self,
+            _addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(vec![])
+        }
+
+        async fn opt_out(
+            &self,
+            sender: &str,
+            list: &MailingList,
+            address: &str,
+        ) -> Result<bool, SuppressionListError> {
+            let entry = (
+                sender.to_owned(),
+                list.as_str().to_owned(),
+                address.to_owned(),
+            );
+            let mut entries = self.entries.lock().unwrap();
+            if entries.contains(&entry) {
+                return Ok(false);
+            }
+            entries.push(entry);
+            Ok(true)
+        }
+
+        async fn find_opted_out(
+            &self,
+            _sender: &str,
+            _list: &MailingList,
+            _addresses: &[String],
+        ) -> Result<Vec<String>, SuppressionListError> {
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct RecordingPublisher {
+        events: Arc<Mutex<Vec<LifecycleEvent>>>,
+    }
+
+    impl EventPublisher for RecordingPublisher {
+        async fn publish(&self, event: &LifecycleEvent) -> Result<(), EventPublisherError> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    fn subscription(id: EmailId) -> Subscription {
+        Subscription {
+            email_id: id,
+            sender: "News@Acme.com".into(),
+            list: MailingList::parse("newsletter").unwrap(),
+            address: "Bob@Example.com".into(),
+            correlation_id: Some("corr-1".into()),
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_token_records_the_opt_out_and_publishes_once() {
+        let id = EmailId::default();
+        let list = MemoryOptOuts::default();
+        let publisher = RecordingPublisher::default();
+        let svc = UnsubscribeService::new(
+            FakeLinks {
+                subscription: subscription(id),
+            },
+            list.clone(),
+            publisher.clone(),
+        );
+
+        svc.execute("valid".into()).await.unwrap();
+        svc.execute("valid".into()).await.unwrap();
+
+        assert_eq!(
+            *list.entries.lock().unwrap(),
+            vec![(
+                "news@acme.com".to_owned(),
+                "newsletter".to_owned(),
+                "bob@example.com".to_owned()
+            )]
+        );
+        assert_eq!(
+            *publisher.events.lock().unwrap(),
+            vec![LifecycleEvent::Unsubscribed {
+                id,
+                address: "Bob@Example.com".into(),
+                list: MailingList::parse("newsletter").unwrap(),
+                correlation_id: Some("corr-1".into()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_token_is_rejected_without_side_effects() {
+        let list = MemoryOptOuts::default();
+        let publisher = RecordingPublisher::default();
+        let svc = UnsubscribeService::new(
+            FakeLinks {
+                subscription: subscription(EmailId::default()),
+            },
+            list.clone(),
+            publisher.clone(),
+        );
+
+        let err = svc.execute("forged".into()).await.unwrap_err();
+
+        assert!(matches!(err, UnsubscribeError::InvalidToken));
+        assert!(list.entries.lock().unwrap().is_empty());
+        assert!(publisher.events.lock().unwrap().is_empty());
+    }
+}